@@ -1,6 +1,6 @@
 use rkyv::{
     access_unchecked, deserialize,
-    rancor::{Failure, Fallible},
+    rancor::{Error, Failure, Fallible},
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
     with::{ArchiveWith, DeserializeWith, SerializeWith},
@@ -47,6 +47,7 @@ impl ArchiveWith<Vec<Opcode>> for EncodeOpcodes {
 impl<S> SerializeWith<Vec<Opcode>, S> for EncodeOpcodes
 where
     S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Error,
 {
     fn serialize_with(
         field: &Vec<Opcode>,
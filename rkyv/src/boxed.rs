@@ -1,6 +1,7 @@
 //! An archived version of `Box`.
 
 use crate::{
+    endian::SwapEndian,
     ser::{Writer, WriterExt as _},
     ArchivePointee, ArchiveUnsized, RelPtr, Serialize, SerializeUnsized,
 };
@@ -218,6 +219,18 @@ impl<T: ArchivePointee + ?Sized> fmt::Pointer for ArchivedBox<T> {
     }
 }
 
+impl<T> SwapEndian for ArchivedBox<T>
+where
+    T: ArchivePointee + SwapEndian + ?Sized,
+    T::ArchivedMetadata: SwapEndian,
+{
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        self.ptr.scalar_ranges(report);
+        self.get().scalar_ranges(report);
+    }
+}
+
 /// The resolver for `Box`.
 pub struct BoxResolver {
     pos: usize,
@@ -236,8 +249,10 @@ impl BoxResolver {
 
 #[cfg(feature = "bytecheck")]
 mod verify {
+    use core::{any::type_name, fmt};
+
     use bytecheck::{
-        rancor::{Error, Fallible},
+        rancor::{Error, Fallible, ResultExt as _},
         CheckBytes, Verify,
     };
 
@@ -247,6 +262,27 @@ mod verify {
         ArchivePointee,
     };
 
+    /// The context for an error that occurred while checking the value of a
+    /// box.
+    #[derive(Debug)]
+    struct CheckBoxContext {
+        /// The name of the archived type being checked
+        type_name: &'static str,
+        /// The offset of the boxed value from the box
+        offset: isize,
+    }
+
+    impl fmt::Display for CheckBoxContext {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "while checking boxed value of type `{}` at offset {} from \
+                 the box",
+                self.type_name, self.offset,
+            )
+        }
+    }
+
     unsafe impl<T, C> Verify<C> for ArchivedBox<T>
     where
         T: ArchivePointee + CheckBytes<C> + LayoutRaw + ?Sized,
@@ -261,7 +297,12 @@ mod verify {
 
             let range = unsafe { context.push_prefix_subtree(ptr)? };
             unsafe {
-                T::check_bytes(ptr, context)?;
+                T::check_bytes(ptr, context).with_trace(|| {
+                    CheckBoxContext {
+                        type_name: type_name::<T>(),
+                        offset: self.ptr.offset(),
+                    }
+                })?;
             }
             unsafe {
                 context.pop_subtree_range(range)?;
@@ -0,0 +1,67 @@
+//! Archived versions of `cmp` types.
+
+use core::{
+    cmp::{Ordering, Reverse},
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// An archived [`Reverse`].
+///
+/// Like `Reverse`, this orders the inner values in reverse. Comparing it
+/// against an unarchived `Reverse` is useful together with
+/// `#[archive(compare(PartialOrd))]`.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedReverse<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedReverse<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Reverse").field(&self.0).finish()
+    }
+}
+
+impl<T> Deref for ArchivedReverse<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ArchivedReverse<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for ArchivedReverse<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.0.partial_cmp(&self.0)
+    }
+}
+
+impl<T: Ord> Ord for ArchivedReverse<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Reverse<U>> for ArchivedReverse<T> {
+    #[inline]
+    fn eq(&self, other: &Reverse<U>) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+impl<T: PartialOrd<U>, U> PartialOrd<Reverse<U>> for ArchivedReverse<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Reverse<U>) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0).map(Ordering::reverse)
+    }
+}
@@ -1,7 +1,7 @@
 //! [`Archive`] implementation for B-tree maps.
 
-// #[cfg(feature = "bytecheck")]
-// pub mod validation;
+#[cfg(feature = "bytecheck")]
+pub mod validation;
 
 use crate::{
     primitive::{ArchivedU16, ArchivedUsize},
@@ -15,7 +15,7 @@ use core::{
     hash::{Hash, Hasher},
     iter::FusedIterator,
     marker::PhantomData,
-    ops::Index,
+    ops::{Bound, Index, RangeBounds},
     ptr::NonNull,
 };
 use ptr_meta::Pointee;
@@ -53,6 +53,7 @@ impl<'a, UK: Archive, UV: Archive> Archive for LeafNodeEntry<&'a UK, &'a UV> {
 }
 
 #[cfg_attr(feature = "strict", repr(C))]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 struct NodeHeader {
     meta: ArchivedU16,
     size: ArchivedUsize,
@@ -208,6 +209,11 @@ impl NodeHeader {
 
 /// An archived [`BTreeMap`](std::collections::BTreeMap).
 #[cfg_attr(feature = "strict", repr(C))]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
 pub struct ArchivedBTreeMap<K, V> {
     len: ArchivedUsize,
     root: RelPtr<NodeHeader>,
@@ -370,6 +376,137 @@ impl<K, V> ArchivedBTreeMap<K, V> {
         }
     }
 
+    /// Finds the position of the first entry whose key does not satisfy
+    /// `is_before`.
+    ///
+    /// The keys of the map must be partitioned by `is_before` such that all
+    /// keys which satisfy it are ordered before all keys which do not. If the
+    /// position is at the end of a leaf node which is followed by another leaf
+    /// node, the position of the first entry of the following leaf is returned
+    /// instead.
+    fn partition_point<F>(&self, is_before: F) -> (NonNull<NodeHeader>, usize)
+    where
+        F: Fn(&K) -> bool,
+    {
+        if let Some(mut current) = self.root() {
+            loop {
+                match current {
+                    ClassifiedNode::Inner(node) => {
+                        let i = node
+                            .tail
+                            .partition_point(|probe| is_before(&probe.key));
+                        let next = if i == 0 {
+                            unsafe { &*node.header.ptr.as_ptr() }
+                        } else {
+                            unsafe { &*node.tail[i - 1].ptr.as_ptr() }
+                        };
+                        current = next.classify();
+                    }
+                    ClassifiedNode::Leaf(node) => {
+                        let index = node
+                            .tail
+                            .partition_point(|probe| is_before(&probe.key));
+                        if index == node.tail.len()
+                            && !node.header.ptr.is_null()
+                        {
+                            let next = unsafe {
+                                NonNull::new_unchecked(
+                                    node.header.ptr.as_ptr() as *mut _
+                                )
+                            };
+                            break (next, 0);
+                        } else {
+                            let leaf = NonNull::from(&node.header);
+                            break (leaf, index);
+                        }
+                    }
+                }
+            }
+        } else {
+            (NonNull::dangling(), 0)
+        }
+    }
+
+    /// Gets an iterator over a sub-range of entries in the map, sorted by key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering on the borrowed
+    /// form must match the ordering on the key type.
+    ///
+    /// Because the keys of an archived B-tree map are ordered, the start and end of the range are
+    /// found with a binary search on each level of the tree instead of by scanning the entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`, or if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{collections::BTreeMap, ops::Bound};
+    /// use rkyv::{access, rancor::Failure, to_bytes};
+    ///
+    /// let mut map = BTreeMap::new();
+    /// for (i, name) in ["ant", "bee", "cat", "dog", "eel"].iter().enumerate() {
+    ///     map.insert(name.to_string(), i as u32);
+    /// }
+    /// let bytes = to_bytes::<_, 1024, Failure>(&map).unwrap();
+    /// let archived = access::<BTreeMap<String, u32>, Failure>(&bytes).unwrap();
+    ///
+    /// let keys = archived
+    ///     .range::<str, _>((Bound::Excluded("ant"), Bound::Included("dog")))
+    ///     .map(|(k, _)| k.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(keys, ["bee", "cat", "dog"]);
+    ///
+    /// let values = archived
+    ///     .range::<str, _>((Bound::Included("c"), Bound::Unbounded))
+    ///     .map(|(_, v)| *v)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(values, [2, 3, 4]);
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        match (range.start_bound(), range.end_bound()) {
+            (Bound::Excluded(start), Bound::Excluded(end)) if start == end => {
+                panic!("range start and end are equal and excluded in ArchivedBTreeMap")
+            }
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) if start > end => {
+                panic!(
+                    "range start is greater than range end in ArchivedBTreeMap"
+                )
+            }
+            _ => (),
+        }
+
+        let (leaf, index) =
+            self.partition_point(|k| match range.start_bound() {
+                Bound::Included(start) => k.borrow() < start,
+                Bound::Excluded(start) => k.borrow() <= start,
+                Bound::Unbounded => false,
+            });
+        let (end_leaf, end_index) =
+            self.partition_point(|k| match range.end_bound() {
+                Bound::Included(end) => k.borrow() <= end,
+                Bound::Excluded(end) => k.borrow() < end,
+                Bound::Unbounded => true,
+            });
+
+        Range {
+            leaf,
+            index,
+            end_leaf,
+            end_index,
+            _phantom: PhantomData,
+        }
+    }
+
     /// Resolves a B-tree map from its length.
     ///
     /// # Safety
@@ -635,6 +772,8 @@ impl<K: Eq, V: Eq> Eq for ArchivedBTreeMap<K, V> {}
 impl<K: Hash, V: Hash> Hash for ArchivedBTreeMap<K, V> {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
+        // Prefix the length to hash the same as `BTreeMap`
+        state.write_usize(self.len());
         for pair in self.iter() {
             pair.hash(state);
         }
@@ -767,6 +906,45 @@ impl<'a, K, V> Iterator for Keys<'a, K, V> {
 impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
 impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
 
+/// An iterator over a sub-range of the key-value pairs of an archived B-tree map.
+pub struct Range<'a, K, V> {
+    leaf: NonNull<NodeHeader>,
+    index: usize,
+    end_leaf: NonNull<NodeHeader>,
+    end_index: usize,
+    _phantom: PhantomData<(&'a K, &'a V)>,
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.leaf == self.end_leaf && self.index == self.end_index {
+            None
+        } else {
+            unsafe {
+                // SAFETY: self.leaf always points to a leaf node header when
+                // the range is not empty
+                let leaf = self.leaf.as_ref().classify_leaf::<K, V>();
+                let result = &leaf.tail[self.index];
+                self.index += 1;
+                if self.index == leaf.tail.len() && !leaf.header.ptr.is_null() {
+                    self.index = 0;
+                    // SAFETY: leaf nodes with a non-null pointer always point
+                    // to the next leaf node
+                    self.leaf = NonNull::new_unchecked(
+                        leaf.header.ptr.as_ptr() as *mut _,
+                    );
+                }
+                Some((&result.key, &result.value))
+            }
+        }
+    }
+}
+
+impl<'a, K, V> FusedIterator for Range<'a, K, V> {}
+
 /// An iterator over the values of an archived B-tree map.
 pub struct Values<'a, K, V> {
     inner: RawIter<'a, K, V>,
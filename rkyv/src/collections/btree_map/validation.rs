@@ -5,41 +5,27 @@ use super::{
     LeafNodeEntry, Node, NodeHeader, MIN_ENTRIES_PER_INNER_NODE,
     MIN_ENTRIES_PER_LEAF_NODE,
 };
-use crate::{
-    rel_ptr::RelPtr,
-    validation::{ArchiveContext, LayoutRaw},
+use crate::validation::{ArchiveContext, LayoutRaw};
+use bytecheck::{
+    rancor::{Error, Fallible},
+    CheckBytes,
 };
-use bytecheck::CheckBytes;
 use core::{
     alloc::{Layout, LayoutError},
-    convert::{Infallible, TryFrom},
-    fmt,
-    ptr,
+    fmt, ptr,
 };
 use ptr_meta::Pointee;
+use rancor::{fail, ResultExt as _};
 
 /// Errors that can occur while checking an archived B-tree.
 #[derive(Debug)]
-pub enum ArchivedBTreeMapError<K, V, C> {
-    /// An error occurred while checking the bytes of a key
-    KeyCheckError(K),
-    /// An error occurred while checking the bytes of a value
-    ValueCheckError(V),
+pub enum ArchivedBTreeMapError {
     /// The number of entries in the inner node is less than the minimum number of entries required
     TooFewInnerNodeEntries(usize),
     /// The number of entries in the leaf node is less than the minimum number of entries
     TooFewLeafNodeEntries(usize),
-    /// An error occurred while checking the entries of an inner node
-    CheckInnerNodeEntryError {
-        /// The index of the inner node entry
-        index: usize,
-        /// The inner error that occurred
-        inner: K,
-    },
     /// The size of an inner node was invalid
     InvalidNodeSize(usize),
-    /// The child of an inner node had a first key that did not match the inner node's key
-    MismatchedInnerChildKey,
     /// The leaf level of the B-tree contained an inner node
     InnerNodeInLeafLevel,
     /// The leaves of the B-tree were not all located at the same depth
@@ -66,26 +52,11 @@ pub enum ArchivedBTreeMapError<K, V, C> {
     },
     /// The keys for an inner node were incorrect
     IncorrectChildKey,
-    /// An context error occurred
-    ContextError(C),
-}
-
-impl<K, V, C> From<Infallible> for ArchivedBTreeMapError<K, V, C> {
-    fn from(_: Infallible) -> Self {
-        unsafe { core::hint::unreachable_unchecked() }
-    }
 }
 
-impl<K, V, C> fmt::Display for ArchivedBTreeMapError<K, V, C>
-where
-    K: fmt::Display,
-    V: fmt::Display,
-    C: fmt::Display,
-{
+impl fmt::Display for ArchivedBTreeMapError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::KeyCheckError(e) => write!(f, "key check error: {}", e),
-            Self::ValueCheckError(e) => write!(f, "value check error: {}", e),
             Self::TooFewInnerNodeEntries(n) => write!(
                 f,
                 "too few inner node entries (expected at least {}): {}",
@@ -96,20 +67,7 @@ where
                 "too few leaf node entries (expected at least {}): {}",
                 MIN_ENTRIES_PER_LEAF_NODE, n,
             ),
-            Self::CheckInnerNodeEntryError { index, inner } => write!(
-                f,
-                "inner node entry check error: index {}, error {}",
-                index, inner
-            ),
-            Self::CheckLeafNodeEntryError { index, inner } => write!(
-                f,
-                "leaf node entry check error: index {}, error {}",
-                index, inner
-            ),
             Self::InvalidNodeSize(n) => write!(f, "invalid node size: {}", n),
-            Self::MismatchedInnerChildKey => {
-                write!(f, "mismatched inner child key")
-            }
             Self::InnerNodeInLeafLevel => write!(f, "inner node in leaf level"),
             Self::InvalidLeafNodeDepth { expected, actual } => write!(
                 f,
@@ -136,48 +94,25 @@ where
             Self::IncorrectChildKey => {
                 write!(f, "incorrect child key in inner node")
             }
-            Self::ContextError(e) => write!(f, "context error: {}", e),
         }
     }
 }
 
 #[cfg(feature = "std")]
-const _: () = {
-    use std::error::Error;
+impl std::error::Error for ArchivedBTreeMapError {}
 
-    impl<K, V, C> Error for ArchivedBTreeMapError<K, V, C>
-    where
-        K: Error + 'static,
-        V: Error + 'static,
-        C: Error + 'static,
-    {
-        fn source(&self) -> Option<&(dyn Error + 'static)> {
-            match self {
-                Self::KeyCheckError(e) => Some(e as &dyn Error),
-                Self::ValueCheckError(e) => Some(e as &dyn Error),
-                Self::TooFewInnerNodeEntries(_) => None,
-                Self::TooFewLeafNodeEntries(_) => None,
-                Self::CheckInnerNodeEntryError { inner, .. } => {
-                    Some(inner as &dyn Error)
-                }
-                Self::CheckLeafNodeEntryError { inner, .. } => {
-                    Some(inner as &dyn Error)
-                }
-                Self::InvalidNodeSize(_) => None,
-                Self::MismatchedInnerChildKey => None,
-                Self::InnerNodeInLeafLevel => None,
-                Self::InvalidLeafNodeDepth { .. } => None,
-                Self::UnsortedLeafNodeEntries => None,
-                Self::UnlinkedLeafNode => None,
-                Self::UnsortedLeafNode => None,
-                Self::LastLeafForwardPointerNotNull => None,
-                Self::LengthMismatch { .. } => None,
-                Self::IncorrectChildKey => None,
-                Self::ContextError(e) => Some(e as &dyn Error),
-            }
-        }
+/// An error occurred while checking the entries of an inner node
+#[derive(Debug)]
+struct CheckInnerNodeEntryContext {
+    /// The index of the inner node entry
+    index: usize,
+}
+
+impl fmt::Display for CheckInnerNodeEntryContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "while checking inner node entry {}", self.index)
     }
-};
+}
 
 /// An error occurred while checking the entries of a leaf node
 #[derive(Debug)]
@@ -197,7 +132,7 @@ impl<T> LayoutRaw for Node<[T]> {
         metadata: <Self as Pointee>::Metadata,
     ) -> Result<Layout, LayoutError> {
         let result = Layout::new::<NodeHeader>()
-            .extend(Layout::array::<T>(metadata).unwrap())?
+            .extend(Layout::array::<T>(metadata)?)?
             .0;
         #[cfg(not(feature = "strict"))]
         {
@@ -212,195 +147,188 @@ impl<T> LayoutRaw for Node<[T]> {
 
 impl NodeHeader {
     #[inline]
-    unsafe fn manual_check_bytes<K, V, C, E>(
+    fn layout<K, V>(&self) -> Result<Layout, LayoutError> {
+        if self.is_inner() {
+            InnerNode::<K>::layout_raw(self.len())
+        } else {
+            LeafNode::<K, V>::layout_raw(self.len())
+        }
+    }
+
+    /// Checks the header of the node at `value`, then claims and checks the
+    /// rest of the node.
+    ///
+    /// # Safety
+    ///
+    /// `value` must have been bounds checked as a `NodeHeader`.
+    #[inline]
+    unsafe fn check_node<'a, K, V, C>(
         value: *const Self,
         context: &mut C,
-    ) -> Result<(), E>
+    ) -> Result<&'a Self, C::Error>
     where
-        K: CheckBytes<C, E>,
-        V: CheckBytes<C, E>,
-        C: ArchiveContext<E> + ?Sized,
+        K: CheckBytes<C>,
+        V: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
     {
-        let raw_node = Self::manual_check_header(value, context)
-            .map_err(ArchivedBTreeMapError::ContextError)?;
-
-        let node_layout = if raw_node.is_inner() {
-            InnerNode::<K>::layout_raw(ptr_meta::metadata(
-                raw_node.classify_inner_ptr::<K>(),
-            ))
-            .map_err(C::wrap_layout_error)
-            .map_err(ArchivedBTreeMapError::ContextError)?
-        } else {
-            LeafNode::<K, V>::layout_raw(ptr_meta::metadata(
-                raw_node.classify_leaf_ptr::<K, V>(),
-            ))
-            .map_err(C::wrap_layout_error)
-            .map_err(ArchivedBTreeMapError::ContextError)?
-        };
-
-        context
-            .check_subtree_ptr(
-                (raw_node as *const NodeHeader).cast(),
-                &node_layout,
-            )
-            .map_err(ArchivedBTreeMapError::ContextError)?;
-
-        Self::manual_check_contents::<K, V, C>(raw_node, context)?;
-
-        Ok(raw_node)
+        let node = Self::check_header::<K, V, C>(value, context)?;
+        node.check_contents::<K, V, C>(context)?;
+        Ok(node)
     }
 
+    /// Checks the header of the node at `value` and bounds checks the entire
+    /// node.
     #[inline]
-    unsafe fn manual_check_header<C: ArchiveContext<E> + ?Sized, E>(
+    unsafe fn check_header<'a, K, V, C>(
         value: *const Self,
         context: &mut C,
-    ) -> Result<(), E> {
-        CheckBytes::check_bytes(ptr::addr_of!((*value).meta), context)?;
-        CheckBytes::check_bytes(ptr::addr_of!((*value).size), context)?;
-        RelPtr::manual_check_bytes(ptr::addr_of!((*value).ptr), context)?;
+    ) -> Result<&'a Self, C::Error>
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
+    {
+        Self::check_bytes(value, context)?;
+        let node = &*value;
 
-        // All the fields have been checked and this is a valid RawNode
-        Ok(())
+        let layout = node.layout::<K, V>().into_error()?;
+        context.check_subtree_ptr(value.cast(), &layout)?;
+
+        Ok(node)
     }
 
+    /// Checks the entries of this node and the block of dependencies that
+    /// precedes it.
     #[inline]
-    unsafe fn manual_check_contents<K, V, C, E>(
-        raw_node: &Self,
+    unsafe fn check_contents<K, V, C>(
+        &self,
         context: &mut C,
-    ) -> Result<(), E>
+    ) -> Result<(), C::Error>
     where
-        K: CheckBytes<C, E>,
-        V: CheckBytes<C, E>,
-        C: ArchiveContext<E> + ?Sized,
+        K: CheckBytes<C>,
+        V: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
     {
-        // Now that the fields have been checked, we can start checking the specific subtype
-        let root = (raw_node as *const Self).cast();
-        let size = raw_node.size.to_native() as usize;
-        let offset = -isize::try_from(size)
-            .map_err(|_| ArchivedBTreeMapError::InvalidNodeSize(size))?;
-        let start = context
-            .check_ptr(root, offset, ())
-            .map_err(ArchivedBTreeMapError::ContextError)?;
+        // The dependencies of the node are located in the `size` bytes
+        // immediately before the node header
+        let root = (self as *const Self).cast::<u8>();
+        let size = self.size.to_native() as usize;
+        if size > root as usize {
+            fail!(ArchivedBTreeMapError::InvalidNodeSize(size));
+        }
+        let start = root.wrapping_sub(size);
+        let block_layout = Layout::from_size_align(size, 1).into_error()?;
+        context.check_subtree_ptr(start, &block_layout)?;
 
         // Push a new suffix range and check the inner or leaf part
-        let range = context
-            .push_suffix_subtree_range(start, root)
-            .map_err(ArchivedBTreeMapError::ContextError)?;
-        if raw_node.is_inner() {
-            InnerNode::manual_check_bytes::<V, C>(
-                raw_node.classify_inner_ptr::<K>(),
+        let range = context.push_suffix_subtree_range(start, root)?;
+        if self.is_inner() {
+            InnerNode::<K>::check_entries::<C>(
+                self.classify_inner_ptr::<K>(),
                 context,
             )?;
         } else {
-            CheckBytes::check_bytes(
-                raw_node.classify_leaf_ptr::<K, V>(),
+            LeafNode::<K, V>::check_entries::<C>(
+                self.classify_leaf_ptr::<K, V>(),
                 context,
             )?;
         }
-        context
-            .pop_suffix_range(range)
-            .map_err(ArchivedBTreeMapError::ContextError)?;
+        context.pop_subtree_range(range)?;
 
         Ok(())
     }
 }
 
 impl<K> InnerNode<K> {
-    #[allow(clippy::type_complexity)]
-    fn verify_integrity<'a, V, C, E>(
-        &'a self,
-    ) -> Result<&K, E>
+    #[inline]
+    unsafe fn check_entries<C>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<(), C::Error>
+    where
+        K: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
+    {
+        // meta, size, and ptr have already been checked by the check_bytes for
+        // NodeHeader
+        let len = ptr_meta::metadata(value);
+
+        // Each inner node actually contains one more entry that the length
+        // indicates (the least child pointer)
+        if len + 1 < MIN_ENTRIES_PER_INNER_NODE {
+            fail!(ArchivedBTreeMapError::TooFewInnerNodeEntries(len + 1));
+        }
+
+        // The subtree range has already been set up for us so we can just
+        // check our tail
+        let tail_ptr = ptr::addr_of!((*value).tail).cast::<InnerNodeEntry<K>>();
+        for index in (0..len).rev() {
+            CheckBytes::check_bytes(tail_ptr.add(index), context)
+                .with_trace(|| CheckInnerNodeEntryContext { index })?;
+        }
+
+        Ok(())
+    }
+
+    fn verify_integrity<'a, V: 'a, E>(&'a self) -> Result<&'a K, E>
     where
-        K: CheckBytes<C, E> + PartialEq,
-        V: CheckBytes<C, E> + 'a,
-        C: ?Sized,
+        K: PartialEq,
+        E: Error,
     {
         for entry in self.tail.iter() {
             let child = unsafe { &*entry.ptr.as_ptr() }.classify::<K, V>();
             let first_key = match child {
-                ClassifiedNode::Inner(c) => c.verify_integrity::<V, C>()?,
+                ClassifiedNode::Inner(c) => c.verify_integrity::<V, E>()?,
                 ClassifiedNode::Leaf(c) => &c.tail[0].key,
             };
             if !entry.key.eq(first_key) {
-                return Err(ArchivedBTreeMapError::IncorrectChildKey);
+                fail!(ArchivedBTreeMapError::IncorrectChildKey);
             }
         }
 
         let least_child =
             unsafe { &*self.header.ptr.as_ptr() }.classify::<K, V>();
         let first_key = match least_child {
-            ClassifiedNode::Inner(c) => c.verify_integrity::<V, C>()?,
+            ClassifiedNode::Inner(c) => c.verify_integrity::<V, E>()?,
             ClassifiedNode::Leaf(c) => &c.tail[0].key,
         };
 
         Ok(first_key)
     }
+}
 
+impl<K, V> LeafNode<K, V> {
     #[inline]
-    unsafe fn manual_check_bytes<'a, V, C, E>(
+    unsafe fn check_entries<C>(
         value: *const Self,
         context: &mut C,
-    ) -> Result<(), E>
+    ) -> Result<(), C::Error>
     where
-        K: CheckBytes<C, E>,
-        V: CheckBytes<C, E>,
-        C: ArchiveContext<E> + ?Sized,
+        K: CheckBytes<C>,
+        V: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
     {
-        // meta, size, and ptr have already been checked by the check_bytes for RawNode
-        let len = ptr_meta::metadata(value);
-
-        // Each inner node actually contains one more entry that the length indicates (the least
-        // child pointer)
-        if len + 1 < MIN_ENTRIES_PER_INNER_NODE {
-            return Err(ArchivedBTreeMapError::TooFewInnerNodeEntries(len + 1));
-        }
-
-        // The subtree range has already been set up for us so we can just check our tail
-        let tail_ptr = ptr::addr_of!((*value).tail) as *const InnerNodeEntry<K>;
-        for index in (0..len).rev() {
-            CheckBytes::check_bytes(tail_ptr.add(index), context).map_err(
-                |inner| ArchivedBTreeMapError::CheckInnerNodeEntryError {
-                    index,
-                    inner,
-                },
-            )?;
-        }
-
-        Ok(&*value)
-    }
-}
-
-unsafe impl<K, V, C> CheckBytes<C, E> for LeafNode<K, V>
-where
-    K: CheckBytes<C, E>,
-    V: CheckBytes<C, E>,
-    C: ArchiveContext<E> + ?Sized,
-{
-    #[inline]
-    unsafe fn check_bytes(
-        value: *const Self,
-        context: &mut C,
-    ) -> Result<(), E> {
-        // meta, size, and ptr have already been checked by the check_bytes for RawNode
+        // meta, size, and ptr have already been checked by the check_bytes for
+        // NodeHeader
         let len = ptr_meta::metadata(value);
 
         if len < MIN_ENTRIES_PER_LEAF_NODE {
-            return Err(ArchivedBTreeMapError::TooFewLeafNodeEntries(len));
+            fail!(ArchivedBTreeMapError::TooFewLeafNodeEntries(len));
         }
 
-        // The subtree range has already been set up for us so we can just check our tail
+        // The subtree range has already been set up for us so we can just
+        // check our tail
         let tail_ptr =
-            ptr::addr_of!((*value).tail) as *const LeafNodeEntry<K, V>;
+            ptr::addr_of!((*value).tail).cast::<LeafNodeEntry<K, V>>();
         for index in (0..len).rev() {
-            CheckBytes::check_bytes(tail_ptr.add(index), context).map_err(
-                |inner| ArchivedBTreeMapError::CheckLeafNodeEntryError {
-                    index,
-                    inner,
-                },
-            )?;
+            CheckBytes::check_bytes(tail_ptr.add(index), context)
+                .with_trace(|| CheckLeafNodeEntryContext { index })?;
         }
 
-        Ok(&*value)
+        Ok(())
     }
 }
 
@@ -408,201 +336,159 @@ where
 const _: () = {
     #[cfg(not(feature = "std"))]
     use alloc::collections::VecDeque;
+    use bytecheck::Verify;
     #[cfg(feature = "std")]
     use std::collections::VecDeque;
 
-    use crate::primitive::ArchivedUsize;
+    use crate::validation::ArchiveContextExt;
 
-    unsafe impl<K, V, C> CheckBytes<C, E> for ArchivedBTreeMap<K, V>
+    unsafe impl<K, V, C> Verify<C> for ArchivedBTreeMap<K, V>
     where
-        K: CheckBytes<C, E> + Ord,
-        V: CheckBytes<C, E>,
-        C: ArchiveContext<E> + ?Sized,
+        K: CheckBytes<C> + Ord,
+        V: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
     {
-        unsafe fn check_bytes(
-            value: *const Self,
-            context: &mut C,
-        ) -> Result<(), E> {
-            let len = ArchivedUsize::check_bytes(
-                ptr::addr_of!((*value).len),
-                context,
-            )?
-            .to_native() as usize;
-
-            if len > 0 {
-                let root_rel_ptr = RelPtr::manual_check_bytes(
-                    ptr::addr_of!((*value).root),
-                    context,
-                )?;
-
-                // Walk all the inner nodes, claim their memory, and check their contents
-                let mut nodes = VecDeque::new();
-                let root_ptr = context
-                    .check_subtree_rel_ptr(root_rel_ptr)
-                    .map_err(ArchivedBTreeMapError::ContextError)?;
-
-                // Before checking all the nodes, we have to push an additional prefix subtree with
-                // the root node
-                // Otherwise, when the suffix subtree of the root node is popped it will remove any
-                // trailing suffix space that should be checked by subsequent fields
-                let root = NodeHeader::manual_check_header(root_ptr, context)
-                    .map_err(ArchivedBTreeMapError::ContextError)?;
-
-                // To push the subtree, we need to know the real size of the root node
-                // Since the header is checked, we can just classify the pointer and use layout_raw
-                let root_layout = if root.is_inner() {
-                    InnerNode::<K>::layout_raw(ptr_meta::metadata(
-                        root.classify_inner_ptr::<K>(),
-                    ))
-                    .map_err(C::wrap_layout_error)
-                    .map_err(ArchivedBTreeMapError::ContextError)?
-                } else {
-                    LeafNode::<K, V>::layout_raw(ptr_meta::metadata(
-                        root.classify_leaf_ptr::<K, V>(),
-                    ))
-                    .map_err(C::wrap_layout_error)
-                    .map_err(ArchivedBTreeMapError::ContextError)?
-                };
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let len = self.len();
+            if len == 0 {
+                return Ok(());
+            }
 
-                // Because the layout of the subtree is dynamic, we need to bounds check the layout
-                // declared by the root node.
-                context
-                    .check_subtree_ptr(
-                        root_ptr.cast(),
-                        &root_layout,
-                    )
-                    .map_err(ArchivedBTreeMapError::ContextError)?;
-
-                // Now we can push the prefix subtree range.
-                let nodes_range = context
-                    .push_prefix_subtree_range(
-                        root_ptr.cast(),
-                        root_ptr.cast::<u8>().add(root_layout.size()),
-                    )
-                    .map_err(ArchivedBTreeMapError::ContextError)?;
-
-                // Now we're finally ready to check node subtrees
-                NodeHeader::manual_check_contents::<K, V, C>(root, context)?;
-
-                nodes.push_back((root, 0));
-
-                while let Some(&(node, depth)) = nodes.front() {
-                    // Break when a leaf is found
-                    if !node.is_inner() {
-                        break;
-                    }
-                    nodes.pop_front();
-                    let inner = node.classify_inner::<K>();
+            // Walk all the inner nodes, claim their memory, and check their
+            // contents
+            let mut nodes = VecDeque::new();
+            let root_ptr =
+                unsafe { context.bounds_check_subtree_rel_ptr(&self.root)? };
+
+            // Before checking all the nodes, we have to push an additional
+            // prefix subtree with the root node. Otherwise, when the suffix
+            // subtree of the root node is popped it will remove any trailing
+            // suffix space that should be checked by subsequent fields.
+            let root = unsafe {
+                NodeHeader::check_header::<K, V, C>(root_ptr, context)?
+            };
+            let root_layout = root.layout::<K, V>().into_error()?;
+            let nodes_range = unsafe {
+                context.push_prefix_subtree_range(
+                    root_ptr.cast(),
+                    root_ptr.cast::<u8>().add(root_layout.size()),
+                )?
+            };
+
+            // Now we're finally ready to check node subtrees
+            unsafe {
+                root.check_contents::<K, V, C>(context)?;
+            }
+
+            nodes.push_back((root, 0));
+
+            while let Some(&(node, depth)) = nodes.front() {
+                // Break when a leaf is found
+                if !node.is_inner() {
+                    break;
+                }
+                nodes.pop_front();
+                let inner = node.classify_inner::<K>();
 
+                let child = unsafe {
                     let child_ptr = context
-                        .check_subtree_rel_ptr(&inner.header.ptr)
-                        .map_err(ArchivedBTreeMapError::ContextError)?;
-                    let child = NodeHeader::manual_check_bytes::<K, V, C>(
-                        child_ptr, context,
-                    )?;
+                        .bounds_check_subtree_rel_ptr(&inner.header.ptr)?;
+                    NodeHeader::check_node::<K, V, C>(child_ptr, context)?
+                };
+                nodes.push_back((child, depth + 1));
+
+                // The invariant that this node contains keys less than the
+                // first key of this node will be checked when we iterate
+                // through the leaf nodes in order and check ordering
+                for entry in inner.tail.iter() {
+                    let child = unsafe {
+                        let child_ptr =
+                            context.bounds_check_subtree_rel_ptr(&entry.ptr)?;
+                        NodeHeader::check_node::<K, V, C>(child_ptr, context)?
+                    };
                     nodes.push_back((child, depth + 1));
+                }
+            }
 
-                    // The invariant that this node contains keys less than the first key of this node will
-                    // be checked when we iterate through the leaf nodes in order and check ordering
-                    for entry in inner.tail.iter() {
-                        let child_ptr = context
-                            .check_subtree_rel_ptr(&entry.ptr)
-                            .map_err(ArchivedBTreeMapError::ContextError)?;
-                        let child = NodeHeader::manual_check_bytes::<K, V, C>(
-                            child_ptr, context,
-                        )?;
-                        nodes.push_back((child, depth + 1));
-                    }
+            // We're done checking node subtrees now
+            unsafe {
+                context.pop_subtree_range(nodes_range)?;
+            }
+
+            // The remaining nodes must all be leaf nodes
+            let mut entry_count = 0;
+            for &(node, depth) in nodes.iter() {
+                if !node.is_leaf() {
+                    fail!(ArchivedBTreeMapError::InnerNodeInLeafLevel);
+                }
+                let leaf = node.classify_leaf::<K, V>();
+
+                // Leaf nodes must all be the same depth
+                let expected = nodes.front().unwrap().1;
+                if depth != expected {
+                    fail!(ArchivedBTreeMapError::InvalidLeafNodeDepth {
+                        expected,
+                        actual: depth,
+                    });
                 }
 
-                // We're done checking node subtrees now
-                context
-                    .pop_prefix_range(nodes_range)
-                    .map_err(ArchivedBTreeMapError::ContextError)?;
-
-                // The remaining nodes must all be leaf nodes
-                let mut entry_count = 0;
-                for (node, depth) in nodes.iter() {
-                    if !node.is_leaf() {
-                        return Err(
-                            ArchivedBTreeMapError::InnerNodeInLeafLevel,
-                        );
-                    }
-                    let leaf = node.classify_leaf::<K, V>();
-
-                    // Leaf nodes must all be the same depth
-                    let expected_depth = nodes.front().unwrap().1;
-                    if *depth != expected_depth {
-                        return Err(
-                            ArchivedBTreeMapError::InvalidLeafNodeDepth {
-                                expected: expected_depth,
-                                actual: *depth,
-                            },
-                        );
+                // They must contain entries in sorted order
+                for (prev, next) in
+                    leaf.tail.iter().zip(leaf.tail.iter().skip(1))
+                {
+                    if next.key <= prev.key {
+                        fail!(ArchivedBTreeMapError::UnsortedLeafNodeEntries);
                     }
+                }
 
-                    // They must contain entries in sorted order
-                    for (prev, next) in
-                        leaf.tail.iter().zip(leaf.tail.iter().skip(1))
-                    {
-                        if next.key < prev.key {
-                            return Err(
-                                ArchivedBTreeMapError::UnsortedLeafNodeEntries,
-                            );
-                        }
-                    }
+                // Keep track of the number of entries found
+                entry_count += leaf.tail.len();
+            }
 
-                    // Keep track of the number of entries found
-                    entry_count += leaf.tail.len();
-                }
+            for (i, &(node, _)) in nodes.iter().enumerate() {
+                let leaf = node.classify_leaf::<K, V>();
 
-                for (i, (node, _)) in nodes.iter().enumerate() {
-                    let leaf = node.classify_leaf::<K, V>();
-
-                    // And they must link together in sorted order
-                    if i < nodes.len() - 1 {
-                        let next_ptr = context
-                            .check_rel_ptr(&leaf.header.ptr)
-                            .map_err(ArchivedBTreeMapError::ContextError)?;
-                        let next_node = nodes[i + 1].0.classify_leaf();
-
-                        if next_ptr
-                            != (next_node as *const LeafNode<K, V>).cast()
-                        {
-                            return Err(
-                                ArchivedBTreeMapError::UnlinkedLeafNode,
-                            );
-                        }
-                        if next_node.tail[0].key
-                            < leaf.tail[leaf.tail.len() - 1].key
-                        {
-                            return Err(
-                                ArchivedBTreeMapError::UnsortedLeafNode,
-                            );
-                        }
-                    } else {
-                        // The last node must have a null pointer forward
-                        if !leaf.header.ptr.is_null() {
-                            return Err(ArchivedBTreeMapError::LastLeafForwardPointerNotNull);
-                        }
+                // And they must link together in sorted order
+                if i < nodes.len() - 1 {
+                    let next_ptr = leaf.header.ptr.as_ptr_wrapping();
+                    let next_node = nodes[i + 1].0.classify_leaf::<K, V>();
+
+                    if next_ptr.cast_const()
+                        != (next_node as *const LeafNode<K, V>).cast()
+                    {
+                        fail!(ArchivedBTreeMapError::UnlinkedLeafNode);
+                    }
+                    if next_node.tail[0].key
+                        <= leaf.tail[leaf.tail.len() - 1].key
+                    {
+                        fail!(ArchivedBTreeMapError::UnsortedLeafNode);
+                    }
+                } else {
+                    // The last node must have a null pointer forward
+                    if !leaf.header.ptr.is_null() {
+                        fail!(
+                            ArchivedBTreeMapError::LastLeafForwardPointerNotNull
+                        );
                     }
                 }
+            }
 
-                // Make sure that the number of entries matches the length
-                if entry_count != len {
-                    return Err(ArchivedBTreeMapError::LengthMismatch {
-                        expected: len,
-                        actual: entry_count,
-                    });
-                }
+            // Make sure that the number of entries matches the length
+            if entry_count != len {
+                fail!(ArchivedBTreeMapError::LengthMismatch {
+                    expected: len,
+                    actual: entry_count,
+                });
+            }
 
-                // Make sure that inner nodes are constructed appropriately
-                if root.is_inner() {
-                    root.classify_inner::<K>().verify_integrity::<V, C>()?;
-                }
+            // Make sure that inner nodes are constructed appropriately
+            if root.is_inner() {
+                root.classify_inner::<K>()
+                    .verify_integrity::<V, C::Error>()?;
             }
 
-            Ok(&*value)
+            Ok(())
         }
     }
 };
@@ -23,6 +23,18 @@ impl<K> ArchivedBTreeSet<K> {
         self.0.contains_key(key)
     }
 
+    /// Returns `true` if the set contains the specified value.
+    ///
+    /// The value may be any borrowed form of the set's value type, but the ordering on the borrowed
+    /// form _must_ match the ordering on the value type.
+    #[inline]
+    pub fn contains<Q: Ord + ?Sized>(&self, value: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+    {
+        self.0.contains_key(value)
+    }
+
     /// Returns a reference to the value int he set, if any, that is equal to the given value.
     ///
     /// The value may be any borrowed form of the set's value type, but the ordering on the borrowed
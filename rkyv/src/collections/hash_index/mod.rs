@@ -9,23 +9,73 @@ use crate::{
 use core::{
     fmt,
     hash::{Hash, Hasher},
+    marker::PhantomData,
     slice,
 };
+use seahash::SeaHasher;
+
+/// The default hasher for archived hash indexes.
+///
+/// This is a [`SeaHasher`] with fixed seeds, so it hashes keys the same way in
+/// every process and on every platform.
+#[derive(Clone, Copy)]
+pub struct DefaultHasher(SeaHasher);
+
+impl fmt::Debug for DefaultHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultHasher").finish_non_exhaustive()
+    }
+}
+
+impl Default for DefaultHasher {
+    #[inline]
+    fn default() -> Self {
+        Self(SeaHasher::with_seeds(
+            0x08576fb6170b5f5f,
+            0x587775eeb84a7e46,
+            0xac701115428ee569,
+            0x910feb91b92bb1cd,
+        ))
+    }
+}
+
+impl Hasher for DefaultHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes)
+    }
+}
 
 /// The hash builder for archived hash indexes.
-pub use seahash::SeaHasher as HashBuilder;
+pub type HashBuilder = DefaultHasher;
 
-// #[cfg(feature = "bytecheck")]
-// pub mod validation;
+#[cfg(feature = "bytecheck")]
+pub mod validation;
 
 /// An archived hash index.
+///
+/// Keys are hashed with `H`, which is created with `H::default()` every time a
+/// key is hashed. The default hasher must hash keys the same way in every
+/// process that accesses the archive, otherwise lookups fail. Hashers that
+/// generate random keys at runtime can't be used.
 #[cfg_attr(feature = "strict", repr(C))]
-pub struct ArchivedHashIndex {
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedHashIndex<H = DefaultHasher> {
     len: ArchivedUsize,
     displace: RelPtr<ArchivedU32>,
+    _hasher: PhantomData<fn() -> H>,
 }
 
-impl ArchivedHashIndex {
+impl<H> ArchivedHashIndex<H> {
     /// Gets the number of items in the hash index.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -33,30 +83,54 @@ impl ArchivedHashIndex {
     }
 
     #[inline]
-    fn make_hasher() -> HashBuilder {
-        HashBuilder::with_seeds(
-            0x08576fb6170b5f5f,
-            0x587775eeb84a7e46,
-            0xac701115428ee569,
-            0x910feb91b92bb1cd,
-        )
+    fn displace_slice(&self) -> &[ArchivedU32] {
+        unsafe { slice::from_raw_parts(self.displace.as_ptr(), self.len()) }
     }
 
-    /// Gets the hasher for this hash index. The hasher for all archived hash indexes is the same
-    /// for reproducibility.
     #[inline]
-    pub fn hasher(&self) -> HashBuilder {
-        Self::make_hasher()
+    fn displace(&self, index: usize) -> u32 {
+        self.displace_slice()[index].to_native()
     }
 
+    /// Returns whether there are no items in the hash index.
     #[inline]
-    fn displace_slice(&self) -> &[ArchivedU32] {
-        unsafe { slice::from_raw_parts(self.displace.as_ptr(), self.len()) }
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
+    /// Resolves an archived hash index from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements in the hash index
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of building and serializing a hash index
     #[inline]
-    fn displace(&self, index: usize) -> u32 {
-        self.displace_slice()[index].to_native()
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: HashIndexResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.len);
+        len.resolve(pos + fp, (), fo);
+
+        let (fp, fo) = out_field!(out.displace);
+        RelPtr::emplace(pos + fp, resolver.displace_pos, fo);
+    }
+}
+
+impl<H: Hasher + Default> ArchivedHashIndex<H> {
+    #[inline]
+    fn make_hasher() -> H {
+        H::default()
+    }
+
+    /// Gets the hasher for this hash index. The hasher for all archived hash
+    /// indexes with the same hasher type is the same for reproducibility.
+    #[inline]
+    pub fn hasher(&self) -> H {
+        Self::make_hasher()
     }
 
     /// Returns the index where a key may be located in the hash index.
@@ -85,33 +159,6 @@ impl ArchivedHashIndex {
             Some(index as usize)
         }
     }
-
-    /// Returns whether there are no items in the hash index.
-    #[inline]
-    pub const fn is_empty(&self) -> bool {
-        self.len() == 0
-    }
-
-    /// Resolves an archived hash index from a given length and parameters.
-    ///
-    /// # Safety
-    ///
-    /// - `len` must be the number of elements in the hash index
-    /// - `pos` must be the position of `out` within the archive
-    /// - `resolver` must be the result of building and serializing a hash index
-    #[inline]
-    pub unsafe fn resolve_from_len(
-        len: usize,
-        pos: usize,
-        resolver: HashIndexResolver,
-        out: *mut Self,
-    ) {
-        let (fp, fo) = out_field!(out.len);
-        len.resolve(pos + fp, (), fo);
-
-        let (fp, fo) = out_field!(out.displace);
-        RelPtr::emplace(pos + fp, resolver.displace_pos, fo);
-    }
 }
 
 #[cfg(feature = "alloc")]
@@ -126,15 +173,48 @@ const _: () = {
         cmp::Reverse,
         mem::{size_of, MaybeUninit},
     };
-    use rancor::Fallible;
+    use rancor::{fail, Error, Fallible};
+
+    /// The number of seeds to try for each bucket of colliding keys before
+    /// giving up.
+    const MAX_SEEDS: u32 = 1 << 16;
 
-    impl ArchivedHashIndex {
+    #[derive(Debug)]
+    struct SeedSearchExhausted {
+        bucket_size: usize,
+    }
+
+    impl fmt::Display for SeedSearchExhausted {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "failed to find a seed that separates a bucket of {} keys \
+                 after {} attempts; the hasher may produce the same hash for \
+                 different keys",
+                self.bucket_size, MAX_SEEDS,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for SeedSearchExhausted {}
+
+    impl<H: Hasher + Default> ArchivedHashIndex<H> {
         /// Builds and serializes a hash index from an iterator of key-value pairs.
         ///
+        /// The position of each entry only depends on the hash of its key, so
+        /// the hash index and the order of `entries` are the same for any
+        /// order of the same keys. Once the hash index is built, `entries`
+        /// contains every key-value pair in its position in the index.
+        ///
+        /// Returns an error if the hasher can't separate some colliding keys,
+        /// which happens when it produces the same hash for different keys.
+        ///
         /// # Safety
         ///
         /// - The keys returned by the iterator must be unique.
-        /// - `entries` must have a capacity of `iter.len()` entries.
+        /// - `entries` must be empty and have a capacity of `iter.len()`
+        ///   entries.
         #[allow(clippy::type_complexity)]
         pub unsafe fn build_and_serialize<'a, K, V, S, I>(
             iter: I,
@@ -145,6 +225,7 @@ const _: () = {
             K: 'a + Hash,
             V: 'a,
             S: Fallible + Writer + Allocator + ?Sized,
+            S::Error: Error,
             I: ExactSizeIterator<Item = (&'a K, &'a V)>,
         {
             let len = iter.len();
@@ -175,9 +256,10 @@ const _: () = {
 
             let mut displacements = ScratchVec::new(serializer, len)?;
             for _ in 0..len {
-                displacements.push(u32::MAX);
+                displacements.push(ArchivedU32::from_native(u32::MAX));
             }
 
+            let entries_ptr = entries.as_mut_ptr();
             let mut first_empty = 0;
             let mut assignments = Vec::with_capacity(8);
 
@@ -190,15 +272,15 @@ const _: () = {
                 start = end;
 
                 if bucket_size > 1 {
-                    'find_seed: for seed in 0x80_00_00_00u32..=0xFF_FF_FF_FFu32
+                    let mut found = false;
+                    'find_seed: for seed in
+                        0x80_00_00_00u32..0x80_00_00_00u32 + MAX_SEEDS
                     {
-                        let mut base_hasher = Self::make_hasher();
-                        seed.hash(&mut base_hasher);
-
                         assignments.clear();
 
                         for &(_, (key, _)) in bucket.iter() {
-                            let mut hasher = base_hasher;
+                            let mut hasher = Self::make_hasher();
+                            seed.hash(&mut hasher);
                             key.hash(&mut hasher);
                             let index = (hasher.finish() % len as u64) as u32;
                             if occupied[index as usize]
@@ -212,13 +294,19 @@ const _: () = {
 
                         for i in 0..bucket_size {
                             occupied[assignments[i] as usize] = true;
-                            entries[assignments[i] as usize]
-                                .as_mut_ptr()
-                                .write(bucket[i].1);
+                            entries_ptr
+                                .add(assignments[i] as usize)
+                                .write(MaybeUninit::new(bucket[i].1));
                         }
-                        displacements[displace as usize] = seed;
+                        displacements[displace as usize] =
+                            ArchivedU32::from_native(seed);
+                        found = true;
                         break;
                     }
+
+                    if !found {
+                        fail!(SeedSearchExhausted { bucket_size });
+                    }
                 } else {
                     let offset = occupied[first_empty..]
                         .iter()
@@ -226,12 +314,18 @@ const _: () = {
                         .unwrap();
                     first_empty += offset;
                     occupied[first_empty] = true;
-                    entries[first_empty].as_mut_ptr().write(bucket[0].1);
-                    displacements[displace as usize] = first_empty as u32;
+                    entries_ptr
+                        .add(first_empty)
+                        .write(MaybeUninit::new(bucket[0].1));
+                    displacements[displace as usize] =
+                        ArchivedU32::from_native(first_empty as u32);
                     first_empty += 1;
                 }
             }
 
+            // Every entry was written because each key was assigned a position
+            entries.set_len(len);
+
             // Write displacements
             let displace_pos = serializer.align_for::<ArchivedU32>()?;
             let displacements_slice = slice::from_raw_parts(
@@ -251,7 +345,7 @@ const _: () = {
     }
 };
 
-impl fmt::Debug for ArchivedHashIndex {
+impl<H> fmt::Debug for ArchivedHashIndex<H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.displace_slice()).finish()
     }
@@ -2,97 +2,68 @@
 
 use crate::{
     collections::ArchivedHashIndex,
-    primitive::{ArchivedU32, ArchivedUsize},
-    validation::ArchiveContext,
-    RelPtr,
+    primitive::ArchivedU32,
+    validation::{ArchiveContext, ArchiveContextExt},
 };
-use bytecheck::CheckBytes;
-use core::{
-    alloc::{Layout, LayoutError},
-    fmt, ptr,
+use bytecheck::{
+    rancor::{Error, Fallible},
+    CheckBytes, Verify,
 };
+use core::fmt;
+use rancor::fail;
 
-/// Errors that can occur while checking an archived hash index.
+/// An error resulting from an invalid displacement in an archived hash index.
 #[derive(Debug)]
-pub enum HashIndexError {
-    /// An error occurred while checking the layouts of displacements or entries
-    LayoutError(LayoutError),
-    /// A displacement value was invalid
-    InvalidDisplacement {
-        /// The index of the entry with an invalid displacement
-        index: usize,
-        /// The value of the entry at the invalid location
-        value: u32,
-    },
+pub struct InvalidDisplacement {
+    /// The index of the entry with an invalid displacement
+    pub index: usize,
+    /// The value of the entry at the invalid location
+    pub value: u32,
 }
 
-impl fmt::Display for HashIndexError {
+impl fmt::Display for InvalidDisplacement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            HashIndexError::LayoutError(e) => write!(f, "layout error: {}", e),
-            HashIndexError::InvalidDisplacement { index, value } => write!(
-                f,
-                "invalid displacement: value {} at index {}",
-                value, index,
-            ),
-        }
+        write!(
+            f,
+            "invalid displacement: value {} at index {}",
+            self.value, self.index,
+        )
     }
 }
 
 #[cfg(feature = "std")]
-const _: () = {
-    use std::error::Error;
+impl std::error::Error for InvalidDisplacement {}
 
-    impl Error for HashIndexError {
-        fn source(&self) -> Option<&(dyn Error + 'static)> {
-            match self {
-                HashIndexError::LayoutError(e) => Some(e as &dyn Error),
-                HashIndexError::InvalidDisplacement { .. } => None,
-            }
-        }
-    }
-};
-
-unsafe impl<C: ArchiveContext<E> + ?Sized, E> CheckBytes<C, E> for ArchivedHashIndex {
-    unsafe fn check_bytes(
-        value: *const Self,
-        context: &mut C,
-    ) -> Result<(), E> {
-        let len =
-            ArchivedUsize::check_bytes(ptr::addr_of!((*value).len), context)?
-                .to_native() as usize;
-        Layout::array::<ArchivedU32>(len)?;
-
-        let displace_rel_ptr = RelPtr::manual_check_bytes(
-            ptr::addr_of!((*value).displace),
-            context,
-        )?;
-        let displace_ptr = context
-            .check_subtree_ptr::<[ArchivedU32]>(
-                displace_rel_ptr.base(),
-                displace_rel_ptr.offset(),
+unsafe impl<C, H> Verify<C> for ArchivedHashIndex<H>
+where
+    C: Fallible + ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+        let len = self.len();
+        let ptr = unsafe {
+            context.bounds_check_subtree_base_offset::<[ArchivedU32]>(
+                self.displace.base(),
+                self.displace.offset(),
                 len,
-            )
-            .map_err(HashIndexError::ContextError)?;
+            )?
+        };
 
-        let range = context
-            .push_prefix_subtree(displace_ptr)
-            .map_err(HashIndexError::ContextError)?;
-        let displace = <[ArchivedU32]>::check_bytes(displace_ptr, context)?;
-        context
-            .pop_prefix_range(range)
-            .map_err(HashIndexError::ContextError)?;
+        let range = unsafe { context.push_prefix_subtree(ptr)? };
+        unsafe {
+            <[ArchivedU32]>::check_bytes(ptr, context)?;
+        }
+        unsafe {
+            context.pop_subtree_range(range)?;
+        }
 
-        for (i, &d) in displace.iter().enumerate() {
-            let d = d.to_native();
-            if d as usize >= len && d < 0x80_00_00_00 {
-                return Err(HashIndexError::InvalidDisplacement {
-                    index: i,
-                    value: d,
-                });
+        for (index, displace) in self.displace_slice().iter().enumerate() {
+            let value = displace.to_native();
+            if value as usize >= len && value < 0x80_00_00_00 {
+                fail!(InvalidDisplacement { index, value });
             }
         }
 
-        Ok(&*value)
+        Ok(())
     }
 }
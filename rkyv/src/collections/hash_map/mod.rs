@@ -3,12 +3,12 @@
 //! During archiving, hashmaps are built into minimal perfect hashmaps using
 //! [compress, hash and displace](http://cmph.sourceforge.net/papers/esa09.pdf).
 
-// #[cfg(feature = "bytecheck")]
-// pub mod validation;
+#[cfg(feature = "bytecheck")]
+pub mod validation;
 
 use crate::{
     collections::{
-        hash_index::{ArchivedHashIndex, HashIndexResolver},
+        hash_index::{ArchivedHashIndex, DefaultHasher, HashIndexResolver},
         util::Entry,
     },
     ser::WriterExt as _,
@@ -20,31 +20,48 @@ use crate::{
     Serialize,
 };
 use core::{
-    borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData,
-    ops::Index, pin::Pin,
+    borrow::Borrow,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::Index,
+    pin::Pin,
 };
 
 /// An archived `HashMap`.
+///
+/// The layout of an archived hash map only depends on its entries. Each entry
+/// is placed at a position computed from the hash of its key with the fixed
+/// [`HashBuilder`](crate::collections::hash_index::HashBuilder), and the
+/// entries are serialized in that order. Archiving maps with the same entries
+/// always produces identical bytes, regardless of the insertion order or the
+/// hasher of the original map. This makes archived hash maps suitable for
+/// content-addressed storage, as long as the keys and values also archive
+/// deterministically.
+///
+/// Keys are hashed with `H`, which defaults to the fixed [`DefaultHasher`].
+/// Maps archived with [`WithHasher`](crate::with::WithHasher) use the hasher
+/// chosen there instead, so lookups hash keys the same way as they were
+/// hashed when the map was built.
 #[cfg_attr(feature = "strict", repr(C))]
-pub struct ArchivedHashMap<K, V> {
-    index: ArchivedHashIndex,
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedHashMap<K, V, H = DefaultHasher> {
+    index: ArchivedHashIndex<H>,
     entries: RelPtr<Entry<K, V>>,
 }
 
-impl<K, V> ArchivedHashMap<K, V> {
+impl<K, V, H> ArchivedHashMap<K, V, H> {
     /// Gets the number of items in the hash map.
     #[inline]
     pub const fn len(&self) -> usize {
         self.index.len()
     }
 
-    /// Gets the hasher for this hashmap. The hasher for all archived hashmaps is the same for
-    /// reproducibility.
-    #[inline]
-    pub fn hasher(&self) -> seahash::SeaHasher {
-        self.index.hasher()
-    }
-
     #[inline]
     unsafe fn entry(&self, index: usize) -> &Entry<K, V> {
         &*self.entries.as_ptr().add(index)
@@ -54,6 +71,15 @@ impl<K, V> ArchivedHashMap<K, V> {
     unsafe fn entry_mut(&mut self, index: usize) -> &mut Entry<K, V> {
         &mut *self.entries.as_ptr().add(index)
     }
+}
+
+impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
+    /// Gets the hasher for this hashmap. The hasher for all archived hashmaps
+    /// with the same hasher type is the same for reproducibility.
+    #[inline]
+    pub fn hasher(&self) -> H {
+        self.index.hasher()
+    }
 
     #[inline]
     fn find<Q: ?Sized>(&self, k: &Q) -> Option<usize>
@@ -187,7 +213,9 @@ impl<K, V> ArchivedHashMap<K, V> {
             })
         }
     }
+}
 
+impl<K, V, H> ArchivedHashMap<K, V, H> {
     /// Returns `true` if the map contains no elements.
     #[inline]
     pub const fn is_empty(&self) -> bool {
@@ -276,11 +304,15 @@ impl<K, V> ArchivedHashMap<K, V> {
 
 #[cfg(feature = "alloc")]
 const _: () = {
-    use rancor::Fallible;
+    use rancor::{Error, Fallible};
 
-    impl<K, V> ArchivedHashMap<K, V> {
+    impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         /// Serializes an iterator of key-value pairs as a hash map.
         ///
+        /// The archived map does not depend on the order of the iterator, so
+        /// iterators that yield the same entries in any order serialize to
+        /// the same bytes.
+        ///
         /// # Safety
         ///
         /// The keys returned by the iterator must be unique.
@@ -292,6 +324,7 @@ const _: () = {
             KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
             VU: 'a + Serialize<S, Archived = V>,
             S: Fallible + Writer + Allocator + ?Sized,
+            S::Error: Error,
             I: ExactSizeIterator<Item = (&'a KU, &'a VU)>,
         {
             use crate::util::ScratchVec;
@@ -299,8 +332,7 @@ const _: () = {
             let len = iter.len();
 
             let mut entries = ScratchVec::new(serializer, len)?;
-            entries.set_len(len);
-            let index_resolver = ArchivedHashIndex::build_and_serialize(
+            let index_resolver = ArchivedHashIndex::<H>::build_and_serialize(
                 iter,
                 serializer,
                 &mut entries,
@@ -338,17 +370,26 @@ const _: () = {
     }
 };
 
-impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ArchivedHashMap<K, V> {
+impl<K: fmt::Debug, V: fmt::Debug, H> fmt::Debug for ArchivedHashMap<K, V, H> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
 
-impl<K: Hash + Eq, V: Eq> Eq for ArchivedHashMap<K, V> {}
+impl<K, V, H> Eq for ArchivedHashMap<K, V, H>
+where
+    K: Hash + Eq,
+    V: Eq,
+    H: Hasher + Default,
+{
+}
 
-impl<K: Eq + Hash + Borrow<Q>, Q: Eq + Hash + ?Sized, V> Index<&'_ Q>
-    for ArchivedHashMap<K, V>
+impl<K, Q, V, H> Index<&'_ Q> for ArchivedHashMap<K, V, H>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+    H: Hasher + Default,
 {
     type Output = V;
 
@@ -358,7 +399,12 @@ impl<K: Eq + Hash + Borrow<Q>, Q: Eq + Hash + ?Sized, V> Index<&'_ Q>
     }
 }
 
-impl<K: Hash + Eq, V: PartialEq> PartialEq for ArchivedHashMap<K, V> {
+impl<K, V, H> PartialEq for ArchivedHashMap<K, V, H>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    H: Hasher + Default,
+{
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -1,104 +1,69 @@
-//! Validation implementation for ArchiveHashMap.
+//! Validation implementation for ArchivedHashMap.
 
 use crate::{
-    collections::{
-        hash_map::ArchivedHashMap,
-        util::Entry,
-        ArchivedHashIndex,
-    },
-    validation::ArchiveContext,
-    RelPtr,
+    collections::{hash_map::ArchivedHashMap, util::Entry},
+    validation::{ArchiveContext, ArchiveContextExt},
+};
+use bytecheck::{
+    rancor::{Error, Fallible},
+    CheckBytes, Verify,
 };
-use bytecheck::CheckBytes;
 use core::{
-    alloc::{Layout, LayoutError},
     fmt,
-    hash::Hash,
-    ptr,
+    hash::{Hash, Hasher},
 };
+use rancor::fail;
 
-/// Errors that can occur while checking an archived hash map.
+/// An error resulting from a key that is not located at its hashed position.
+///
+/// Because every key must be located at the position its hash maps to, this
+/// also rejects hash maps that contain duplicate keys.
 #[derive(Debug)]
-pub enum HashMapError {
-    /// An error occurred while checking the layouts of displacements or entries
-    LayoutError(LayoutError),
-    /// A key is not located at the correct position
-    InvalidKeyPosition {
-        /// The index of the key when iterating
-        index: usize,
-    },
+pub struct InvalidKeyPosition {
+    /// The index of the key when iterating
+    pub index: usize,
 }
 
-impl fmt::Display for HashMapError {
+impl fmt::Display for InvalidKeyPosition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            HashMapError::LayoutError(e) => write!(f, "layout error: {}", e),
-            HashMapError::InvalidKeyPosition { index } => {
-                write!(f, "invalid key position: at index {}", index)
-            }
-        }
+        write!(f, "invalid key position: at index {}", self.index)
     }
 }
 
 #[cfg(feature = "std")]
-const _: () = {
-    use std::error::Error;
+impl std::error::Error for InvalidKeyPosition {}
 
-    impl Error for HashMapError {
-        fn source(&self) -> Option<&(dyn Error + 'static)> {
-            match self {
-                HashMapError::HashIndexError(e) => Some(e as &dyn Error),
-                HashMapError::LayoutError(e) => Some(e as &dyn Error),
-                HashMapError::CheckEntryError(e) => Some(e as &dyn Error),
-                HashMapError::InvalidKeyPosition { .. } => None,
-                HashMapError::ContextError(e) => Some(e as &dyn Error),
-            }
-        }
-    }
-};
-
-unsafe impl<K, V, C> CheckBytes<C, E> for ArchivedHashMap<K, V>
+unsafe impl<K, V, H, C> Verify<C> for ArchivedHashMap<K, V, H>
 where
-    K: CheckBytes<C, E> + Eq + Hash,
-    V: CheckBytes<C, E>,
-    C: ArchiveContext<E> + ?Sized,
+    K: CheckBytes<C> + Eq + Hash,
+    V: CheckBytes<C>,
+    H: Hasher + Default,
+    C: Fallible + ArchiveContext + ?Sized,
+    C::Error: Error,
 {
-    unsafe fn check_bytes(
-        value: *const Self,
-        context: &mut C,
-    ) -> Result<(), E> {
-        let index = ArchivedHashIndex::check_bytes(
-            ptr::addr_of!((*value).index),
-            context,
-        )?;
-        Layout::array::<Entry<K, V>>(index.len())?;
-
-        let entries_rel_ptr = RelPtr::manual_check_bytes(
-            ptr::addr_of!((*value).entries),
-            context,
-        )?;
-        let entries_ptr = context
-            .check_subtree_ptr::<[Entry<K, V>]>(
-                entries_rel_ptr.base(),
-                entries_rel_ptr.offset(),
-                index.len(),
-            )
-            .map_err(HashMapError::ContextError)?;
+    fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+        let ptr = unsafe {
+            context.bounds_check_subtree_base_offset::<[Entry<K, V>]>(
+                self.entries.base(),
+                self.entries.offset(),
+                self.len(),
+            )?
+        };
 
-        let range = context
-            .push_prefix_subtree(entries_ptr)
-            .map_err(HashMapError::ContextError)?;
-        let entries = <[Entry<K, V>]>::check_bytes(entries_ptr, context)?;
-        context
-            .pop_prefix_range(range)
-            .map_err(HashMapError::ContextError)?;
+        let range = unsafe { context.push_prefix_subtree(ptr)? };
+        unsafe {
+            <[Entry<K, V>]>::check_bytes(ptr, context)?;
+        }
+        unsafe {
+            context.pop_subtree_range(range)?;
+        }
 
-        for (i, entry) in entries.iter().enumerate() {
-            if index.index(&entry.key) != Some(i) {
-                return Err(HashMapError::InvalidKeyPosition { index: i });
+        for (index, key) in self.keys().enumerate() {
+            if self.index.index(key) != Some(index) {
+                fail!(InvalidKeyPosition { index });
             }
         }
 
-        Ok(&*value)
+        Ok(())
     }
 }
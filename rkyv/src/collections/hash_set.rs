@@ -3,22 +3,32 @@
 //! During archiving, hashsets are built into minimal perfect hashsets using
 //! [compress, hash and displace](http://cmph.sourceforge.net/papers/esa09.pdf).
 
-use crate::collections::hash_map::{ArchivedHashMap, HashMapResolver, Keys};
+use crate::collections::{
+    hash_index::DefaultHasher,
+    hash_map::{ArchivedHashMap, HashMapResolver, Keys},
+};
 #[cfg(feature = "alloc")]
 use crate::{
     ser::{Allocator, Writer},
     Serialize,
 };
-use core::{borrow::Borrow, fmt, hash::Hash};
-use rancor::Fallible;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{Hash, Hasher},
+};
+use rancor::{Error, Fallible};
 
 /// An archived `HashSet`. This is a wrapper around a hash map with the same key and a value of
 /// `()`.
+///
+/// Like [`ArchivedHashMap`], archiving sets with the same elements always produces identical bytes
+/// regardless of their insertion order or hasher. Keys are hashed with `H`.
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 #[repr(transparent)]
-pub struct ArchivedHashSet<K>(ArchivedHashMap<K, ()>);
+pub struct ArchivedHashSet<K, H = DefaultHasher>(ArchivedHashMap<K, (), H>);
 
-impl<K> ArchivedHashSet<K> {
+impl<K, H> ArchivedHashSet<K, H> {
     /// Gets the number of items in the hash set.
     #[inline]
     pub const fn len(&self) -> usize {
@@ -31,6 +41,7 @@ impl<K> ArchivedHashSet<K> {
     where
         K: Borrow<Q>,
         Q: Hash + Eq,
+        H: Hasher + Default,
     {
         self.0.get_key_value(k).map(|(k, _)| k)
     }
@@ -41,14 +52,17 @@ impl<K> ArchivedHashSet<K> {
     where
         K: Borrow<Q>,
         Q: Hash + Eq,
+        H: Hasher + Default,
     {
         self.0.contains_key(k)
     }
 
     /// Gets the hasher for the underlying hash map.
-    #[cfg(feature = "alloc")]
     #[inline]
-    pub fn hasher(&self) -> seahash::SeaHasher {
+    pub fn hasher(&self) -> H
+    where
+        H: Hasher + Default,
+    {
         self.0.hasher()
     }
 
@@ -96,16 +110,20 @@ impl<K> ArchivedHashSet<K> {
     where
         KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
         S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Error,
         I: ExactSizeIterator<Item = &'a KU>,
+        H: Hasher + Default,
     {
-        Ok(HashSetResolver(ArchivedHashMap::serialize_from_iter(
-            iter.map(|x| (x, &())),
-            serializer,
-        )?))
+        Ok(HashSetResolver(
+            ArchivedHashMap::<K, (), H>::serialize_from_iter(
+                iter.map(|x| (x, &())),
+                serializer,
+            )?,
+        ))
     }
 }
 
-impl<K: fmt::Debug> fmt::Debug for ArchivedHashSet<K> {
+impl<K: fmt::Debug, H> fmt::Debug for ArchivedHashSet<K, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
     }
@@ -114,11 +132,11 @@ impl<K: fmt::Debug> fmt::Debug for ArchivedHashSet<K> {
 /// The resolver for archived hash sets.
 pub struct HashSetResolver(HashMapResolver);
 
-impl<K: Hash + Eq> PartialEq for ArchivedHashSet<K> {
+impl<K: Hash + Eq, H: Hasher + Default> PartialEq for ArchivedHashSet<K, H> {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
     }
 }
 
-impl<K: Hash + Eq> Eq for ArchivedHashSet<K> {}
+impl<K: Hash + Eq, H: Hasher + Default> Eq for ArchivedHashSet<K, H> {}
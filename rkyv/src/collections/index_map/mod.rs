@@ -224,7 +224,7 @@ impl<K, V> ArchivedIndexMap<K, V> {
 
 #[cfg(feature = "alloc")]
 const _: () = {
-    use rancor::Fallible;
+    use rancor::{Error, Fallible};
 
     use crate::{
         ser::{Allocator, Writer},
@@ -249,14 +249,12 @@ const _: () = {
             I: Clone + ExactSizeIterator<Item = (&'a UK, &'a UV)>,
             F: Fn(&UK) -> usize,
             S: Fallible + Writer + Allocator + ?Sized,
+            S::Error: Error,
         {
             use crate::util::ScratchVec;
 
-            let len = iter.len();
-
             let mut entries = ScratchVec::new(serializer, iter.len())?;
-            entries.set_len(len);
-            let index_resolver = ArchivedHashIndex::build_and_serialize(
+            let index_resolver = <ArchivedHashIndex>::build_and_serialize(
                 iter.clone(),
                 serializer,
                 &mut entries,
@@ -11,7 +11,7 @@ use crate::{
     out_field,
 };
 use core::{borrow::Borrow, fmt, hash::Hash};
-use rancor::Fallible;
+use rancor::{Error, Fallible};
 
 /// An archived `IndexSet`.
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
@@ -147,6 +147,7 @@ const _: () = {
             I: Clone + ExactSizeIterator<Item = &'a UK>,
             F: Fn(&UK) -> usize,
             S: Fallible + Allocator + Writer + ?Sized,
+            S::Error: Error,
         {
             Ok(IndexSetResolver(
                 ArchivedIndexMap::serialize_from_iter_index(
@@ -1,3 +1,6 @@
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use ::alloc::boxed::Box;
+
 use super::{Pooling, SharedPointer};
 
 /// A shared pointer strategy that duplicates deserializations of the same
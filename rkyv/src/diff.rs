@@ -0,0 +1,153 @@
+//! Field-level differences between archived values.
+//!
+//! Deriving `Archive` with `#[archive(diff)]` implements [`DiffFields`] for the
+//! archived type, which compares two archived values field by field. [`diff`]
+//! and [`diff_archived`] use it to find the byte ranges of the fields that
+//! changed between two archives, for example to send only those ranges as an
+//! incremental update.
+//!
+//! Only the inline bytes of each field are reported. Fields that point to data
+//! elsewhere in the archive, like `String`, `Vec`, and `Box` fields, are
+//! reported when the data they point to differs, but the reported ranges only
+//! cover their relative pointers and lengths. Copying the reported ranges into
+//! an old archive is not enough to update those fields.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{mem::size_of_val, ops::Range};
+
+#[cfg(feature = "bytecheck")]
+use bytecheck::CheckBytes;
+#[cfg(feature = "bytecheck")]
+use rancor::{Error, Strategy};
+
+#[cfg(feature = "bytecheck")]
+use crate::{validation::validators::DefaultValidator, Archive};
+
+/// A field that differs between two archived values.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldDiff {
+    /// The name of the archived field that differs.
+    ///
+    /// Tuple fields are named by their index, and the fields of enum variants
+    /// are prefixed with the name of the variant (e.g. `Variant::field`). This
+    /// is `None` if the values are different variants of an enum, in which
+    /// case the ranges cover the entire values.
+    pub field: Option<&'static str>,
+    /// The range of bytes of the field in the old archive.
+    pub old: Range<usize>,
+    /// The range of bytes of the field in the new archive.
+    pub new: Range<usize>,
+}
+
+/// An archived type that can compare itself to another value field by field.
+///
+/// This can be derived by adding `#[archive(diff)]` to a type that derives
+/// `Archive`. The archived type of each field must implement `PartialEq`.
+pub trait DiffFields {
+    /// Calls `report` for each field that differs between `self` and `other`.
+    ///
+    /// `report` is called with the name of the field (see
+    /// [`FieldDiff::field`]) and the address ranges of the field in `self` and
+    /// `other`. Fields are compared with `PartialEq`, and the ranges only cover
+    /// the inline bytes of each field. If a field contains a relative pointer,
+    /// like a `String`, `Vec`, or `Box` field, the data it points to is not
+    /// included in the range even if that data is what differs.
+    fn diff_fields(
+        &self,
+        other: &Self,
+        report: &mut dyn FnMut(Option<&'static str>, Range<usize>, Range<usize>),
+    );
+}
+
+/// Returns the range of addresses occupied by the given value.
+#[doc(hidden)]
+#[inline]
+pub fn address_range<T: ?Sized>(value: &T) -> Range<usize> {
+    let start = value as *const T as *const u8 as usize;
+    start..start + size_of_val(value)
+}
+
+/// Returns the fields that differ between two archived values.
+///
+/// The byte ranges of the returned differences are relative to the start of
+/// `old_bytes` and `new_bytes`. The ranges only cover the inline bytes of each
+/// field, see [`DiffFields::diff_fields`] for details.
+///
+/// # Panics
+///
+/// Panics if a differing field of `old` is not located in `old_bytes` or a
+/// differing field of `new` is not located in `new_bytes`.
+pub fn diff_archived<T: DiffFields + ?Sized>(
+    old_bytes: &[u8],
+    old: &T,
+    new_bytes: &[u8],
+    new: &T,
+) -> Vec<FieldDiff> {
+    let mut differences = Vec::new();
+    old.diff_fields(new, &mut |field, old, new| {
+        differences.push(FieldDiff {
+            field,
+            old: relative_range(old_bytes, old),
+            new: relative_range(new_bytes, new),
+        });
+    });
+    differences
+}
+
+/// Returns the given range of addresses relative to the start of `bytes`.
+fn relative_range(bytes: &[u8], range: Range<usize>) -> Range<usize> {
+    let base = bytes.as_ptr() as usize;
+    assert!(
+        base <= range.start
+            && range.start <= range.end
+            && range.end <= base + bytes.len(),
+        "archived field is not located in the given bytes",
+    );
+    range.start - base..range.end - base
+}
+
+/// Checks two archives of the same type and returns the fields of their roots
+/// that differ.
+///
+/// See [`diff_archived`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{diff::diff, rancor::Failure, to_bytes, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive(check_bytes, diff)]
+/// struct Example {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// let old = to_bytes::<_, 256, Failure>(&Example {
+///     id: 1,
+///     name: "hello".to_string(),
+/// })
+/// .unwrap();
+/// let new = to_bytes::<_, 256, Failure>(&Example {
+///     id: 2,
+///     name: "hello".to_string(),
+/// })
+/// .unwrap();
+///
+/// let differences = diff::<Example, Failure>(&old, &new).unwrap();
+/// assert_eq!(differences.len(), 1);
+/// assert_eq!(differences[0].field, Some("id"));
+/// ```
+#[cfg(feature = "bytecheck")]
+#[inline]
+pub fn diff<T, E>(old: &[u8], new: &[u8]) -> Result<Vec<FieldDiff>, E>
+where
+    T: Archive,
+    T::Archived: DiffFields + CheckBytes<Strategy<DefaultValidator, E>>,
+    E: Error,
+{
+    let old_root = crate::access::<T, E>(old)?;
+    let new_root = crate::access::<T, E>(new)?;
+    Ok(diff_archived(old, old_root, new, new_root))
+}
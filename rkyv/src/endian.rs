@@ -0,0 +1,221 @@
+//! Conversion of archives between little- and big-endian byte orders.
+//!
+//! The byte order of archived multi-byte scalars is chosen when rkyv is built
+//! with the `little_endian` or `big_endian` feature. Archives written with one
+//! byte order can be converted to the other by reversing the bytes of each
+//! multi-byte scalar in place, since the positions of all archived values stay
+//! the same. [`SwapEndian`] finds those scalars by walking an archived value
+//! and the values it points to, and [`swap_endian`] uses it to convert a whole
+//! archive. This allows archive files to be migrated between builds with
+//! different byte orders offline.
+//!
+//! Deriving `Archive` with `#[archive(swap_endian)]` implements `SwapEndian`
+//! for the archived type.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+use core::{marker::PhantomData, mem::size_of_val, ops::Range};
+
+#[cfg(all(feature = "alloc", feature = "bytecheck"))]
+use bytecheck::CheckBytes;
+#[cfg(all(feature = "alloc", feature = "bytecheck"))]
+use rancor::{Error, Strategy};
+
+use crate::primitive::{
+    ArchivedChar, ArchivedF32, ArchivedF64, ArchivedI128, ArchivedI16,
+    ArchivedI32, ArchivedI64, ArchivedNonZeroI128, ArchivedNonZeroI16,
+    ArchivedNonZeroI32, ArchivedNonZeroI64, ArchivedNonZeroU128,
+    ArchivedNonZeroU16, ArchivedNonZeroU32, ArchivedNonZeroU64, ArchivedU128,
+    ArchivedU16, ArchivedU32, ArchivedU64,
+};
+#[cfg(all(feature = "alloc", feature = "bytecheck"))]
+use crate::{
+    util::AlignedVec, validation::validators::DefaultValidator, Archive,
+};
+
+/// An archived type that can find the multi-byte scalars it contains.
+///
+/// This can be derived by adding `#[archive(swap_endian)]` to a type that
+/// derives `Archive`. The archived type of each field must implement
+/// `SwapEndian`.
+pub trait SwapEndian {
+    /// Calls `report` with the address range of each multi-byte scalar in
+    /// this value and the values it points to.
+    ///
+    /// Reversing the bytes of every reported range converts the value to the
+    /// opposite byte order. Values that are pointed to more than once (like
+    /// shared pointers) may be reported more than once.
+    fn scalar_ranges(&self, report: &mut dyn FnMut(Range<usize>));
+}
+
+/// Returns the range of addresses occupied by the given value.
+#[inline]
+fn address_range<T: ?Sized>(value: &T) -> Range<usize> {
+    let start = value as *const T as *const u8 as usize;
+    start..start + size_of_val(value)
+}
+
+macro_rules! impl_swap_endian_scalar {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl SwapEndian for $ty {
+                #[inline]
+                fn scalar_ranges(
+                    &self,
+                    report: &mut dyn FnMut(Range<usize>),
+                ) {
+                    report(address_range(self));
+                }
+            }
+        )*
+    };
+}
+
+impl_swap_endian_scalar! {
+    ArchivedI16,
+    ArchivedI32,
+    ArchivedI64,
+    ArchivedI128,
+    ArchivedU16,
+    ArchivedU32,
+    ArchivedU64,
+    ArchivedU128,
+    ArchivedF32,
+    ArchivedF64,
+    ArchivedChar,
+    ArchivedNonZeroI16,
+    ArchivedNonZeroI32,
+    ArchivedNonZeroI64,
+    ArchivedNonZeroI128,
+    ArchivedNonZeroU16,
+    ArchivedNonZeroU32,
+    ArchivedNonZeroU64,
+    ArchivedNonZeroU128,
+}
+
+macro_rules! impl_swap_endian_single_byte {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl SwapEndian for $ty {
+                #[inline]
+                fn scalar_ranges(&self, _: &mut dyn FnMut(Range<usize>)) {}
+            }
+        )*
+    };
+}
+
+impl_swap_endian_single_byte! {
+    (),
+    bool,
+    i8,
+    u8,
+    core::num::NonZeroI8,
+    core::num::NonZeroU8,
+    str,
+}
+
+impl<T: ?Sized> SwapEndian for PhantomData<T> {
+    #[inline]
+    fn scalar_ranges(&self, _: &mut dyn FnMut(Range<usize>)) {}
+}
+
+impl<T: SwapEndian> SwapEndian for [T] {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(Range<usize>)) {
+        for value in self {
+            value.scalar_ranges(report);
+        }
+    }
+}
+
+impl<T: SwapEndian, const N: usize> SwapEndian for [T; N] {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(Range<usize>)) {
+        self.as_slice().scalar_ranges(report);
+    }
+}
+
+/// Returns the ranges of the multi-byte scalars in an archived value.
+///
+/// The returned ranges are relative to the start of `bytes`, sorted, and
+/// without duplicates, so `root` and all of the values that it points to must
+/// be located in `bytes`. The ranges are the same in both byte orders, so they
+/// can be used with [`swap_scalars`] to convert the archive back and forth.
+#[cfg(feature = "alloc")]
+pub fn scalar_ranges<T: SwapEndian + ?Sized>(
+    bytes: &[u8],
+    root: &T,
+) -> Vec<Range<usize>> {
+    let base = bytes.as_ptr() as usize;
+    let mut ranges = Vec::new();
+    root.scalar_ranges(&mut |range| {
+        ranges.push(range.start - base..range.end - base);
+    });
+    ranges.sort_by_key(|range| range.start);
+    ranges.dedup();
+    ranges
+}
+
+/// Reverses the bytes of each of the given ranges in `bytes`.
+///
+/// Each range must only be given once, otherwise its bytes are swapped back.
+#[inline]
+pub fn swap_scalars(bytes: &mut [u8], ranges: &[Range<usize>]) {
+    for range in ranges {
+        bytes[range.clone()].reverse();
+    }
+}
+
+/// Checks an archive of a type and converts it to the opposite byte order.
+///
+/// The returned archive can only be accessed by builds of rkyv with the other
+/// `*_endian` feature enabled. Converting it back requires the ranges of its
+/// scalars, which can be found from the original archive with
+/// [`scalar_ranges`].
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{
+///     access,
+///     endian::{scalar_ranges, swap_endian, swap_scalars},
+///     rancor::Failure,
+///     to_bytes, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive(check_bytes, swap_endian)]
+/// struct Example {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// let value = Example {
+///     id: 0x01020304,
+///     name: "hello".to_string(),
+/// };
+/// let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+/// let mut swapped = swap_endian::<Example, Failure>(&bytes).unwrap();
+/// assert_ne!(swapped.as_slice(), bytes.as_slice());
+///
+/// // Swapping the same scalars again restores the original archive
+/// let archived = access::<Example, Failure>(&bytes).unwrap();
+/// swap_scalars(&mut swapped, &scalar_ranges(&bytes, archived));
+/// assert_eq!(swapped.as_slice(), bytes.as_slice());
+/// ```
+#[cfg(all(feature = "alloc", feature = "bytecheck"))]
+#[inline]
+pub fn swap_endian<T, E>(bytes: &[u8]) -> Result<AlignedVec, E>
+where
+    T: Archive,
+    T::Archived: SwapEndian + CheckBytes<Strategy<DefaultValidator, E>>,
+    E: Error,
+{
+    let root = crate::access::<T, E>(bytes)?;
+    let ranges = scalar_ranges(bytes, root);
+
+    let mut result = AlignedVec::with_capacity(bytes.len());
+    result.extend_from_slice(bytes);
+    swap_scalars(&mut result, &ranges);
+    Ok(result)
+}
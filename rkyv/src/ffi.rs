@@ -10,6 +10,14 @@ use core::{
     pin::Pin,
 };
 use std::ffi::CStr;
+#[cfg(unix)]
+use std::{ffi::OsStr, os::unix::ffi::OsStrExt as _};
+
+#[cfg(unix)]
+use crate::{
+    ser::Allocator,
+    vec::{ArchivedVec, VecResolver},
+};
 
 /// An archived [`CString`](std::ffi::CString).
 ///
@@ -77,10 +85,14 @@ impl ArchivedCString {
 
     /// Serializes a C string.
     #[inline]
-    pub fn serialize_from_c_str<S: Fallible + Writer + ?Sized>(
+    pub fn serialize_from_c_str<S>(
         c_str: &CStr,
         serializer: &mut S,
-    ) -> Result<CStringResolver, S::Error> {
+    ) -> Result<CStringResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+        CStr: SerializeUnsized<S>,
+    {
         Ok(CStringResolver {
             pos: c_str.serialize_unsized(serializer)?,
         })
@@ -174,6 +186,127 @@ pub struct CStringResolver {
     pos: usize,
 }
 
+/// An archived [`OsString`](std::ffi::OsString).
+///
+/// This stores the raw bytes of the OS string, and so is only available on
+/// Unix platforms where OS strings are arbitrary byte sequences. On other
+/// platforms (e.g. Windows, where OS strings are potentially ill-formed
+/// UTF-16), use the [`AsString`](crate::with::AsString) wrapper instead.
+#[cfg(unix)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct ArchivedOsString {
+    bytes: ArchivedVec<u8>,
+}
+
+#[cfg(unix)]
+impl ArchivedOsString {
+    /// Returns the raw bytes of this OS string.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Extracts an `OsStr` slice containing the entire string.
+    #[inline]
+    pub fn as_os_str(&self) -> &OsStr {
+        OsStr::from_bytes(self.as_bytes())
+    }
+
+    /// Resolves an archived OS string from the given OS string and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `os_str`
+    #[inline]
+    pub unsafe fn resolve_from_os_str(
+        os_str: &OsStr,
+        pos: usize,
+        resolver: OsStringResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.bytes);
+        ArchivedVec::resolve_from_slice(
+            os_str.as_bytes(),
+            pos + fp,
+            resolver.0,
+            fo,
+        );
+    }
+
+    /// Serializes an OS string.
+    #[inline]
+    pub fn serialize_from_os_str<S>(
+        os_str: &OsStr,
+        serializer: &mut S,
+    ) -> Result<OsStringResolver, S::Error>
+    where
+        S: Fallible + Allocator + Writer + ?Sized,
+        [u8]: SerializeUnsized<S>,
+    {
+        Ok(OsStringResolver(ArchivedVec::serialize_from_slice(
+            os_str.as_bytes(),
+            serializer,
+        )?))
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<OsStr> for ArchivedOsString {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+#[cfg(unix)]
+impl Borrow<OsStr> for ArchivedOsString {
+    #[inline]
+    fn borrow(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+#[cfg(unix)]
+impl fmt::Debug for ArchivedOsString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_os_str().fmt(f)
+    }
+}
+
+#[cfg(unix)]
+impl Deref for ArchivedOsString {
+    type Target = OsStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_os_str()
+    }
+}
+
+#[cfg(unix)]
+impl PartialEq<&OsStr> for ArchivedOsString {
+    #[inline]
+    fn eq(&self, other: &&OsStr) -> bool {
+        PartialEq::eq(self.as_os_str(), *other)
+    }
+}
+
+#[cfg(unix)]
+impl PartialEq<ArchivedOsString> for &OsStr {
+    #[inline]
+    fn eq(&self, other: &ArchivedOsString) -> bool {
+        PartialEq::eq(other.as_os_str(), *self)
+    }
+}
+
+/// The resolver for `OsString`.
+#[cfg(unix)]
+pub struct OsStringResolver(VecResolver);
+
 #[cfg(feature = "bytecheck")]
 mod verify {
     use core::ffi::CStr;
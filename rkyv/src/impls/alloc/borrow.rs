@@ -0,0 +1,132 @@
+use crate::{
+    ser::{Allocator, Writer},
+    string::{ArchivedString, StringResolver},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, DeserializeUnsized, Serialize, SerializeUnsized,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, vec::Vec};
+use rancor::Fallible;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+// Cow<'a, str>
+
+impl<'a> Archive for Cow<'a, str> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedString::resolve_from_str(self, pos, resolver, out);
+    }
+}
+
+impl<'a, S: Fallible + ?Sized> Serialize<S> for Cow<'a, str>
+where
+    str: SerializeUnsized<S>,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self, serializer)
+    }
+}
+
+impl<'a, D: Fallible + ?Sized> Deserialize<Cow<'a, str>, D> for ArchivedString
+where
+    str: DeserializeUnsized<str, D>,
+{
+    #[inline]
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Cow<'a, str>, D::Error> {
+        Ok(Cow::Owned(self.deserialize(deserializer)?))
+    }
+}
+
+impl<'a> PartialEq<Cow<'a, str>> for ArchivedString {
+    #[inline]
+    fn eq(&self, other: &Cow<'a, str>) -> bool {
+        PartialEq::eq(self.as_str(), &**other)
+    }
+}
+
+impl<'a> PartialEq<ArchivedString> for Cow<'a, str> {
+    #[inline]
+    fn eq(&self, other: &ArchivedString) -> bool {
+        PartialEq::eq(other.as_str(), &**self)
+    }
+}
+
+// Cow<'a, [T]>
+
+impl<'a, T: Archive + Clone> Archive for Cow<'a, [T]> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedVec::resolve_from_slice(self, pos, resolver, out);
+    }
+}
+
+impl<'a, T, S> Serialize<S> for Cow<'a, [T]>
+where
+    T: Serialize<S> + Clone,
+    S: Fallible + Allocator + Writer + ?Sized,
+    [T]: SerializeUnsized<S>,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_slice(self, serializer)
+    }
+}
+
+impl<'a, T, D> Deserialize<Cow<'a, [T]>, D> for ArchivedVec<T::Archived>
+where
+    T: Archive + Clone,
+    [T::Archived]: DeserializeUnsized<[T], D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Cow<'a, [T]>, D::Error> {
+        let vec: Vec<T> = self.deserialize(deserializer)?;
+        Ok(Cow::Owned(vec))
+    }
+}
+
+impl<'a, T: PartialEq<U>, U: Clone> PartialEq<Cow<'a, [U]>> for ArchivedVec<T> {
+    #[inline]
+    fn eq(&self, other: &Cow<'a, [U]>) -> bool {
+        self.as_slice().eq(&**other)
+    }
+}
+
+impl<'a, T: PartialEq<U> + Clone, U> PartialEq<ArchivedVec<U>>
+    for Cow<'a, [T]>
+{
+    #[inline]
+    fn eq(&self, other: &ArchivedVec<U>) -> bool {
+        (**self).eq(other.as_slice())
+    }
+}
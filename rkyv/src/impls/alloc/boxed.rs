@@ -4,7 +4,7 @@ use crate::{
     Serialize, SerializeUnsized,
 };
 #[cfg(not(feature = "std"))]
-use alloc::{alloc, boxed::Box};
+use ::alloc::{alloc, boxed::Box};
 use core::cmp;
 use rancor::Fallible;
 #[cfg(feature = "std")]
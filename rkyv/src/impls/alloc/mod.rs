@@ -1,3 +1,4 @@
+mod borrow;
 mod boxed;
 mod collections;
 mod niche;
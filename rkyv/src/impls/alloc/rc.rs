@@ -9,7 +9,7 @@ use crate::{
     Serialize, SerializeUnsized,
 };
 #[cfg(not(feature = "std"))]
-use alloc::{alloc, boxed::Box, rc, sync};
+use ::alloc::{alloc, boxed::Box, rc, sync};
 use core::mem::forget;
 use rancor::Fallible;
 #[cfg(feature = "std")]
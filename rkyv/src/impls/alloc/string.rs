@@ -11,6 +11,11 @@ impl Archive for String {
     type Archived = ArchivedString;
     type Resolver = StringResolver;
 
+    #[inline]
+    fn serialized_size_hint(&self) -> usize {
+        self.len()
+    }
+
     #[inline]
     unsafe fn resolve(
         &self,
@@ -1,10 +1,10 @@
 use crate::{
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
-    Archive, Deserialize, DeserializeUnsized, Serialize,
+    Archive, Deserialize, DeserializeUnsized, Serialize, SerializeUnsized,
 };
 #[cfg(not(feature = "std"))]
-use alloc::{alloc, boxed::Box, vec::Vec};
+use ::alloc::{alloc, boxed::Box, vec::Vec};
 use core::cmp;
 use rancor::Fallible;
 #[cfg(feature = "std")]
@@ -42,6 +42,12 @@ impl<T: Archive> Archive for Vec<T> {
     type Archived = ArchivedVec<T::Archived>;
     type Resolver = VecResolver;
 
+    #[inline]
+    fn serialized_size_hint(&self) -> usize {
+        self.len() * core::mem::size_of::<T::Archived>()
+            + self.iter().map(T::serialized_size_hint).sum::<usize>()
+    }
+
     #[inline]
     unsafe fn resolve(
         &self,
@@ -53,8 +59,11 @@ impl<T: Archive> Archive for Vec<T> {
     }
 }
 
-impl<T: Serialize<S>, S: Fallible + Allocator + Writer + ?Sized> Serialize<S>
-    for Vec<T>
+impl<T, S> Serialize<S> for Vec<T>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    [T]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
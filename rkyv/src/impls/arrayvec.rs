@@ -1,7 +1,7 @@
 use crate::{
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
-    Archive, Archived, Deserialize, Fallible, Serialize,
+    Archive, Archived, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
 use arrayvec::ArrayVec;
 
@@ -27,6 +27,7 @@ impl<T, S, const CAP: usize> Serialize<S> for ArrayVec<T, CAP>
 where
     T: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    [T]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
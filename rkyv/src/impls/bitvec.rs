@@ -6,7 +6,7 @@ use crate::{
     primitive::ArchivedUsize,
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
-    Archive, Archived, Deserialize, Fallible, Serialize,
+    Archive, Archived, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
 use core::convert::{TryFrom, TryInto};
 use core::{marker::PhantomData, ops::Deref};
@@ -52,6 +52,8 @@ where
     T: BitStore + Archive + Serialize<S>,
     O: BitOrder,
     S: Fallible + ?Sized + Allocator + Writer,
+    [T]: SerializeUnsized<S>,
+    usize: Serialize<S>,
     Archived<T>: BitStore,
 {
     fn serialize(
@@ -73,6 +75,7 @@ where
     O: BitOrder,
     D: Fallible + ?Sized,
     Archived<T>: Deserialize<T, D> + BitStore,
+    Archived<usize>: Deserialize<usize, D>,
 {
     fn deserialize(
         &self,
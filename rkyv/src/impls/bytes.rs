@@ -1,7 +1,7 @@
 use crate::{
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
-    Archive, Archived, Deserialize, Fallible, Serialize,
+    Archive, Archived, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
 use bytes::{Bytes, BytesMut};
 
@@ -20,7 +20,11 @@ impl Archive for Bytes {
     }
 }
 
-impl<S: Fallible + Allocator + Writer + ?Sized> Serialize<S> for Bytes {
+impl<S> Serialize<S> for Bytes
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    [u8]: SerializeUnsized<S>,
+{
     #[inline]
     fn serialize(
         &self,
@@ -74,4 +78,48 @@ mod tests {
             deserialize::<Bytes, _, Infallible>(archived, &mut ()).unwrap();
         assert_eq!(value, deserialized);
     }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn access_bytes() {
+        use crate::{
+            to_bytes,
+            util::AlignedVec,
+            validation::util::{access_bytes, access_bytes_copied},
+        };
+
+        let buf =
+            to_bytes::<_, 256, Failure>(&vec!["hello".to_string()]).unwrap();
+
+        let mut unaligned = AlignedVec::new();
+        unaligned.push(0);
+        unaligned.extend_from_slice(&buf);
+        let storage = Box::leak(Box::new(unaligned));
+        let aligned = Bytes::from_static(Box::leak(Box::new(buf)).as_slice());
+        let unaligned = Bytes::from_static(&storage[1..]);
+
+        let archived = access_bytes::<Vec<String>, Failure>(&aligned).unwrap();
+        assert_eq!(archived[0], "hello");
+        access_bytes::<Vec<String>, Failure>(&unaligned)
+            .expect_err("expected unaligned bytes error");
+
+        // Aligned bytes are accessed without copying
+        let mut buffer = AlignedVec::new();
+        let archived =
+            access_bytes_copied::<Vec<String>, Failure>(&aligned, &mut buffer)
+                .unwrap();
+        assert_eq!(archived[0], "hello");
+        assert!(core::ptr::eq(archived.as_ptr(), unsafe {
+            crate::access_unchecked::<Vec<String>>(&aligned).as_ptr()
+        }));
+        assert!(buffer.is_empty());
+
+        let archived = access_bytes_copied::<Vec<String>, Failure>(
+            &unaligned,
+            &mut buffer,
+        )
+        .unwrap();
+        assert_eq!(archived[0], "hello");
+        assert_eq!(buffer.as_slice(), &unaligned[..]);
+    }
 }
@@ -0,0 +1,172 @@
+use crate::{Archive, Deserialize, Serialize};
+use core::sync::atomic::Ordering;
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::{AtomicBool, AtomicI8, AtomicU8};
+use rancor::Fallible;
+#[cfg(target_has_atomic = "16")]
+use {
+    crate::primitive::{ArchivedI16, ArchivedU16},
+    core::sync::atomic::{AtomicI16, AtomicU16},
+};
+#[cfg(target_has_atomic = "32")]
+use {
+    crate::primitive::{ArchivedI32, ArchivedU32},
+    core::sync::atomic::{AtomicI32, AtomicU32},
+};
+#[cfg(target_has_atomic = "64")]
+use {
+    crate::primitive::{ArchivedI64, ArchivedU64},
+    core::sync::atomic::{AtomicI64, AtomicU64},
+};
+#[cfg(target_has_atomic = "ptr")]
+use {
+    crate::primitive::{ArchivedIsize, ArchivedUsize, FixedIsize, FixedUsize},
+    core::sync::atomic::{AtomicIsize, AtomicUsize},
+    rancor::{Error, ResultExt as _},
+};
+
+// Atomics are archived as the plain values they contain, since archived values
+// are read-only and don't need any atomic operations.
+
+macro_rules! impl_serialize_noop {
+    ($atomic:ty) => {
+        impl<S: Fallible + ?Sized> Serialize<S> for $atomic {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! impl_single_byte_atomic {
+    ($atomic:ty, $non_atomic:ty) => {
+        impl Archive for $atomic {
+            type Archived = $non_atomic;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                _: usize,
+                _: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                out.write(self.load(Ordering::Relaxed));
+            }
+        }
+
+        impl_serialize_noop!($atomic);
+
+        impl<D: Fallible + ?Sized> Deserialize<$atomic, D> for $non_atomic {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$atomic, D::Error> {
+                Ok(<$atomic>::new(*self))
+            }
+        }
+    };
+}
+
+#[cfg(target_has_atomic = "8")]
+impl_single_byte_atomic!(AtomicBool, bool);
+#[cfg(target_has_atomic = "8")]
+impl_single_byte_atomic!(AtomicI8, i8);
+#[cfg(target_has_atomic = "8")]
+impl_single_byte_atomic!(AtomicU8, u8);
+
+macro_rules! impl_multi_byte_atomic {
+    ($atomic:ty, $archived:ty) => {
+        impl Archive for $atomic {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                _: usize,
+                _: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                out.write(<$archived>::from_native(
+                    self.load(Ordering::Relaxed),
+                ));
+            }
+        }
+
+        impl_serialize_noop!($atomic);
+
+        impl<D: Fallible + ?Sized> Deserialize<$atomic, D> for $archived {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$atomic, D::Error> {
+                Ok(<$atomic>::new(self.to_native()))
+            }
+        }
+    };
+}
+
+#[cfg(target_has_atomic = "16")]
+impl_multi_byte_atomic!(AtomicI16, ArchivedI16);
+#[cfg(target_has_atomic = "16")]
+impl_multi_byte_atomic!(AtomicU16, ArchivedU16);
+#[cfg(target_has_atomic = "32")]
+impl_multi_byte_atomic!(AtomicI32, ArchivedI32);
+#[cfg(target_has_atomic = "32")]
+impl_multi_byte_atomic!(AtomicU32, ArchivedU32);
+#[cfg(target_has_atomic = "64")]
+impl_multi_byte_atomic!(AtomicI64, ArchivedI64);
+#[cfg(target_has_atomic = "64")]
+impl_multi_byte_atomic!(AtomicU64, ArchivedU64);
+
+// AtomicUsize and AtomicIsize
+
+macro_rules! impl_atomic_size_type {
+    ($atomic:ty, $size:ty, $fixed:ty, $archived:ty) => {
+        impl Archive for $atomic {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                _: usize,
+                _: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                out.write(<$archived>::from_native(
+                    self.load(Ordering::Relaxed) as _,
+                ));
+            }
+        }
+
+        impl<S> Serialize<S> for $atomic
+        where
+            S: Fallible + ?Sized,
+            S::Error: Error,
+        {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                <$fixed>::try_from(self.load(Ordering::Relaxed))
+                    .into_error()?;
+                Ok(())
+            }
+        }
+
+        impl<D> Deserialize<$atomic, D> for $archived
+        where
+            D: Fallible + ?Sized,
+            D::Error: Error,
+        {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$atomic, D::Error> {
+                Ok(<$atomic>::new(
+                    <$size>::try_from(self.to_native()).into_error()?,
+                ))
+            }
+        }
+    };
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl_atomic_size_type!(AtomicUsize, usize, FixedUsize, ArchivedUsize);
+#[cfg(target_has_atomic = "ptr")]
+impl_atomic_size_type!(AtomicIsize, isize, FixedIsize, ArchivedIsize);
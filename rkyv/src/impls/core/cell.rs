@@ -0,0 +1,120 @@
+use crate::{Archive, CopyOptimization, Deserialize, Serialize};
+use core::{
+    cell::{Cell, RefCell},
+    fmt,
+};
+use rancor::{fail, Error, Fallible};
+
+// Cell
+
+impl<T: Archive + Copy> Archive for Cell<T> {
+    const COPY_OPTIMIZATION: CopyOptimization<Self> = unsafe {
+        CopyOptimization::enable_if(T::COPY_OPTIMIZATION.is_enabled())
+    };
+
+    type Archived = T::Archived;
+    type Resolver = T::Resolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        self.get().resolve(pos, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for Cell<T>
+where
+    T: Serialize<S> + Copy,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Cell<T>, D> for T::Archived
+where
+    T: Archive + Copy,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Cell<T>, D::Error> {
+        Ok(Cell::new(self.deserialize(deserializer)?))
+    }
+}
+
+// RefCell
+
+#[derive(Debug)]
+struct MutablyBorrowed;
+
+impl fmt::Display for MutablyBorrowed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tried to serialize a `RefCell` while it was mutably borrowed"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MutablyBorrowed {}
+
+impl<T: Archive> Archive for RefCell<T> {
+    type Archived = T::Archived;
+    type Resolver = T::Resolver;
+
+    // This panics if the `RefCell` has been mutably borrowed since it was
+    // serialized, in which case the resolver may not match its contents anyway
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        self.borrow().resolve(pos, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for RefCell<T>
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        match self.try_borrow() {
+            Ok(value) => value.serialize(serializer),
+            Err(_) => fail!(MutablyBorrowed),
+        }
+    }
+}
+
+impl<T, D> Deserialize<RefCell<T>, D> for T::Archived
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<RefCell<T>, D::Error> {
+        Ok(RefCell::new(self.deserialize(deserializer)?))
+    }
+}
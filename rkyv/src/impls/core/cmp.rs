@@ -0,0 +1,50 @@
+use crate::{
+    cmp::ArchivedReverse, Archive, CopyOptimization, Deserialize, Serialize,
+};
+use core::cmp::Reverse;
+use rancor::Fallible;
+
+impl<T: Archive> Archive for Reverse<T> {
+    const COPY_OPTIMIZATION: CopyOptimization<Self> = unsafe {
+        CopyOptimization::enable_if(T::COPY_OPTIMIZATION.is_enabled())
+    };
+
+    type Archived = ArchivedReverse<T::Archived>;
+    type Resolver = T::Resolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        let (fp, fo) = out_field!(out.0);
+        self.0.resolve(pos + fp, resolver, fo);
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Reverse<T> {
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Reverse<T>, D> for ArchivedReverse<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<Reverse<T>, D::Error> {
+        Ok(Reverse(self.0.deserialize(deserializer)?))
+    }
+}
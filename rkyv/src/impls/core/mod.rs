@@ -1,15 +1,20 @@
 #[cfg(feature = "copy")]
 use crate::copy::ArchiveCopyOptimize;
 use crate::{
-    primitive::ArchivedUsize,
+    primitive::{ArchivedUsize, FixedUsize},
     ser::{Allocator, Writer, WriterExt as _},
-    Archive, ArchivePointee, ArchiveUnsized, ArchivedMetadata, Deserialize,
-    DeserializeUnsized, Serialize, SerializeUnsized,
+    Archive, ArchivePointee, ArchiveUnsized, ArchivedMetadata,
+    CopyOptimization, Deserialize, DeserializeUnsized, Serialize,
+    SerializeUnsized,
 };
 use core::{alloc::Layout, mem::ManuallyDrop, ptr, str};
 use ptr_meta::Pointee;
-use rancor::Fallible;
+use rancor::{Error, Fallible, ResultExt as _};
 
+mod atomic;
+mod cell;
+mod cmp;
+mod num;
 mod ops;
 mod option;
 mod primitive;
@@ -157,9 +162,18 @@ impl_tuple!(
 );
 
 impl<T: Archive, const N: usize> Archive for [T; N] {
+    const COPY_OPTIMIZATION: CopyOptimization<Self> = unsafe {
+        CopyOptimization::enable_if(T::COPY_OPTIMIZATION.is_enabled())
+    };
+
     type Archived = [T::Archived; N];
     type Resolver = [T::Resolver; N];
 
+    #[inline]
+    fn serialized_size_hint(&self) -> usize {
+        self.iter().map(T::serialized_size_hint).sum()
+    }
+
     #[inline]
     unsafe fn resolve(
         &self,
@@ -167,6 +181,11 @@ impl<T: Archive, const N: usize> Archive for [T; N] {
         resolver: Self::Resolver,
         out: *mut Self::Archived,
     ) {
+        if Self::COPY_OPTIMIZATION.is_enabled() {
+            out.cast::<T>().copy_from_nonoverlapping(self.as_ptr(), N);
+            return;
+        }
+
         let mut resolvers = core::mem::MaybeUninit::new(resolver);
         let resolvers_ptr = resolvers.as_mut_ptr().cast::<T::Resolver>();
         let out_ptr = out.cast::<T::Archived>();
@@ -224,6 +243,7 @@ impl<T: Archive> ArchiveUnsized for [T] {
     type Archived = [T::Archived];
 
     fn archived_metadata(&self) -> ArchivedMetadata<Self> {
+        // The length was checked to fit while serializing
         ArchivedUsize::from_native(ptr_meta::metadata(self) as _)
     }
 }
@@ -243,11 +263,28 @@ impl<T, S> SerializeUnsized<S> for [T]
 where
     T: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Error,
 {
     default! {
         fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
             use crate::util::ScratchVec;
 
+            FixedUsize::try_from(self.len()).into_error()?;
+
+            if T::COPY_OPTIMIZATION.is_enabled() {
+                let result = serializer.align_for::<T::Archived>()?;
+                // SAFETY: copy optimized types contain no padding bytes, so
+                // every byte of the slice is initialized
+                let bytes = unsafe {
+                    core::slice::from_raw_parts(
+                        self.as_ptr().cast::<u8>(),
+                        core::mem::size_of_val(self),
+                    )
+                };
+                serializer.write(bytes)?;
+                return Ok(result);
+            }
+
             unsafe {
                 let mut resolvers = ScratchVec::new(serializer, self.len())?;
 
@@ -272,8 +309,10 @@ impl<T, S> SerializeUnsized<S> for [T]
 where
     T: Serialize<S> + crate::copy::ArchiveCopyOptimize,
     S: Allocator + Writer + ?Sized,
+    S::Error: Error,
 {
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, E> {
+        FixedUsize::try_from(self.len()).into_error()?;
         unsafe {
             let result = serializer.align_for::<T>()?;
             if !self.is_empty() {
@@ -363,6 +402,7 @@ impl ArchiveUnsized for str {
 
     #[inline]
     fn archived_metadata(&self) -> ArchivedMetadata<Self> {
+        // The length was checked to fit while serializing
         ArchivedUsize::from_native(ptr_meta::metadata(self) as _)
     }
 }
@@ -378,9 +418,14 @@ impl ArchivePointee for str {
     }
 }
 
-impl<S: Fallible + Writer + ?Sized> SerializeUnsized<S> for str {
+impl<S> SerializeUnsized<S> for str
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Error,
+{
     #[inline]
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
+        FixedUsize::try_from(self.len()).into_error()?;
         let result = serializer.pos();
         serializer.write(self.as_bytes())?;
         Ok(result)
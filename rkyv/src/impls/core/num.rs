@@ -0,0 +1,58 @@
+use crate::{
+    num::{ArchivedSaturating, ArchivedWrapping},
+    Archive, CopyOptimization, Deserialize, Serialize,
+};
+use core::num::{Saturating, Wrapping};
+use rancor::Fallible;
+
+macro_rules! impl_num_wrapper {
+    ($ty:ident, $archived:ident) => {
+        impl<T: Archive> Archive for $ty<T> {
+            const COPY_OPTIMIZATION: CopyOptimization<Self> = unsafe {
+                CopyOptimization::enable_if(T::COPY_OPTIMIZATION.is_enabled())
+            };
+
+            type Archived = $archived<T::Archived>;
+            type Resolver = T::Resolver;
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                pos: usize,
+                resolver: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                let (fp, fo) = out_field!(out.0);
+                self.0.resolve(pos + fp, resolver, fo);
+            }
+        }
+
+        impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for $ty<T> {
+            #[inline]
+            fn serialize(
+                &self,
+                serializer: &mut S,
+            ) -> Result<Self::Resolver, S::Error> {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<T, D> Deserialize<$ty<T>, D> for $archived<T::Archived>
+        where
+            T: Archive,
+            T::Archived: Deserialize<T, D>,
+            D: Fallible + ?Sized,
+        {
+            #[inline]
+            fn deserialize(
+                &self,
+                deserializer: &mut D,
+            ) -> Result<$ty<T>, D::Error> {
+                Ok($ty(self.0.deserialize(deserializer)?))
+            }
+        }
+    };
+}
+
+impl_num_wrapper!(Wrapping, ArchivedWrapping);
+impl_num_wrapper!(Saturating, ArchivedSaturating);
@@ -1,12 +1,17 @@
 use crate::{
     ops::{
-        ArchivedRange, ArchivedRangeFrom, ArchivedRangeInclusive,
-        ArchivedRangeTo, ArchivedRangeToInclusive,
+        ArchivedBound, ArchivedControlFlow, ArchivedRange, ArchivedRangeFrom,
+        ArchivedRangeInclusive, ArchivedRangeTo, ArchivedRangeToInclusive,
     },
     Archive, Archived, Deserialize, Serialize,
 };
-use core::ops::{
-    Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+use core::{
+    hint::unreachable_unchecked,
+    ops::{
+        Bound, ControlFlow, Range, RangeFrom, RangeFull, RangeInclusive,
+        RangeTo, RangeToInclusive,
+    },
+    ptr,
 };
 use rancor::Fallible;
 
@@ -319,3 +324,188 @@ impl<T, U: PartialEq<T>> PartialEq<RangeToInclusive<T>>
         self.end.eq(&other.end)
     }
 }
+
+// Bound
+
+#[allow(dead_code)]
+#[repr(u8)]
+enum ArchivedBoundTag {
+    Included,
+    Excluded,
+    Unbounded,
+}
+
+#[repr(C)]
+struct ArchivedBoundVariantValue<T>(ArchivedBoundTag, T);
+
+#[repr(C)]
+struct ArchivedBoundVariantUnbounded(ArchivedBoundTag);
+
+impl<T: Archive> Archive for Bound<T> {
+    type Archived = ArchivedBound<T::Archived>;
+    type Resolver = Bound<T::Resolver>;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        let (tag, value, resolver) = match (self, resolver) {
+            (Bound::Included(value), Bound::Included(resolver)) => {
+                (ArchivedBoundTag::Included, value, resolver)
+            }
+            (Bound::Excluded(value), Bound::Excluded(resolver)) => {
+                (ArchivedBoundTag::Excluded, value, resolver)
+            }
+            (Bound::Unbounded, Bound::Unbounded) => {
+                let out = out.cast::<ArchivedBoundVariantUnbounded>();
+                ptr::addr_of_mut!((*out).0).write(ArchivedBoundTag::Unbounded);
+                return;
+            }
+            _ => unreachable_unchecked(),
+        };
+
+        let out = out.cast::<ArchivedBoundVariantValue<T::Archived>>();
+        ptr::addr_of_mut!((*out).0).write(tag);
+
+        let (fp, fo) = out_field!(out.1);
+        value.resolve(pos + fp, resolver, fo);
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Bound<T> {
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        Ok(match self {
+            Bound::Included(value) => {
+                Bound::Included(value.serialize(serializer)?)
+            }
+            Bound::Excluded(value) => {
+                Bound::Excluded(value.serialize(serializer)?)
+            }
+            Bound::Unbounded => Bound::Unbounded,
+        })
+    }
+}
+
+impl<T, D> Deserialize<Bound<T>, D> for ArchivedBound<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Bound<T>, D::Error> {
+        Ok(match self {
+            ArchivedBound::Included(value) => {
+                Bound::Included(value.deserialize(deserializer)?)
+            }
+            ArchivedBound::Excluded(value) => {
+                Bound::Excluded(value.deserialize(deserializer)?)
+            }
+            ArchivedBound::Unbounded => Bound::Unbounded,
+        })
+    }
+}
+
+// ControlFlow
+
+#[allow(dead_code)]
+#[repr(u8)]
+enum ArchivedControlFlowTag {
+    Continue,
+    Break,
+}
+
+#[repr(C)]
+struct ArchivedControlFlowVariantContinue<C>(ArchivedControlFlowTag, C);
+
+#[repr(C)]
+struct ArchivedControlFlowVariantBreak<B>(ArchivedControlFlowTag, B);
+
+impl<B: Archive, C: Archive> Archive for ControlFlow<B, C> {
+    type Archived = ArchivedControlFlow<B::Archived, C::Archived>;
+    type Resolver = ControlFlow<B::Resolver, C::Resolver>;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        match (self, resolver) {
+            (ControlFlow::Continue(value), ControlFlow::Continue(resolver)) => {
+                let out = out
+                    .cast::<ArchivedControlFlowVariantContinue<C::Archived>>();
+                ptr::addr_of_mut!((*out).0)
+                    .write(ArchivedControlFlowTag::Continue);
+
+                let (fp, fo) = out_field!(out.1);
+                value.resolve(pos + fp, resolver, fo);
+            }
+            (ControlFlow::Break(value), ControlFlow::Break(resolver)) => {
+                let out =
+                    out.cast::<ArchivedControlFlowVariantBreak<B::Archived>>();
+                ptr::addr_of_mut!((*out).0)
+                    .write(ArchivedControlFlowTag::Break);
+
+                let (fp, fo) = out_field!(out.1);
+                value.resolve(pos + fp, resolver, fo);
+            }
+            _ => unreachable_unchecked(),
+        }
+    }
+}
+
+impl<B, C, S> Serialize<S> for ControlFlow<B, C>
+where
+    B: Serialize<S>,
+    C: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        Ok(match self {
+            ControlFlow::Continue(value) => {
+                ControlFlow::Continue(value.serialize(serializer)?)
+            }
+            ControlFlow::Break(value) => {
+                ControlFlow::Break(value.serialize(serializer)?)
+            }
+        })
+    }
+}
+
+impl<B, C, D> Deserialize<ControlFlow<B, C>, D>
+    for ArchivedControlFlow<B::Archived, C::Archived>
+where
+    B: Archive,
+    C: Archive,
+    B::Archived: Deserialize<B, D>,
+    C::Archived: Deserialize<C, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<ControlFlow<B, C>, D::Error> {
+        Ok(match self {
+            ArchivedControlFlow::Continue(value) => {
+                ControlFlow::Continue(value.deserialize(deserializer)?)
+            }
+            ArchivedControlFlow::Break(value) => {
+                ControlFlow::Break(value.deserialize(deserializer)?)
+            }
+        })
+    }
+}
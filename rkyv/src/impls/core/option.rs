@@ -19,6 +19,11 @@ impl<T: Archive> Archive for Option<T> {
     type Archived = ArchivedOption<T::Archived>;
     type Resolver = Option<T::Resolver>;
 
+    #[inline]
+    fn serialized_size_hint(&self) -> usize {
+        self.as_ref().map_or(0, T::serialized_size_hint)
+    }
+
     #[inline]
     unsafe fn resolve(
         &self,
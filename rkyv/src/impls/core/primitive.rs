@@ -6,8 +6,9 @@ use crate::{
         ArchivedNonZeroIsize, ArchivedNonZeroU128, ArchivedNonZeroU16,
         ArchivedNonZeroU32, ArchivedNonZeroU64, ArchivedNonZeroUsize,
         ArchivedU128, ArchivedU16, ArchivedU32, ArchivedU64, ArchivedUsize,
+        FixedIsize, FixedNonZeroIsize, FixedNonZeroUsize, FixedUsize,
     },
-    Archive, Archived, Deserialize, Serialize,
+    Archive, Archived, CopyOptimization, Deserialize, Serialize,
 };
 use core::{
     marker::{PhantomData, PhantomPinned},
@@ -17,7 +18,7 @@ use core::{
         NonZeroU8, NonZeroUsize,
     },
 };
-use rancor::Fallible;
+use rancor::{Error, Fallible, ResultExt as _};
 
 macro_rules! impl_serialize_noop {
     ($type:ty) => {
@@ -33,6 +34,9 @@ macro_rules! impl_serialize_noop {
 macro_rules! impl_portable_primitive {
     ($type:ty) => {
         impl Archive for $type {
+            const COPY_OPTIMIZATION: CopyOptimization<Self> =
+                unsafe { CopyOptimization::enable() };
+
             type Archived = Self;
             type Resolver = ();
 
@@ -78,6 +82,16 @@ impl_portable_primitives! {
 macro_rules! impl_multibyte_primitive {
     ($archived:ident: $type:ty) => {
         impl Archive for $type {
+            // Multibyte primitives have the same representation as their
+            // archived counterparts when the archive endianness matches the
+            // target endianness
+            const COPY_OPTIMIZATION: CopyOptimization<Self> = unsafe {
+                CopyOptimization::enable_if(cfg!(any(
+                    all(target_endian = "little", feature = "little_endian"),
+                    all(target_endian = "big", feature = "big_endian"),
+                )))
+            };
+
             type Archived = $archived;
             type Resolver = ();
 
@@ -136,6 +150,9 @@ impl_multibyte_primitives! {
 // PhantomData
 
 impl<T: ?Sized> Archive for PhantomData<T> {
+    const COPY_OPTIMIZATION: CopyOptimization<Self> =
+        unsafe { CopyOptimization::enable() };
+
     type Archived = PhantomData<T>;
     type Resolver = ();
 
@@ -167,6 +184,9 @@ impl<T: ?Sized, D: Fallible + ?Sized> Deserialize<PhantomData<T>, D>
 
 // PhantomPinned
 impl Archive for PhantomPinned {
+    const COPY_OPTIMIZATION: CopyOptimization<Self> =
+        unsafe { CopyOptimization::enable() };
+
     type Archived = PhantomPinned;
     type Resolver = ();
 
@@ -211,17 +231,26 @@ impl Archive for usize {
     }
 }
 
-impl<S: Fallible + ?Sized> Serialize<S> for usize {
+impl<S> Serialize<S> for usize
+where
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
     #[inline]
     fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        FixedUsize::try_from(*self).into_error()?;
         Ok(())
     }
 }
 
-impl<D: Fallible + ?Sized> Deserialize<usize, D> for ArchivedUsize {
+impl<D> Deserialize<usize, D> for ArchivedUsize
+where
+    D: Fallible + ?Sized,
+    D::Error: Error,
+{
     #[inline]
     fn deserialize(&self, _: &mut D) -> Result<usize, D::Error> {
-        Ok(self.to_native() as usize)
+        usize::try_from(self.to_native()).into_error()
     }
 }
 
@@ -242,17 +271,26 @@ impl Archive for isize {
     }
 }
 
-impl<S: Fallible + ?Sized> Serialize<S> for isize {
+impl<S> Serialize<S> for isize
+where
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
     #[inline]
     fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        FixedIsize::try_from(*self).into_error()?;
         Ok(())
     }
 }
 
-impl<D: Fallible + ?Sized> Deserialize<isize, D> for Archived<isize> {
+impl<D> Deserialize<isize, D> for Archived<isize>
+where
+    D: Fallible + ?Sized,
+    D::Error: Error,
+{
     #[inline]
     fn deserialize(&self, _: &mut D) -> Result<isize, D::Error> {
-        Ok(self.to_native() as isize)
+        isize::try_from(self.to_native()).into_error()
     }
 }
 
@@ -273,19 +311,28 @@ impl Archive for NonZeroUsize {
     }
 }
 
-impl<S: Fallible + ?Sized> Serialize<S> for NonZeroUsize {
+impl<S> Serialize<S> for NonZeroUsize
+where
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
     #[inline]
     fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        FixedNonZeroUsize::try_from(*self).into_error()?;
         Ok(())
     }
 }
 
-impl<D: Fallible + ?Sized> Deserialize<NonZeroUsize, D>
-    for Archived<NonZeroUsize>
+impl<D> Deserialize<NonZeroUsize, D> for Archived<NonZeroUsize>
+where
+    D: Fallible + ?Sized,
+    D::Error: Error,
 {
     #[inline]
     fn deserialize(&self, _: &mut D) -> Result<NonZeroUsize, D::Error> {
-        Ok(unsafe { NonZeroUsize::new_unchecked(self.get() as usize) })
+        let value = usize::try_from(self.get()).into_error()?;
+        // SAFETY: `value` was converted losslessly from a non-zero integer.
+        Ok(unsafe { NonZeroUsize::new_unchecked(value) })
     }
 }
 
@@ -306,18 +353,27 @@ impl Archive for NonZeroIsize {
     }
 }
 
-impl<S: Fallible + ?Sized> Serialize<S> for NonZeroIsize {
+impl<S> Serialize<S> for NonZeroIsize
+where
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
     #[inline]
     fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        FixedNonZeroIsize::try_from(*self).into_error()?;
         Ok(())
     }
 }
 
-impl<D: Fallible + ?Sized> Deserialize<NonZeroIsize, D>
-    for Archived<NonZeroIsize>
+impl<D> Deserialize<NonZeroIsize, D> for Archived<NonZeroIsize>
+where
+    D: Fallible + ?Sized,
+    D::Error: Error,
 {
     #[inline]
     fn deserialize(&self, _: &mut D) -> Result<NonZeroIsize, D::Error> {
-        Ok(unsafe { NonZeroIsize::new_unchecked(self.get() as isize) })
+        let value = isize::try_from(self.get()).into_error()?;
+        // SAFETY: `value` was converted losslessly from a non-zero integer.
+        Ok(unsafe { NonZeroIsize::new_unchecked(value) })
     }
 }
@@ -0,0 +1,101 @@
+use half::{bf16, f16};
+
+use crate::{
+    primitive::{ArchivedBf16, ArchivedF16},
+    Archive, Archived, Deserialize, Fallible, Serialize,
+};
+
+macro_rules! impl_half {
+    ($type:ty, $archived:ty) => {
+        impl Archive for $type {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                _: usize,
+                _: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                out.write(<$archived>::from_native(*self));
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $type {
+            #[inline]
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$type, D> for Archived<$type> {
+            #[inline]
+            fn deserialize(&self, _: &mut D) -> Result<$type, D::Error> {
+                Ok(self.to_native())
+            }
+        }
+    };
+}
+
+impl_half!(f16, ArchivedF16);
+impl_half!(bf16, ArchivedBf16);
+
+#[cfg(test)]
+mod rkyv_tests {
+    use half::{bf16, f16};
+    use rancor::Infallible;
+
+    use crate::{access_unchecked, deserialize, util::AlignedVec};
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn test_check_any_bits() {
+        use rancor::Failure;
+
+        // Every bit pattern is a valid float, including NaNs
+        for bits in [0u16, 0x7c01, 0xffff, 0x8000] {
+            let buf = crate::util::serialize_into::<_, _, Failure>(
+                &bits,
+                AlignedVec::new(),
+            )
+            .unwrap();
+            let archived = crate::access::<f16, Failure>(&buf).unwrap();
+            assert_eq!(archived.to_native().to_bits(), bits);
+            let archived = crate::access::<bf16, Failure>(&buf).unwrap();
+            assert_eq!(archived.to_native().to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn test_serialize_deserialize() {
+        let values = [
+            (f16::from_f32(1.5), bf16::from_f32(-2.25)),
+            (f16::MAX, bf16::MIN_POSITIVE),
+            (f16::NEG_INFINITY, bf16::INFINITY),
+        ];
+
+        for (a, b) in values {
+            let buf = crate::util::serialize_into::<_, _, Infallible>(
+                &[a],
+                AlignedVec::new(),
+            )
+            .expect("failed to archive f16");
+            let archived = unsafe { access_unchecked::<[f16; 1]>(&buf) };
+            assert_eq!(archived[0], a);
+            let deserialized =
+                deserialize::<[f16; 1], _, Infallible>(archived, &mut ())
+                    .expect("failed to deserialize f16");
+            assert_eq!(deserialized[0].to_bits(), a.to_bits());
+
+            let buf = crate::util::serialize_into::<_, _, Infallible>(
+                &[b],
+                AlignedVec::new(),
+            )
+            .expect("failed to archive bf16");
+            let archived = unsafe { access_unchecked::<[bf16; 1]>(&buf) };
+            assert_eq!(archived[0], b);
+            assert_eq!(archived[0].to_native().to_bits(), b.to_bits());
+        }
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
 };
 use core::hash::{BuildHasher, Hash};
 use indexmap::IndexMap;
+use rancor::Error;
 
 impl<K: Archive, V: Archive, S> Archive for IndexMap<K, V, S> {
     type Archived = ArchivedIndexMap<K::Archived, V::Archived>;
@@ -25,6 +26,7 @@ where
     K: Hash + Eq + Serialize<S>,
     V: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Error,
     RandomState: BuildHasher,
 {
     fn serialize(
@@ -5,6 +5,7 @@ use crate::{
 };
 use core::hash::{BuildHasher, Hash};
 use indexmap::IndexSet;
+use rancor::Error;
 
 impl<K: Archive, S> Archive for IndexSet<K, S> {
     type Archived = ArchivedIndexSet<K::Archived>;
@@ -24,6 +25,7 @@ impl<K, S, RandomState> Serialize<S> for IndexSet<K, RandomState>
 where
     K: Hash + Eq + Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Error,
     RandomState: BuildHasher,
 {
     fn serialize(
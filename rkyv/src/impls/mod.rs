@@ -19,6 +19,8 @@ mod arrayvec;
 mod bitvec;
 #[cfg(feature = "bytes")]
 mod bytes;
+#[cfg(feature = "half")]
+mod half;
 #[cfg(feature = "hashbrown")]
 mod hashbrown;
 #[cfg(feature = "indexmap")]
@@ -4,6 +4,7 @@ use core::num::{
 };
 
 use crate::{
+    niche::option_char::ArchivedOptionChar,
     niche::option_nonzero::{
         ArchivedOptionNonZeroI128, ArchivedOptionNonZeroI16,
         ArchivedOptionNonZeroI32, ArchivedOptionNonZeroI64,
@@ -69,3 +70,41 @@ impl_nonzero_niche!(ArchivedOptionNonZeroU16, NonZeroU16, u16);
 impl_nonzero_niche!(ArchivedOptionNonZeroU32, NonZeroU32, u32);
 impl_nonzero_niche!(ArchivedOptionNonZeroU64, NonZeroU64, u64);
 impl_nonzero_niche!(ArchivedOptionNonZeroU128, NonZeroU128, u128);
+
+impl ArchiveWith<Option<char>> for Niche {
+    type Archived = ArchivedOptionChar;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Option<char>,
+        _: usize,
+        _: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedOptionChar::resolve_from_option(*field, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> SerializeWith<Option<char>, S> for Niche {
+    #[inline]
+    fn serialize_with(
+        _: &Option<char>,
+        _: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D> DeserializeWith<ArchivedOptionChar, Option<char>, D> for Niche
+where
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedOptionChar,
+        _: &mut D,
+    ) -> Result<Option<char>, D::Error> {
+        Ok(field.to_native())
+    }
+}
@@ -1,9 +1,16 @@
-use crate::{rend::*, Archive, Archived, Deserialize, Serialize};
+use crate::{
+    rend::*, Archive, Archived, CopyOptimization, Deserialize, Serialize,
+};
 use rancor::Fallible;
 
 macro_rules! impl_rend_primitive {
     ($type:ty) => {
         impl Archive for $type {
+            // Safety: rend primitives always have the same representation
+            // archived and unarchived and contain no padding
+            const COPY_OPTIMIZATION: CopyOptimization<Self> =
+                unsafe { CopyOptimization::enable() };
+
             type Archived = Self;
             type Resolver = ();
 
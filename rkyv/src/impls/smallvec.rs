@@ -1,7 +1,7 @@
 use crate::{
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
-    Archive, Archived, Deserialize, Fallible, Serialize,
+    Archive, Archived, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
 use smallvec::{Array, SmallVec};
 
@@ -28,6 +28,7 @@ where
     A: Array,
     A::Item: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    [A::Item]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
@@ -1,7 +1,7 @@
 use crate::{
     ser::{Allocator, Writer},
     string::{ArchivedString, StringResolver},
-    Archive, Deserialize, Fallible, Serialize,
+    Archive, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
 use smol_str::SmolStr;
 
@@ -23,6 +23,7 @@ impl Archive for SmolStr {
 impl<S> Serialize<S> for SmolStr
 where
     S: Fallible + Allocator + Writer + ?Sized,
+    str: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
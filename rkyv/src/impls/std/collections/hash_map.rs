@@ -5,9 +5,9 @@ use crate::{
 };
 use core::{
     borrow::Borrow,
-    hash::{BuildHasher, Hash},
+    hash::{BuildHasher, Hash, Hasher},
 };
-use rancor::Fallible;
+use rancor::{Error, Fallible};
 use std::collections::HashMap;
 
 impl<K: Archive + Hash + Eq, V: Archive, S> Archive for HashMap<K, V, S>
@@ -34,18 +34,24 @@ where
     K::Archived: Hash + Eq,
     V: Serialize<S>,
     S: Fallible + Writer + Allocator + ?Sized,
+    S::Error: Error,
 {
     #[inline]
     fn serialize(
         &self,
         serializer: &mut S,
     ) -> Result<Self::Resolver, S::Error> {
-        unsafe { ArchivedHashMap::serialize_from_iter(self.iter(), serializer) }
+        unsafe {
+            <ArchivedHashMap<K::Archived, V::Archived>>::serialize_from_iter(
+                self.iter(),
+                serializer,
+            )
+        }
     }
 }
 
-impl<K, V, D, S> Deserialize<HashMap<K, V, S>, D>
-    for ArchivedHashMap<K::Archived, V::Archived>
+impl<K, V, D, S, H> Deserialize<HashMap<K, V, S>, D>
+    for ArchivedHashMap<K::Archived, V::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D> + Hash + Eq,
@@ -77,7 +83,8 @@ impl<
         AK: Hash + Eq,
         AV: PartialEq<V>,
         S: BuildHasher,
-    > PartialEq<HashMap<K, V, S>> for ArchivedHashMap<AK, AV>
+        H: Hasher + Default,
+    > PartialEq<HashMap<K, V, S>> for ArchivedHashMap<AK, AV, H>
 {
     #[inline]
     fn eq(&self, other: &HashMap<K, V, S>) -> bool {
@@ -91,11 +98,17 @@ impl<
     }
 }
 
-impl<K: Hash + Eq + Borrow<AK>, V, AK: Hash + Eq, AV: PartialEq<V>>
-    PartialEq<ArchivedHashMap<AK, AV>> for HashMap<K, V>
+impl<K, V, AK, AV, S, H> PartialEq<ArchivedHashMap<AK, AV, H>>
+    for HashMap<K, V, S>
+where
+    K: Hash + Eq + Borrow<AK>,
+    AK: Hash + Eq,
+    AV: PartialEq<V>,
+    S: BuildHasher,
+    H: Hasher + Default,
 {
     #[inline]
-    fn eq(&self, other: &ArchivedHashMap<AK, AV>) -> bool {
+    fn eq(&self, other: &ArchivedHashMap<AK, AV, H>) -> bool {
         other.eq(self)
     }
 }
@@ -5,9 +5,9 @@ use crate::{
 };
 use core::{
     borrow::Borrow,
-    hash::{BuildHasher, Hash},
+    hash::{BuildHasher, Hash, Hasher},
 };
-use rancor::Fallible;
+use rancor::{Error, Fallible};
 use std::collections::HashSet;
 
 impl<K: Archive + Hash + Eq, S> Archive for HashSet<K, S>
@@ -38,17 +38,24 @@ where
     K::Archived: Hash + Eq,
     K: Serialize<S> + Hash + Eq,
     S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Error,
 {
     #[inline]
     fn serialize(
         &self,
         serializer: &mut S,
     ) -> Result<Self::Resolver, S::Error> {
-        unsafe { ArchivedHashSet::serialize_from_iter(self.iter(), serializer) }
+        unsafe {
+            <ArchivedHashSet<K::Archived>>::serialize_from_iter(
+                self.iter(),
+                serializer,
+            )
+        }
     }
 }
 
-impl<K, D, S> Deserialize<HashSet<K, S>, D> for ArchivedHashSet<K::Archived>
+impl<K, D, S, H> Deserialize<HashSet<K, S>, D>
+    for ArchivedHashSet<K::Archived, H>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D> + Hash + Eq,
@@ -68,8 +75,12 @@ where
     }
 }
 
-impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
-    PartialEq<HashSet<K, S>> for ArchivedHashSet<AK>
+impl<K, AK, S, H> PartialEq<HashSet<K, S>> for ArchivedHashSet<AK, H>
+where
+    K: Hash + Eq + Borrow<AK>,
+    AK: Hash + Eq,
+    S: BuildHasher,
+    H: Hasher + Default,
 {
     #[inline]
     fn eq(&self, other: &HashSet<K, S>) -> bool {
@@ -81,11 +92,15 @@ impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
     }
 }
 
-impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
-    PartialEq<ArchivedHashSet<AK>> for HashSet<K, S>
+impl<K, AK, S, H> PartialEq<ArchivedHashSet<AK, H>> for HashSet<K, S>
+where
+    K: Hash + Eq + Borrow<AK>,
+    AK: Hash + Eq,
+    S: BuildHasher,
+    H: Hasher + Default,
 {
     #[inline]
-    fn eq(&self, other: &ArchivedHashSet<AK>) -> bool {
+    fn eq(&self, other: &ArchivedHashSet<AK, H>) -> bool {
         other.eq(self)
     }
 }
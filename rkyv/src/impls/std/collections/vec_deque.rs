@@ -3,7 +3,7 @@ use std::{alloc, cmp, collections::VecDeque};
 use crate::{
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
-    Archive, Deserialize, DeserializeUnsized, Serialize,
+    Archive, Deserialize, DeserializeUnsized, Serialize, SerializeUnsized,
 };
 
 use rancor::Fallible;
@@ -52,6 +52,7 @@ impl<T, S> Serialize<S> for VecDeque<T>
 where
     T: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    [T]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
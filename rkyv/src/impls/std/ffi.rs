@@ -1,14 +1,21 @@
 use crate::{
     ffi::{ArchivedCString, CStringResolver},
-    primitive::ArchivedUsize,
+    primitive::{ArchivedUsize, FixedUsize},
     ser::Writer,
     Archive, ArchivePointee, ArchiveUnsized, Archived, ArchivedMetadata,
     Deserialize, DeserializeUnsized, Serialize, SerializeUnsized,
 };
+#[cfg(unix)]
+use crate::{
+    ffi::{ArchivedOsString, OsStringResolver},
+    ser::Allocator,
+};
 use core::{alloc::Layout, ptr};
 use ptr_meta::Pointee;
-use rancor::Fallible;
+use rancor::{Error, Fallible, ResultExt as _};
 use std::alloc;
+#[cfg(unix)]
+use std::ffi::OsString;
 use std::ffi::{CStr, CString};
 
 // CStr
@@ -18,6 +25,7 @@ impl ArchiveUnsized for CStr {
 
     #[inline]
     fn archived_metadata(&self) -> ArchivedMetadata<Self> {
+        // The length was checked to fit while serializing
         ArchivedUsize::from_native(ptr_meta::metadata(self) as _)
     }
 }
@@ -33,9 +41,14 @@ impl ArchivePointee for CStr {
     }
 }
 
-impl<S: Fallible + Writer + ?Sized> SerializeUnsized<S> for CStr {
+impl<S> SerializeUnsized<S> for CStr
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Error,
+{
     #[inline]
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
+        FixedUsize::try_from(ptr_meta::metadata(self)).into_error()?;
         let result = serializer.pos();
         serializer.write(self.to_bytes_with_nul())?;
         Ok(result)
@@ -103,7 +116,11 @@ impl Archive for CString {
     }
 }
 
-impl<S: Fallible + Writer + ?Sized> Serialize<S> for CString {
+impl<S> Serialize<S> for CString
+where
+    S: Fallible + Writer + ?Sized,
+    CStr: SerializeUnsized<S>,
+{
     #[inline]
     fn serialize(
         &self,
@@ -132,3 +149,65 @@ where
         }
     }
 }
+
+// OsString
+
+#[cfg(unix)]
+impl PartialEq<OsString> for ArchivedOsString {
+    #[inline]
+    fn eq(&self, other: &OsString) -> bool {
+        PartialEq::eq(self.as_os_str(), other.as_os_str())
+    }
+}
+
+#[cfg(unix)]
+impl PartialEq<ArchivedOsString> for OsString {
+    #[inline]
+    fn eq(&self, other: &ArchivedOsString) -> bool {
+        PartialEq::eq(other.as_os_str(), self.as_os_str())
+    }
+}
+
+#[cfg(unix)]
+impl Archive for OsString {
+    type Archived = ArchivedOsString;
+    type Resolver = OsStringResolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedOsString::resolve_from_os_str(
+            self.as_os_str(),
+            pos,
+            resolver,
+            out,
+        );
+    }
+}
+
+#[cfg(unix)]
+impl<S> Serialize<S> for OsString
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    [u8]: SerializeUnsized<S>,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedOsString::serialize_from_os_str(self.as_os_str(), serializer)
+    }
+}
+
+#[cfg(unix)]
+impl<D: Fallible + ?Sized> Deserialize<OsString, D> for ArchivedOsString {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<OsString, D::Error> {
+        Ok(self.as_os_str().to_owned())
+    }
+}
@@ -1,4 +1,7 @@
 mod collections;
 mod ffi;
 mod net;
+#[cfg(unix)]
+mod path;
+mod sync;
 mod time;
@@ -0,0 +1,59 @@
+use crate::{
+    path::{ArchivedPathBuf, PathBufResolver},
+    ser::{Allocator, Writer},
+    Archive, Deserialize, Serialize, SerializeUnsized,
+};
+use rancor::Fallible;
+use std::path::PathBuf;
+
+// PathBuf
+
+impl PartialEq<PathBuf> for ArchivedPathBuf {
+    #[inline]
+    fn eq(&self, other: &PathBuf) -> bool {
+        PartialEq::eq(self.as_path(), other.as_path())
+    }
+}
+
+impl PartialEq<ArchivedPathBuf> for PathBuf {
+    #[inline]
+    fn eq(&self, other: &ArchivedPathBuf) -> bool {
+        PartialEq::eq(other.as_path(), self.as_path())
+    }
+}
+
+impl Archive for PathBuf {
+    type Archived = ArchivedPathBuf;
+    type Resolver = PathBufResolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedPathBuf::resolve_from_path(self.as_path(), pos, resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for PathBuf
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    [u8]: SerializeUnsized<S>,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPathBuf::serialize_from_path(self.as_path(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<PathBuf, D> for ArchivedPathBuf {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<PathBuf, D::Error> {
+        Ok(self.as_path().to_path_buf())
+    }
+}
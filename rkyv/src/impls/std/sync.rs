@@ -0,0 +1,99 @@
+use crate::{with::Poisoned, Archive, Deserialize, Serialize};
+use rancor::{Error, Fallible, OptionExt as _};
+use std::sync::{Mutex, PoisonError, RwLock};
+
+// Mutex
+
+impl<T: Archive> Archive for Mutex<T> {
+    type Archived = T::Archived;
+    type Resolver = T::Resolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        // Serializing already failed if the lock was poisoned. If it was
+        // poisoned since then, the value is still resolved instead of
+        // panicking because resolve must be infallible.
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .resolve(pos, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for Mutex<T>
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        self.lock().ok().into_trace(Poisoned)?.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Mutex<T>, D> for T::Archived
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Mutex<T>, D::Error> {
+        Ok(Mutex::new(self.deserialize(deserializer)?))
+    }
+}
+
+// RwLock
+
+impl<T: Archive> Archive for RwLock<T> {
+    type Archived = T::Archived;
+    type Resolver = T::Resolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        // See the comment in the `Mutex` impl
+        self.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .resolve(pos, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for RwLock<T>
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        self.read().ok().into_trace(Poisoned)?.serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<RwLock<T>, D> for T::Archived
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<RwLock<T>, D::Error> {
+        Ok(RwLock::new(self.deserialize(deserializer)?))
+    }
+}
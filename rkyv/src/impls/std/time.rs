@@ -1,5 +1,7 @@
-use crate::time::ArchivedDuration;
-use std::time::Duration;
+use crate::{time::ArchivedDuration, Archive, Deserialize, Serialize};
+use core::fmt;
+use rancor::{fail, Error, Fallible, ResultExt as _};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 impl PartialEq<Duration> for ArchivedDuration {
     #[inline]
@@ -14,3 +16,57 @@ impl PartialEq<ArchivedDuration> for Duration {
         other.eq(self)
     }
 }
+
+impl Archive for SystemTime {
+    type Archived = ArchivedDuration;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        // We already checked the duration during serialize
+        let duration = self.duration_since(UNIX_EPOCH).unwrap();
+        Archive::resolve(&duration, pos, resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for SystemTime
+where
+    S: Fallible + ?Sized,
+    S::Error: Error,
+{
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.duration_since(UNIX_EPOCH).into_error()?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct SystemTimeOverflow;
+
+impl fmt::Display for SystemTimeOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the archived duration overflowed `SystemTime`")
+    }
+}
+
+impl std::error::Error for SystemTimeOverflow {}
+
+impl<D> Deserialize<SystemTime, D> for ArchivedDuration
+where
+    D: Fallible + ?Sized,
+    D::Error: Error,
+{
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<SystemTime, D::Error> {
+        match UNIX_EPOCH.checked_add(Duration::from(*self)) {
+            Some(time) => Ok(time),
+            None => fail!(SystemTimeOverflow),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
-    Archive, Archived, Deserialize, Fallible, Serialize,
+    Archive, Archived, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
 #[cfg(all(feature = "tinyvec", feature = "alloc"))]
 use tinyvec::TinyVec;
@@ -32,6 +32,7 @@ where
     A: Array,
     A::Item: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    [A::Item]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
@@ -83,6 +84,7 @@ impl<'s, T, S> Serialize<S> for SliceVec<'s, T>
 where
     T: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    [T]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
@@ -122,6 +124,7 @@ where
     A: Array,
     A::Item: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    [A::Item]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize(
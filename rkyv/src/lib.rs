@@ -66,7 +66,9 @@
 //! - `size_32`: Archives integral `*size` types as 32-bit integers. Enabled by default.
 //! - `size_64`: Archives integral `*size` types as 64-bit integers. This is intended to be used
 //!   only for very large archives and may cause unnecessary data bloat.
-//! - `std`: Enables standard library support. Enabled by default.
+//! - `std`: Enables standard library support. Enabled by default. Without it, rkyv is `no_std`
+//!   and the `alloc` feature can be enabled on its own to keep `Box`, `Vec`, `String`, and
+//!   validation support.
 //! - `strict`: Guarantees that types will have the same representations across platforms and
 //!   compilations. This is already the case in practice, but this feature provides a guarantee
 //!   along with C type compatibility.
@@ -74,7 +76,10 @@
 //!   *Note*: Enabling `strict` will disable [`Archive`] implementations for tuples, as tuples
 //!   do not have a C type layout. Making a generic `Tuple<T1, T2>` and deriving [`Archive`] for it
 //!   should provide similar functionality.
-//! - `bytecheck`: Enables validation support through `bytecheck`.
+//! - `bytecheck`: Enables validation support through `bytecheck`. This is enabled by default.
+//!   If all archives come from trusted sources, it can be disabled to drop the dependency on
+//!   `bytecheck`. Types can keep their `#[archive(check_bytes)]` attributes, which do nothing
+//!   while the feature is disabled.
 //!
 //! ## Crate support
 //!
@@ -85,6 +90,9 @@
 //!
 //! Crates supported by rkyv:
 //!
+//! - [`half`](https://docs.rs/half) *Archives `f16` and `bf16` as
+//!   [`ArchivedF16`](primitive::ArchivedF16) and
+//!   [`ArchivedBf16`](primitive::ArchivedBf16).*
 //! - [`indexmap`](https://docs.rs/indexmap)
 //! - [`rend`](https://docs.rs/rend) *Enabled automatically when using endian-specific archive
 //!   features.*
@@ -141,10 +149,14 @@ pub mod macros;
 #[cfg(feature = "bitvec")]
 pub mod bitvec;
 pub mod boxed;
+pub mod cmp;
 pub mod collections;
 #[cfg(feature = "copy")]
 pub mod copy;
 pub mod de;
+#[cfg(feature = "alloc")]
+pub mod diff;
+pub mod endian;
 // This is pretty unfortunate. CStr doesn't rely on the rest of std, but it's not in core.
 // If CStr ever gets moved into `core` then this module will no longer need cfg(feature = "std")
 #[cfg(feature = "std")]
@@ -152,8 +164,11 @@ pub mod ffi;
 mod impls;
 pub mod net;
 pub mod niche;
+pub mod num;
 pub mod ops;
 pub mod option;
+#[cfg(all(feature = "std", unix))]
+pub mod path;
 pub mod primitive;
 pub mod rc;
 pub mod rel_ptr;
@@ -173,9 +188,9 @@ pub use rend;
 #[cfg(feature = "bytecheck")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "bytecheck")))]
 pub use bytecheck;
-use core::{alloc::Layout, hash::Hash};
+use core::{alloc::Layout, hash::Hash, marker::PhantomData};
 use ptr_meta::Pointee;
-use rancor::Fallible;
+use rancor::{Error, Fallible};
 pub use rkyv_derive::{Archive, Deserialize, Serialize};
 #[doc(inline)]
 pub use util::{
@@ -426,6 +441,31 @@ pub trait Archive {
     /// needed to make the archived type from the normal type.
     type Resolver;
 
+    /// Whether slices and arrays of this type may be serialized by directly copying their bytes.
+    ///
+    /// This is disabled by default. Types that have the same archived and unarchived
+    /// representations and contain no padding bytes can enable it to have containers like `Vec`
+    /// write their elements with a single copy instead of resolving them one at a time. See
+    /// [`CopyOptimization`] for the requirements.
+    const COPY_OPTIMIZATION: CopyOptimization<Self> =
+        CopyOptimization::disable();
+
+    /// Returns a hint for the number of bytes that serializing this value
+    /// writes in addition to its archived representation.
+    ///
+    /// Writers that grow as they are written to can use this to reserve space
+    /// up front and avoid most reallocations while serializing. This is only a
+    /// hint: it may be smaller or larger than the actual number of bytes
+    /// written, and must not be relied on for safety. The default hint is `0`,
+    /// which is exact for types that don't serialize any dependencies.
+    ///
+    /// Deriving `Archive` implements this as the sum of the hints of the
+    /// archived fields.
+    #[inline]
+    fn serialized_size_hint(&self) -> usize {
+        0
+    }
+
     /// Creates the archived version of this value at the given position and writes it to the given
     /// output.
     ///
@@ -446,6 +486,58 @@ pub trait Archive {
     );
 }
 
+/// An [`Archive`] flag indicating whether a type may be archived by directly copying its bytes.
+///
+/// Copy optimization is only sound for types that have the same size, alignment, and bit
+/// representation as their archived type and contain no padding bytes. Enabling it is therefore
+/// `unsafe`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, CopyOptimization};
+///
+/// assert!(u8::COPY_OPTIMIZATION.is_enabled());
+/// assert!(<[u8; 4]>::COPY_OPTIMIZATION.is_enabled());
+/// assert!(!String::COPY_OPTIMIZATION.is_enabled());
+/// ```
+pub struct CopyOptimization<T: ?Sized>(bool, PhantomData<T>);
+
+impl<T: ?Sized> CopyOptimization<T> {
+    /// Returns a `CopyOptimization` indicating that the type may not be copy optimized.
+    #[inline]
+    pub const fn disable() -> Self {
+        Self(false, PhantomData)
+    }
+
+    /// Returns a `CopyOptimization` indicating that the type may be copy optimized.
+    ///
+    /// # Safety
+    ///
+    /// `T` and its archived type must have the same size, alignment, and bit representation, and
+    /// must not contain any padding bytes.
+    #[inline]
+    pub const unsafe fn enable() -> Self {
+        Self(true, PhantomData)
+    }
+
+    /// Returns a `CopyOptimization` which is enabled only if `value` is `true`.
+    ///
+    /// # Safety
+    ///
+    /// If `value` is `true`, the safety requirements of [`enable`](Self::enable) must be met.
+    #[inline]
+    pub const unsafe fn enable_if(value: bool) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Returns whether copy optimization is enabled.
+    #[inline]
+    pub const fn is_enabled(&self) -> bool {
+        self.0
+    }
+}
+
 /// Converts a type to its archived form.
 ///
 /// Objects perform any supportive serialization during [`serialize`](Serialize::serialize). For
@@ -455,6 +547,60 @@ pub trait Archive {
 /// serialize their dependencies during `serialize`.
 ///
 /// See [`Archive`] for examples of implementing `Serialize`.
+///
+/// # Errors
+///
+/// Serialization fails with the error type of the serializer, `S::Error`.
+/// Impls that can fail for their own reasons should bound it with
+/// [`rancor::Error`] instead of choosing a concrete type, so that callers
+/// decide how much detail to keep about the failure:
+///
+/// ```
+/// use core::fmt;
+/// use rkyv::{
+///     rancor::{fail, BoxedError, Error, Fallible},
+///     to_bytes, Archive, Archived, Serialize,
+/// };
+///
+/// #[derive(Debug)]
+/// struct NegativeLength;
+///
+/// impl fmt::Display for NegativeLength {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "length may not be negative")
+///     }
+/// }
+///
+/// impl std::error::Error for NegativeLength {}
+///
+/// struct Length(i32);
+///
+/// impl Archive for Length {
+///     type Archived = Archived<i32>;
+///     type Resolver = ();
+///
+///     unsafe fn resolve(&self, pos: usize, _: (), out: *mut Self::Archived) {
+///         self.0.resolve(pos, (), out);
+///     }
+/// }
+///
+/// impl<S> Serialize<S> for Length
+/// where
+///     S: Fallible + ?Sized,
+///     S::Error: Error,
+/// {
+///     fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+///         if self.0 < 0 {
+///             fail!(NegativeLength);
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// assert!(to_bytes::<_, 256, BoxedError>(&Length(4)).is_ok());
+/// let error = to_bytes::<_, 256, BoxedError>(&Length(-1)).unwrap_err();
+/// assert_eq!(error.to_string(), "length may not be negative");
+/// ```
 pub trait Serialize<S: Fallible + ?Sized>: Archive {
     /// Writes the dependencies for the object and returns a resolver that can create the archived
     /// type.
@@ -488,6 +634,18 @@ pub trait Deserialize<T, D: Fallible + ?Sized> {
     fn deserialize(&self, deserializer: &mut D) -> Result<T, D::Error>;
 }
 
+/// A type whose bytes are always initialized.
+///
+/// Unions that derive `Archive` with `#[archive(unsafe_union)]` are archived as
+/// a copy of all of their bytes, so they must implement this trait.
+///
+/// # Safety
+///
+/// Every byte of every value of the type must be initialized. For a union,
+/// this means that whichever field was last written covers all of the bytes
+/// of the union and contains no padding.
+pub unsafe trait FullyInitialized {}
+
 /// A counterpart of [`Archive`] that's suitable for unsized types.
 ///
 /// Unlike `Archive`, types that implement `ArchiveUnsized` must be serialized separately from their
@@ -696,6 +854,7 @@ pub trait SerializeUnsized<S: Fallible + ?Sized>: ArchiveUnsized {
     ) -> Result<usize, S::Error>
     where
         S: Writer,
+        S::Error: Error,
     {
         let to = self.serialize_unsized(serializer)?;
         serializer.align_for::<RelPtr<Self::Archived>>()?;
@@ -739,13 +898,63 @@ pub type RelPtr<T> = rel_ptr::RelPtr<T, ArchivedIsize>;
 /// Alias for the archived version of some [`Archive`] type.
 ///
 /// This can be useful for reducing the lengths of type definitions.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{rancor::Failure, to_bytes, Archived};
+///
+/// fn total(values: &Archived<Vec<u32>>) -> u32 {
+///     values.iter().map(|x| x.to_native()).sum()
+/// }
+///
+/// let bytes = to_bytes::<_, 256, Failure>(&vec![1u32, 2, 3]).unwrap();
+/// let archived = unsafe { rkyv::access_unchecked::<Vec<u32>>(&bytes) };
+/// assert_eq!(total(archived), 6);
+/// ```
 pub type Archived<T> = <T as Archive>::Archived;
 /// Alias for the resolver for some [`Archive`] type.
 ///
 /// This can be useful for reducing the lengths of type definitions.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{
+///     access_unchecked,
+///     rancor::{Failure, Strategy},
+///     ser::{AllocSerializer, WriterExt as _},
+///     Resolver, Serialize,
+/// };
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let value = "hello world".to_string();
+/// let resolver: Resolver<String> = value
+///     .serialize(Strategy::<_, Failure>::wrap(&mut serializer))
+///     .unwrap();
+/// let strategy = Strategy::<_, Failure>::wrap(&mut serializer);
+/// strategy.align_for::<rkyv::Archived<String>>().unwrap();
+/// // SAFETY: `resolver` is the result of serializing `value`, and the
+/// // serializer was just aligned for an archived `String`.
+/// unsafe { strategy.resolve_aligned(&value, resolver).unwrap() };
+///
+/// let bytes = serializer.into_writer();
+/// let archived = unsafe { access_unchecked::<String>(&bytes) };
+/// assert_eq!(archived, "hello world");
+/// ```
 pub type Resolver<T> = <T as Archive>::Resolver;
 /// Alias for the archived metadata for some [`ArchiveUnsized`] type.
 ///
 /// This can be useful for reducing the lengths of type definitions.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{primitive::ArchivedUsize, ArchivedMetadata};
+///
+/// // Archived slices store their length as their metadata
+/// let len: ArchivedMetadata<[u32]> = ArchivedUsize::from_native(3);
+/// assert_eq!(len.to_native(), 3);
+/// ```
 pub type ArchivedMetadata<T> =
     <<T as ArchiveUnsized>::Archived as ArchivePointee>::ArchivedMetadata;
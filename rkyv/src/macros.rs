@@ -59,6 +59,72 @@ macro_rules! out_field {
     }};
 }
 
+/// Asserts at compile time that the archived form of a type has the given size
+/// and alignment.
+///
+/// This fails the build if the layout of `Archived<T>` changes, for example
+/// because a field was added or reordered. It's useful to guard the binary
+/// compatibility of archives that are stored for a long time or read by other
+/// languages. Either the size or the alignment may be omitted.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{assert_archived_layout, Archive};
+///
+/// #[derive(Archive)]
+/// struct Record {
+///     id: u64,
+///     flags: u32,
+///     kind: u8,
+/// }
+///
+/// assert_archived_layout!(Record, size = 16, align = 8);
+/// assert_archived_layout!(u32, size = 4);
+/// assert_archived_layout!([u16; 3], align = 2);
+/// ```
+///
+/// A mismatch is a compile error:
+///
+/// ```compile_fail
+/// use rkyv::{assert_archived_layout, Archive};
+///
+/// #[derive(Archive)]
+/// struct Record {
+///     id: u64,
+///     flags: u32,
+/// }
+///
+/// assert_archived_layout!(Record, size = 12);
+/// ```
+#[macro_export]
+macro_rules! assert_archived_layout {
+    ($ty:ty, size = $size:expr, align = $align:expr $(,)?) => {
+        $crate::assert_archived_layout!($ty, size = $size);
+        $crate::assert_archived_layout!($ty, align = $align);
+    };
+    ($ty:ty, size = $size:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<$crate::Archived<$ty>>() == $size,
+            ::core::concat!(
+                "the archived size of `",
+                ::core::stringify!($ty),
+                "` does not match the expected size",
+            ),
+        );
+    };
+    ($ty:ty, align = $align:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            ::core::mem::align_of::<$crate::Archived<$ty>>() == $align,
+            ::core::concat!(
+                "the archived alignment of `",
+                ::core::stringify!($ty),
+                "` does not match the expected alignment",
+            ),
+        );
+    };
+}
+
 #[cfg(feature = "pointer_width_16")]
 macro_rules! match_pointer_width {
     ($s16:ty, $s32:ty, $s64:ty $(,)?) => {
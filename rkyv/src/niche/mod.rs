@@ -2,4 +2,5 @@
 
 #[cfg(feature = "alloc")]
 pub mod option_box;
+pub mod option_char;
 pub mod option_nonzero;
@@ -223,3 +223,42 @@ pub enum OptionBoxResolver {
     /// The resolver for the `ArchivedBox`
     Some(BoxResolver),
 }
+
+#[cfg(feature = "bytecheck")]
+const _: () = {
+    use bytecheck::{
+        rancor::{Error, Fallible},
+        CheckBytes,
+    };
+
+    use crate::{
+        validation::{ArchiveContext, LayoutRaw},
+        RelPtr,
+    };
+
+    unsafe impl<T, C> CheckBytes<C> for ArchivedOptionBox<T>
+    where
+        T: ArchivePointee + CheckBytes<C> + LayoutRaw + ?Sized,
+        T::ArchivedMetadata: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
+    {
+        #[inline]
+        unsafe fn check_bytes(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<(), C::Error> {
+            // `ArchivedOptionBox` and `ArchivedBox` are both transparent
+            // wrappers around a `RelPtr`, so the relative pointer can be
+            // checked before reading whether it's null.
+            let inner = core::ptr::addr_of!((*value).inner);
+            RelPtr::<T>::check_bytes(inner.cast(), context)?;
+
+            if (*inner).is_null() {
+                Ok(())
+            } else {
+                ArchivedBox::<T>::check_bytes(inner, context)
+            }
+        }
+    }
+};
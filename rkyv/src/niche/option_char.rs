@@ -0,0 +1,205 @@
+//! A niched archived `Option<char>` that uses less space.
+
+use core::{cmp, fmt, hash, pin::Pin};
+
+use crate::primitive::{ArchivedChar, ArchivedU32};
+
+/// The bit pattern of a `None` value.
+///
+/// This is above `char::MAX`, so it is never a valid `char`.
+const NICHE: u32 = u32::MAX;
+
+/// A niched archived `Option<char>`.
+///
+/// `None` is stored as a value that is not a valid `char`, so this takes the
+/// same space as an [`ArchivedChar`].
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+#[repr(transparent)]
+pub struct ArchivedOptionChar {
+    inner: ArchivedU32,
+}
+
+impl ArchivedOptionChar {
+    /// Returns `true` if the option is a `None` value.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.inner == NICHE
+    }
+
+    /// Returns `true` if the option is a `Some` value.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.inner != NICHE
+    }
+
+    /// Converts to an `Option<&ArchivedChar>`.
+    #[inline]
+    pub fn as_ref(&self) -> Option<&ArchivedChar> {
+        if self.is_some() {
+            // SAFETY: Archived chars have the same memory layout as archived
+            // `u32`s, and every value other than the niche is a valid `char`.
+            Some(unsafe { &*(&self.inner as *const _ as *const ArchivedChar) })
+        } else {
+            None
+        }
+    }
+
+    /// Converts to an `Option<&mut ArchivedChar>`.
+    #[inline]
+    pub fn as_mut(&mut self) -> Option<&mut ArchivedChar> {
+        if self.is_some() {
+            // SAFETY: Archived chars have the same memory layout as archived
+            // `u32`s, and every value other than the niche is a valid `char`.
+            Some(unsafe {
+                &mut *(&mut self.inner as *mut _ as *mut ArchivedChar)
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Converts from `Pin<&ArchivedOptionChar>` to
+    /// `Option<Pin<&ArchivedChar>>`.
+    #[inline]
+    pub fn as_pin_ref(self: Pin<&Self>) -> Option<Pin<&ArchivedChar>> {
+        unsafe { Pin::get_ref(self).as_ref().map(|x| Pin::new_unchecked(x)) }
+    }
+
+    /// Converts from `Pin<&mut ArchivedOptionChar>` to
+    /// `Option<Pin<&mut ArchivedChar>>`.
+    #[inline]
+    pub fn as_pin_mut(self: Pin<&mut Self>) -> Option<Pin<&mut ArchivedChar>> {
+        unsafe {
+            Pin::get_unchecked_mut(self)
+                .as_mut()
+                .map(|x| Pin::new_unchecked(x))
+        }
+    }
+
+    /// Returns the contained `char`, if any.
+    #[inline]
+    pub fn to_native(&self) -> Option<char> {
+        self.as_ref().map(|c| c.to_native())
+    }
+
+    /// Returns an iterator over the possibly contained value.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.as_ref(),
+        }
+    }
+
+    /// Returns a mutable iterator over the possibly contained value.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            inner: self.as_mut(),
+        }
+    }
+
+    /// Resolves an `ArchivedOptionChar` from an `Option<char>`.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    #[inline]
+    pub unsafe fn resolve_from_option(field: Option<char>, out: *mut Self) {
+        let (_, fo) = out_field!(out.inner);
+        fo.write(ArchivedU32::from_native(field.map_or(NICHE, u32::from)));
+    }
+}
+
+impl fmt::Debug for ArchivedOptionChar {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_ref() {
+            Some(inner) => inner.fmt(f),
+            None => f.debug_tuple("None").finish(),
+        }
+    }
+}
+
+impl Eq for ArchivedOptionChar {}
+
+impl hash::Hash for ArchivedOptionChar {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+impl Ord for ArchivedOptionChar {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
+impl PartialEq for ArchivedOptionChar {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().eq(&other.as_ref())
+    }
+}
+
+impl PartialOrd for ArchivedOptionChar {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<Option<char>> for ArchivedOptionChar {
+    #[inline]
+    fn eq(&self, other: &Option<char>) -> bool {
+        self.to_native() == *other
+    }
+}
+
+/// An iterator over a reference to the `Some` variant of an
+/// `ArchivedOptionChar`.
+///
+/// This iterator yields one value if the `ArchivedOptionChar` is a `Some`,
+/// otherwise none.
+pub type Iter<'a> = crate::option::Iter<'a, ArchivedChar>;
+
+/// An iterator over a mutable reference to the `Some` variant of an
+/// `ArchivedOptionChar`.
+///
+/// This iterator yields one value if the `ArchivedOptionChar` is a `Some`,
+/// otherwise none.
+pub type IterMut<'a> = crate::option::IterMut<'a, ArchivedChar>;
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use bytecheck::{
+        rancor::{Error, Fallible},
+        CheckBytes, Verify,
+    };
+
+    use super::ArchivedOptionChar;
+    use crate::primitive::ArchivedChar;
+
+    unsafe impl<C> Verify<C> for ArchivedOptionChar
+    where
+        C: Fallible + ?Sized,
+        C::Error: Error,
+    {
+        #[inline]
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            // The niche is `None`, and anything else must be a valid `char`
+            if self.is_some() {
+                let ptr = (self as *const Self).cast::<ArchivedChar>();
+                unsafe {
+                    ArchivedChar::check_bytes(ptr, context)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
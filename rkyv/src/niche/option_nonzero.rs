@@ -13,6 +13,7 @@ use core::{
 macro_rules! impl_archived_option_nonzero {
     ($ar:ident, $nz:ty, $ne:ty) => {
         #[doc = concat!("A niched archived `Option<", stringify!($nz), ">`")]
+        #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
         #[repr(transparent)]
         pub struct $ar {
             inner: Archived<$ne>,
@@ -0,0 +1,112 @@
+//! Archived versions of `num` types.
+
+use core::{
+    fmt,
+    num::{Saturating, Wrapping},
+    ops::{Deref, DerefMut},
+};
+
+/// An archived [`Wrapping`].
+///
+/// The inner value is archived as-is, so wrapping arithmetic can be
+/// performed by converting it back to a native value.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedWrapping<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedWrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ArchivedWrapping<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> Deref for ArchivedWrapping<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ArchivedWrapping<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Wrapping<U>> for ArchivedWrapping<T> {
+    #[inline]
+    fn eq(&self, other: &Wrapping<U>) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+impl<T: PartialOrd<U>, U> PartialOrd<Wrapping<U>> for ArchivedWrapping<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Wrapping<U>) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+/// An archived [`Saturating`].
+///
+/// The inner value is archived as-is, so saturating arithmetic can be
+/// performed by converting it back to a native value.
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedSaturating<T>(pub T);
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedSaturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ArchivedSaturating<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> Deref for ArchivedSaturating<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ArchivedSaturating<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Saturating<U>> for ArchivedSaturating<T> {
+    #[inline]
+    fn eq(&self, other: &Saturating<U>) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+impl<T: PartialOrd<U>, U> PartialOrd<Saturating<U>> for ArchivedSaturating<T> {
+    #[inline]
+    fn partial_cmp(
+        &self,
+        other: &Saturating<U>,
+    ) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
@@ -2,7 +2,7 @@
 
 use core::{
     cmp, fmt,
-    ops::{Bound, RangeBounds},
+    ops::{Bound, ControlFlow, RangeBounds},
 };
 
 /// An archived [`Range`](::core::ops::Range).
@@ -12,7 +12,7 @@ use core::{
 pub struct ArchivedRange<T> {
     /// The lower bound of the range (inclusive).
     pub start: T,
-    /// The upper bound of the range (inclusive).
+    /// The upper bound of the range (exclusive).
     pub end: T,
 }
 
@@ -63,6 +63,10 @@ impl<T> RangeBounds<T> for ArchivedRange<T> {
 // RangeInclusive
 
 /// An archived [`RangeInclusive`](::core::ops::RangeInclusive).
+///
+/// Only the start and end bounds are archived. A `RangeInclusive` that has
+/// been exhausted by iteration is archived with its remaining bounds, so it
+/// may not be empty after archiving.
 #[derive(Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 #[cfg_attr(feature = "strict", repr(C))]
@@ -238,3 +242,136 @@ impl<T> RangeBounds<T> for ArchivedRangeToInclusive<T> {
         Bound::Included(&self.end)
     }
 }
+
+/// An archived [`Bound`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedBound<T> {
+    /// An inclusive bound.
+    Included(T),
+    /// An exclusive bound.
+    Excluded(T),
+    /// An infinite endpoint. Indicates that there is no bound in this
+    /// direction.
+    Unbounded,
+}
+
+impl<T> ArchivedBound<T> {
+    /// Converts from `&ArchivedBound<T>` to `Bound<&T>`.
+    #[inline]
+    pub fn as_ref(&self) -> Bound<&T> {
+        match self {
+            ArchivedBound::Included(value) => Bound::Included(value),
+            ArchivedBound::Excluded(value) => Bound::Excluded(value),
+            ArchivedBound::Unbounded => Bound::Unbounded,
+        }
+    }
+
+    /// Converts from `&mut ArchivedBound<T>` to `Bound<&mut T>`.
+    #[inline]
+    pub fn as_mut(&mut self) -> Bound<&mut T> {
+        match self {
+            ArchivedBound::Included(value) => Bound::Included(value),
+            ArchivedBound::Excluded(value) => Bound::Excluded(value),
+            ArchivedBound::Unbounded => Bound::Unbounded,
+        }
+    }
+
+    /// Maps an `ArchivedBound<T>` to a `Bound<U>` by applying a function to
+    /// the contained value, if any.
+    #[inline]
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Bound<U> {
+        match self {
+            ArchivedBound::Included(value) => Bound::Included(f(value)),
+            ArchivedBound::Excluded(value) => Bound::Excluded(f(value)),
+            ArchivedBound::Unbounded => Bound::Unbounded,
+        }
+    }
+}
+
+impl<T, U: PartialEq<T>> PartialEq<Bound<T>> for ArchivedBound<U> {
+    #[inline]
+    fn eq(&self, other: &Bound<T>) -> bool {
+        match (self, other) {
+            (ArchivedBound::Included(a), Bound::Included(b))
+            | (ArchivedBound::Excluded(a), Bound::Excluded(b)) => a.eq(b),
+            (ArchivedBound::Unbounded, Bound::Unbounded) => true,
+            _ => false,
+        }
+    }
+}
+
+/// An archived [`ControlFlow`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedControlFlow<B, C> {
+    /// Move on to the next phase of the operation as normal.
+    Continue(C),
+    /// Exit the operation without running subsequent phases.
+    Break(B),
+}
+
+impl<B, C> ArchivedControlFlow<B, C> {
+    /// Returns `true` if this is a [`Break`](ArchivedControlFlow::Break)
+    /// variant.
+    #[inline]
+    pub const fn is_break(&self) -> bool {
+        matches!(self, ArchivedControlFlow::Break(_))
+    }
+
+    /// Returns `true` if this is a
+    /// [`Continue`](ArchivedControlFlow::Continue) variant.
+    #[inline]
+    pub const fn is_continue(&self) -> bool {
+        matches!(self, ArchivedControlFlow::Continue(_))
+    }
+
+    /// Returns a reference to the break value if this is a
+    /// [`Break`](ArchivedControlFlow::Break) variant, or `None` otherwise.
+    #[inline]
+    pub fn break_value(&self) -> Option<&B> {
+        match self {
+            ArchivedControlFlow::Continue(_) => None,
+            ArchivedControlFlow::Break(value) => Some(value),
+        }
+    }
+
+    /// Returns a reference to the continue value if this is a
+    /// [`Continue`](ArchivedControlFlow::Continue) variant, or `None`
+    /// otherwise.
+    #[inline]
+    pub fn continue_value(&self) -> Option<&C> {
+        match self {
+            ArchivedControlFlow::Continue(value) => Some(value),
+            ArchivedControlFlow::Break(_) => None,
+        }
+    }
+
+    /// Converts from `&ArchivedControlFlow<B, C>` to `ControlFlow<&B, &C>`.
+    #[inline]
+    pub fn as_ref(&self) -> ControlFlow<&B, &C> {
+        match self {
+            ArchivedControlFlow::Continue(value) => {
+                ControlFlow::Continue(value)
+            }
+            ArchivedControlFlow::Break(value) => ControlFlow::Break(value),
+        }
+    }
+}
+
+impl<B, C, BB: PartialEq<B>, CC: PartialEq<C>> PartialEq<ControlFlow<B, C>>
+    for ArchivedControlFlow<BB, CC>
+{
+    #[inline]
+    fn eq(&self, other: &ControlFlow<B, C>) -> bool {
+        match (self, other) {
+            (ArchivedControlFlow::Continue(a), ControlFlow::Continue(b)) => {
+                a.eq(b)
+            }
+            (ArchivedControlFlow::Break(a), ControlFlow::Break(b)) => a.eq(b),
+            _ => false,
+        }
+    }
+}
@@ -8,6 +8,8 @@ use core::{
     pin::Pin,
 };
 
+use crate::endian::SwapEndian;
+
 /// An archived [`Option`].
 ///
 /// It functions identically to [`Option`] but has a different internal
@@ -246,6 +248,15 @@ impl<T: PartialEq<U>, U> PartialEq<ArchivedOption<T>> for Option<U> {
     }
 }
 
+impl<T: SwapEndian> SwapEndian for ArchivedOption<T> {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        if let ArchivedOption::Some(value) = self {
+            value.scalar_ranges(report);
+        }
+    }
+}
+
 impl<T> From<T> for ArchivedOption<T> {
     /// Moves `val` into a new [`Some`].
     ///
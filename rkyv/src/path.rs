@@ -0,0 +1,128 @@
+//! Archived versions of path types.
+//!
+//! Archived paths store the raw bytes of their underlying OS string, and so are
+//! only available on Unix platforms. See
+//! [`ArchivedOsString`](crate::ffi::ArchivedOsString) for more details.
+
+use crate::{
+    ffi::{ArchivedOsString, OsStringResolver},
+    ser::{Allocator, Writer},
+    SerializeUnsized,
+};
+use core::{borrow::Borrow, fmt, ops::Deref};
+use rancor::Fallible;
+use std::{ffi::OsStr, path::Path};
+
+/// An archived [`PathBuf`](std::path::PathBuf).
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct ArchivedPathBuf {
+    inner: ArchivedOsString,
+}
+
+impl ArchivedPathBuf {
+    /// Extracts a `Path` slice containing the entire path.
+    #[inline]
+    pub fn as_path(&self) -> &Path {
+        Path::new(self.inner.as_os_str())
+    }
+
+    /// Extracts an `OsStr` slice containing the entire path.
+    #[inline]
+    pub fn as_os_str(&self) -> &OsStr {
+        self.inner.as_os_str()
+    }
+
+    /// Resolves an archived path from the given path and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `path`
+    #[inline]
+    pub unsafe fn resolve_from_path(
+        path: &Path,
+        pos: usize,
+        resolver: PathBufResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.inner);
+        ArchivedOsString::resolve_from_os_str(
+            path.as_os_str(),
+            pos + fp,
+            resolver.0,
+            fo,
+        );
+    }
+
+    /// Serializes a path.
+    #[inline]
+    pub fn serialize_from_path<S>(
+        path: &Path,
+        serializer: &mut S,
+    ) -> Result<PathBufResolver, S::Error>
+    where
+        S: Fallible + Allocator + Writer + ?Sized,
+        [u8]: SerializeUnsized<S>,
+    {
+        Ok(PathBufResolver(ArchivedOsString::serialize_from_os_str(
+            path.as_os_str(),
+            serializer,
+        )?))
+    }
+}
+
+impl AsRef<Path> for ArchivedPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl AsRef<OsStr> for ArchivedPathBuf {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl Borrow<Path> for ArchivedPathBuf {
+    #[inline]
+    fn borrow(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl fmt::Debug for ArchivedPathBuf {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_path().fmt(f)
+    }
+}
+
+impl Deref for ArchivedPathBuf {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_path()
+    }
+}
+
+impl PartialEq<&Path> for ArchivedPathBuf {
+    #[inline]
+    fn eq(&self, other: &&Path) -> bool {
+        PartialEq::eq(self.as_path(), *other)
+    }
+}
+
+impl PartialEq<ArchivedPathBuf> for &Path {
+    #[inline]
+    fn eq(&self, other: &ArchivedPathBuf) -> bool {
+        PartialEq::eq(other.as_path(), *self)
+    }
+}
+
+/// The resolver for `PathBuf`.
+pub struct PathBufResolver(OsStringResolver);
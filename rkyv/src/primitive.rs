@@ -29,12 +29,25 @@ use rend::{AtomicI64_be, AtomicU64_be};
 
 macro_rules! define_multibyte_primitive {
     ($archived:ident: $name:ident, $le:ty, $be:ty) => {
-        #[doc = concat!(
-            "The archived version of `",
-            stringify!($)
-        )]
+        define_multibyte_primitive!(
+            @doc concat!(
+                "The archived version of `",
+                stringify!($name),
+                "`.\n\n",
+                "This is stored in little-endian byte order when the ",
+                "`little_endian` feature is enabled and big-endian byte ",
+                "order when the `big_endian` feature is enabled, regardless ",
+                "of the host. Use `from_native` and `to_native` to convert to ",
+                "and from the native type.",
+            ),
+            $archived, $le, $be
+        );
+    };
+    (@doc $doc:expr, $archived:ident, $le:ty, $be:ty) => {
+        #[doc = $doc]
         #[cfg(feature = "little_endian")]
         pub type $archived = $le;
+        #[doc = $doc]
         #[cfg(feature = "big_endian")]
         pub type $archived = $be;
     };
@@ -78,10 +91,13 @@ pub type ArchivedIsize =
 ///
 /// This will be `u16`, `u32`, or `u64` when the `pointer_width_16`,
 /// `pointer_width_32`, or `pointer_width_64` features are enabled,
-/// respectively.
+/// respectively. The width is independent of the host, so archives are
+/// portable between 32- and 64-bit machines. Serializing a `usize` that does
+/// not fit in this type fails, as does deserializing an archived `usize` that
+/// does not fit in the host's `usize`.
 pub type FixedUsize = match_pointer_width!(u16, u32, u64);
 
-/// The archived version of `isize` chosen based on the currently-enabled
+/// The archived version of `usize` chosen based on the currently-enabled
 /// `pointer_width_*` feature.
 pub type ArchivedUsize =
     match_pointer_width!(ArchivedU16, ArchivedU32, ArchivedU64);
@@ -111,7 +127,7 @@ pub type FixedNonZeroIsize = match_pointer_width!(
 /// The archived version of `NonZeroIsize` chosen based on the currently-enabled
 /// `pointer_width_*` feature.
 pub type ArchivedNonZeroIsize = match_pointer_width!(
-    ArchviedNonZeroI16,
+    ArchivedNonZeroI16,
     ArchivedNonZeroI32,
     ArchivedNonZeroI64
 );
@@ -199,3 +215,121 @@ macro_rules! define_size_atomics {
     all(target_has_atomic = "64", feature = "pointer_width_64"),
 ))]
 define_size_atomics!();
+
+#[cfg(feature = "half")]
+macro_rules! define_archived_half {
+    ($archived:ident: $name:ident) => {
+        #[doc = concat!(
+            "The archived version of [`half::",
+            stringify!($name),
+            "`].\n\n",
+            "This stores the bits of the float as an [`ArchivedU16`], so it ",
+            "has the same byte order as the other archived primitives. Every ",
+            "bit pattern is a valid `",
+            stringify!($name),
+            "`, so validating it always succeeds.",
+        )]
+        #[derive(Clone, Copy, Default)]
+        #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+        #[repr(transparent)]
+        pub struct $archived(ArchivedU16);
+
+        impl $archived {
+            #[doc = concat!(
+                "Returns an archived `",
+                stringify!($name),
+                "` containing the given value.",
+            )]
+            #[inline]
+            pub fn from_native(value: half::$name) -> Self {
+                Self(ArchivedU16::from_native(value.to_bits()))
+            }
+
+            #[doc = concat!(
+                "Returns the value of the archived `",
+                stringify!($name),
+                "` as a native `",
+                stringify!($name),
+                "`.",
+            )]
+            #[inline]
+            pub fn to_native(&self) -> half::$name {
+                half::$name::from_bits(self.0.to_native())
+            }
+        }
+
+        impl From<half::$name> for $archived {
+            #[inline]
+            fn from(value: half::$name) -> Self {
+                Self::from_native(value)
+            }
+        }
+
+        impl From<$archived> for half::$name {
+            #[inline]
+            fn from(value: $archived) -> Self {
+                value.to_native()
+            }
+        }
+
+        impl core::fmt::Debug for $archived {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.to_native().fmt(f)
+            }
+        }
+
+        impl core::fmt::Display for $archived {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.to_native().fmt(f)
+            }
+        }
+
+        impl PartialEq for $archived {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.to_native() == other.to_native()
+            }
+        }
+
+        impl PartialEq<half::$name> for $archived {
+            #[inline]
+            fn eq(&self, other: &half::$name) -> bool {
+                self.to_native() == *other
+            }
+        }
+
+        impl PartialEq<$archived> for half::$name {
+            #[inline]
+            fn eq(&self, other: &$archived) -> bool {
+                *self == other.to_native()
+            }
+        }
+
+        impl PartialOrd for $archived {
+            #[inline]
+            fn partial_cmp(
+                &self,
+                other: &Self,
+            ) -> Option<core::cmp::Ordering> {
+                self.to_native().partial_cmp(&other.to_native())
+            }
+        }
+
+        impl PartialOrd<half::$name> for $archived {
+            #[inline]
+            fn partial_cmp(
+                &self,
+                other: &half::$name,
+            ) -> Option<core::cmp::Ordering> {
+                self.to_native().partial_cmp(other)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "half")]
+define_archived_half!(ArchivedF16: f16);
+#[cfg(feature = "half")]
+define_archived_half!(ArchivedBf16: bf16);
@@ -184,7 +184,10 @@ pub struct RcResolver {
 
 /// An archived `rc::Weak`.
 ///
-/// This is essentially just an optional [`ArchivedRc`].
+/// This is essentially just an optional [`ArchivedRc`]. Weak pointers that can
+/// be upgraded when they are serialized share their target with the other
+/// shared pointers to it, so they don't duplicate the target or own it. Weak
+/// pointers that can't be upgraded are archived as `None`.
 #[repr(u8)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 pub enum ArchivedRcWeak<T: ArchivePointee + ?Sized, F> {
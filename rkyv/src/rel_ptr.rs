@@ -3,6 +3,7 @@
 use rancor::{fail, Error, Panic, ResultExt as _};
 
 use crate::{
+    endian::SwapEndian,
     primitive::{
         ArchivedI16, ArchivedI32, ArchivedI64, ArchivedU16, ArchivedU32,
         ArchivedU64,
@@ -43,9 +44,55 @@ impl fmt::Display for ExceedsStorageRange {
 #[cfg(feature = "std")]
 impl std::error::Error for ExceedsStorageRange {}
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct NegativeUnsignedOffset;
+
+impl fmt::Display for NegativeUnsignedOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the offset is negative but the offset type of the relative \
+             pointer is unsigned",
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NegativeUnsignedOffset {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PositionOverflow;
+
+impl fmt::Display for PositionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the position of the relative pointer overflowed `usize`")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PositionOverflow {}
+
+/// Returns the position of a field at `offset` bytes into a value at `pos`.
+#[inline]
+fn field_pos<E: Error>(pos: usize, offset: usize) -> Result<usize, E> {
+    match pos.checked_add(offset) {
+        Some(pos) => Ok(pos),
+        None => fail!(PositionOverflow),
+    }
+}
+
 /// A offset that can be used with [`RawRelPtr`].
+///
+/// Smaller offsets produce smaller archives, but limit how far apart a relative
+/// pointer and its target may be. The offset type used by the archived
+/// containers (`Box`, `String`, `Vec`, etc.) is chosen by the
+/// `pointer_width_16`, `pointer_width_32` (default), and `pointer_width_64`
+/// features.
 pub trait Offset: Copy {
     /// Creates a new offset between a `from` position and a `to` position.
+    ///
+    /// Returns an error if the value is out of range for the offset type,
+    /// including if it is negative and the offset type is unsigned.
     fn from_isize<E: Error>(value: isize) -> Result<Self, E>;
 
     /// Gets the offset as an `isize`.
@@ -61,7 +108,13 @@ macro_rules! impl_offset_single_byte {
                 // computed offsets cannot overflow an isize, which is why we're
                 // using signed_offset instead of `checked_sub` for unsized
                 // types.
-                Self::try_from(value).into_error()
+                match Self::try_from(value) {
+                    Ok(offset) => Ok(offset),
+                    Err(_) if value < 0 && Self::MIN == 0 => {
+                        fail!(NegativeUnsignedOffset)
+                    }
+                    Err(_) => fail!(ExceedsStorageRange),
+                }
             }
 
             #[inline]
@@ -86,9 +139,13 @@ macro_rules! impl_offset_multi_byte {
                 // computed offsets cannot overflow an isize, which is why we're
                 // using signed_offset instead of `checked_sub` for unsized
                 // types.
-                Ok(<$archived>::from_native(
-                    <$ty>::try_from(value).into_error()?,
-                ))
+                match <$ty>::try_from(value) {
+                    Ok(offset) => Ok(<$archived>::from_native(offset)),
+                    Err(_) if value < 0 && <$ty>::MIN == 0 => {
+                        fail!(NegativeUnsignedOffset)
+                    }
+                    Err(_) => fail!(ExceedsStorageRange),
+                }
             }
 
             #[inline]
@@ -150,16 +207,18 @@ pub struct RawRelPtr<O> {
 /// # Examples
 ///
 /// ```
-/// use rkyv::rel_ptr::{signed_offset, OffsetError};
+/// use rkyv::{rancor::Failure, rel_ptr::signed_offset};
 ///
-/// assert_eq!(signed_offset(0, 1), Ok(1));
-/// assert_eq!(signed_offset(1, 0), Ok(-1));
-/// assert_eq!(signed_offset(0, isize::MAX as usize), Ok(isize::MAX));
-/// assert_eq!(signed_offset(isize::MAX as usize, 0), Ok(-isize::MAX));
-/// assert_eq!(signed_offset(0, isize::MAX as usize + 1), Err(IsizeOverflow));
-/// assert_eq!(signed_offset(isize::MAX as usize + 1, 0), Ok(isize::MIN));
-/// assert_eq!(signed_offset(0, isize::MAX as usize + 2), Err(IsizeOverflow));
-/// assert_eq!(signed_offset(isize::MAX as usize + 2, 0), Err(IsizeOverflow));
+/// let offset = |from, to| signed_offset::<Failure>(from, to);
+///
+/// assert_eq!(offset(0, 1).unwrap(), 1);
+/// assert_eq!(offset(1, 0).unwrap(), -1);
+/// assert_eq!(offset(0, isize::MAX as usize).unwrap(), isize::MAX);
+/// assert_eq!(offset(isize::MAX as usize, 0).unwrap(), -isize::MAX);
+/// assert!(offset(0, isize::MAX as usize + 1).is_err());
+/// assert_eq!(offset(isize::MAX as usize + 1, 0).unwrap(), isize::MIN);
+/// assert!(offset(0, isize::MAX as usize + 2).is_err());
+/// assert!(offset(isize::MAX as usize + 2, 0).is_err());
 /// ```
 #[inline]
 pub fn signed_offset<E: Error>(from: usize, to: usize) -> Result<isize, E> {
@@ -285,7 +344,44 @@ pub type RawRelPtrU64 = RawRelPtr<ArchivedU64>;
 ///
 /// This is a strongly-typed version of [`RawRelPtr`].
 ///
-/// See [`Archive`](crate::Archive) for an example of creating one.
+/// See [`Archive`](crate::Archive) for an example of creating one while
+/// serializing. Relative pointers can also be used to build custom archived
+/// layouts by hand. When validating them in a custom `CheckBytes`
+/// implementation, use `ArchiveContextExt::bounds_check_subtree_rel_ptr` to
+/// get a checked pointer to the target instead of computing it manually.
+///
+/// # Example
+///
+/// ```
+/// use core::{
+///     mem::{size_of, MaybeUninit},
+///     ptr::addr_of_mut,
+/// };
+/// use rkyv::{primitive::ArchivedU32, RelPtr};
+///
+/// #[repr(C)]
+/// struct Example {
+///     ptr: RelPtr<ArchivedU32>,
+///     value: ArchivedU32,
+/// }
+///
+/// let mut example = MaybeUninit::<Example>::uninit();
+/// let out = example.as_mut_ptr();
+/// let example = unsafe {
+///     // The pointer is at position 0 and the value directly follows it
+///     RelPtr::emplace(
+///         0,
+///         size_of::<RelPtr<ArchivedU32>>(),
+///         addr_of_mut!((*out).ptr),
+///     );
+///     addr_of_mut!((*out).value).write(ArchivedU32::from_native(42));
+///     example.assume_init()
+/// };
+///
+/// assert!(!example.ptr.is_null());
+/// assert_eq!(example.ptr.offset(), size_of::<RelPtr<ArchivedU32>>() as isize);
+/// assert_eq!(unsafe { *example.ptr.as_ptr() }, 42);
+/// ```
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 pub struct RelPtr<T: ArchivePointee + ?Sized, O> {
     raw_ptr: RawRelPtr<O>,
@@ -307,7 +403,7 @@ impl<T, O: Offset> RelPtr<T, O> {
     ) -> Result<(), E> {
         let (fp, fo) = out_field!(out.raw_ptr);
         // Skip metadata since sized T is guaranteed to be ()
-        RawRelPtr::try_emplace(from + fp, to, fo)
+        RawRelPtr::try_emplace(field_pos(from, fp)?, to, fo)
     }
 
     /// Creates a relative pointer from one position to another.
@@ -341,7 +437,7 @@ where
         out: *mut Self,
     ) -> Result<(), E> {
         let (fp, fo) = out_field!(out.raw_ptr);
-        RawRelPtr::try_emplace(pos + fp, pos, fo)?;
+        RawRelPtr::try_emplace(field_pos(pos, fp)?, pos, fo)?;
         let (_, fo) = out_field!(out.metadata);
         fo.write(Default::default());
         Ok(())
@@ -377,7 +473,7 @@ impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
         out: *mut Self,
     ) -> Result<(), E> {
         let (fp, fo) = out_field!(out.raw_ptr);
-        RawRelPtr::try_emplace(from + fp, to, fo)?;
+        RawRelPtr::try_emplace(field_pos(from, fp)?, to, fo)?;
         addr_of_mut!((*out).metadata).write(metadata);
         Ok(())
     }
@@ -470,3 +566,25 @@ impl<T: ArchivePointee + ?Sized, O: Offset> fmt::Pointer for RelPtr<T, O> {
         fmt::Pointer::fmt(&self.as_ptr_wrapping(), f)
     }
 }
+
+impl<O: SwapEndian> SwapEndian for RawRelPtr<O> {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        self.offset.scalar_ranges(report);
+    }
+}
+
+/// This only reports the offset and metadata of the relative pointer, and not
+/// the value that it points to.
+impl<T, O> SwapEndian for RelPtr<T, O>
+where
+    T: ArchivePointee + ?Sized,
+    T::ArchivedMetadata: SwapEndian,
+    O: SwapEndian,
+{
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        self.raw_ptr.scalar_ranges(report);
+        self.metadata.scalar_ranges(report);
+    }
+}
@@ -26,6 +26,13 @@ impl<T, E> ArchivedResult<T, E> {
             ArchivedResult::Err(_) => None,
         }
     }
+    /// Converts from `ArchivedResult<T, E>` to `Option<E>`.
+    pub fn err(self) -> Option<E> {
+        match self {
+            ArchivedResult::Ok(_) => None,
+            ArchivedResult::Err(err) => Some(err),
+        }
+    }
     /// Returns the contained [`Ok`](ArchivedResult::Ok) value, consuming the `self` value.
     pub fn unwrap(self) -> T {
         match self {
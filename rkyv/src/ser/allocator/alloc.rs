@@ -1,7 +1,11 @@
 use core::{alloc::Layout, fmt, ptr::NonNull};
 
 #[cfg(not(feature = "std"))]
-use alloc::alloc::{alloc, alloc_zeroed, dealloc};
+use alloc::{
+    alloc::{alloc, alloc_zeroed, dealloc},
+    boxed::Box,
+    vec::Vec,
+};
 use rancor::{fail, Error};
 #[cfg(feature = "std")]
 use std::alloc::{alloc, alloc_zeroed, dealloc};
@@ -41,6 +45,12 @@ impl<const N: usize> BumpAllocator<N> {
     pub fn layout() -> Layout {
         unsafe { Layout::from_size_align_unchecked(N, 1) }
     }
+
+    /// Resets the allocator to its initial state without freeing its heap
+    /// space.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
 }
 
 impl<const N: usize> Default for BumpAllocator<N> {
@@ -149,10 +159,10 @@ impl GlobalAllocator {
             allocations: Vec::new(),
         }
     }
-}
 
-impl Drop for GlobalAllocator {
-    fn drop(&mut self) {
+    /// Frees any allocations that were not popped and resets the allocator to
+    /// its initial state.
+    pub fn clear(&mut self) {
         for (ptr, layout) in self.allocations.drain(..).rev() {
             unsafe {
                 dealloc(ptr, layout);
@@ -161,6 +171,12 @@ impl Drop for GlobalAllocator {
     }
 }
 
+impl Drop for GlobalAllocator {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
 impl Default for GlobalAllocator {
     fn default() -> Self {
         Self::new()
@@ -63,6 +63,16 @@ impl<P, B> BackupAllocator<P, B> {
     pub fn new(primary: P, backup: B) -> Self {
         Self { primary, backup }
     }
+
+    /// Returns a mutable reference to the primary allocator.
+    pub fn primary_mut(&mut self) -> &mut P {
+        &mut self.primary
+    }
+
+    /// Returns a mutable reference to the backup allocator.
+    pub fn backup_mut(&mut self) -> &mut B {
+        &mut self.backup
+    }
 }
 
 impl<P: Default, B: Default> Default for BackupAllocator<P, B> {
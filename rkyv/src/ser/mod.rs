@@ -142,3 +142,41 @@ pub type AllocSerializer<const A: usize> = Composite<
     BackupAllocator<BumpAllocator<A>, GlobalAllocator>,
     Unify,
 >;
+
+#[cfg(feature = "alloc")]
+impl<const A: usize> AllocSerializer<A> {
+    /// Resets the serializer so that it can be reused for another value.
+    ///
+    /// This clears the written bytes, scratch space, and shared pointers while
+    /// keeping their allocated memory. Reusing a serializer this way avoids
+    /// reallocating them for every value when serializing many values in a
+    /// row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rkyv::{
+    ///     rancor::Failure, ser::AllocSerializer, util::serialize,
+    ///     access_unchecked,
+    /// };
+    ///
+    /// let mut serializer = AllocSerializer::<256>::default();
+    /// for i in 0..3 {
+    ///     serializer.reset();
+    ///
+    ///     let value = vec![i; 4];
+    ///     serialize::<_, _, Failure>(&value, &mut serializer).unwrap();
+    ///     let archived = unsafe {
+    ///         access_unchecked::<Vec<i32>>(serializer.writer.as_slice())
+    ///     };
+    ///     assert_eq!(archived.as_slice(), &[i; 4]);
+    /// }
+    /// ```
+    #[inline]
+    pub fn reset(&mut self) {
+        self.writer.clear();
+        self.allocator.primary_mut().clear();
+        self.allocator.backup_mut().clear();
+        self.share.clear();
+    }
+}
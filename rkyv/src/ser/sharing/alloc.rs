@@ -48,6 +48,12 @@ impl Unify {
             shared_address_to_pos: hash_map::HashMap::with_capacity(capacity),
         }
     }
+
+    /// Forgets all of the shared pointers that have been serialized while
+    /// keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.shared_address_to_pos.clear();
+    }
 }
 
 impl Default for Unify {
@@ -27,21 +27,45 @@ const _: () = {
     impl Error for BufferOverflow {}
 };
 
+#[derive(Debug)]
+struct PositionOverflow {
+    bytes: usize,
+    pos: usize,
+}
+
+impl fmt::Display for PositionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "overflowed the position while writing {} bytes at pos {}",
+            self.bytes, self.pos,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl Error for PositionOverflow {}
+};
+
 /// Wraps a byte buffer and equips it with [`Writer`].
 ///
 /// Common uses include archiving in `#![no_std]` environments and archiving small objects without
 /// allocating.
 ///
+/// If a write would overflow the end of the buffer, the writer returns an error describing how
+/// many bytes were being written, the position they were written at, and the length of the buffer.
+///
 /// # Examples
 /// ```
 /// use rkyv::{
-///     archived_value,
-///     ser::{Serializer, serializers::BufferSerializer},
-///     AlignedBytes,
-///     AlignedVec,
-///     Archive,
-///     Archived,
-///     Serialize,
+///     access_unchecked,
+///     rancor::{BoxedError, Failure},
+///     ser::{writer::BufferWriter, Positional},
+///     util::{serialize_into, AlignedBytes},
+///     Archive, Archived, Serialize,
 /// };
 ///
 /// #[derive(Archive, Serialize)]
@@ -51,16 +75,27 @@ const _: () = {
 ///     Die,
 /// }
 ///
-/// let mut serializer = BufferSerializer::new(AlignedBytes([0u8; 256]));
-/// let pos = serializer.serialize_value(&Event::Speak("Help me!".to_string()))
-///     .expect("failed to archive event");
-/// let buf = serializer.into_inner();
-/// let archived = unsafe { archived_value::<Event>(buf.as_ref(), pos) };
+/// let event = Event::Speak("Help me!".to_string());
+/// let writer = serialize_into::<_, _, Failure>(
+///     &event,
+///     BufferWriter::new(AlignedBytes([0u8; 256])),
+/// )
+/// .expect("failed to archive event");
+/// let len = writer.pos();
+/// let buf = writer.into_inner();
+/// let archived = unsafe { access_unchecked::<Event>(&buf[..len]) };
 /// if let Archived::<Event>::Speak(message) = archived {
 ///     assert_eq!(message.as_str(), "Help me!");
 /// } else {
 ///     panic!("archived event was of the wrong type");
 /// }
+///
+/// // Archiving into a buffer that is too small returns an error
+/// let result = serialize_into::<_, _, BoxedError>(
+///     &event,
+///     BufferWriter::new(AlignedBytes([0u8; 4])),
+/// );
+/// assert!(result.is_err());
 /// ```
 #[derive(Debug)]
 pub struct BufferWriter<T> {
@@ -96,6 +131,56 @@ impl<T> BufferWriter<T> {
         Self { inner, pos }
     }
 
+    /// Moves the writer to the given position, so that the next write starts
+    /// there.
+    ///
+    /// This can be used to archive values into free regions of a larger buffer,
+    /// like an arena or an append-only file. Positions are always relative to
+    /// the start of the buffer, so relative pointers within each archived value
+    /// remain correct and the value can be accessed at the position of its
+    /// root. As with [`with_pos`](Self::with_pos), the buffer must start at an
+    /// address that is aligned for the archived types.
+    ///
+    /// Moving the writer backwards does not erase any bytes, and writing over
+    /// a previously archived value invalidates it. The writer may be moved past
+    /// the end of the buffer, but writing there fails with an error.
+    ///
+    /// # Examples
+    /// ```
+    /// use core::mem::size_of;
+    /// use rkyv::{
+    ///     rancor::Failure,
+    ///     ser::{writer::BufferWriter, Positional as _},
+    ///     util::{serialize, AlignedBytes},
+    ///     validation::util::access_pos,
+    ///     Archived,
+    /// };
+    ///
+    /// let mut writer = BufferWriter::new(AlignedBytes([0u8; 256]));
+    ///
+    /// // Archive a value into the region starting at 128
+    /// writer.set_pos(128);
+    /// serialize::<_, _, Failure>(&"second".to_string(), &mut writer).unwrap();
+    /// let second = writer.pos() - size_of::<Archived<String>>();
+    ///
+    /// // Then archive another value into the region before it
+    /// writer.set_pos(0);
+    /// serialize::<_, _, Failure>(&"first".to_string(), &mut writer).unwrap();
+    /// let first = writer.pos() - size_of::<Archived<String>>();
+    ///
+    /// let buf = writer.into_inner();
+    /// let archived =
+    ///     access_pos::<String, Failure>(buf.as_ref(), first as isize).unwrap();
+    /// assert_eq!(archived, "first");
+    /// let archived =
+    ///     access_pos::<String, Failure>(buf.as_ref(), second as isize).unwrap();
+    /// assert_eq!(archived, "second");
+    /// ```
+    #[inline]
+    pub fn set_pos(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
     /// Consumes the serializer and returns the underlying type.
     #[inline]
     pub fn into_inner(self) -> T {
@@ -103,6 +188,59 @@ impl<T> BufferWriter<T> {
     }
 }
 
+impl<T: AsRef<[u8]>> BufferWriter<T> {
+    /// Returns the bytes of the buffer up to the current position.
+    ///
+    /// This allows inspecting the written bytes without giving up the writer
+    /// with [`into_inner`](Self::into_inner), for example to check an archive
+    /// before writing more values after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer was moved past the end of the buffer with
+    /// [`set_pos`](Self::set_pos).
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     access,
+    ///     rancor::Failure,
+    ///     ser::writer::BufferWriter,
+    ///     util::{serialize, AlignedBytes},
+    /// };
+    ///
+    /// let mut writer = BufferWriter::new(AlignedBytes([0u8; 256]));
+    /// assert!(writer.is_empty());
+    ///
+    /// serialize::<_, _, Failure>(&"hello".to_string(), &mut writer).unwrap();
+    /// assert_eq!(writer.len(), writer.as_bytes().len());
+    /// let archived = access::<String, Failure>(writer.as_bytes()).unwrap();
+    /// assert_eq!(archived, "hello");
+    ///
+    /// // The writer can keep appending after the bytes were checked
+    /// serialize::<_, _, Failure>(&"world".to_string(), &mut writer).unwrap();
+    /// let archived = access::<String, Failure>(writer.as_bytes()).unwrap();
+    /// assert_eq!(archived, "world");
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner.as_ref()[..self.pos]
+    }
+
+    /// Returns the number of bytes written to the buffer, which is the current
+    /// position of the writer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns whether no bytes have been written to the buffer.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+}
+
 impl<T: Default> Default for BufferWriter<T> {
     #[inline]
     fn default() -> Self {
@@ -119,24 +257,87 @@ impl<T> Positional for BufferWriter<T> {
 
 impl<T: AsMut<[u8]>, E: Error> Writer<E> for BufferWriter<T> {
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
-        let end_pos = self.pos + bytes.len();
         let len = self.inner.as_mut().len();
-        if end_pos > len {
-            fail!(BufferOverflow {
+        // The position may have been moved anywhere with `set_pos`
+        let end_pos = match self.pos.checked_add(bytes.len()) {
+            Some(end_pos) if end_pos <= len => end_pos,
+            _ => fail!(BufferOverflow {
                 bytes: bytes.len(),
                 pos: self.pos,
                 len,
-            });
-        } else {
-            unsafe {
-                copy_nonoverlapping(
-                    bytes.as_ptr(),
-                    self.inner.as_mut().as_mut_ptr().add(self.pos),
-                    bytes.len(),
-                );
-            }
-            self.pos = end_pos;
-            Ok(())
+            }),
+        };
+        unsafe {
+            copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.inner.as_mut().as_mut_ptr().add(self.pos),
+                bytes.len(),
+            );
         }
+        self.pos = end_pos;
+        Ok(())
+    }
+}
+
+/// A writer that discards all written bytes and only tracks its position.
+///
+/// Archiving a value into a `CountingWriter` computes exactly how many bytes
+/// it would take to archive with any other writer starting at the same
+/// position, including alignment padding. This can be used to size a buffer
+/// before archiving.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     rancor::Failure,
+///     ser::{writer::CountingWriter, Positional},
+///     to_bytes,
+///     util::serialize_into,
+/// };
+///
+/// let value = "hello world".to_string();
+///
+/// let counter =
+///     serialize_into::<_, _, Failure>(&value, CountingWriter::new()).unwrap();
+/// let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+/// assert_eq!(counter.pos(), bytes.len());
+/// ```
+#[derive(Debug, Default)]
+pub struct CountingWriter {
+    pos: usize,
+}
+
+impl CountingWriter {
+    /// Creates a new counting writer starting at position 0.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_pos(0)
+    }
+
+    /// Creates a new counting writer starting at the given position.
+    #[inline]
+    pub fn with_pos(pos: usize) -> Self {
+        Self { pos }
+    }
+}
+
+impl Positional for CountingWriter {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<E: Error> Writer<E> for CountingWriter {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.pos = match self.pos.checked_add(bytes.len()) {
+            Some(pos) => pos,
+            None => fail!(PositionOverflow {
+                bytes: bytes.len(),
+                pos: self.pos,
+            }),
+        };
+        Ok(())
     }
 }
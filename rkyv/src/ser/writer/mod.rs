@@ -13,7 +13,7 @@ pub use self::core::*;
 pub use self::std::*;
 
 use ::core::{mem, slice};
-use rancor::{Fallible, Strategy};
+use rancor::{Error, Fallible, Strategy};
 
 use crate::{Archive, ArchiveUnsized, RelPtr};
 
@@ -55,19 +55,57 @@ where
     }
 }
 
-/// TODO: Document
+/// Helper methods for [`Writer`]s.
+///
+/// These are useful when laying out archived data by hand. Archived values are
+/// read in place, so each one must be written at a position that is a multiple
+/// of its alignment. Positions are relative to the start of the output, which
+/// must itself be aligned to at least the largest alignment of the archived
+/// types it contains (see [`Aligned`](crate::util::Aligned)). Any gaps left to
+/// satisfy alignment are filled with zeroes so that archives never contain
+/// uninitialized bytes.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     rancor::{Failure, Strategy},
+///     ser::{Positional, Writer, WriterExt},
+///     util::AlignedVec,
+/// };
+///
+/// let mut writer = AlignedVec::new();
+/// let writer = Strategy::<_, Failure>::wrap(&mut writer);
+///
+/// writer.write(&[1, 2, 3]).unwrap();
+/// assert_eq!(writer.pos(), 3);
+///
+/// // Write zero padding up to the next multiple of 8
+/// assert_eq!(writer.align(8).unwrap(), 8);
+/// assert_eq!(writer.as_slice(), &[1, 2, 3, 0, 0, 0, 0, 0]);
+/// ```
 pub trait WriterExt<E>: Writer<E> {
     /// Advances the given number of bytes as padding.
+    ///
+    /// The padding bytes are written as zeroes.
     #[inline]
-    fn pad(&mut self, padding: usize) -> Result<(), E> {
+    fn pad(&mut self, mut padding: usize) -> Result<(), E> {
         const MAX_ZEROES: usize = 32;
         const ZEROES: [u8; MAX_ZEROES] = [0; MAX_ZEROES];
-        debug_assert!(padding < MAX_ZEROES);
 
-        self.write(&ZEROES[0..padding])
+        while padding > 0 {
+            let len = usize::min(padding, MAX_ZEROES);
+            self.write(&ZEROES[0..len])?;
+            padding -= len;
+        }
+
+        Ok(())
     }
 
     /// Aligns the position of the serializer to the given alignment.
+    ///
+    /// `align` must be a power of two. Zero padding is written until the
+    /// position is a multiple of `align`, and the aligned position is returned.
     #[inline]
     fn align(&mut self, align: usize) -> Result<usize, E> {
         let mask = align - 1;
@@ -111,7 +149,9 @@ pub trait WriterExt<E>: Writer<E> {
 
     /// Resolves the given reference with its resolver and writes the archived reference.
     ///
-    /// Returns the position of the written archived `RelPtr`.
+    /// Returns the position of the written archived `RelPtr`. Fails if the
+    /// offset from the written `RelPtr` to `to` does not fit in its offset
+    /// type.
     ///
     /// # Safety
     ///
@@ -120,7 +160,10 @@ pub trait WriterExt<E>: Writer<E> {
         &mut self,
         value: &T,
         to: usize,
-    ) -> Result<usize, E> {
+    ) -> Result<usize, E>
+    where
+        E: Error,
+    {
         let from = self.pos();
         debug_assert_eq!(
             from & (mem::align_of::<RelPtr<T::Archived>>() - 1),
@@ -129,18 +172,77 @@ pub trait WriterExt<E>: Writer<E> {
 
         let mut resolved = mem::MaybeUninit::<RelPtr<T::Archived>>::uninit();
         resolved.as_mut_ptr().write_bytes(0, 1);
-        RelPtr::emplace_unsized(
+        RelPtr::try_emplace_unsized::<E>(
             from,
             to,
             value.archived_metadata(),
             resolved.as_mut_ptr(),
-        );
+        )?;
 
         let data = resolved.as_ptr().cast::<u8>();
         let len = mem::size_of::<RelPtr<T::Archived>>();
         self.write(slice::from_raw_parts(data, len))?;
         Ok(from)
     }
+
+    /// Writes a [`RelPtr`] to an archived `T` that was already written at the
+    /// given position.
+    ///
+    /// This references previously archived data instead of serializing it
+    /// again, for example to build an index that points into values written
+    /// earlier. The written pointer has the same layout as an archived
+    /// `Box<T>`, so a root written with `archive_ref` can be accessed as a
+    /// `Box<T>`.
+    ///
+    /// Returns the position of the written `RelPtr`. Fails if the offset to
+    /// `pos` does not fit in a `RelPtr`.
+    ///
+    /// The validator requires sibling pointers to point to non-overlapping
+    /// values in increasing order, so an archive that references values out of
+    /// order or more than once can only be accessed without validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rkyv::{
+    ///     access,
+    ///     rancor::{Failure, Strategy},
+    ///     ser::{AllocSerializer, WriterExt as _},
+    ///     Serialize as _,
+    /// };
+    ///
+    /// let mut serializer = AllocSerializer::<256>::default();
+    /// let strategy = Strategy::<_, Failure>::wrap(&mut serializer);
+    /// let pos = "hello world"
+    ///     .to_string()
+    ///     .serialize_and_resolve(strategy)
+    ///     .unwrap();
+    /// strategy.archive_ref::<String>(pos).unwrap();
+    /// let bytes = serializer.into_writer();
+    ///
+    /// let archived = access::<Box<String>, Failure>(&bytes).unwrap();
+    /// assert_eq!(archived.as_ref(), "hello world");
+    /// ```
+    #[inline]
+    fn archive_ref<T: Archive>(&mut self, pos: usize) -> Result<usize, E>
+    where
+        E: Error,
+    {
+        let from = self.align_for::<RelPtr<T::Archived>>()?;
+
+        let mut resolved = mem::MaybeUninit::<RelPtr<T::Archived>>::uninit();
+        // SAFETY: `resolved` is valid for writes and its bytes are
+        // initialized before they are written out.
+        unsafe {
+            resolved.as_mut_ptr().write_bytes(0, 1);
+            RelPtr::try_emplace::<E>(from, pos, resolved.as_mut_ptr())?;
+
+            let data = resolved.as_ptr().cast::<u8>();
+            let len = mem::size_of::<RelPtr<T::Archived>>();
+            self.write(slice::from_raw_parts(data, len))?;
+        }
+        Ok(from)
+    }
 }
 
 impl<T, E> WriterExt<E> for T where T: Writer<E> + ?Sized {}
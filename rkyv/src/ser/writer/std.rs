@@ -5,15 +5,28 @@ use std::io;
 
 /// Wraps a type that implements [`io::Write`](std::io::Write) and equips it with [`Writer`].
 ///
+/// Archives are always written front-to-back: the dependencies of an object are written before the
+/// object itself, and the root object is written last. Relative pointers only ever point to bytes
+/// that were already written, so an `IoWriter` never needs to seek or buffer the output. This makes
+/// it possible to stream an archive directly into a file or socket. Padding is written as zero
+/// bytes.
+///
+/// The position of the root object is the final position of the writer minus the size of the
+/// archived root type. Reading the archive back still requires loading it into properly aligned
+/// memory.
+///
 /// # Examples
 /// ```
-/// use rkyv::ser::{serializers::IoWriter, Serializer};
+/// use rkyv::{
+///     rancor::Failure,
+///     ser::{writer::IoWriter, Positional, Writer},
+/// };
 ///
-/// let mut serializer = IoWriter::new(Vec::new());
-/// assert_eq!(serializer.pos(), 0);
-/// serializer.write(&[0u8, 1u8, 2u8, 3u8]);
-/// assert_eq!(serializer.pos(), 4);
-/// let buf = serializer.into_inner();
+/// let mut writer = IoWriter::new(Vec::new());
+/// assert_eq!(writer.pos(), 0);
+/// Writer::<Failure>::write(&mut writer, &[0u8, 1u8, 2u8, 3u8]).unwrap();
+/// assert_eq!(writer.pos(), 4);
+/// let buf = writer.into_inner();
 /// assert_eq!(buf.len(), 4);
 /// assert_eq!(buf, vec![0u8, 1u8, 2u8, 3u8]);
 /// ```
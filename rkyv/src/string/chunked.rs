@@ -0,0 +1,516 @@
+//! An archived `String` that stores its text in fixed-size chunks.
+
+use core::{
+    fmt::{self, Write as _},
+    iter::FusedIterator,
+    slice, str,
+};
+
+use rancor::{Error, Fallible, ResultExt as _};
+
+use crate::{
+    endian::SwapEndian,
+    primitive::{ArchivedUsize, FixedUsize},
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Serialize,
+};
+
+/// An archived `String` that stores its bytes in separate fixed-size chunks.
+///
+/// Every chunk except the last holds exactly [`chunk_size`](Self::chunk_size)
+/// bytes, and the last chunk holds the rest. Chunks are split at byte offsets
+/// rather than at `char` boundaries, so a multi-byte `char` may start in one
+/// chunk and end in the next. Only the text as a whole is valid UTF-8, but
+/// bytes and `char`s can still be read at any offset without copying the text
+/// into one contiguous string.
+///
+/// This is the archived type of strings serialized with
+/// [`Chunked`](crate::with::Chunked).
+#[cfg_attr(feature = "strict", repr(C))]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedChunkedString {
+    chunks: ArchivedVec<ArchivedVec<u8>>,
+    chunk_size: ArchivedUsize,
+}
+
+/// Returns whether the given byte continues a multi-byte UTF-8 sequence.
+#[inline]
+fn is_continuation(byte: u8) -> bool {
+    byte & 0xc0 == 0x80
+}
+
+/// Returns the length of the UTF-8 sequence that starts with the given byte.
+#[inline]
+fn utf8_width(lead: u8) -> usize {
+    match lead {
+        0x00..=0x7f => 1,
+        0x80..=0xdf => 2,
+        0xe0..=0xef => 3,
+        _ => 4,
+    }
+}
+
+impl ArchivedChunkedString {
+    /// Returns the length of the archived string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.chunks.last() {
+            Some(last) => {
+                (self.chunks.len() - 1) * self.chunk_size() + last.len()
+            }
+            None => 0,
+        }
+    }
+
+    /// Returns whether the archived string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Returns the number of bytes in each chunk except the last.
+    #[inline]
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size.to_native() as usize
+    }
+
+    /// Returns the number of chunks in the archived string.
+    #[inline]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the bytes of the chunk at the given index, if any.
+    ///
+    /// The bytes of a chunk may start or end in the middle of a `char`.
+    #[inline]
+    pub fn chunk(&self, index: usize) -> Option<&[u8]> {
+        self.chunks.get(index).map(|chunk| chunk.as_slice())
+    }
+
+    /// Returns the byte at the given offset, if any.
+    #[inline]
+    pub fn get_byte(&self, index: usize) -> Option<u8> {
+        if index >= self.len() {
+            return None;
+        }
+        let chunk_size = self.chunk_size();
+        Some(self.chunks[index / chunk_size][index % chunk_size])
+    }
+
+    /// Returns whether the given byte offset is the start or end of a `char`.
+    ///
+    /// Like [`str::is_char_boundary`], the start and end of the string are
+    /// both boundaries.
+    #[inline]
+    pub fn is_char_boundary(&self, index: usize) -> bool {
+        index == self.len()
+            || matches!(self.get_byte(index), Some(b) if !is_continuation(b))
+    }
+
+    /// Returns the `char` that starts at the given byte offset.
+    ///
+    /// Returns `None` if the offset is out of bounds or is not the start of a
+    /// `char`. The bytes of the `char` may be split across chunks.
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        let lead = self.get_byte(index)?;
+        if is_continuation(lead) {
+            return None;
+        }
+
+        let width = utf8_width(lead);
+        let mut bytes = [lead, 0, 0, 0];
+        for (i, byte) in bytes.iter_mut().enumerate().take(width).skip(1) {
+            *byte = self.get_byte(index + i)?;
+        }
+        str::from_utf8(&bytes[..width]).ok()?.chars().next()
+    }
+
+    /// Returns an iterator over the `char`s of the archived string.
+    #[inline]
+    pub fn chars(&self) -> ChunkedStringChars<'_> {
+        ChunkedStringChars {
+            string: self,
+            next_chunk: 0,
+            chars: "".chars(),
+            straddling: None,
+        }
+    }
+
+    /// Returns an iterator over the bytes of the archived string.
+    #[inline]
+    pub fn bytes(&self) -> ChunkedStringBytes<'_> {
+        ChunkedStringBytes {
+            chunks: self.chunks.iter(),
+            bytes: [].iter(),
+        }
+    }
+
+    /// Splits the chunk at the given index into the `char`s that start and end
+    /// in it and the `char` that starts in it and ends in a later chunk.
+    fn split_chunk(&self, index: usize) -> (&str, Option<char>) {
+        let chunk = self.chunks[index].as_slice();
+
+        // Continuation bytes at the start of the chunk belong to a `char` that
+        // started in an earlier chunk
+        let start = chunk
+            .iter()
+            .take(3)
+            .take_while(|&&b| is_continuation(b))
+            .count();
+
+        // Only a `char` that starts in the last three bytes can be cut off
+        let mut end = chunk.len();
+        for i in (start.max(chunk.len().saturating_sub(3))..chunk.len()).rev() {
+            if !is_continuation(chunk[i]) {
+                if i + utf8_width(chunk[i]) > chunk.len() {
+                    end = i;
+                }
+                break;
+            }
+        }
+
+        // SAFETY: The archived string is valid UTF-8, and `start..end` covers
+        // exactly the `char`s that start and end in this chunk.
+        let middle = unsafe { str::from_utf8_unchecked(&chunk[start..end]) };
+        let straddling = if end < chunk.len() {
+            self.char_at(index * self.chunk_size() + end)
+        } else {
+            None
+        };
+
+        (middle, straddling)
+    }
+
+    /// Resolves an archived chunked string from a given length.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a string of `len` bytes
+    ///   with [`serialize_from_str`](Self::serialize_from_str)
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: ChunkedStringResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.chunks);
+        ArchivedVec::resolve_from_len(
+            len.div_ceil(resolver.chunk_size),
+            pos + fp,
+            resolver.chunks,
+            fo,
+        );
+        let (fp, fo) = out_field!(out.chunk_size);
+        usize::resolve(&resolver.chunk_size, pos + fp, (), fo);
+    }
+
+    /// Serializes an archived chunked string from a given `str`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[inline]
+    pub fn serialize_from_str<S>(
+        value: &str,
+        chunk_size: usize,
+        serializer: &mut S,
+    ) -> Result<ChunkedStringResolver, S::Error>
+    where
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Error,
+    {
+        assert!(chunk_size != 0, "chunk size must be greater than zero");
+        FixedUsize::try_from(chunk_size).into_error()?;
+
+        let chunks = ArchivedVec::serialize_from_iter::<Chunk<'_>, _, _>(
+            value.as_bytes().chunks(chunk_size).map(Chunk),
+            serializer,
+        )?;
+
+        Ok(ChunkedStringResolver { chunks, chunk_size })
+    }
+}
+
+/// A chunk of bytes that is archived as an [`ArchivedVec`].
+struct Chunk<'a>(&'a [u8]);
+
+impl Archive for Chunk<'_> {
+    type Archived = ArchivedVec<u8>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(
+        &self,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedVec::resolve_from_len(self.0.len(), pos, resolver, out);
+    }
+}
+
+impl<S: Fallible + Writer + ?Sized> Serialize<S> for Chunk<'_> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+        // SAFETY: `u8` is copy-safe.
+        unsafe { ArchivedVec::serialize_copy_from_slice(self.0, serializer) }
+    }
+}
+
+impl fmt::Debug for ArchivedChunkedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.chars() {
+            for escaped in c.escape_debug() {
+                f.write_char(escaped)?;
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+impl fmt::Display for ArchivedChunkedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Each chunk is written as it is read, so the text is never copied
+        // into one contiguous string
+        for index in 0..self.chunk_count() {
+            let (middle, straddling) = self.split_chunk(index);
+            f.write_str(middle)?;
+            if let Some(c) = straddling {
+                f.write_char(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Eq for ArchivedChunkedString {}
+
+impl PartialEq for ArchivedChunkedString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.bytes().eq(other.bytes())
+    }
+}
+
+impl PartialEq<str> for ArchivedChunkedString {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.len() == other.len() && self.bytes().eq(other.bytes())
+    }
+}
+
+impl PartialEq<ArchivedChunkedString> for str {
+    #[inline]
+    fn eq(&self, other: &ArchivedChunkedString) -> bool {
+        other.eq(self)
+    }
+}
+
+impl SwapEndian for ArchivedChunkedString {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        self.chunks.scalar_ranges(report);
+        self.chunk_size.scalar_ranges(report);
+    }
+}
+
+/// An iterator over the `char`s of an [`ArchivedChunkedString`].
+pub struct ChunkedStringChars<'a> {
+    string: &'a ArchivedChunkedString,
+    next_chunk: usize,
+    chars: str::Chars<'a>,
+    straddling: Option<char>,
+}
+
+impl Iterator for ChunkedStringChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(c) = self.chars.next() {
+                return Some(c);
+            }
+            if let Some(c) = self.straddling.take() {
+                return Some(c);
+            }
+            if self.next_chunk == self.string.chunk_count() {
+                return None;
+            }
+
+            let (middle, straddling) = self.string.split_chunk(self.next_chunk);
+            self.next_chunk += 1;
+            self.chars = middle.chars();
+            self.straddling = straddling;
+        }
+    }
+}
+
+impl FusedIterator for ChunkedStringChars<'_> {}
+
+/// An iterator over the bytes of an [`ArchivedChunkedString`].
+pub struct ChunkedStringBytes<'a> {
+    chunks: slice::Iter<'a, ArchivedVec<u8>>,
+    bytes: slice::Iter<'a, u8>,
+}
+
+impl Iterator for ChunkedStringBytes<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(&byte) = self.bytes.next() {
+                return Some(byte);
+            }
+            self.bytes = self.chunks.next()?.iter();
+        }
+    }
+}
+
+impl FusedIterator for ChunkedStringBytes<'_> {}
+
+/// The resolver for [`ArchivedChunkedString`].
+pub struct ChunkedStringResolver {
+    chunks: VecResolver,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{fmt, str};
+
+    use bytecheck::{
+        rancor::{Error, Fallible},
+        Verify,
+    };
+    use rancor::fail;
+
+    use super::{utf8_width, ArchivedChunkedString};
+
+    #[derive(Debug)]
+    struct InvalidChunkLength {
+        index: usize,
+        len: usize,
+        chunk_size: usize,
+    }
+
+    impl fmt::Display for InvalidChunkLength {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "chunk {} has length {} but the chunk size is {}",
+                self.index, self.len, self.chunk_size,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for InvalidChunkLength {}
+
+    #[derive(Debug)]
+    struct InvalidUtf8 {
+        offset: usize,
+    }
+
+    impl fmt::Display for InvalidUtf8 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "chunked string has invalid UTF-8 at byte {}",
+                self.offset,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for InvalidUtf8 {}
+
+    unsafe impl<C> Verify<C> for ArchivedChunkedString
+    where
+        C: Fallible + ?Sized,
+        C::Error: Error,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let chunk_size = self.chunk_size();
+            let last = self.chunks.len().saturating_sub(1);
+            for (index, chunk) in self.chunks.iter().enumerate() {
+                let len = chunk.len();
+                let is_valid = if index == last {
+                    len != 0 && len <= chunk_size
+                } else {
+                    len == chunk_size
+                };
+                if !is_valid {
+                    fail!(InvalidChunkLength {
+                        index,
+                        len,
+                        chunk_size,
+                    });
+                }
+            }
+
+            // A `char` may be split across chunks, so the bytes of a sequence
+            // that is cut off are carried over and checked once the rest of
+            // it has been read
+            let mut pending = [0u8; 4];
+            let mut pending_len = 0;
+            let mut pending_offset = 0;
+            let mut offset = 0;
+            for chunk in self.chunks.iter() {
+                let mut rest = chunk.as_slice();
+
+                if pending_len != 0 {
+                    let width = utf8_width(pending[0]);
+                    let count = usize::min(width - pending_len, rest.len());
+                    pending[pending_len..pending_len + count]
+                        .copy_from_slice(&rest[..count]);
+                    pending_len += count;
+                    rest = &rest[count..];
+
+                    if pending_len == width {
+                        if str::from_utf8(&pending[..width]).is_err() {
+                            fail!(InvalidUtf8 {
+                                offset: pending_offset
+                            });
+                        }
+                        pending_len = 0;
+                    }
+                }
+
+                let rest_offset = offset + chunk.len() - rest.len();
+                if let Err(error) = str::from_utf8(rest) {
+                    let valid = error.valid_up_to();
+                    if error.error_len().is_some() {
+                        fail!(InvalidUtf8 {
+                            offset: rest_offset + valid,
+                        });
+                    }
+
+                    let tail = &rest[valid..];
+                    pending[..tail.len()].copy_from_slice(tail);
+                    pending_len = tail.len();
+                    pending_offset = rest_offset + valid;
+                }
+
+                offset += chunk.len();
+            }
+
+            if pending_len != 0 {
+                fail!(InvalidUtf8 {
+                    offset: pending_offset
+                });
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! An archived string with a configurable inline capacity.
+
+use core::{borrow::Borrow, cmp, fmt, hash, mem, ops::Deref, ptr, slice, str};
+
+use rancor::{Error, Fallible, Panic, ResultExt as _};
+
+use crate::{
+    primitive::{ArchivedUsize, FixedIsize, FixedUsize},
+    ser::Writer,
+    string::StringResolver,
+    SerializeUnsized,
+};
+
+const OFFSET_BYTES: usize = mem::size_of::<FixedIsize>();
+
+/// An archived string that stores strings of up to `N` bytes inline.
+///
+/// Strings that are at most `N` bytes long are stored directly inside the
+/// archived string, and longer strings store a relative offset to their bytes
+/// instead, like [`ArchivedString`](crate::string::ArchivedString). Compared
+/// to `ArchivedString`, this allows choosing how many bytes are inlined, which
+/// saves space and a pointer indirection for archives with many short strings.
+/// The archived string always dereferences to a `str`, whether the string is
+/// inline or not.
+///
+/// `N` must be at least the size of an archived `isize` (2, 4, or 8 bytes
+/// depending on the enabled `pointer_width_*` feature) so that the offset of
+/// long strings fits in the inline bytes. Smaller capacities fail to compile
+/// when the type is used.
+///
+/// This is the archived type of strings serialized with
+/// [`InlineString`](crate::with::InlineString).
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+#[repr(C)]
+pub struct ArchivedInlineString<const N: usize> {
+    len: ArchivedUsize,
+    // The bytes of inline strings, or the offset to the bytes of out-of-line
+    // strings as a little-endian `FixedIsize`
+    bytes: [u8; N],
+}
+
+impl<const N: usize> ArchivedInlineString<N> {
+    const CAPACITY_CHECK: () = assert!(
+        N >= OFFSET_BYTES,
+        "the inline capacity must be able to hold an archived isize",
+    );
+
+    /// Returns whether the string is stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.len() <= N
+    }
+
+    /// Returns the length of the string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the offset from the start of this string to its bytes.
+    ///
+    /// # Safety
+    ///
+    /// The string must not be stored inline.
+    #[inline]
+    unsafe fn out_of_line_offset(&self) -> isize {
+        let mut offset = [0; OFFSET_BYTES];
+        offset.copy_from_slice(&self.bytes[..OFFSET_BYTES]);
+        FixedIsize::from_le_bytes(offset) as isize
+    }
+
+    /// Returns a pointer to the bytes of the string.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        if self.is_inline() {
+            self.bytes.as_ptr()
+        } else {
+            unsafe {
+                (self as *const Self)
+                    .cast::<u8>()
+                    .offset(self.out_of_line_offset())
+            }
+        }
+    }
+
+    /// Extracts a string slice containing the entire string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe {
+            str::from_utf8_unchecked(slice::from_raw_parts(
+                self.as_ptr(),
+                self.len(),
+            ))
+        }
+    }
+
+    /// Resolves an archived inline string from a given `str`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset from `pos` to the bytes of an out-of-line string
+    /// does not fit in an archived `isize`. Serializing checks this offset from
+    /// the position the bytes were serialized at, so this only happens if the
+    /// string is resolved far away from that position.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `value` with
+    ///   [`serialize_from_str`](Self::serialize_from_str)
+    #[inline]
+    pub unsafe fn resolve_from_str(
+        value: &str,
+        pos: usize,
+        resolver: StringResolver,
+        out: *mut Self,
+    ) {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CAPACITY_CHECK;
+
+        // The length was checked to fit while serializing
+        let out_len = ptr::addr_of_mut!((*out).len);
+        out_len.write(ArchivedUsize::from_native(value.len() as FixedUsize));
+
+        let out_bytes = ptr::addr_of_mut!((*out).bytes).cast::<u8>();
+        out_bytes.write_bytes(0, N);
+        if value.len() <= N {
+            ptr::copy_nonoverlapping(
+                value.as_bytes().as_ptr(),
+                out_bytes,
+                value.len(),
+            );
+        } else {
+            let offset =
+                crate::rel_ptr::signed_offset::<Panic>(pos, resolver.pos)
+                    .always_ok();
+            let offset = FixedIsize::try_from(offset)
+                .into_error::<Panic>()
+                .always_ok();
+            ptr::copy_nonoverlapping(
+                offset.to_le_bytes().as_ptr(),
+                out_bytes,
+                OFFSET_BYTES,
+            );
+        }
+    }
+
+    /// Serializes an archived inline string from a given `str`.
+    ///
+    /// Only strings longer than `N` bytes are written out of line. Returns an
+    /// error if the offset to the bytes of an out-of-line string does not fit
+    /// in an archived `isize`.
+    #[inline]
+    pub fn serialize_from_str<S>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<StringResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+        S::Error: Error,
+        str: SerializeUnsized<S>,
+    {
+        FixedUsize::try_from(value.len()).into_error()?;
+        if value.len() <= N {
+            Ok(StringResolver { pos: 0 })
+        } else {
+            let pos = value.serialize_unsized(serializer)?;
+            // The string is resolved after its bytes, so it's at least this far
+            // away from them
+            let offset = crate::rel_ptr::signed_offset(serializer.pos(), pos)?;
+            FixedIsize::try_from(offset).into_error()?;
+            Ok(StringResolver { pos })
+        }
+    }
+}
+
+impl<const N: usize> AsRef<str> for ArchivedInlineString<N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Borrow<str> for ArchivedInlineString<N> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Deref for ArchivedInlineString<N> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArchivedInlineString<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Display for ArchivedInlineString<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Eq for ArchivedInlineString<N> {}
+
+impl<const N: usize> hash::Hash for ArchivedInlineString<N> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<const N: usize> Ord for ArchivedInlineString<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const N: usize> PartialEq for ArchivedInlineString<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> PartialOrd for ArchivedInlineString<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for ArchivedInlineString<N> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        PartialEq::eq(self.as_str(), *other)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for ArchivedInlineString<N> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        PartialEq::eq(self.as_str(), other)
+    }
+}
+
+impl<const N: usize> PartialEq<ArchivedInlineString<N>> for &str {
+    #[inline]
+    fn eq(&self, other: &ArchivedInlineString<N>) -> bool {
+        PartialEq::eq(other.as_str(), *self)
+    }
+}
+
+impl<const N: usize> PartialEq<ArchivedInlineString<N>> for str {
+    #[inline]
+    fn eq(&self, other: &ArchivedInlineString<N>) -> bool {
+        PartialEq::eq(other.as_str(), self)
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use bytecheck::{
+        rancor::{Error, Fallible},
+        CheckBytes, Verify,
+    };
+
+    use super::ArchivedInlineString;
+    use crate::validation::{ArchiveContext, ArchiveContextExt};
+
+    unsafe impl<C, const N: usize> Verify<C> for ArchivedInlineString<N>
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
+    {
+        #[inline]
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let len = self.len();
+            if self.is_inline() {
+                let ptr = ptr_meta::from_raw_parts::<str>(
+                    self.bytes.as_ptr().cast(),
+                    len,
+                );
+                unsafe {
+                    str::check_bytes(ptr, context)?;
+                }
+            } else {
+                let base = (self as *const Self).cast();
+                let offset = unsafe { self.out_of_line_offset() };
+
+                let ptr = unsafe {
+                    context.bounds_check_subtree_base_offset::<str>(
+                        base, offset, len,
+                    )?
+                };
+
+                let range = unsafe { context.push_prefix_subtree(ptr)? };
+                unsafe {
+                    str::check_bytes(ptr, context)?;
+                }
+                unsafe {
+                    context.pop_subtree_range(range)?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
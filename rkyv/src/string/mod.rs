@@ -1,8 +1,16 @@
 //! Archived versions of string types.
 
+mod chunked;
+mod inline;
 pub mod repr;
 
-use crate::SerializeUnsized;
+pub use self::chunked::{
+    ArchivedChunkedString, ChunkedStringBytes, ChunkedStringChars,
+    ChunkedStringResolver,
+};
+pub use self::inline::ArchivedInlineString;
+
+use crate::{endian::SwapEndian, SerializeUnsized};
 use core::{
     borrow::Borrow,
     cmp, fmt, hash,
@@ -231,6 +239,13 @@ impl PartialOrd<ArchivedString> for str {
     }
 }
 
+impl SwapEndian for ArchivedString {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(Range<usize>)) {
+        self.repr.scalar_ranges(report);
+    }
+}
+
 /// The resolver for `String`.
 pub struct StringResolver {
     pos: usize,
@@ -2,7 +2,10 @@
 
 use rancor::{Error, Panic, ResultExt as _};
 
-use crate::primitive::{ArchivedUsize, FixedIsize};
+use crate::{
+    endian::SwapEndian,
+    primitive::{ArchivedUsize, FixedIsize},
+};
 use core::{marker::PhantomPinned, mem, ptr, slice, str};
 
 const OFFSET_BYTES: usize = mem::size_of::<FixedIsize>();
@@ -166,7 +169,8 @@ impl ArchivedStringRepr {
 
         let out_offset = ptr::addr_of_mut!((*out).out_of_line.offset);
         let offset = crate::rel_ptr::signed_offset(pos, target)?;
-        *out_offset = (offset as FixedIsize).to_le_bytes();
+        let offset = FixedIsize::try_from(offset).into_error()?;
+        *out_offset = offset.to_le_bytes();
 
         Ok(())
     }
@@ -194,6 +198,17 @@ impl ArchivedStringRepr {
     }
 }
 
+/// The offset of an out-of-line representation is always stored in
+/// little-endian byte order, so only the length is reported.
+impl SwapEndian for ArchivedStringRepr {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        if !self.is_inline() {
+            unsafe { self.out_of_line.len.scalar_ranges(report) }
+        }
+    }
+}
+
 #[cfg(feature = "bytecheck")]
 const _: () = {
     use bytecheck::{rancor::Fallible, CheckBytes};
@@ -119,7 +119,7 @@ impl ArchivedDuration {
 
 #[cfg(feature = "bytecheck")]
 mod verify {
-    use super::ArchivedDuration;
+    use super::{ArchivedDuration, NANOS_PER_SEC};
     use bytecheck::{
         rancor::{Error, Fallible},
         Verify,
@@ -139,7 +139,7 @@ mod verify {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             write!(
                 f,
-                "`nanos` field of `Duration` is greater than 1 billion: {}",
+                "`nanos` field of `Duration` is not less than 1 billion: {}",
                 self.nanos,
             )
         }
@@ -156,7 +156,7 @@ mod verify {
         #[inline]
         fn verify(&self, _: &mut C) -> Result<(), C::Error> {
             let nanos = self.nanos.to_native();
-            if nanos > 1_000_000_000 {
+            if nanos >= NANOS_PER_SEC {
                 fail!(DurationError { nanos });
             } else {
                 Ok(())
@@ -0,0 +1,128 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+#[cfg(feature = "bytecheck")]
+use rancor::Fallible;
+
+macro_rules! define_aligns {
+    ($($name:ident = $align:literal),* $(,)?) => {
+        $(
+            #[doc = concat!(
+                "A zero-sized type with an alignment of ",
+                stringify!($align),
+                " bytes.",
+            )]
+            ///
+            /// Use this with [`Aligned`] to align a value to this alignment.
+            #[derive(
+                Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+            )]
+            #[repr(align($align))]
+            pub struct $name;
+
+            #[cfg(feature = "bytecheck")]
+            // SAFETY: `$name` is a zero-sized type, so any pointer to it is
+            // valid.
+            unsafe impl<C: Fallible + ?Sized> bytecheck::CheckBytes<C> for $name {
+                #[inline]
+                unsafe fn check_bytes(
+                    _: *const Self,
+                    _: &mut C,
+                ) -> Result<(), C::Error> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+define_aligns! {
+    Align1 = 1,
+    Align2 = 2,
+    Align4 = 4,
+    Align8 = 8,
+    Align16 = 16,
+    Align32 = 32,
+    Align64 = 64,
+    Align128 = 128,
+    Align256 = 256,
+    Align512 = 512,
+    Align1024 = 1024,
+    Align2048 = 2048,
+    Align4096 = 4096,
+}
+
+/// A value aligned to at least the alignment of `A`.
+///
+/// This is useful for byte buffers that will hold archives containing types with large alignment
+/// requirements, like SIMD vectors. As long as the start of the buffer is sufficiently aligned,
+/// writers will place each archived value (including the root) at its required alignment.
+///
+/// # Examples
+///
+/// ```
+/// use core::mem;
+/// use rkyv::util::{Align32, Aligned};
+///
+/// let bytes = Aligned::<Align32, _>::new([0u8; 256]);
+/// assert_eq!(mem::align_of_val(&bytes), 32);
+/// assert_eq!(bytes.as_ptr() as usize % 32, 0);
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(C)]
+pub struct Aligned<A, T: ?Sized> {
+    _align: [A; 0],
+    value: T,
+}
+
+impl<A, T> Aligned<A, T> {
+    /// Aligns the given value.
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self { _align: [], value }
+    }
+
+    /// Consumes the `Aligned`, returning the underlying value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<A, T: fmt::Debug + ?Sized> fmt::Debug for Aligned<A, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<A, T: ?Sized> Deref for Aligned<A, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<A, T: ?Sized> DerefMut for Aligned<A, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<A, T: AsRef<[u8]> + ?Sized> AsRef<[u8]> for Aligned<A, T> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.value.as_ref()
+    }
+}
+
+impl<A, T: AsMut<[u8]> + ?Sized> AsMut<[u8]> for Aligned<A, T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.value.as_mut()
+    }
+}
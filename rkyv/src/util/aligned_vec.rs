@@ -2,11 +2,11 @@ use crate::vec::VecResolver;
 use crate::{
     ser::{Allocator, Writer, WriterExt as _},
     vec::ArchivedVec,
-    Archive, Archived, Serialize,
+    Archive, Archived, Serialize, SerializeUnsized,
 };
 
 #[cfg(not(feature = "std"))]
-use alloc::{alloc, boxed::Box, vec::Vec};
+use ::alloc::{alloc, boxed::Box, vec::Vec};
 use core::borrow::{Borrow, BorrowMut};
 use core::{
     fmt,
@@ -20,32 +20,39 @@ use std::{alloc, io};
 
 /// A vector of bytes that aligns its memory to 16 bytes.
 ///
+/// `AlignedVec` implements [`Writer`] and grows as bytes are written to it, so
+/// it can be used to archive values of any size without choosing a buffer
+/// size up front.
+///
 /// The alignment also applies to `ArchivedAlignedVec`, which is useful for aligning opaque bytes inside of an archived data
 /// type.
 ///
 /// ```
-/// # use rkyv::{archived_value, AlignedBytes, AlignedVec, Archive, Serialize};
-/// # use rkyv::ser::Serializer;
-/// # use rkyv::ser::serializers::CoreSerializer;
+/// # use rkyv::{
+/// #     access_unchecked, rancor::Failure, ser::{AllocSerializer, Positional, Writer},
+/// #     util::{serialize_into, AlignedVec}, Archive, Serialize,
+/// # };
 /// #
 /// #[derive(Archive, Serialize)]
 /// struct HasAlignedBytes {
 ///     pub bytes: AlignedVec,
 /// }
 ///
-/// let mut serializer = CoreSerializer::<256, 0>::default();
+/// let mut serializer = AllocSerializer::<256>::default();
 ///
 /// // Write a single byte to force re-alignment.
-/// serializer.write(&[0]).unwrap();
+/// Writer::<Failure>::write(&mut serializer, &[0]).unwrap();
 /// assert_eq!(serializer.pos(), 1);
 ///
 /// let mut bytes = AlignedVec::new();
 /// bytes.extend_from_slice(&[1, 2, 3]);
-/// let pos = serializer.serialize_value(&HasAlignedBytes { bytes }).unwrap();
+/// let serializer =
+///     serialize_into::<_, _, Failure>(&HasAlignedBytes { bytes }, serializer)
+///         .unwrap();
 ///
 /// // Make sure we can recover the archived type with the expected alignment.
-/// let buf = serializer.into_serializer().into_inner();
-/// let archived = unsafe { archived_value::<HasAlignedBytes>(buf.as_ref(), pos) };
+/// let buf = serializer.into_writer();
+/// let archived = unsafe { access_unchecked::<HasAlignedBytes>(&buf) };
 /// assert_eq!(archived.bytes.as_slice(), &[1, 2, 3]);
 /// assert_eq!(archived.bytes.as_ptr().align_offset(16), 0);
 /// ```
@@ -83,7 +90,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// ```
@@ -103,7 +110,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::with_capacity(10);
     ///
@@ -164,7 +171,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut v = AlignedVec::new();
     /// v.extend_from_slice(&[1, 2, 3, 4]);
@@ -239,7 +246,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::with_capacity(10);
     /// vec.extend_from_slice(&[1, 2, 3]);
@@ -267,7 +274,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// // Allocate vector big enough for 4 bytes.
     /// let size = 4;
@@ -294,7 +301,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.extend_from_slice(&[1, 2, 3, 4, 5]);
@@ -323,7 +330,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut x = AlignedVec::new();
     /// x.extend_from_slice(&[1, 2, 4]);
@@ -346,7 +353,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.extend_from_slice(&[1, 2, 3, 4, 5]);
@@ -364,7 +371,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let vec = AlignedVec::with_capacity(10);
     /// assert_eq!(vec.capacity(), 10);
@@ -385,7 +392,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.push(1);
@@ -443,7 +450,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.push(1);
@@ -480,7 +487,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.push(3);
@@ -514,7 +521,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut v = Vec::new();
     /// assert!(v.is_empty());
@@ -531,7 +538,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut a = AlignedVec::new();
     /// a.extend_from_slice(&[1, 2, 3]);
@@ -548,7 +555,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.push(1);
@@ -574,7 +581,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.extend_from_slice(&[1, 2, 3]);
@@ -600,7 +607,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.extend_from_slice(&[1, 2]);
@@ -647,7 +654,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::new();
     /// vec.push(1);
@@ -683,7 +690,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::with_capacity(3);
     /// vec.extend_from_slice(&[1, 2, 3]);
@@ -708,7 +715,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut v = AlignedVec::new();
     /// v.extend_from_slice(&[1, 2, 3]);
@@ -719,7 +726,7 @@ impl AlignedVec {
     /// Any excess capacity is removed:
     ///
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut vec = AlignedVec::with_capacity(10);
     /// vec.extend_from_slice(&[1, 2, 3]);
@@ -739,7 +746,7 @@ impl AlignedVec {
     ///
     /// # Examples
     /// ```
-    /// use rkyv::AlignedVec;
+    /// use rkyv::util::AlignedVec;
     ///
     /// let mut v = AlignedVec::new();
     /// v.extend_from_slice(&[1, 2, 3]);
@@ -765,7 +772,7 @@ const _: () = {
         ///
         /// # Examples
         /// ```
-        /// use rkyv::AlignedVec;
+        /// use rkyv::util::AlignedVec;
         ///
         /// let source = (0..4096).map(|x| (x % 256) as u8).collect::<Vec<_>>();
         /// let mut bytes = AlignedVec::new();
@@ -1007,7 +1014,11 @@ impl io::Write for AlignedVec {
 // SAFETY: AlignedVec is safe to send to another thread
 unsafe impl Send for AlignedVec {}
 
-impl<S: Fallible + Allocator + Writer + ?Sized> Serialize<S> for AlignedVec {
+impl<S> Serialize<S> for AlignedVec
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    [u8]: SerializeUnsized<S>,
+{
     #[inline]
     fn serialize(
         &self,
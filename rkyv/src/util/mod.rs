@@ -9,9 +9,12 @@
 //! Alignment helpers ensure that byte buffers are properly aligned when accessing and deserializing
 //! data.
 
+mod aligned;
 #[cfg(feature = "alloc")]
 mod aligned_vec;
 mod scratch_vec;
+#[cfg(feature = "alloc")]
+mod sections;
 
 #[cfg(feature = "alloc")]
 use crate::{de::pooling::Unify, ser::AllocSerializer};
@@ -24,13 +27,18 @@ use core::{
     ops::{Deref, DerefMut},
     pin::Pin,
 };
-use rancor::Strategy;
+use rancor::{Error, Strategy};
 
+#[doc(inline)]
+pub use self::aligned::*;
 #[doc(inline)]
 #[cfg(feature = "alloc")]
 pub use self::aligned_vec::*;
 #[doc(inline)]
 pub use self::scratch_vec::*;
+#[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use self::sections::*;
 
 #[cfg(debug_assertions)]
 #[inline]
@@ -238,6 +246,8 @@ pub unsafe fn access_unsized_unchecked_mut<T: ArchiveUnsized + ?Sized>(
 
 /// A buffer of bytes aligned to 16 bytes.
 ///
+/// For buffers that need a different alignment, use [`Aligned`].
+///
 /// # Examples
 ///
 /// ```
@@ -296,20 +306,28 @@ impl<const N: usize> AsMut<[u8]> for AlignedBytes<N> {
 /// [`AllocationTracker`](crate::ser::allocator::AllocationTracker) to determine
 /// how much scratch space is typically used.
 ///
+/// The output is presized with the [`serialized_size_hint`] of the value to
+/// avoid reallocating it while serializing.
+///
 /// This function is only available with the `alloc` feature because it uses a
 /// general-purpose serializer. In no-alloc and high-performance environments,
 /// the serializer should be customized for the specific situation.
 ///
+/// [`serialized_size_hint`]: crate::Archive::serialized_size_hint
+///
 /// # Examples
 /// ```
+/// use rkyv::rancor::Failure;
+///
 /// let value = vec![1, 2, 3, 4];
 ///
-/// let bytes = rkyv::to_bytes::<_, 1024>(&value).expect("failed to serialize vec");
+/// let bytes = rkyv::to_bytes::<_, 1024, Failure>(&value)
+///     .expect("failed to serialize vec");
 /// // SAFETY:
 /// // - The byte slice represents an archived object
 /// // - The root of the object is stored at the end of the slice
 /// let deserialized = unsafe {
-///     rkyv::from_bytes_unchecked::<Vec<i32>>(&bytes)
+///     rkyv::from_bytes_unchecked::<Vec<i32>, Failure>(&bytes)
 ///         .expect("failed to deserialize vec")
 /// };
 ///
@@ -321,7 +339,13 @@ pub fn to_bytes<T, const N: usize, E>(value: &T) -> Result<AlignedVec, E>
 where
     T: Serialize<Strategy<AllocSerializer<N>, E>>,
 {
-    Ok(serialize_into(value, AllocSerializer::<N>::default())?.into_writer())
+    let capacity = mem::size_of::<T::Archived>() + value.serialized_size_hint();
+    let serializer = AllocSerializer::<N>::new(
+        AlignedVec::with_capacity(capacity),
+        Default::default(),
+        Default::default(),
+    );
+    Ok(serialize_into(value, serializer)?.into_writer())
 }
 
 /// Serializes the given value into the given serializer and then returns the
@@ -346,6 +370,7 @@ pub fn serialize_rel_ptr_into<T, S, E>(
 where
     T: SerializeUnsized<Strategy<S, E>> + ?Sized,
     S: Writer<E>,
+    E: Error,
 {
     serialize_rel_ptr(value, &mut serializer)?;
     Ok(serializer)
@@ -372,6 +397,7 @@ pub fn serialize_rel_ptr<T, S, E>(
 where
     T: SerializeUnsized<Strategy<S, E>> + ?Sized,
     S: Writer<E> + ?Sized,
+    E: Error,
 {
     value.serialize_and_resolve_rel_ptr(Strategy::wrap(serializer))?;
     Ok(())
@@ -391,14 +417,17 @@ where
 ///
 /// # Examples
 /// ```
+/// use rkyv::rancor::Failure;
+///
 /// let value = vec![1, 2, 3, 4];
 ///
-/// let bytes = rkyv::to_bytes::<_, 1024>(&value).expect("failed to serialize vec");
+/// let bytes = rkyv::to_bytes::<_, 1024, Failure>(&value)
+///     .expect("failed to serialize vec");
 /// // SAFETY:
 /// // - The byte slice represents an archived object
 /// // - The root of the object is stored at the end of the slice
 /// let deserialized = unsafe {
-///     rkyv::from_bytes_unchecked::<Vec<i32>>(&bytes)
+///     rkyv::from_bytes_unchecked::<Vec<i32>, Failure>(&bytes)
 ///         .expect("failed to deserialize vec")
 /// };
 ///
@@ -414,7 +443,41 @@ where
     deserialize(access_unchecked::<T>(bytes), &mut Unify::default())
 }
 
-/// TODO: document
+/// Deserializes an archived value into an owned `T` using the given
+/// deserializer.
+///
+/// This is the inverse of [`serialize`]: every archived type that implements
+/// [`Deserialize<T, _>`](Deserialize) can be turned back into the value it was
+/// archived from. Use [`Unify`](crate::de::pooling::Unify) as the deserializer
+/// to preserve shared pointers.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     de::pooling::Unify, deserialize, rancor::Failure, to_bytes,
+///     util::access_unchecked, Archive, Deserialize, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+/// struct Example {
+///     name: String,
+///     values: Vec<Option<u32>>,
+/// }
+///
+/// let value = Example {
+///     name: "example".to_string(),
+///     values: vec![Some(1), None, Some(3)],
+/// };
+///
+/// let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+/// // SAFETY: `bytes` was just produced by serializing an `Example`.
+/// let archived = unsafe { access_unchecked::<Example>(&bytes) };
+/// let deserialized =
+///     deserialize::<Example, _, Failure>(archived, &mut Unify::default())
+///         .unwrap();
+///
+/// assert_eq!(deserialized, value);
+/// ```
 #[inline]
 pub fn deserialize<T, D, E>(
     value: &T::Archived,
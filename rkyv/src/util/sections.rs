@@ -0,0 +1,178 @@
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::{btree_map::Entry, BTreeMap},
+    string::{String, ToString as _},
+};
+#[cfg(feature = "std")]
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use rancor::{fail, Error, Strategy};
+
+use crate::{
+    collections::btree_map::ArchivedBTreeMap, primitive::ArchivedUsize,
+    ser::Writer, string::ArchivedString, Archive, Serialize,
+};
+
+/// The archived directory of a sectioned archive, which maps the name of each
+/// section to its position.
+pub type ArchivedSections = ArchivedBTreeMap<ArchivedString, ArchivedUsize>;
+
+#[derive(Debug)]
+struct DuplicateSection {
+    name: String,
+}
+
+impl fmt::Display for DuplicateSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a section named `{}` was already serialized", self.name)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DuplicateSection {}
+
+/// A builder for archives that contain multiple independent roots.
+///
+/// Each root is serialized as a named section, and then [`finish`] writes a
+/// directory that maps each section name to its position as the root of the
+/// archive. Sections can then be accessed by name with
+/// [`access_section_unchecked`] or the safe
+/// `validation::util::access_section`.
+///
+/// [`finish`]: Sections::finish
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{
+///     rancor::Failure,
+///     ser::AllocSerializer,
+///     util::{access_section_unchecked, Sections},
+/// };
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let mut sections = Sections::new();
+/// sections
+///     .serialize::<_, _, Failure>(
+///         "strings",
+///         &vec!["main".to_string(), "exit".to_string()],
+///         &mut serializer,
+///     )
+///     .unwrap();
+/// sections
+///     .serialize::<_, _, Failure>("symbols", &vec![0u32, 1], &mut serializer)
+///     .unwrap();
+/// sections.finish::<_, Failure>(&mut serializer).unwrap();
+/// let bytes = serializer.into_writer();
+///
+/// let strings = unsafe {
+///     access_section_unchecked::<Vec<String>>(bytes.as_slice(), "strings")
+/// }
+/// .unwrap();
+/// assert_eq!(strings[1], "exit");
+/// let symbols = unsafe {
+///     access_section_unchecked::<Vec<u32>>(bytes.as_slice(), "symbols")
+/// }
+/// .unwrap();
+/// assert_eq!(symbols.len(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct Sections {
+    positions: BTreeMap<String, usize>,
+}
+
+impl Sections {
+    /// Creates a new builder with no sections.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of sections that have been serialized.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns whether no sections have been serialized.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the position of the section with the given name, if it has been
+    /// serialized.
+    #[inline]
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.positions.get(name).copied()
+    }
+
+    /// Serializes a value as the section with the given name.
+    ///
+    /// Returns an error if a section with the same name was already
+    /// serialized.
+    pub fn serialize<T, S, E>(
+        &mut self,
+        name: &str,
+        value: &T,
+        serializer: &mut S,
+    ) -> Result<usize, E>
+    where
+        T: Serialize<Strategy<S, E>>,
+        S: Writer<E> + ?Sized,
+        E: Error,
+    {
+        match self.positions.entry(name.to_string()) {
+            Entry::Occupied(_) => fail!(DuplicateSection {
+                name: name.to_string(),
+            }),
+            Entry::Vacant(entry) => {
+                let pos =
+                    value.serialize_and_resolve(Strategy::wrap(serializer))?;
+                entry.insert(pos);
+                Ok(pos)
+            }
+        }
+    }
+
+    /// Serializes the directory of sections as the root of the archive.
+    ///
+    /// This must be the last value serialized to the archive.
+    pub fn finish<S, E>(self, serializer: &mut S) -> Result<(), E>
+    where
+        S: Writer<E> + ?Sized,
+        E: Error,
+    {
+        crate::util::serialize(&self.positions, serializer)
+    }
+}
+
+/// Accesses the directory of a sectioned archive without checking it.
+///
+/// # Safety
+///
+/// The bytes must contain a valid archive that was built with [`Sections`].
+#[inline]
+pub unsafe fn access_sections_unchecked(bytes: &[u8]) -> &ArchivedSections {
+    crate::access_unchecked::<BTreeMap<String, usize>>(bytes)
+}
+
+/// Accesses the section with the given name from a sectioned archive without
+/// checking it.
+///
+/// Returns `None` if the archive does not have a section with the given name.
+///
+/// # Safety
+///
+/// The bytes must contain a valid archive that was built with [`Sections`],
+/// and the section with the given name must be a valid archived `T`.
+#[inline]
+pub unsafe fn access_section_unchecked<'a, T: Archive>(
+    bytes: &'a [u8],
+    name: &str,
+) -> Option<&'a T::Archived> {
+    let pos = access_sections_unchecked(bytes).get(name)?.to_native();
+    Some(crate::util::access_pos_unchecked::<T>(bytes, pos as usize))
+}
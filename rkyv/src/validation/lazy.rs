@@ -0,0 +1,266 @@
+//! Lazy validation for large archives.
+//!
+//! [`access`](crate::access) checks the entire archive before returning the
+//! root, which touches every byte of the buffer. For very large archives (for
+//! example, memory-mapped files several gigabytes in size) where only a few
+//! values are read, a [`LazyArchive`] can instead check each value the first
+//! time it is accessed.
+
+use core::{any::type_name, any::TypeId, fmt, mem::size_of};
+
+use bytecheck::CheckBytes;
+use rancor::{fail, Error, ResultExt as _, Strategy};
+
+use crate::{
+    validation::{
+        validators::DefaultValidator, ArchiveContext as _,
+        ArchiveContextExt as _, LayoutRaw,
+    },
+    ArchivePointee, RelPtr,
+};
+
+#[cfg(not(feature = "std"))]
+use hashbrown::HashSet;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+/// The context for an error that occurred while lazily checking a value.
+#[derive(Debug)]
+struct CheckLazyContext {
+    type_name: &'static str,
+    pos: isize,
+}
+
+impl fmt::Display for CheckLazyContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "while lazily checking value of type `{}` at position {}",
+            self.type_name, self.pos,
+        )
+    }
+}
+
+#[derive(Debug)]
+struct RelPtrOutOfBounds {
+    address: usize,
+    start: usize,
+    end: usize,
+}
+
+impl fmt::Display for RelPtrOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "relative pointer at {:#x} is not inside the archive {:#x}..{:#x}",
+            self.address, self.start, self.end,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RelPtrOutOfBounds {}
+
+/// An archive that is validated piece by piece as it is accessed.
+///
+/// Values are checked with a [`DefaultValidator`] the first time they are
+/// accessed, and the positions that have been checked are cached so that
+/// accessing them again is free. Only the bytes of the values that are
+/// actually accessed are read.
+///
+/// To get the most out of lazy validation, the archived layout should contain
+/// [`RelPtr`]s to the parts that are accessed independently. Checking a
+/// `RelPtr` only checks the pointer itself, and [`follow`](Self::follow)
+/// checks the value it points to when the pointer is first followed.
+///
+/// # Safety guarantees
+///
+/// Every reference returned by the safe methods of a `LazyArchive` points to
+/// a valid value of its type. However, the archive as a whole is never
+/// checked:
+///
+/// - Invalid data in parts of the archive that are never accessed is not
+///   detected, so a corrupt archive may only fail partway through its use.
+/// - Values that are checked separately are not required to occupy disjoint
+///   regions of the archive, and shared pointers are only deduplicated within
+///   a single check. A malicious archive may make the same bytes get checked
+///   many times.
+/// - Values obtained through [`root_unchecked`](Self::root_unchecked) are not
+///   checked at all.
+///
+/// Because of this, a `LazyArchive` only provides shared access to the
+/// archive.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{
+///     rancor::Failure,
+///     ser::{writer::BufferWriter, Positional as _},
+///     util::{serialize_rel_ptr_into, AlignedBytes},
+///     validation::lazy::LazyArchive,
+///     RelPtr,
+/// };
+///
+/// let writer = serialize_rel_ptr_into::<str, _, Failure>(
+///     "hello world",
+///     BufferWriter::new(AlignedBytes([0u8; 64])),
+/// )
+/// .unwrap();
+/// let len = writer.pos();
+/// let buf = writer.into_inner();
+///
+/// let mut archive = LazyArchive::new(&buf.as_ref()[..len]);
+/// // Only the relative pointer at the root is checked here
+/// let root = archive.root::<RelPtr<str>, Failure>().unwrap();
+/// // The string it points to is checked here
+/// let value = archive.follow::<_, Failure>(root).unwrap();
+/// assert_eq!(value, "hello world");
+/// ```
+#[derive(Debug)]
+pub struct LazyArchive<'a> {
+    bytes: &'a [u8],
+    checked_values: HashSet<(usize, TypeId)>,
+    followed_ptrs: HashSet<(usize, TypeId)>,
+}
+
+impl<'a> LazyArchive<'a> {
+    /// Creates a new lazily-validated archive from the given bytes.
+    ///
+    /// This does not check any part of the archive.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            checked_values: HashSet::new(),
+            followed_ptrs: HashSet::new(),
+        }
+    }
+
+    /// Returns the bytes of the archive.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the archived root without checking it.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must contain a valid archived `T` at the root position. See
+    /// [`access_unchecked`](crate::access_unchecked) for details.
+    #[inline]
+    pub unsafe fn root_unchecked<T>(&self) -> &'a T {
+        let pos = self.bytes.len() - size_of::<T>();
+        &*self.bytes.as_ptr().add(pos).cast::<T>()
+    }
+
+    /// Checks and returns the archived root, which is located at the end of the
+    /// archive.
+    #[inline]
+    pub fn root<T, E>(&mut self) -> Result<&'a T, E>
+    where
+        T: CheckBytes<Strategy<DefaultValidator, E>> + 'static,
+        E: Error,
+    {
+        let pos = self.bytes.len() as isize - size_of::<T>() as isize;
+        self.access_pos(pos)
+    }
+
+    /// Checks and returns the archived value at the given position.
+    ///
+    /// The value is only checked the first time it is accessed as a `T`.
+    pub fn access_pos<T, E>(&mut self, pos: isize) -> Result<&'a T, E>
+    where
+        T: CheckBytes<Strategy<DefaultValidator, E>> + 'static,
+        E: Error,
+    {
+        let key = (pos as usize, TypeId::of::<T>());
+        if self.checked_values.contains(&key) {
+            // SAFETY: A valid `T` was already checked at this position.
+            return Ok(unsafe {
+                &*self.bytes.as_ptr().offset(pos).cast::<T>()
+            });
+        }
+
+        let mut validator = DefaultValidator::new(self.bytes);
+        let result = unsafe {
+            validator
+                .bounds_check_subtree_base_offset::<T>(
+                    self.bytes.as_ptr(),
+                    pos,
+                    (),
+                )
+                .and_then(|ptr| {
+                    let range = validator.push_prefix_subtree(ptr)?;
+                    CheckBytes::check_bytes(
+                        ptr,
+                        Strategy::wrap(&mut validator),
+                    )?;
+                    validator.pop_subtree_range(range)?;
+                    Ok(&*ptr)
+                })
+        };
+        let value = result.with_trace(|| CheckLazyContext {
+            type_name: type_name::<T>(),
+            pos,
+        })?;
+
+        self.checked_values.insert(key);
+        Ok(value)
+    }
+
+    /// Checks and returns the value that the given relative pointer points to.
+    ///
+    /// The relative pointer must be located inside of this archive. The value
+    /// is only checked the first time the pointer is followed.
+    pub fn follow<T, E>(&mut self, rel_ptr: &RelPtr<T>) -> Result<&'a T, E>
+    where
+        T: ArchivePointee
+            + CheckBytes<Strategy<DefaultValidator, E>>
+            + LayoutRaw
+            + ?Sized
+            + 'static,
+        E: Error,
+    {
+        let start = self.bytes.as_ptr() as usize;
+        let end = start + self.bytes.len();
+        let address = rel_ptr as *const RelPtr<T> as usize;
+        if address < start || address + size_of::<RelPtr<T>>() > end {
+            fail!(RelPtrOutOfBounds {
+                address,
+                start,
+                end
+            });
+        }
+
+        let key = (address, TypeId::of::<T>());
+        if self.followed_ptrs.contains(&key) {
+            // SAFETY: The target of this pointer was already checked.
+            return Ok(unsafe { &*rel_ptr.as_ptr() });
+        }
+
+        let mut validator = DefaultValidator::new(self.bytes);
+        // SAFETY: The relative pointer is located inside of the archive.
+        let result = unsafe {
+            validator
+                .bounds_check_subtree_rel_ptr(rel_ptr)
+                .and_then(|ptr| {
+                    let range = validator.push_prefix_subtree(ptr)?;
+                    CheckBytes::check_bytes(
+                        ptr,
+                        Strategy::wrap(&mut validator),
+                    )?;
+                    validator.pop_subtree_range(range)?;
+                    Ok(&*ptr)
+                })
+        };
+        let value = result.with_trace(|| CheckLazyContext {
+            type_name: type_name::<T>(),
+            pos: (address - start) as isize + rel_ptr.offset(),
+        })?;
+
+        self.followed_ptrs.insert(key);
+        Ok(value)
+    }
+}
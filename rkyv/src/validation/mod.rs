@@ -1,5 +1,8 @@
 //! Validation implementations and helper types.
 
+pub mod lazy;
+#[cfg(feature = "std")]
+pub mod streaming;
 pub mod util;
 pub mod validators;
 
@@ -79,6 +82,19 @@ pub unsafe trait ArchiveContext<E = <Self as Fallible>::Error> {
         layout: &Layout,
     ) -> Result<(), E>;
 
+    /// Returns the byte ranges that have been claimed by subtree pointers, for
+    /// debugging.
+    ///
+    /// Each range is a `(start, len)` pair, where `start` is relative to the
+    /// start of the archive. The ranges are in the order that they were
+    /// checked, and include the last range checked even if it was invalid.
+    /// Contexts that don't record their claims return an empty slice, which is
+    /// the default.
+    #[inline]
+    fn claimed_ranges(&self) -> &[(usize, usize)] {
+        &[]
+    }
+
     /// Pushes a new subtree range onto the validator and starts validating it.
     ///
     /// After calling `push_subtree_claim_to`, the validator will have a subtree range starting at
@@ -138,6 +154,10 @@ where
         T::check_subtree_ptr(self, ptr, layout)
     }
 
+    fn claimed_ranges(&self) -> &[(usize, usize)] {
+        T::claimed_ranges(self)
+    }
+
     unsafe fn push_prefix_subtree_range(
         &mut self,
         root: *const u8,
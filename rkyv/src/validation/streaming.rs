@@ -0,0 +1,301 @@
+//! Forward-only validation of archives that are read from a stream.
+//!
+//! [`access`](crate::access) needs the entire archive in memory to check it.
+//! A [`StreamingValidator`] instead reads an archive from a [`Read`] and checks
+//! a sequence of values in the order they appear, only keeping the bytes of the
+//! value that is currently being checked.
+//!
+//! # Streamable archives
+//!
+//! rkyv writes the dependencies of a value before the value itself, so the
+//! relative pointers in an archive always point backwards. An archive can be
+//! validated while streaming if it is made of a sequence of values where each
+//! value only points to bytes written after the end of the previous value. This
+//! is the case when the values are serialized one after another into the same
+//! serializer, as long as they don't share any pointers. For example:
+//!
+//! - A log of records that are each serialized as they are produced.
+//! - A large collection that is split into chunks that are serialized
+//!   separately.
+//!
+//! Archives that are not streamable include:
+//!
+//! - Regular archives with a single root, like those produced by
+//!   [`to_bytes`](crate::to_bytes). These can still be checked, but the whole
+//!   archive must fit in the window of the validator.
+//! - Values that share a pointer (for example an `Rc`) with a previous value.
+//!   Shared pointers are deduplicated by the serializer, so later values point
+//!   back to the bytes of the earlier one.
+//! - Sectioned archives, since the directory of sections points back to every
+//!   section.
+//!
+//! Pointers that would need bytes which have already been discarded fail with
+//! an error instead of being resolved.
+
+use core::{any::type_name, fmt, mem::size_of};
+use std::io::{self, Read};
+
+use bytecheck::CheckBytes;
+use rancor::{fail, Error, ResultExt as _, Strategy};
+
+use crate::{
+    util::AlignedVec,
+    validation::{util::access_pos_with_context, validators::DefaultValidator},
+    Archive,
+};
+
+/// The context for an error that occurred while checking a streamed value.
+#[derive(Debug)]
+struct CheckStreamedContext {
+    type_name: &'static str,
+    pos: usize,
+    start: usize,
+}
+
+impl fmt::Display for CheckStreamedContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "while checking streamed value of type `{}` at position {} (only \
+             bytes from position {} onward are available)",
+            self.type_name, self.pos, self.start,
+        )
+    }
+}
+
+#[derive(Debug)]
+struct BackwardValue {
+    pos: usize,
+    start: usize,
+}
+
+impl fmt::Display for BackwardValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "value at position {} starts before the end of the previously \
+             checked value at position {}",
+            self.pos, self.start,
+        )
+    }
+}
+
+impl std::error::Error for BackwardValue {}
+
+#[derive(Debug)]
+struct WindowExceeded {
+    required: usize,
+    max_window: usize,
+}
+
+impl fmt::Display for WindowExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "value requires a window of {} bytes, but the maximum window is {} \
+             bytes",
+            self.required, self.max_window,
+        )
+    }
+}
+
+impl std::error::Error for WindowExceeded {}
+
+#[derive(Debug)]
+struct PositionOverflow {
+    pos: usize,
+}
+
+impl fmt::Display for PositionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the end of the value at position {} overflows a usize",
+            self.pos,
+        )
+    }
+}
+
+impl std::error::Error for PositionOverflow {}
+
+/// A validator that checks a sequence of archived values as they are read
+/// from a stream.
+///
+/// Each call to [`check_pos`](Self::check_pos) reads forward to the end of the
+/// requested value and checks it with a [`DefaultValidator`]. Once a value has
+/// been checked, the bytes before its end are discarded, so values must be
+/// checked in the order they appear in the stream and may only point to bytes
+/// written after the end of the previous value. See the
+/// [module documentation](self) for which archives can be streamed.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{
+///     rancor::{Failure, Strategy},
+///     ser::AllocSerializer,
+///     validation::streaming::StreamingValidator,
+///     Serialize as _,
+/// };
+///
+/// // Serialize each record separately and remember where its root is
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let mut positions = Vec::new();
+/// for i in 0..3 {
+///     let record = vec![format!("record {i}"); 4];
+///     let pos = record
+///         .serialize_and_resolve(Strategy::<_, Failure>::wrap(&mut serializer))
+///         .unwrap();
+///     positions.push(pos);
+/// }
+/// let bytes = serializer.into_writer();
+///
+/// // Any `Read` can be validated, like a file or a socket
+/// let mut validator = StreamingValidator::new(bytes.as_slice());
+/// for (i, &pos) in positions.iter().enumerate() {
+///     let record = validator.check_pos::<Vec<String>, Failure>(pos).unwrap();
+///     assert_eq!(record[3], format!("record {i}"));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct StreamingValidator<R> {
+    reader: R,
+    buffer: AlignedVec,
+    base: usize,
+    start: usize,
+    max_window: Option<usize>,
+}
+
+impl<R: Read> StreamingValidator<R> {
+    /// Creates a new streaming validator that reads from the given reader.
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self::with_max_window(reader, None)
+    }
+
+    /// Creates a new streaming validator that reads from the given reader and
+    /// keeps at most `max_window` bytes in memory.
+    ///
+    /// Checking a value whose bytes span more than `max_window` bytes fails
+    /// instead of reading it.
+    #[inline]
+    pub fn with_max_window(reader: R, max_window: Option<usize>) -> Self {
+        Self {
+            reader,
+            buffer: AlignedVec::new(),
+            base: 0,
+            start: 0,
+            max_window,
+        }
+    }
+
+    /// Returns the position in the stream where the next value may start.
+    ///
+    /// This is the end of the last value that was checked.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.start
+    }
+
+    /// Consumes the validator, returning the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads forward to the end of the archived `T` at the given position in
+    /// the stream and checks it.
+    ///
+    /// The value and everything it points to must be located between the end
+    /// of the previously checked value and the end of this value.
+    pub fn check_pos<T, E>(&mut self, pos: usize) -> Result<&T::Archived, E>
+    where
+        T: Archive,
+        T::Archived: CheckBytes<Strategy<DefaultValidator, E>>,
+        E: Error,
+    {
+        if pos < self.start {
+            fail!(BackwardValue {
+                pos,
+                start: self.start,
+            });
+        }
+        let end = match pos.checked_add(size_of::<T::Archived>()) {
+            Some(end) => end,
+            None => fail!(PositionOverflow { pos }),
+        };
+
+        self.discard();
+        self.fill(end)?;
+
+        // The window starts exactly at the end of the previous value so that
+        // none of its bytes can be reached from this value
+        let start = self.start;
+        let window =
+            &self.buffer.as_slice()[start - self.base..end - self.base];
+        let mut validator = DefaultValidator::new(window);
+        let value = access_pos_with_context::<T, DefaultValidator, E>(
+            window,
+            (pos - start) as isize,
+            &mut validator,
+        )
+        .with_trace(|| CheckStreamedContext {
+            type_name: type_name::<T::Archived>(),
+            pos,
+            start,
+        })?;
+
+        self.start = end;
+        Ok(value)
+    }
+
+    /// Discards the buffered bytes before the start of the next value.
+    ///
+    /// The new base is rounded down to the alignment of the buffer so that
+    /// values in the buffer stay aligned. The bytes kept before the start are
+    /// never part of the window that values are checked in.
+    fn discard(&mut self) {
+        let base = self.start & !(AlignedVec::ALIGNMENT - 1);
+        let discarded = base - self.base;
+        if discarded > 0 {
+            let len = self.buffer.len();
+            self.buffer.as_mut_slice().copy_within(discarded..len, 0);
+            self.buffer.resize(len - discarded, 0);
+            self.base = base;
+        }
+    }
+
+    /// Reads from the stream until the bytes up to `end` are buffered.
+    fn fill<E: Error>(&mut self, end: usize) -> Result<(), E> {
+        let required = end - self.base;
+        if let Some(max_window) = self.max_window {
+            if required > max_window {
+                fail!(WindowExceeded {
+                    required,
+                    max_window,
+                });
+            }
+        }
+
+        let mut len = self.buffer.len();
+        if required > len {
+            self.buffer.resize(required, 0);
+            let result = loop {
+                if len == required {
+                    break Ok(());
+                }
+                match self.reader.read(&mut self.buffer.as_mut_slice()[len..]) {
+                    Ok(0) => break Err(io::ErrorKind::UnexpectedEof.into()),
+                    Ok(n) => len += n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                    Err(e) => break Err(e),
+                }
+            };
+            // Only keep the bytes that were actually read so that the buffer
+            // stays in sync with the stream
+            self.buffer.resize(len, 0);
+            result.into_error()?;
+        }
+        Ok(())
+    }
+}
@@ -1,21 +1,55 @@
 //! Utility methods for accessing and deserializing safely.
 
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString as _},
+};
+use core::{any::type_name, fmt};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
 use bytecheck::CheckBytes;
 use ptr_meta::Pointee;
-use rancor::{Error, Strategy};
+use rancor::{Error, OptionExt as _, ResultExt as _, Strategy};
 
 use crate::{
+    boxed::ArchivedBox,
     de::pooling::Unify,
     deserialize,
+    util::ArchivedSections,
     validation::{
         validators::DefaultValidator, ArchiveContext, ArchiveContextExt as _,
     },
-    Archive, Deserialize,
+    Archive, ArchiveUnsized, Deserialize, RelPtr,
 };
 
+/// The context for an error that occurred while checking the root of an
+/// archive.
+#[derive(Debug)]
+struct CheckRootContext {
+    /// The name of the archived type being checked
+    type_name: &'static str,
+    /// The position of the root within the archive
+    pos: isize,
+}
+
+impl fmt::Display for CheckRootContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "while checking root of type `{}` at position {}",
+            self.type_name, self.pos,
+        )
+    }
+}
+
 /// Accesses an archived value from the given byte slice at the given position
 /// after checking its validity with the given context.
 ///
+/// If validation fails, the returned error is traced with the position of the
+/// root and the name of its archived type.
+///
 /// This is a safe alternative to [`access_pos_unchecked`][unsafe_version].
 ///
 /// [unsafe_version]: crate::util::access_pos_unchecked
@@ -31,24 +65,54 @@ where
     C: ArchiveContext<E> + ?Sized,
     E: Error,
 {
-    unsafe {
-        let ptr =
-            context.bounds_check_subtree_base_offset(buf.as_ptr(), pos, ())?;
+    let result = unsafe {
+        context
+            .bounds_check_subtree_base_offset(buf.as_ptr(), pos, ())
+            .and_then(|ptr| {
+                let range = context.push_prefix_subtree(ptr)?;
+                CheckBytes::check_bytes(ptr, Strategy::wrap(context))?;
+                context.pop_subtree_range(range)?;
+                Ok(&*ptr)
+            })
+    };
 
-        let range = context.push_prefix_subtree(ptr)?;
-        CheckBytes::check_bytes(ptr, Strategy::wrap(context))?;
-        context.pop_subtree_range(range)?;
-
-        Ok(&*ptr)
-    }
+    result.with_trace(|| CheckRootContext {
+        type_name: type_name::<T::Archived>(),
+        pos,
+    })
 }
 
 /// Accesses an archived value from the given byte slice by calculating the root
 /// position after checking its validity with the given context.
 ///
+/// Any type implementing [`ArchiveContext`] can be used as the context, which
+/// allows validation to carry custom state like limits or shared pointer
+/// tables. [`access`] is a convenience wrapper that uses a
+/// [`DefaultValidator`].
+///
 /// This is a safe alternative to [`access_unchecked`][unsafe_version].
 ///
 /// [unsafe_version]: crate::access_unchecked
+///
+/// # Examples
+/// ```
+/// use core::num::NonZeroUsize;
+/// use rkyv::{
+///     rancor::Failure,
+///     to_bytes,
+///     validation::{util::access_with_context, validators::DefaultValidator},
+/// };
+///
+/// let value = vec![1, 2, 3, 4];
+/// let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+///
+/// let mut validator =
+///     DefaultValidator::with_limits(buf.as_ref(), NonZeroUsize::new(8), Some(64));
+/// let archived =
+///     access_with_context::<Vec<i32>, _, Failure>(buf.as_ref(), &mut validator)
+///         .unwrap();
+/// assert_eq!(archived.as_slice(), [1, 2, 3, 4]);
+/// ```
 #[inline]
 pub fn access_with_context<'a, T, C, E>(
     buf: &'a [u8],
@@ -72,21 +136,22 @@ where
 ///
 /// This is a safe alternative to [`access_pos_unchecked`][unsafe_version].
 ///
+/// Like [`access`], this does not limit the nesting depth of the archive, so
+/// it should not be used to check untrusted input. See [`access`] for
+/// details.
+///
 /// [unsafe_version]: crate::util::access_pos_unchecked
 ///
 /// # Examples
 /// ```
+/// use core::mem::size_of;
 /// use rkyv::{
-///     check_archived_value,
-///     ser::{Serializer, serializers::AlignedSerializer},
-///     AlignedVec,
-///     Archive,
-///     Serialize,
+///     rancor::Failure, to_bytes, validation::util::access_pos, Archive,
+///     Archived, Serialize,
 /// };
-/// use bytecheck::CheckBytes;
 ///
 /// #[derive(Archive, Serialize)]
-/// #[archive_attr(derive(CheckBytes))]
+/// #[archive(check_bytes)]
 /// struct Example {
 ///     name: String,
 ///     value: i32,
@@ -97,11 +162,17 @@ where
 ///     value: 31415926,
 /// };
 ///
-/// let mut serializer = AlignedSerializer::new(AlignedVec::new());
-/// let pos = serializer.serialize_value(&value)
-///     .expect("failed to archive test");
-/// let buf = serializer.into_inner();
-/// let archived = check_archived_value::<Example>(buf.as_ref(), pos).unwrap();
+/// let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+/// let pos = (buf.len() - size_of::<Archived<Example>>()) as isize;
+///
+/// // The returned reference is typed as the archived form of `Example`.
+/// let archived: &Archived<Example> =
+///     access_pos::<Example, Failure>(buf.as_ref(), pos).unwrap();
+/// assert_eq!(archived.name, "pi");
+/// assert_eq!(archived.value, 31415926);
+///
+/// // Positions that don't point at a valid `Example` are rejected.
+/// assert!(access_pos::<Example, Failure>(buf.as_ref(), pos + 1).is_err());
 /// ```
 #[inline]
 pub fn access_pos<T: Archive, E>(
@@ -125,6 +196,14 @@ where
 ///
 /// This is a safe alternative to [`access_unchecked`][unsafe_version].
 ///
+/// # Untrusted input
+///
+/// The validator used by this function does not limit how deeply subtrees are
+/// nested. Checking a recursive type like a linked list of `Box`es recurses
+/// once per level, so a malicious archive can overflow the stack. To check
+/// untrusted input, use [`access_with_context`] with a validator created by
+/// [`DefaultValidator::with_max_depth`] or [`DefaultValidator::with_limits`].
+///
 /// [unsafe_version]: crate::access_unchecked
 #[inline]
 pub fn access<T: Archive, E>(bytes: &[u8]) -> Result<&T::Archived, E>
@@ -136,11 +215,341 @@ where
     access_with_context::<T, DefaultValidator, E>(bytes, &mut validator)
 }
 
+/// Checks that the given byte slice contains a valid archived `T` at its root.
+///
+/// This performs the same validation as [`access`], but only reports whether
+/// the archive is valid. It is suitable for use on untrusted and arbitrary
+/// input like fuzzer-generated buffers: any malformed archive, including one
+/// that is too short, misaligned, or contains pointers that overrun or wrap
+/// around the buffer, returns an error instead of panicking. This relies on
+/// the `CheckBytes` implementations for the archived types never panicking,
+/// which holds for all of the implementations provided by rkyv.
+///
+/// # Examples
+/// ```
+/// use rkyv::{rancor::Failure, to_bytes, validation::util::check_archive};
+///
+/// let buf = to_bytes::<_, 256, Failure>(&vec![1, 2, 3, 4]).unwrap();
+/// assert!(check_archive::<Vec<i32>, Failure>(buf.as_ref()).is_ok());
+///
+/// // Truncated archives are rejected
+/// assert!(check_archive::<Vec<i32>, Failure>(&buf[..buf.len() - 1]).is_err());
+/// assert!(check_archive::<Vec<i32>, Failure>(&[]).is_err());
+/// ```
+#[inline]
+pub fn check_archive<T: Archive, E>(bytes: &[u8]) -> Result<(), E>
+where
+    T::Archived: CheckBytes<Strategy<DefaultValidator, E>>,
+    E: Error,
+{
+    access::<T, E>(bytes).map(|_| ())
+}
+
+#[cfg(feature = "bytes")]
+#[derive(Debug)]
+struct UnalignedBytes {
+    address: usize,
+    align: usize,
+}
+
+#[cfg(feature = "bytes")]
+impl fmt::Display for UnalignedBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bytes at address {:#x} are not aligned to {} bytes, copy them \
+             into an aligned buffer with `access_bytes_copied`",
+            self.address, self.align,
+        )
+    }
+}
+
+#[cfg(all(feature = "bytes", feature = "std"))]
+impl std::error::Error for UnalignedBytes {}
+
+/// Accesses an archived value from the given [`Bytes`](bytes::Bytes) after
+/// checking its validity, without copying it.
+///
+/// `Bytes` does not guarantee any alignment for its data, but archives must
+/// start at an address that is aligned to
+/// [`AlignedVec::ALIGNMENT`](crate::util::AlignedVec::ALIGNMENT) to be
+/// accessed. This returns an error if the bytes are not aligned, in which case
+/// [`access_bytes_copied`] can be used to copy them into an aligned buffer
+/// instead. A [`BytesMut`](bytes::BytesMut) can be accessed by freezing it,
+/// which does not copy its data.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use rkyv::{rancor::Failure, to_bytes, validation::util::access_bytes};
+///
+/// let buf = to_bytes::<_, 256, Failure>(&vec![1, 2, 3, 4]).unwrap();
+/// // `Bytes` keeps the alignment of the buffer it was created from
+/// let bytes = Bytes::from_static(Box::leak(Box::new(buf)).as_slice());
+///
+/// let archived = access_bytes::<Vec<i32>, Failure>(&bytes).unwrap();
+/// assert_eq!(archived.as_slice(), &[1, 2, 3, 4]);
+/// ```
+#[cfg(feature = "bytes")]
+#[inline]
+pub fn access_bytes<T: Archive, E>(
+    bytes: &bytes::Bytes,
+) -> Result<&T::Archived, E>
+where
+    T::Archived: CheckBytes<Strategy<DefaultValidator, E>>,
+    E: Error,
+{
+    let address = bytes.as_ptr() as usize;
+    let align = crate::util::AlignedVec::ALIGNMENT;
+    if address & (align - 1) != 0 {
+        rancor::fail!(UnalignedBytes { address, align });
+    }
+    access::<T, E>(bytes)
+}
+
+/// Accesses an archived value from the given [`Bytes`](bytes::Bytes) after
+/// checking its validity, copying it into `buffer` if it is not aligned.
+///
+/// This behaves like [`access_bytes`], except that unaligned bytes are copied
+/// into `buffer` and accessed from there instead of returning an error.
+/// Aligned bytes are accessed directly and `buffer` is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use rkyv::{
+///     rancor::Failure, to_bytes, util::AlignedVec,
+///     validation::util::access_bytes_copied,
+/// };
+///
+/// let buf = to_bytes::<_, 256, Failure>(&vec![1, 2, 3, 4]).unwrap();
+/// let mut unaligned = vec![0u8];
+/// unaligned.extend_from_slice(&buf);
+/// let bytes = Bytes::from(unaligned).slice(1..);
+///
+/// let mut buffer = AlignedVec::new();
+/// let archived =
+///     access_bytes_copied::<Vec<i32>, Failure>(&bytes, &mut buffer).unwrap();
+/// assert_eq!(archived.as_slice(), &[1, 2, 3, 4]);
+/// ```
+#[cfg(feature = "bytes")]
+#[inline]
+pub fn access_bytes_copied<'a, T: Archive, E>(
+    bytes: &'a bytes::Bytes,
+    buffer: &'a mut crate::util::AlignedVec,
+) -> Result<&'a T::Archived, E>
+where
+    T::Archived: CheckBytes<Strategy<DefaultValidator, E>>,
+    E: Error,
+{
+    let align = crate::util::AlignedVec::ALIGNMENT;
+    if bytes.as_ptr() as usize & (align - 1) == 0 {
+        access::<T, E>(bytes)
+    } else {
+        buffer.clear();
+        buffer.extend_from_slice(bytes);
+        access::<T, E>(buffer)
+    }
+}
+
+/// Accesses a [`RelPtr`] that points to an archived value from the given byte
+/// slice at the given position after checking its validity with the given
+/// context.
+///
+/// This is a safe alternative to
+/// [`access_pos_unsized_unchecked`][unsafe_version].
+///
+/// [unsafe_version]: crate::util::access_pos_unsized_unchecked
+#[inline]
+pub fn access_pos_unsized_with_context<'a, T, C, E>(
+    buf: &'a [u8],
+    pos: isize,
+    context: &mut C,
+) -> Result<&'a T::Archived, E>
+where
+    T: ArchiveUnsized + ?Sized,
+    ArchivedBox<T::Archived>: CheckBytes<Strategy<C, E>>,
+    C: ArchiveContext<E> + ?Sized,
+    E: Error,
+{
+    // An `ArchivedBox` is a transparent wrapper around a `RelPtr`, so checking
+    // one checks both the relative pointer and the value it points to.
+    let result = unsafe {
+        context
+            .bounds_check_subtree_base_offset::<ArchivedBox<T::Archived>>(
+                buf.as_ptr(),
+                pos,
+                (),
+            )
+            .and_then(|ptr| {
+                let range = context.push_prefix_subtree(ptr)?;
+                CheckBytes::check_bytes(ptr, Strategy::wrap(context))?;
+                context.pop_subtree_range(range)?;
+                Ok((*ptr).get())
+            })
+    };
+
+    result.with_trace(|| CheckRootContext {
+        type_name: type_name::<RelPtr<T::Archived>>(),
+        pos,
+    })
+}
+
+/// Accesses a [`RelPtr`] that points to an archived value from the given byte
+/// slice by calculating the root position after checking its validity with the
+/// given context.
+///
+/// This is a safe alternative to [`access_unsized_unchecked`][unsafe_version].
+///
+/// [unsafe_version]: crate::util::access_unsized_unchecked
+#[inline]
+pub fn access_unsized_with_context<'a, T, C, E>(
+    buf: &'a [u8],
+    context: &mut C,
+) -> Result<&'a T::Archived, E>
+where
+    T: ArchiveUnsized + ?Sized,
+    ArchivedBox<T::Archived>: CheckBytes<Strategy<C, E>>,
+    C: ArchiveContext<E> + ?Sized,
+    E: Error,
+{
+    access_pos_unsized_with_context::<T, C, E>(
+        buf,
+        buf.len() as isize
+            - core::mem::size_of::<RelPtr<T::Archived>>() as isize,
+        context,
+    )
+}
+
+/// Accesses a [`RelPtr`] that points to an archived value from the given byte
+/// slice by calculating the root position after checking its validity.
+///
+/// This is a safe alternative to [`access_unsized_unchecked`][unsafe_version].
+///
+/// [unsafe_version]: crate::util::access_unsized_unchecked
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     rancor::Failure,
+///     ser::{writer::BufferWriter, Positional as _},
+///     util::{serialize_rel_ptr_into, AlignedBytes},
+///     validation::util::access_unsized,
+/// };
+///
+/// let writer = serialize_rel_ptr_into::<str, _, Failure>(
+///     "hello world",
+///     BufferWriter::new(AlignedBytes([0u8; 64])),
+/// )
+/// .unwrap();
+/// let len = writer.pos();
+/// let buf = writer.into_inner();
+///
+/// let archived = access_unsized::<str, Failure>(&buf.as_ref()[..len]).unwrap();
+/// assert_eq!(archived, "hello world");
+/// ```
+#[inline]
+pub fn access_unsized<T, E>(bytes: &[u8]) -> Result<&T::Archived, E>
+where
+    T: ArchiveUnsized + ?Sized,
+    ArchivedBox<T::Archived>: CheckBytes<Strategy<DefaultValidator, E>>,
+    E: Error,
+{
+    let mut validator = DefaultValidator::new(bytes);
+    access_unsized_with_context::<T, DefaultValidator, E>(bytes, &mut validator)
+}
+
+#[derive(Debug)]
+struct MissingSection {
+    name: String,
+}
+
+impl fmt::Display for MissingSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the archive does not have a section named `{}`",
+            self.name
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MissingSection {}
+
+/// Accesses the directory of a sectioned archive after checking its validity.
+///
+/// Only the directory is checked, not the sections it points to.
+///
+/// This is a safe alternative to
+/// [`access_sections_unchecked`][unsafe_version].
+///
+/// [unsafe_version]: crate::util::access_sections_unchecked
+#[inline]
+pub fn access_sections<E>(bytes: &[u8]) -> Result<&ArchivedSections, E>
+where
+    E: Error,
+{
+    access::<BTreeMap<String, usize>, E>(bytes)
+}
+
+/// Accesses the section with the given name from a sectioned archive after
+/// checking its validity.
+///
+/// The directory and the requested section are checked, but the other
+/// sections in the archive are not.
+///
+/// This is a safe alternative to [`access_section_unchecked`][unsafe_version].
+///
+/// [unsafe_version]: crate::util::access_section_unchecked
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     rancor::Failure, ser::AllocSerializer, util::Sections,
+///     validation::util::access_section,
+/// };
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let mut sections = Sections::new();
+/// sections
+///     .serialize::<_, _, Failure>("name", &"rkyv".to_string(), &mut serializer)
+///     .unwrap();
+/// sections
+///     .serialize::<_, _, Failure>("data", &vec![1u8, 2, 3], &mut serializer)
+///     .unwrap();
+/// sections.finish::<_, Failure>(&mut serializer).unwrap();
+/// let bytes = serializer.into_writer();
+///
+/// let data = access_section::<Vec<u8>, Failure>(&bytes, "data").unwrap();
+/// assert_eq!(data.as_slice(), [1, 2, 3]);
+/// assert!(access_section::<Vec<u8>, Failure>(&bytes, "missing").is_err());
+/// ```
+pub fn access_section<'a, T, E>(
+    bytes: &'a [u8],
+    name: &str,
+) -> Result<&'a T::Archived, E>
+where
+    T: Archive,
+    T::Archived: CheckBytes<Strategy<DefaultValidator, E>>,
+    E: Error,
+{
+    let pos = access_sections::<E>(bytes)?
+        .get(name)
+        .map(|pos| pos.to_native())
+        .into_trace(MissingSection {
+            name: name.to_string(),
+        })?;
+    access_pos::<T, E>(bytes, pos as isize)
+}
+
 // TODO: access_mut/access_mut_*
 
 /// Checks and deserializes a value from the given bytes.
 ///
-/// This function is only available with the `alloc` and `validation` features
+/// This function is only available with the `alloc` and `bytecheck` features
 /// because it uses a general-purpose deserializer and performs validation on
 /// the data before deserializing. In no-alloc and high-performance
 /// environments, the deserializer should be customized for the specific
@@ -148,15 +557,21 @@ where
 ///
 /// This is a safe alternative to [`from_bytes_unchecked`][unsafe_version].
 ///
+/// Like [`access`], this does not limit the nesting depth of the archive, so
+/// it should not be used to check untrusted input. See [`access`] for
+/// details.
+///
 /// [unsafe_version]: crate::from_bytes_unchecked
 ///
 /// # Examples
 /// ```
+/// use rkyv::rancor::Failure;
+///
 /// let value = vec![1, 2, 3, 4];
 ///
-/// let bytes = rkyv::to_bytes::<_, 1024>(&value)
+/// let bytes = rkyv::to_bytes::<_, 1024, Failure>(&value)
 ///     .expect("failed to serialize vec");
-/// let deserialized = rkyv::from_bytes::<Vec<i32>>(&bytes)
+/// let deserialized = rkyv::from_bytes::<Vec<i32>, Failure>(&bytes)
 ///     .expect("failed to deserialize vec");
 ///
 /// assert_eq!(deserialized, value);
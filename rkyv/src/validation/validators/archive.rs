@@ -1,5 +1,7 @@
 //! The provided implementation for `ArchiveContext`.
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 use core::{
     alloc::{Layout, LayoutError},
     fmt,
@@ -35,6 +37,12 @@ pub enum ArchiveError {
     RangePoppedTooManyTimes,
     /// The maximum subtree depth was reached or exceeded.
     ExceededMaximumSubtreeDepth,
+    /// The total size of the checked subtree pointers exceeded the maximum
+    /// number of claimed bytes.
+    ExceededMaximumClaimedBytes {
+        /// The maximum number of bytes that may be claimed
+        max_claimed_bytes: usize,
+    },
     /// A layout error occurred
     LayoutError {
         /// A layout error
@@ -67,6 +75,13 @@ impl fmt::Display for ArchiveError {
                 f,
                 "pushed a subtree range that exceeded the maximum subtree depth",
             ),
+            ArchiveError::ExceededMaximumClaimedBytes { max_claimed_bytes } => {
+                write!(
+                    f,
+                    "subtree pointers claimed more than the maximum of {} bytes",
+                    max_claimed_bytes,
+                )
+            }
             ArchiveError::LayoutError { layout_error } => {
                 write!(f, "a layout error occurred: {}", layout_error)
             }
@@ -80,8 +95,13 @@ impl std::error::Error for ArchiveError {}
 /// A validator that can verify archives with nonlocal memory.
 #[derive(Debug)]
 pub struct ArchiveValidator {
+    base: usize,
     subtree_range: Range<usize>,
     max_subtree_depth: Option<NonZeroUsize>,
+    max_claimed_bytes: Option<usize>,
+    claimed_bytes: usize,
+    #[cfg(feature = "alloc")]
+    claimed_ranges: Option<Vec<(usize, usize)>>,
 }
 
 // SAFETY: `ArchiveValidator` is safe to send between threads because the
@@ -101,21 +121,70 @@ impl ArchiveValidator {
         Self::with_max_depth(bytes, None)
     }
 
-    /// Crates a new bounds validator for the given bytes with a maximum validation depth.
+    /// Creates a new bounds validator for the given bytes with a maximum validation depth.
+    ///
+    /// Pushing a subtree range fails with
+    /// [`ExceededMaximumSubtreeDepth`](ArchiveError::ExceededMaximumSubtreeDepth)
+    /// once `max_subtree_depth` subtree ranges are pushed at the same time.
     #[inline]
     pub fn with_max_depth(
         bytes: &[u8],
         max_subtree_depth: Option<NonZeroUsize>,
+    ) -> Self {
+        Self::with_limits(bytes, max_subtree_depth, None)
+    }
+
+    /// Creates a new bounds validator for the given bytes with a maximum
+    /// validation depth and a maximum number of claimed bytes.
+    ///
+    /// Checking a subtree pointer fails with
+    /// [`ExceededMaximumClaimedBytes`](ArchiveError::ExceededMaximumClaimedBytes)
+    /// once the total size of all checked subtree pointers exceeds
+    /// `max_claimed_bytes`. This bounds the validation work for an archive
+    /// independently of the length of its buffer.
+    #[inline]
+    pub fn with_limits(
+        bytes: &[u8],
+        max_subtree_depth: Option<NonZeroUsize>,
+        max_claimed_bytes: Option<usize>,
     ) -> Self {
         let Range { start, end } = bytes.as_ptr_range();
         Self {
+            base: start as usize,
             subtree_range: Range {
                 start: start as usize,
                 end: end as usize,
             },
             max_subtree_depth,
+            max_claimed_bytes,
+            claimed_bytes: 0,
+            #[cfg(feature = "alloc")]
+            claimed_ranges: None,
         }
     }
+
+    /// Starts recording the byte ranges claimed by subtree pointers.
+    ///
+    /// After validation, the recorded ranges can be retrieved with
+    /// [`claimed_ranges`](ArchiveContext::claimed_ranges). This is meant for
+    /// debugging invalid archives and slows down validation, so it should not
+    /// be enabled in production.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn record_claims(&mut self) {
+        self.claimed_ranges.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the recorded byte ranges claimed by subtree pointers.
+    ///
+    /// This is empty unless [`record_claims`](Self::record_claims) was called
+    /// before validating. See
+    /// [`ArchiveContext::claimed_ranges`] for the format of the ranges.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn claimed_ranges(&self) -> &[(usize, usize)] {
+        self.claimed_ranges.as_deref().unwrap_or(&[])
+    }
 }
 
 unsafe impl<E: Error> ArchiveContext<E> for ArchiveValidator {
@@ -126,7 +195,13 @@ unsafe impl<E: Error> ArchiveContext<E> for ArchiveValidator {
         layout: &Layout,
     ) -> Result<(), E> {
         let start = ptr as usize;
-        let end = ptr.wrapping_add(layout.size()) as usize;
+        // A subtree that would wrap around the end of the address space can't
+        // be inside the archive
+        let end = start.saturating_add(layout.size());
+        #[cfg(feature = "alloc")]
+        if let Some(claimed_ranges) = &mut self.claimed_ranges {
+            claimed_ranges.push((start.wrapping_sub(self.base), layout.size()));
+        }
         if start < self.subtree_range.start || end > self.subtree_range.end {
             fail!(ArchiveError::InvalidSubtreePointer {
                 address: start,
@@ -138,9 +213,25 @@ unsafe impl<E: Error> ArchiveContext<E> for ArchiveValidator {
                 address: ptr as usize,
                 align: layout.align(),
             });
-        } else {
-            Ok(())
         }
+
+        if let Some(max_claimed_bytes) = self.max_claimed_bytes {
+            self.claimed_bytes =
+                self.claimed_bytes.saturating_add(layout.size());
+            if self.claimed_bytes > max_claimed_bytes {
+                fail!(ArchiveError::ExceededMaximumClaimedBytes {
+                    max_claimed_bytes,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn claimed_ranges(&self) -> &[(usize, usize)] {
+        ArchiveValidator::claimed_ranges(self)
     }
 
     #[inline]
@@ -5,7 +5,7 @@ mod shared;
 
 use crate::validation::{ArchiveContext, SharedContext};
 pub use archive::*;
-use core::{any::TypeId, ops::Range};
+use core::{any::TypeId, num::NonZeroUsize, ops::Range};
 pub use shared::*;
 
 /// The default validator.
@@ -17,6 +17,9 @@ pub struct DefaultValidator {
 
 impl DefaultValidator {
     /// Creates a new validator from a byte range.
+    ///
+    /// This validator does not limit the nesting depth of subtrees. Use
+    /// [`with_max_depth`](Self::with_max_depth) to check untrusted input.
     #[inline]
     pub fn new(bytes: &[u8]) -> Self {
         Self {
@@ -33,6 +36,62 @@ impl DefaultValidator {
             shared: SharedValidator::with_capacity(capacity),
         }
     }
+
+    /// Creates a new validator from a byte range with a maximum subtree depth.
+    ///
+    /// Validation fails with an error instead of overflowing the stack when
+    /// subtrees are nested `max_subtree_depth` or more levels deep. A depth of
+    /// `None` does not limit the nesting of subtrees.
+    #[inline]
+    pub fn with_max_depth(
+        bytes: &[u8],
+        max_subtree_depth: Option<NonZeroUsize>,
+    ) -> Self {
+        Self {
+            archive: ArchiveValidator::with_max_depth(bytes, max_subtree_depth),
+            shared: SharedValidator::new(),
+        }
+    }
+
+    /// Creates a new validator from a byte range with a maximum subtree depth
+    /// and a maximum number of claimed bytes.
+    ///
+    /// See [`ArchiveValidator::with_limits`] for how the limits are enforced.
+    #[inline]
+    pub fn with_limits(
+        bytes: &[u8],
+        max_subtree_depth: Option<NonZeroUsize>,
+        max_claimed_bytes: Option<usize>,
+    ) -> Self {
+        Self {
+            archive: ArchiveValidator::with_limits(
+                bytes,
+                max_subtree_depth,
+                max_claimed_bytes,
+            ),
+            shared: SharedValidator::new(),
+        }
+    }
+}
+
+impl DefaultValidator {
+    /// Starts recording the byte ranges claimed by subtree pointers.
+    ///
+    /// See [`ArchiveValidator::record_claims`] for details.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn record_claims(&mut self) {
+        self.archive.record_claims();
+    }
+
+    /// Returns the recorded byte ranges claimed by subtree pointers.
+    ///
+    /// See [`ArchiveValidator::claimed_ranges`] for details.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn claimed_ranges(&self) -> &[(usize, usize)] {
+        self.archive.claimed_ranges()
+    }
 }
 
 unsafe impl<E> ArchiveContext<E> for DefaultValidator
@@ -48,6 +107,11 @@ where
         self.archive.check_subtree_ptr(ptr, layout)
     }
 
+    #[inline]
+    fn claimed_ranges(&self) -> &[(usize, usize)] {
+        ArchiveContext::<E>::claimed_ranges(&self.archive)
+    }
+
     #[inline]
     unsafe fn push_prefix_subtree_range(
         &mut self,
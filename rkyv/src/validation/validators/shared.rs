@@ -8,9 +8,9 @@ use rancor::fail;
 use crate::validation::SharedContext;
 
 #[cfg(not(feature = "std"))]
-use hashbrown::HashMap;
+use hashbrown::hash_map::{Entry, HashMap};
 #[cfg(feature = "std")]
-use std::collections::HashMap;
+use std::collections::hash_map::{Entry, HashMap};
 
 /// Errors that can occur when checking shared memory.
 #[derive(Debug)]
@@ -85,8 +85,6 @@ impl<E: Error> SharedContext<E> for SharedValidator {
         address: usize,
         type_id: TypeId,
     ) -> Result<bool, E> {
-        use std::collections::hash_map::Entry;
-
         match self.shared.entry(address) {
             Entry::Occupied(previous_type_entry) => {
                 let previous_type_id = previous_type_entry.get();
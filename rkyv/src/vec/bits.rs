@@ -0,0 +1,290 @@
+//! An archived `Vec<bool>` that packs its values as bits.
+
+use core::{fmt, hash, iter::FusedIterator, ops::Range};
+
+use rancor::{Error, Fallible, ResultExt as _};
+
+use crate::{
+    endian::SwapEndian,
+    primitive::{ArchivedUsize, FixedUsize},
+    ser::Writer,
+    Archive, RelPtr,
+};
+
+/// An archived `Vec<bool>` that stores eight values per byte.
+///
+/// The value at index `i` is stored in bit `i % 8` of byte `i / 8`, counting
+/// from the least significant bit. The unused bits of the last byte are always
+/// zero.
+///
+/// This is the archived type of vecs serialized with
+/// [`PackedBits`](crate::with::PackedBits).
+#[cfg_attr(feature = "strict", repr(C))]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedPackedBits {
+    ptr: RelPtr<u8>,
+    len: ArchivedUsize,
+}
+
+/// Returns the number of bytes needed to store `len` bits.
+#[inline]
+fn byte_len(len: usize) -> usize {
+    len.div_ceil(8)
+}
+
+impl ArchivedPackedBits {
+    /// Returns the number of values in the archived vec.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the archived vec is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the packed bytes of the archived vec.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            core::slice::from_raw_parts(
+                self.ptr.as_ptr().cast_const(),
+                byte_len(self.len()),
+            )
+        }
+    }
+
+    /// Returns the value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the length of the archived vec.
+    #[inline]
+    pub fn get(&self, index: usize) -> bool {
+        assert!(
+            index < self.len(),
+            "index {} out of range for packed bits of length {}",
+            index,
+            self.len(),
+        );
+        self.as_bytes()[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Returns an iterator over the values of the archived vec.
+    #[inline]
+    pub fn iter(&self) -> PackedBitsIter<'_> {
+        PackedBitsIter {
+            bits: self,
+            range: 0..self.len(),
+        }
+    }
+
+    /// Resolves an archived packed vec from a given length.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `len` values with
+    ///   [`serialize_from_slice`](Self::serialize_from_slice)
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: PackedBitsResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.ptr);
+        RelPtr::emplace(pos + fp, resolver.pos, fo);
+        let (fp, fo) = out_field!(out.len);
+        usize::resolve(&len, pos + fp, (), fo);
+    }
+
+    /// Serializes an archived packed vec from a given slice.
+    #[inline]
+    pub fn serialize_from_slice<S>(
+        slice: &[bool],
+        serializer: &mut S,
+    ) -> Result<PackedBitsResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+        S::Error: Error,
+    {
+        const BUFFER_LEN: usize = 32;
+
+        FixedUsize::try_from(slice.len()).into_error()?;
+
+        let pos = serializer.pos();
+        let mut buffer = [0u8; BUFFER_LEN];
+        for chunk in slice.chunks(8 * BUFFER_LEN) {
+            let bytes = &mut buffer[..byte_len(chunk.len())];
+            bytes.fill(0);
+            for (i, &value) in chunk.iter().enumerate() {
+                bytes[i / 8] |= (value as u8) << (i % 8);
+            }
+            serializer.write(bytes)?;
+        }
+
+        Ok(PackedBitsResolver { pos })
+    }
+}
+
+impl fmt::Debug for ArchivedPackedBits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Eq for ArchivedPackedBits {}
+
+impl hash::Hash for ArchivedPackedBits {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        self.as_bytes().hash(state);
+    }
+}
+
+impl<'a> IntoIterator for &'a ArchivedPackedBits {
+    type Item = bool;
+    type IntoIter = PackedBitsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl PartialEq for ArchivedPackedBits {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // Unused bits are always zero, so the bytes can be compared directly
+        self.len() == other.len() && self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<[bool]> for ArchivedPackedBits {
+    #[inline]
+    fn eq(&self, other: &[bool]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+impl PartialEq<ArchivedPackedBits> for [bool] {
+    #[inline]
+    fn eq(&self, other: &ArchivedPackedBits) -> bool {
+        other.eq(self)
+    }
+}
+
+impl SwapEndian for ArchivedPackedBits {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(Range<usize>)) {
+        self.ptr.scalar_ranges(report);
+        self.len.scalar_ranges(report);
+    }
+}
+
+/// An iterator over the values of an [`ArchivedPackedBits`].
+pub struct PackedBitsIter<'a> {
+    bits: &'a ArchivedPackedBits,
+    range: Range<usize>,
+}
+
+impl Iterator for PackedBitsIter<'_> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| self.bits.get(i))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for PackedBitsIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| self.bits.get(i))
+    }
+}
+
+impl ExactSizeIterator for PackedBitsIter<'_> {}
+
+impl FusedIterator for PackedBitsIter<'_> {}
+
+/// The resolver for [`ArchivedPackedBits`].
+pub struct PackedBitsResolver {
+    pos: usize,
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::fmt;
+
+    use bytecheck::{
+        rancor::{Error, Fallible},
+        Verify,
+    };
+    use rancor::fail;
+
+    use super::{byte_len, ArchivedPackedBits};
+    use crate::validation::{ArchiveContext, ArchiveContextExt};
+
+    #[derive(Debug)]
+    struct TrailingBitsSet {
+        len: usize,
+    }
+
+    impl fmt::Display for TrailingBitsSet {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "packed bits of length {} have unused trailing bits set",
+                self.len,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for TrailingBitsSet {}
+
+    unsafe impl<C> Verify<C> for ArchivedPackedBits
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let len = self.len();
+            let ptr = unsafe {
+                context.bounds_check_subtree_base_offset::<[u8]>(
+                    self.ptr.base(),
+                    self.ptr.offset(),
+                    byte_len(len),
+                )?
+            };
+
+            let range = unsafe { context.push_prefix_subtree(ptr)? };
+            let used_bits = len % 8;
+            if used_bits != 0 {
+                let last = self.as_bytes()[len / 8];
+                if last >> used_bits != 0 {
+                    fail!(TrailingBitsSet { len });
+                }
+            }
+            unsafe {
+                context.pop_subtree_range(range)?;
+            }
+
+            Ok(())
+        }
+    }
+}
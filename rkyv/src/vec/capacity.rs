@@ -0,0 +1,377 @@
+//! An archived `Vec` that preserves its capacity.
+
+use core::{
+    borrow::Borrow,
+    cmp, fmt, hash,
+    mem::size_of,
+    ops::{Deref, Index},
+    pin::Pin,
+    slice::{self, SliceIndex},
+};
+
+use rancor::{fail, Error, Fallible, ResultExt as _};
+
+use crate::{
+    endian::SwapEndian,
+    primitive::{ArchivedUsize, FixedUsize},
+    ser::{Writer, WriterExt as _},
+    Archive, RelPtr, Serialize, SerializeUnsized,
+};
+
+/// An archived `Vec` that reserves space for its original capacity.
+///
+/// Like [`ArchivedVec`](crate::vec::ArchivedVec), this uses a [`RelPtr`] to a
+/// `[T]`. After the elements, the archive also contains zeroed space for the
+/// rest of the capacity of the serialized vec. Elements can be
+/// [`push`](Self::push)ed into that space through a pinned mutable reference
+/// until the archived vec is full, which allows archives to grow in place
+/// without reallocating the buffer.
+///
+/// This is the archived type of vecs serialized with
+/// [`WithCapacity`](crate::with::WithCapacity).
+#[cfg_attr(feature = "strict", repr(C))]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    check_bytes(verify)
+)]
+pub struct ArchivedCapacityVec<T> {
+    ptr: RelPtr<T>,
+    len: ArchivedUsize,
+    capacity: ArchivedUsize,
+}
+
+impl<T> ArchivedCapacityVec<T> {
+    /// Returns a pointer to the first element of the archived vec.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        unsafe { self.ptr.as_ptr().cast_const() }
+    }
+
+    /// Returns the number of elements in the archived vec.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the archived vec is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements that the archived vec can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity.to_native() as usize
+    }
+
+    /// Gets the elements of the archived vec as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Gets the elements of the archived vec as a pinned mutable slice.
+    #[inline]
+    pub fn pin_mut_slice(self: Pin<&mut Self>) -> Pin<&mut [T]> {
+        unsafe {
+            self.map_unchecked_mut(|s| {
+                slice::from_raw_parts_mut(s.ptr.as_ptr(), s.len())
+            })
+        }
+    }
+
+    /// Gets the elements of the archived vec as a mutable slice.
+    ///
+    /// This is only available for `Unpin` element types.
+    #[inline]
+    pub fn as_mut_slice(self: Pin<&mut Self>) -> &mut [T]
+    where
+        T: Unpin,
+    {
+        Pin::into_inner(self.pin_mut_slice())
+    }
+
+    /// Appends an element to the back of the archived vec.
+    ///
+    /// Returns the element back if the archived vec is already full. Like
+    /// [`as_mut_slice`](Self::as_mut_slice), this is only available for
+    /// `Unpin` element types, since archived types that contain relative
+    /// pointers can't be moved into the archive.
+    #[inline]
+    pub fn push(self: Pin<&mut Self>, value: T) -> Result<(), T>
+    where
+        T: Unpin,
+    {
+        let len = self.len();
+        if len == self.capacity() {
+            return Err(value);
+        }
+
+        // SAFETY: The archived vec itself is not moved, and the space for its
+        // capacity is part of the archive.
+        unsafe {
+            let this = self.get_unchecked_mut();
+            this.ptr.as_ptr().add(len).write(value);
+            this.len = ArchivedUsize::from_native((len + 1) as FixedUsize);
+        }
+        Ok(())
+    }
+
+    /// Resolves an archived `Vec` with capacity from a given length.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a slice of `len`
+    ///   elements with [`serialize_from_slice`](Self::serialize_from_slice)
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: CapacityVecResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.ptr);
+        RelPtr::emplace(pos + fp, resolver.pos, fo);
+        let (fp, fo) = out_field!(out.len);
+        usize::resolve(&len, pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.capacity);
+        usize::resolve(&resolver.capacity, pos + fp, (), fo);
+    }
+
+    /// Serializes an archived `Vec` with capacity from a given slice.
+    ///
+    /// Zeroed space for `capacity` elements is written after the elements of
+    /// the slice. The capacity is raised to the length of the slice if it is
+    /// smaller. Returns an error if the size of the capacity overflows a
+    /// `usize`.
+    #[inline]
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        capacity: usize,
+        serializer: &mut S,
+    ) -> Result<CapacityVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Writer + ?Sized,
+        S::Error: Error,
+        [U]: SerializeUnsized<S>,
+    {
+        let capacity = usize::max(capacity, slice.len());
+        FixedUsize::try_from(capacity).into_error()?;
+
+        let uninit = capacity - slice.len();
+        let padding = match uninit.checked_mul(size_of::<T>()) {
+            Some(padding) => padding,
+            None => fail!(CapacityOverflow { capacity }),
+        };
+
+        let pos = slice.serialize_unsized(serializer)?;
+        serializer.pad(padding)?;
+
+        Ok(CapacityVecResolver { pos, capacity })
+    }
+}
+
+impl<T> AsRef<[T]> for ArchivedCapacityVec<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Borrow<[T]> for ArchivedCapacityVec<T> {
+    #[inline]
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedCapacityVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T> Deref for ArchivedCapacityVec<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for ArchivedCapacityVec<T> {}
+
+impl<T: hash::Hash> hash::Hash for ArchivedCapacityVec<T> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>> Index<I> for ArchivedCapacityVec<T> {
+    type Output = <[T] as Index<I>>::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        self.as_slice().index(index)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArchivedCapacityVec<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: Ord> Ord for ArchivedCapacityVec<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<ArchivedCapacityVec<U>>
+    for ArchivedCapacityVec<T>
+{
+    #[inline]
+    fn eq(&self, other: &ArchivedCapacityVec<U>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for ArchivedCapacityVec<T> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]>
+    for ArchivedCapacityVec<T>
+{
+    #[inline]
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.as_slice().eq(&other[..])
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for ArchivedCapacityVec<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: SwapEndian> SwapEndian for ArchivedCapacityVec<T> {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        self.ptr.scalar_ranges(report);
+        self.len.scalar_ranges(report);
+        self.capacity.scalar_ranges(report);
+        self.as_slice().scalar_ranges(report);
+    }
+}
+
+/// The resolver for [`ArchivedCapacityVec`].
+pub struct CapacityVecResolver {
+    pos: usize,
+    capacity: usize,
+}
+
+#[derive(Debug)]
+struct CapacityOverflow {
+    capacity: usize,
+}
+
+impl fmt::Display for CapacityOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the size of an archived vec with capacity {} overflows a usize",
+            self.capacity,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityOverflow {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::fmt;
+
+    use bytecheck::{
+        rancor::{Error, Fallible},
+        CheckBytes, Verify,
+    };
+    use rancor::fail;
+
+    use super::ArchivedCapacityVec;
+    use crate::validation::{ArchiveContext, ArchiveContextExt};
+
+    #[derive(Debug)]
+    struct LengthExceedsCapacity {
+        len: usize,
+        capacity: usize,
+    }
+
+    impl fmt::Display for LengthExceedsCapacity {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "archived vec length {} exceeds its capacity {}",
+                self.len, self.capacity,
+            )
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for LengthExceedsCapacity {}
+
+    unsafe impl<T, C> Verify<C> for ArchivedCapacityVec<T>
+    where
+        T: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Error,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let len = self.len();
+            let capacity = self.capacity();
+            if len > capacity {
+                fail!(LengthExceedsCapacity { len, capacity });
+            }
+
+            // The whole capacity is claimed so that pushed elements can't
+            // overlap other archived data, but only the initialized elements
+            // are checked
+            let ptr = unsafe {
+                context.bounds_check_subtree_base_offset::<[T]>(
+                    self.ptr.base(),
+                    self.ptr.offset(),
+                    capacity,
+                )?
+            };
+
+            let range = unsafe { context.push_prefix_subtree(ptr)? };
+            let elements =
+                ptr_meta::from_raw_parts::<[T]>(ptr.cast::<()>(), len);
+            unsafe {
+                <[T]>::check_bytes(elements, context)?;
+            }
+            unsafe {
+                context.pop_subtree_range(range)?;
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -1,8 +1,11 @@
 //! An archived version of `Vec`.
 
+mod bits;
+mod capacity;
 // mod raw;
 
 use crate::{
+    endian::SwapEndian,
     primitive::ArchivedUsize,
     ser::{Allocator, Writer, WriterExt as _},
     Archive, RelPtr, Serialize, SerializeUnsized,
@@ -12,10 +15,12 @@ use core::{
     cmp, fmt, hash,
     ops::{Deref, Index, IndexMut},
     pin::Pin,
-    slice::SliceIndex,
+    slice::{self, SliceIndex},
 };
 use rancor::Fallible;
 
+pub use self::bits::{ArchivedPackedBits, PackedBitsIter, PackedBitsResolver};
+pub use self::capacity::{ArchivedCapacityVec, CapacityVecResolver};
 // pub use self::raw::*;
 
 /// An archived [`Vec`].
@@ -68,6 +73,38 @@ impl<T> ArchivedVec<T> {
         }
     }
 
+    /// Gets the elements of the archived vec as a mutable slice.
+    ///
+    /// This is only available for `Unpin` element types. Archived types that
+    /// contain relative pointers are never `Unpin`, so elements can be
+    /// modified or swapped in place without moving the targets of any
+    /// pointers.
+    #[inline]
+    pub fn as_mut_slice(self: Pin<&mut Self>) -> &mut [T]
+    where
+        T: Unpin,
+    {
+        Pin::into_inner(self.pin_mut_slice())
+    }
+
+    /// Gets a mutable reference to an element or subslice of the archived vec,
+    /// or `None` if the index is out of bounds.
+    ///
+    /// Like [`as_mut_slice`](Self::as_mut_slice), this is only available for
+    /// `Unpin` element types. It isn't named `get_mut` because calls on a
+    /// pinned vec would be ambiguous with [`Pin::get_mut`].
+    #[inline]
+    pub fn get_mut_at<I>(
+        self: Pin<&mut Self>,
+        index: I,
+    ) -> Option<&mut I::Output>
+    where
+        T: Unpin,
+        I: SliceIndex<[T]>,
+    {
+        self.as_mut_slice().get_mut(index)
+    }
+
     // This method can go away once pinned slices have indexing support
     // https://github.com/rust-lang/rust/pull/78370
 
@@ -251,6 +288,16 @@ impl<T, I: SliceIndex<[T]>> Index<I> for ArchivedVec<T> {
     }
 }
 
+impl<'a, T> IntoIterator for &'a ArchivedVec<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
 impl<T: Ord> Ord for ArchivedVec<T> {
     #[inline]
     fn cmp(&self, other: &Self) -> cmp::Ordering {
@@ -314,6 +361,15 @@ impl<T: PartialOrd> PartialOrd<ArchivedVec<T>> for [T] {
     }
 }
 
+impl<T: SwapEndian> SwapEndian for ArchivedVec<T> {
+    #[inline]
+    fn scalar_ranges(&self, report: &mut dyn FnMut(core::ops::Range<usize>)) {
+        self.ptr.scalar_ranges(report);
+        self.len.scalar_ranges(report);
+        self.as_slice().scalar_ranges(report);
+    }
+}
+
 /// The resolver for [`ArchivedVec`].
 pub struct VecResolver {
     pos: usize,
@@ -345,6 +401,9 @@ mod verify {
                 )?
             };
 
+            // The elements are claimed before they are checked, so the data
+            // that they point to (which is always serialized before them) is
+            // checked in the part of the subtree that precedes the elements
             let range = unsafe { context.push_prefix_subtree(ptr)? };
             unsafe {
                 <[T]>::check_bytes(ptr, context)?;
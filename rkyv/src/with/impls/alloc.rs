@@ -3,11 +3,18 @@ use crate::{
     collections::util::Entry,
     niche::option_box::{ArchivedOptionBox, OptionBoxResolver},
     ser::{Allocator, Writer},
-    string::{ArchivedString, StringResolver},
-    vec::{ArchivedVec, VecResolver},
+    string::{
+        ArchivedChunkedString, ArchivedInlineString, ArchivedString,
+        ChunkedStringResolver, StringResolver,
+    },
+    vec::{
+        ArchivedCapacityVec, ArchivedPackedBits, ArchivedVec,
+        CapacityVecResolver, PackedBitsResolver, VecResolver,
+    },
     with::{
-        ArchiveWith, AsOwned, AsVec, BoxedInline, CopyOptimize,
-        DeserializeWith, Map, Niche, SerializeWith, With,
+        ArchiveWith, AsOwned, AsVec, BoxedInline, Chunked, CopyOptimize,
+        DeserializeWith, InlineString, Map, Niche, PackedBits, SerializeWith,
+        With, WithCapacity,
     },
     Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized,
     Serialize, SerializeUnsized,
@@ -17,6 +24,7 @@ use alloc::{
     borrow::Cow,
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    string::String,
     vec::Vec,
 };
 use core::marker::PhantomData;
@@ -175,6 +183,7 @@ impl<'a, T, S> SerializeWith<Cow<'a, [T]>, S> for AsOwned
 where
     T: Serialize<S> + Clone,
     S: Fallible + Allocator + Writer + ?Sized,
+    [T]: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize_with(
@@ -185,22 +194,6 @@ where
     }
 }
 
-impl<'a, T, D> DeserializeWith<ArchivedVec<T::Archived>, Cow<'a, [T]>, D>
-    for AsOwned
-where
-    T: Archive + Clone,
-    T::Archived: Deserialize<T, D>,
-    D: Fallible + ?Sized,
-{
-    #[inline]
-    fn deserialize_with(
-        field: &ArchivedVec<T::Archived>,
-        deserializer: &mut D,
-    ) -> Result<Cow<'a, [T]>, D::Error> {
-        Ok(Cow::Owned(field.deserialize(deserializer)?))
-    }
-}
-
 impl<'a> ArchiveWith<Cow<'a, str>> for AsOwned {
     type Archived = ArchivedString;
     type Resolver = StringResolver;
@@ -219,6 +212,7 @@ impl<'a> ArchiveWith<Cow<'a, str>> for AsOwned {
 impl<'a, S> SerializeWith<Cow<'a, str>, S> for AsOwned
 where
     S: Fallible + Writer + ?Sized,
+    str: SerializeUnsized<S>,
 {
     #[inline]
     fn serialize_with(
@@ -229,19 +223,6 @@ where
     }
 }
 
-impl<'a, D> DeserializeWith<ArchivedString, Cow<'a, str>, D> for AsOwned
-where
-    D: Fallible + ?Sized,
-{
-    #[inline]
-    fn deserialize_with(
-        field: &ArchivedString,
-        deserializer: &mut D,
-    ) -> Result<Cow<'a, str>, D::Error> {
-        Ok(Cow::Owned(field.deserialize(deserializer)?))
-    }
-}
-
 #[cfg(feature = "std")]
 const _: () = {
     use crate::ffi::{ArchivedCString, CStringResolver};
@@ -262,8 +243,10 @@ const _: () = {
         }
     }
 
-    impl<'a, S: Fallible + Writer + ?Sized> SerializeWith<Cow<'a, CStr>, S>
-        for AsOwned
+    impl<'a, S> SerializeWith<Cow<'a, CStr>, S> for AsOwned
+    where
+        S: Fallible + Writer + ?Sized,
+        CStr: SerializeUnsized<S>,
     {
         #[inline]
         fn serialize_with(
@@ -287,6 +270,203 @@ const _: () = {
     }
 };
 
+// InlineString
+
+impl<const N: usize> ArchiveWith<String> for InlineString<N> {
+    type Archived = ArchivedInlineString<N>;
+    type Resolver = StringResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &String,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedInlineString::resolve_from_str(field, pos, resolver, out);
+    }
+}
+
+impl<S, const N: usize> SerializeWith<String, S> for InlineString<N>
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: rancor::Error,
+{
+    #[inline]
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedInlineString::<N>::serialize_from_str(field, serializer)
+    }
+}
+
+impl<D, const N: usize> DeserializeWith<ArchivedInlineString<N>, String, D>
+    for InlineString<N>
+where
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedInlineString<N>,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        Ok(field.as_str().into())
+    }
+}
+
+// Chunked
+
+impl<const N: usize> ArchiveWith<String> for Chunked<N> {
+    type Archived = ArchivedChunkedString;
+    type Resolver = ChunkedStringResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &String,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedChunkedString::resolve_from_len(
+            field.len(),
+            pos,
+            resolver,
+            out,
+        );
+    }
+}
+
+impl<S, const N: usize> SerializeWith<String, S> for Chunked<N>
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: rancor::Error,
+{
+    #[inline]
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedChunkedString::serialize_from_str(field, N, serializer)
+    }
+}
+
+impl<D, const N: usize> DeserializeWith<ArchivedChunkedString, String, D>
+    for Chunked<N>
+where
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedChunkedString,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        let mut result = String::with_capacity(field.len());
+        result.extend(field.chars());
+        Ok(result)
+    }
+}
+
+// WithCapacity
+
+impl<T: Archive> ArchiveWith<Vec<T>> for WithCapacity {
+    type Archived = ArchivedCapacityVec<T::Archived>;
+    type Resolver = CapacityVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedCapacityVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Vec<T>, S> for WithCapacity
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: rancor::Error,
+{
+    #[inline]
+    fn serialize_with(
+        field: &Vec<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedCapacityVec::serialize_from_slice(
+            field.as_slice(),
+            field.capacity(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedCapacityVec<T::Archived>, Vec<T>, D>
+    for WithCapacity
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedCapacityVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        let mut result = Vec::with_capacity(field.capacity());
+        for value in field.iter() {
+            result.push(value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// PackedBits
+
+impl ArchiveWith<Vec<bool>> for PackedBits {
+    type Archived = ArchivedPackedBits;
+    type Resolver = PackedBitsResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<bool>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedPackedBits::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<S> SerializeWith<Vec<bool>, S> for PackedBits
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: rancor::Error,
+{
+    #[inline]
+    fn serialize_with(
+        field: &Vec<bool>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPackedBits::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<D> DeserializeWith<ArchivedPackedBits, Vec<bool>, D> for PackedBits
+where
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedPackedBits,
+        _: &mut D,
+    ) -> Result<Vec<bool>, D::Error> {
+        Ok(field.iter().collect())
+    }
+}
+
 // AsVec
 
 impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsVec {
@@ -4,21 +4,24 @@ use crate::{
         ArchivedOptionNonZeroIsize, ArchivedOptionNonZeroUsize,
     },
     option::ArchivedOption,
-    primitive::{FixedNonZeroIsize, FixedNonZeroUsize},
+    primitive::{
+        ArchivedF32, ArchivedF64, FixedNonZeroIsize, FixedNonZeroUsize,
+    },
     with::{
-        ArchiveWith, Boxed, BoxedInline, DeserializeWith, Inline, Map, Niche,
-        SerializeWith, Skip, Unsafe,
+        ArchiveWith, ArchivedFinite, Boxed, BoxedInline, DeserializeWith,
+        Finite, Inline, Map, Niche, SerializeWith, Skip, Unsafe,
     },
     Archive, ArchiveUnsized, Deserialize, Serialize, SerializeUnsized,
 };
 use core::{
     cell::{Cell, UnsafeCell},
     convert::TryInto,
+    fmt,
     hint::unreachable_unchecked,
     num::{NonZeroIsize, NonZeroUsize},
     ptr,
 };
-use rancor::Fallible;
+use rancor::{fail, Error, Fallible};
 
 // Map for Options
 
@@ -299,6 +302,102 @@ impl<D: Fallible + ?Sized>
     }
 }
 
+// Finite
+
+#[derive(Debug)]
+struct NotFinite {
+    value: f64,
+}
+
+impl fmt::Display for NotFinite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a finite float but found {}", self.value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotFinite {}
+
+macro_rules! impl_finite {
+    ($type:ty, $archived:ty) => {
+        impl ArchiveWith<$type> for Finite {
+            type Archived = ArchivedFinite<$archived>;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve_with(
+                field: &$type,
+                _: usize,
+                _: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                out.cast::<$archived>()
+                    .write(<$archived>::from_native(*field));
+            }
+        }
+
+        impl<S> SerializeWith<$type, S> for Finite
+        where
+            S: Fallible + ?Sized,
+            S::Error: Error,
+        {
+            #[inline]
+            fn serialize_with(
+                field: &$type,
+                _: &mut S,
+            ) -> Result<Self::Resolver, S::Error> {
+                if !field.is_finite() {
+                    fail!(NotFinite {
+                        value: *field as f64,
+                    });
+                }
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized>
+            DeserializeWith<ArchivedFinite<$archived>, $type, D> for Finite
+        {
+            #[inline]
+            fn deserialize_with(
+                field: &ArchivedFinite<$archived>,
+                _: &mut D,
+            ) -> Result<$type, D::Error> {
+                Ok(field.value().to_native())
+            }
+        }
+
+        #[cfg(feature = "bytecheck")]
+        // SAFETY: `check_bytes` only returns `Ok` if the archived float is
+        // valid and finite.
+        unsafe impl<C> bytecheck::CheckBytes<C> for ArchivedFinite<$archived>
+        where
+            C: Fallible + ?Sized,
+            C::Error: Error,
+        {
+            unsafe fn check_bytes(
+                value: *const Self,
+                context: &mut C,
+            ) -> Result<(), C::Error> {
+                let ptr = value.cast::<$archived>();
+                <$archived as bytecheck::CheckBytes<C>>::check_bytes(
+                    ptr, context,
+                )?;
+                let value = (*ptr).to_native();
+                if !value.is_finite() {
+                    fail!(NotFinite {
+                        value: value as f64,
+                    });
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_finite!(f32, ArchivedF32);
+impl_finite!(f64, ArchivedF64);
+
 // Unsafe
 
 impl<F: Archive> ArchiveWith<UnsafeCell<F>> for Unsafe {
@@ -1,16 +1,23 @@
 use crate::{
-    collections::util::Entry,
+    collections::{
+        hash_map::{ArchivedHashMap, HashMapResolver},
+        hash_set::{ArchivedHashSet, HashSetResolver},
+        util::Entry,
+    },
     ser::{Allocator, Writer},
     string::{ArchivedString, StringResolver},
     time::ArchivedDuration,
     vec::{ArchivedVec, VecResolver},
     with::{
         ArchiveWith, AsString, AsVec, DeserializeWith, Immutable, InvalidStr,
-        Lock, Poisoned, SerializeWith, UnixTimestamp,
+        Lock, Poisoned, SerializeWith, UnixTimestamp, WithHasher,
     },
     Archive, Deserialize, Serialize, SerializeUnsized,
 };
-use core::{hash::Hash, str::FromStr};
+use core::{
+    hash::{BuildHasher, Hash, Hasher},
+    str::FromStr,
+};
 use rancor::{Error, Fallible, OptionExt, ResultExt};
 use std::{
     collections::{HashMap, HashSet},
@@ -236,6 +243,133 @@ where
     }
 }
 
+// WithHasher
+
+impl<K, V, RS, H> ArchiveWith<HashMap<K, V, RS>> for WithHasher<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Archive,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived, H>;
+    type Resolver = HashMapResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &HashMap<K, V, RS>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<K, V, RS, H, S> SerializeWith<HashMap<K, V, RS>, S> for WithHasher<H>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    H: Hasher + Default,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Error,
+{
+    #[inline]
+    fn serialize_with(
+        field: &HashMap<K, V, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            ArchivedHashMap::<K::Archived, V::Archived, H>::serialize_from_iter(
+                field.iter(),
+                serializer,
+            )
+        }
+    }
+}
+
+impl<K, V, RS, H, D>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, V::Archived, H>,
+        HashMap<K, V, RS>,
+        D,
+    > for WithHasher<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    RS: Default + BuildHasher,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, RS>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+impl<K, RS, H> ArchiveWith<HashSet<K, RS>> for WithHasher<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+{
+    type Archived = ArchivedHashSet<K::Archived, H>;
+    type Resolver = HashSetResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &HashSet<K, RS>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedHashSet::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<K, RS, H, S> SerializeWith<HashSet<K, RS>, S> for WithHasher<H>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    H: Hasher + Default,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Error,
+{
+    #[inline]
+    fn serialize_with(
+        field: &HashSet<K, RS>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            ArchivedHashSet::<K::Archived, H>::serialize_from_iter(
+                field.iter(),
+                serializer,
+            )
+        }
+    }
+}
+
+impl<K, RS, H, D>
+    DeserializeWith<ArchivedHashSet<K::Archived, H>, HashSet<K, RS>, D>
+    for WithHasher<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    RS: Default + BuildHasher,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedHashSet<K::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<K, RS>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
 // AsVec
 
 impl<K: Archive, V: Archive> ArchiveWith<HashMap<K, V>> for AsVec {
@@ -306,6 +306,8 @@ pub struct SeqCst;
 ///
 /// When serializing, the specified ordering will be used to load the value from
 /// the source atomic. The underlying archived type is still a non-atomic value.
+/// Atomics also implement `Archive` directly with a relaxed load, so this is
+/// only needed to load with a stronger ordering.
 ///
 /// See [`AsAtomic`] for an unsafe alternative which archives as an atomic.
 ///
@@ -427,6 +429,9 @@ pub struct BoxedInline;
 /// Types like `OsString` and `PathBuf` aren't guaranteed to be encoded as UTF-8, but they usually
 /// are anyway. Using this wrapper will archive them as if they were regular `String`s.
 ///
+/// On Unix, `OsString` and `PathBuf` can also be archived natively as raw bytes without this
+/// wrapper. This wrapper is the portable alternative for other platforms.
+///
 /// Regular serializers don't support the custom error handling needed for this type by default. To
 /// use this wrapper, a custom serializer with an error type satisfying
 /// `<S as Fallible>::Error: From<AsStringError>` must be provided.
@@ -448,9 +453,11 @@ pub struct BoxedInline;
 #[derive(Debug)]
 pub struct AsString;
 
+#[cfg(feature = "std")]
 #[derive(Debug)]
 struct InvalidStr;
 
+#[cfg(feature = "std")]
 impl fmt::Display for InvalidStr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "invalid UTF-8")
@@ -462,6 +469,10 @@ impl ::std::error::Error for InvalidStr {}
 
 /// A wrapper that locks a lock and serializes the value immutably.
 ///
+/// `Mutex` and `RwLock` can also be archived without a wrapper, in which case they are archived
+/// as their inner value and don't panic if the lock is poisoned while resolving. This wrapper
+/// archives the value as [`Immutable`] instead.
+///
 /// This wrapper can panic under very specific circumstances when:
 ///
 /// 1. `serialize_with` is called and succeeds in locking the value to serialize it.
@@ -495,9 +506,11 @@ impl ::std::error::Error for InvalidStr {}
 #[derive(Debug)]
 pub struct Lock;
 
+#[cfg(feature = "std")]
 #[derive(Debug)]
-struct Poisoned;
+pub(crate) struct Poisoned;
 
+#[cfg(feature = "std")]
 impl fmt::Display for Poisoned {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "lock poisoned")
@@ -524,6 +537,209 @@ impl ::std::error::Error for Poisoned {}
 #[derive(Debug)]
 pub struct AsOwned;
 
+/// A wrapper that archives a `String` with up to `N` bytes stored inline.
+///
+/// The archived type is an [`ArchivedInlineString<N>`](crate::string::ArchivedInlineString),
+/// which stores strings of up to `N` bytes directly inside the archived value and longer strings
+/// out of line. This saves space and a pointer indirection compared to
+/// [`ArchivedString`](crate::string::ArchivedString) when most strings are short. `N` must be at
+/// least the size of an archived `isize`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{rancor::Failure, to_bytes, with::InlineString, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     #[with(InlineString<16>)]
+///     name: String,
+/// }
+///
+/// let value = Example {
+///     name: "short name".to_string(),
+/// };
+/// let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+/// let archived = unsafe { rkyv::access_unchecked::<Example>(&bytes) };
+/// assert!(archived.name.is_inline());
+/// assert_eq!(&*archived.name, "short name");
+/// ```
+#[derive(Debug)]
+pub struct InlineString<const N: usize>;
+
+/// A wrapper that archives a `String` in chunks of `N` bytes.
+///
+/// The archived type is an
+/// [`ArchivedChunkedString`](crate::string::ArchivedChunkedString), which
+/// stores each chunk separately instead of in one contiguous `str`. The text
+/// can still be read by byte or `char` offset and displayed without copying
+/// it, so this is useful for very large strings. Chunks are cut at byte
+/// offsets, and multi-byte `char`s that are split between chunks are checked
+/// during validation like any other. `N` must be greater than zero.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{rancor::Failure, to_bytes, with::Chunked, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     #[with(Chunked<3>)]
+///     text: String,
+/// }
+///
+/// let value = Example {
+///     text: "crème brûlée".to_string(),
+/// };
+/// let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+/// let archived = unsafe { rkyv::access_unchecked::<Example>(&bytes) };
+/// assert_eq!(archived.text.chunk_count(), 5);
+/// // 'è' is split between the first two chunks
+/// assert_eq!(archived.text.char_at(2), Some('è'));
+/// assert_eq!(archived.text.to_string(), "crème brûlée");
+/// ```
+#[derive(Debug)]
+pub struct Chunked<const N: usize>;
+
+/// A wrapper that archives a `Vec` along with its capacity.
+///
+/// The archived type is an [`ArchivedCapacityVec`](crate::vec::ArchivedCapacityVec), which reserves
+/// zeroed space in the archive for the unused capacity of the vec. Through a pinned mutable
+/// reference, elements can be pushed into that space without reallocating the archive.
+/// Deserializing creates a `Vec` with the archived capacity.
+///
+/// # Example
+///
+/// ```
+/// use core::pin::Pin;
+/// use rkyv::{
+///     access_unchecked_mut, rancor::Failure, to_bytes, with::WithCapacity,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive(pin_fields)]
+/// struct Example {
+///     #[with(WithCapacity)]
+///     values: Vec<u32>,
+/// }
+///
+/// let mut values = Vec::with_capacity(4);
+/// values.extend([1, 2]);
+/// let mut bytes = to_bytes::<_, 256, Failure>(&Example { values }).unwrap();
+///
+/// let mut archived =
+///     unsafe { access_unchecked_mut::<Example>(Pin::new(bytes.as_mut())) };
+/// let mut values = archived.as_mut().values_pin();
+/// assert_eq!(values.capacity(), 4);
+/// values.as_mut().push(3.into()).unwrap();
+/// values.as_mut().push(4.into()).unwrap();
+/// assert!(values.as_mut().push(5.into()).is_err());
+/// assert_eq!(*values, [1, 2, 3, 4]);
+/// ```
+#[derive(Debug)]
+pub struct WithCapacity;
+
+/// A wrapper that archives a `Vec<bool>` as packed bits.
+///
+/// The archived type is an [`ArchivedPackedBits`](crate::vec::ArchivedPackedBits), which stores
+/// eight values per byte instead of one. Values are unpacked when they are read with
+/// [`get`](crate::vec::ArchivedPackedBits::get) or by iterating.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{rancor::Failure, to_bytes, with::PackedBits, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     #[with(PackedBits)]
+///     flags: Vec<bool>,
+/// }
+///
+/// let value = Example {
+///     flags: (0..100).map(|i| i % 3 == 0).collect(),
+/// };
+/// let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+/// let archived = unsafe { rkyv::access_unchecked::<Example>(&bytes) };
+/// assert_eq!(archived.flags.len(), 100);
+/// assert_eq!(archived.flags.as_bytes().len(), 13);
+/// assert!(archived.flags.get(99));
+/// assert!(!archived.flags.get(98));
+/// ```
+#[derive(Debug)]
+pub struct PackedBits;
+
+/// A wrapper that archives a `HashMap` or `HashSet` with a chosen hasher.
+///
+/// By default, archived hash maps and sets hash their keys with
+/// [`DefaultHasher`](crate::collections::hash_index::DefaultHasher). With this
+/// wrapper, they use `H` instead, and the hasher is recorded in the archived
+/// type so that lookups hash keys the same way as they were hashed while
+/// archiving. This is useful for keys that are expensive to hash with the
+/// default hasher.
+///
+/// `H::default()` must hash keys the same way in every process that accesses
+/// the archive. Hashers that generate random keys at runtime (like the hasher
+/// of [`RandomState`](std::collections::hash_map::RandomState)) can't be used.
+/// Colliding keys are separated by hashing a seed before the key, so the low
+/// bits of the finished hash must depend on everything that was written to the
+/// hasher. Otherwise, archiving a map fails with an error when no seed in a
+/// bounded search separates the keys.
+///
+/// # Example
+///
+/// ```
+/// use core::hash::{BuildHasherDefault, Hasher};
+/// use std::collections::HashMap;
+///
+/// use rkyv::{rancor::Failure, to_bytes, with::WithHasher, Archive, Serialize};
+///
+/// // A 64-bit FNV-1a hasher, with the high bits folded into the low bits
+/// struct Fnv(u64);
+///
+/// impl Default for Fnv {
+///     fn default() -> Self {
+///         Self(0xcbf29ce484222325)
+///     }
+/// }
+///
+/// impl Hasher for Fnv {
+///     fn finish(&self) -> u64 {
+///         self.0 ^ (self.0 >> 32)
+///     }
+///
+///     fn write(&mut self, bytes: &[u8]) {
+///         for &byte in bytes {
+///             self.0 = (self.0 ^ byte as u64).wrapping_mul(0x100000001b3);
+///         }
+///     }
+/// }
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     #[with(WithHasher<Fnv>)]
+///     values: HashMap<String, u32, BuildHasherDefault<Fnv>>,
+/// }
+///
+/// let mut values = HashMap::default();
+/// values.insert("hello".to_string(), 1);
+/// values.insert("world".to_string(), 2);
+/// let bytes = to_bytes::<_, 256, Failure>(&Example { values }).unwrap();
+///
+/// let archived = unsafe { rkyv::access_unchecked::<Example>(&bytes) };
+/// assert_eq!(archived.values["world"], 2);
+/// ```
+pub struct WithHasher<H> {
+    _hasher: PhantomData<H>,
+}
+
+impl<H> fmt::Debug for WithHasher<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WithHasher").finish()
+    }
+}
+
 /// A wrapper that serializes associative containers as a `Vec` of key-value pairs.
 ///
 /// This provides faster serialization for containers like `HashMap` and `BTreeMap` by serializing
@@ -547,7 +763,9 @@ pub struct AsVec;
 /// A wrapper that niches some type combinations.
 ///
 /// A common type combination is `Option<Box<T>>`. By using a null pointer, the archived version can
-/// save some space on-disk.
+/// save some space on-disk. `Option<NonZero*>` integers and `Option<char>` are
+/// also supported, and store `None` as zero and as an invalid `char` value
+/// respectively.
 ///
 /// # Example
 ///
@@ -571,6 +789,52 @@ pub struct AsVec;
 #[derive(Debug)]
 pub struct Niche;
 
+/// A wrapper that requires floating-point values to be finite.
+///
+/// By default, archived floats accept any bit pattern. With this wrapper, serializing a NaN or
+/// infinite value fails, and so does validating an archived value that is NaN or infinite. This is
+/// useful for fields where a non-finite value indicates corrupted data.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::Finite};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(Finite)]
+///     ratio: f32,
+///     #[with(Finite)]
+///     amount: f64,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Finite;
+
+/// An archived floating-point value that is finite.
+///
+/// This is the archived type of floats serialized with [`Finite`].
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct ArchivedFinite<F>(F);
+
+impl<F> ArchivedFinite<F> {
+    /// Gets the underlying archived float.
+    #[inline]
+    pub fn value(&self) -> &F {
+        &self.0
+    }
+}
+
+impl<F> Deref for ArchivedFinite<F> {
+    type Target = F;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 /// A wrapper that provides specialized, performant implementations of serialization and
 /// deserialization.
 ///
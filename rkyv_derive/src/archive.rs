@@ -1,13 +1,22 @@
 use crate::{
-    attributes::{parse_attributes, Attributes},
-    util::{is_not_omitted, strip_raw},
+    attributes::{int_repr_range, parse_attributes, Attributes},
+    copy::derive_archive_copy,
+    diff::derive_diff_fields,
+    swap_endian::derive_swap_endian,
+    union::derive_archive_union,
+    util::{
+        archived_field_name, field_align, is_not_omitted, is_not_skipped,
+        is_skipped, known_archived_align, strip_raw, to_snake_case,
+    },
     with::{make_with_cast, make_with_ty},
 };
-use proc_macro2::{Literal, TokenStream};
+use core::cmp::Reverse;
+use proc_macro2::{Literal, Span, TokenStream};
 use quote::quote;
 use syn::{
-    parse_quote, spanned::Spanned, Attribute, Data, DeriveInput, Error, Field,
-    Fields, Ident, Index, LitStr, Meta, Type,
+    parse_quote, spanned::Spanned, Attribute, Data, DataEnum, DeriveInput,
+    Error, Expr, ExprLit, ExprUnary, Field, Fields, Ident, Index, Lit, LitStr,
+    Meta, Path, Type, UnOp,
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -31,16 +40,160 @@ fn field_archive_attrs(
     })
 }
 
+/// Returns the discriminant of each variant, honoring explicit discriminants.
+pub fn enum_discriminants(data: &DataEnum) -> Result<Vec<i128>, Error> {
+    let mut discriminants = Vec::with_capacity(data.variants.len());
+    let mut next = 0;
+    for variant in data.variants.iter() {
+        if let Some((_, ref expr)) = variant.discriminant {
+            next = parse_discriminant(expr)?;
+        }
+        discriminants.push(next);
+        next += 1;
+    }
+    Ok(discriminants)
+}
+
+fn parse_discriminant(expr: &Expr) -> Result<i128, Error> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int.base10_parse::<i128>().map(|x| -x),
+            _ => Err(Error::new_spanned(
+                expr,
+                "archived enum discriminants must be integer literals",
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            expr,
+            "archived enum discriminants must be integer literals",
+        )),
+    }
+}
+
+/// Chooses the type of an archived enum's tag.
+///
+/// The tag is the type given with `repr(...)` if any. Otherwise, it is the
+/// smallest unsigned integer that fits both the number of variants and the
+/// largest discriminant.
+pub fn enum_tag_ty(
+    data: &DataEnum,
+    discriminants: &[i128],
+    attributes: &Attributes,
+) -> Result<Ident, Error> {
+    if let Some(ref repr) = attributes.repr {
+        let (min, max) = int_repr_range(repr).unwrap();
+        for (variant, &discriminant) in data.variants.iter().zip(discriminants)
+        {
+            if discriminant < min || discriminant > max {
+                return Err(Error::new_spanned(
+                    variant,
+                    format!(
+                        "discriminant {} does not fit in repr({})",
+                        discriminant, repr,
+                    ),
+                ));
+            }
+        }
+        return Ok(repr.clone());
+    }
+
+    for (variant, &discriminant) in data.variants.iter().zip(discriminants) {
+        if discriminant < 0 {
+            return Err(Error::new_spanned(
+                variant,
+                "negative discriminants require a signed repr, add \
+                 #[archive(repr(...))] to choose one",
+            ));
+        }
+    }
+
+    let len = data.variants.len() as i128;
+    let max = discriminants.iter().copied().max().unwrap_or(0);
+    let tag_ty = ["u8", "u16", "u32", "u64"]
+        .into_iter()
+        .map(|ty| Ident::new(ty, Span::call_site()))
+        .find(|ty| {
+            let (_, ty_max) = int_repr_range(ty).unwrap();
+            len <= ty_max && max <= ty_max
+        })
+        .unwrap_or_else(|| Ident::new("u128", Span::call_site()));
+    Ok(tag_ty)
+}
+
+fn discriminant_tokens(discriminant: i128) -> TokenStream {
+    let literal = Literal::u128_unsuffixed(discriminant.unsigned_abs());
+    if discriminant < 0 {
+        quote! { -#literal }
+    } else {
+        quote! { #literal }
+    }
+}
+
+fn ordered_discriminant(discriminant: i128) -> TokenStream {
+    let literal = Literal::u128_suffixed(discriminant.unsigned_abs());
+    if discriminant < 0 {
+        quote! { -(#literal as i128) }
+    } else {
+        quote! { (#literal as i128) }
+    }
+}
+
+/// Returns an expression that sums the serialized size hints of the given
+/// field values.
+fn sum_size_hints(rkyv_path: &Path, values: &[Expr]) -> TokenStream {
+    if values.is_empty() {
+        quote! { 0 }
+    } else {
+        quote! {
+            #(#rkyv_path::Archive::serialized_size_hint(#values))+*
+        }
+    }
+}
+
 fn derive_archive_impl(
     mut input: DeriveInput,
     attributes: &Attributes,
 ) -> Result<TokenStream, Error> {
+    if attributes.copy.is_some() {
+        let default_rkyv_path = parse_quote! { ::rkyv };
+        let rkyv_path =
+            attributes.rkyv_path.as_ref().unwrap_or(&default_rkyv_path);
+        return derive_archive_copy(&input, attributes, rkyv_path);
+    }
+    if attributes.unsafe_union.is_some() {
+        let default_rkyv_path = parse_quote! { ::rkyv };
+        let rkyv_path =
+            attributes.rkyv_path.as_ref().unwrap_or(&default_rkyv_path);
+        return derive_archive_union(&input, attributes, rkyv_path);
+    }
+
     let where_clause = input.generics.make_where_clause();
     if let Some(ref bounds) = attributes.archive_bounds {
         for bound in bounds {
             where_clause.predicates.push(bound.clone());
         }
     }
+    if let Some(ref bounds) = attributes.bound.archive {
+        for bound in bounds {
+            where_clause.predicates.push(bound.clone());
+        }
+    }
+    // Field bounds are only inferred when they aren't replaced with
+    // `bound(archive = "...")`
+    let infer_archive_bounds = |f: &&Field| {
+        attributes.bound.archive.is_none()
+            && is_not_omitted(f)
+            && is_not_skipped(f)
+    };
 
     let name = &input.ident;
     let vis = &input.vis;
@@ -55,21 +208,44 @@ fn derive_archive_impl(
     let with_ty = make_with_ty(rkyv_path);
     let with_cast = make_with_cast(rkyv_path);
 
-    let derive_check_bytes = if attributes.check_bytes.is_some() {
-        let bytecheck_path_str = attributes
-            .rkyv_path_str
-            .as_ref()
-            .map(|x| {
-                LitStr::new(&format!("{}::bytecheck", x.value()), x.span())
-            })
-            .unwrap_or_else(|| parse_quote!("::rkyv::bytecheck"));
-        vec![
-            parse_quote! { #[derive(#rkyv_path::bytecheck::CheckBytes)] },
-            parse_quote! { #[check_bytes(crate = #bytecheck_path_str)] },
-        ]
-    } else {
-        Vec::new()
-    };
+    if let Some(ref bounds) = attributes.bound.check {
+        if attributes.check_bytes.is_none() {
+            return Err(Error::new_spanned(
+                bounds,
+                "bound(check = \"...\") may only be used with check_bytes",
+            ));
+        }
+    }
+    // Field bounds are omitted from the `CheckBytes` impl when they're
+    // replaced with `bound(check = "...")`
+    let omit_check_bounds = attributes
+        .bound
+        .check
+        .as_ref()
+        .map(|_| quote! { #[omit_bounds] });
+
+    // `CheckBytes` is only derived when validation is enabled, so types can
+    // support validation without requiring it of every user
+    let derive_check_bytes =
+        if attributes.check_bytes.is_some() && cfg!(feature = "bytecheck") {
+            let bytecheck_path_str = attributes
+                .rkyv_path_str
+                .as_ref()
+                .map(|x| {
+                    LitStr::new(&format!("{}::bytecheck", x.value()), x.span())
+                })
+                .unwrap_or_else(|| parse_quote!("::rkyv::bytecheck"));
+            let mut attrs: Vec<Attribute> = vec![
+                parse_quote! { #[derive(#rkyv_path::bytecheck::CheckBytes)] },
+                parse_quote! { #[check_bytes(crate = #bytecheck_path_str)] },
+            ];
+            if let Some(ref bounds) = attributes.bound.check {
+                attrs.push(parse_quote! { #[check_bytes(bounds(#bounds))] });
+            }
+            attrs
+        } else {
+            Vec::new()
+        };
 
     let archive_attrs = derive_check_bytes.into_iter().chain(
         attributes
@@ -78,6 +254,66 @@ fn derive_archive_impl(
             .map::<Attribute, _>(|d| parse_quote! { #[#d] }),
     );
 
+    if let Some(ref pin_fields) = attributes.pin_fields {
+        let is_named_struct = matches!(
+            input.data,
+            Data::Struct(ref data) if matches!(data.fields, Fields::Named(_)),
+        );
+        if !is_named_struct {
+            return Err(Error::new_spanned(
+                pin_fields,
+                "pin_fields may only be used on structs with named fields",
+            ));
+        }
+        if attributes.archive_as.is_some() {
+            return Err(Error::new_spanned(
+                pin_fields,
+                "pin_fields may not be used with as = \"...\" because no type is generated",
+            ));
+        }
+    }
+
+    if let Some(ref minimize_padding) = attributes.minimize_padding {
+        let is_named_struct = matches!(
+            input.data,
+            Data::Struct(ref data) if matches!(data.fields, Fields::Named(_)),
+        );
+        if !is_named_struct {
+            return Err(Error::new_spanned(
+                minimize_padding,
+                "minimize_padding may only be used on structs with named fields",
+            ));
+        }
+        if attributes.archive_as.is_some() {
+            return Err(Error::new_spanned(
+                minimize_padding,
+                "minimize_padding may not be used with as = \"...\" because no type is generated",
+            ));
+        }
+    }
+
+    if let Some(ref repr) = attributes.repr {
+        if repr == "C" {
+            if !matches!(input.data, Data::Struct(_)) {
+                return Err(Error::new_spanned(
+                    repr,
+                    "repr(C) may only be used on structs, archived enums take an integer repr",
+                ));
+            }
+            if attributes.archive_as.is_some() {
+                return Err(Error::new_spanned(
+                    repr,
+                    "repr(C) may not be used with as = \"...\" because no type is generated",
+                ));
+            }
+        } else if !matches!(input.data, Data::Enum(_)) {
+            return Err(Error::new_spanned(
+                repr,
+                "integer reprs may only be used on enums, use archive_attr(repr(...)) instead",
+            ));
+        }
+    }
+
     if let Some(ref archive_as) = attributes.archive_as {
         if let Some(ref ident) = attributes.archived {
             return Err(Error::new_spanned(
@@ -118,7 +354,29 @@ fn derive_archive_impl(
 
     let (archive_types, archive_impls) = match input.data {
         Data::Struct(ref data) => {
-            let repr = if cfg!(feature = "strict") {
+            let has_aligned_fields =
+                data.fields.iter().any(|f| field_align(f).is_some());
+            // Fields are only reordered when the alignment of every field is
+            // known. Otherwise, the sorted order may have more padding than
+            // the layout the compiler would choose.
+            let reorder_fields = attributes.minimize_padding.is_some()
+                && data.fields.iter().filter(is_not_skipped).all(|f| {
+                    with_ty(f)
+                        .ok()
+                        .as_ref()
+                        .and_then(known_archived_align)
+                        .is_some()
+                });
+            // Reordered fields are laid out in their sorted order regardless
+            // of the strict feature so that the layout is stable, and only
+            // repr(C) is accepted on structs. Aligned fields are placed after
+            // a zero-sized marker, which only aligns them if the field order
+            // is kept.
+            let repr = if cfg!(feature = "strict")
+                || reorder_fields
+                || attributes.repr.is_some()
+                || has_aligned_fields
+            {
                 Some(quote! { #[repr(C)] })
             } else {
                 None
@@ -127,37 +385,88 @@ fn derive_archive_impl(
             match data.fields {
                 Fields::Named(ref fields) => {
                     let mut archive_where = where_clause.clone();
-                    for field in fields.named.iter().filter(is_not_omitted) {
+                    for field in
+                        fields.named.iter().filter(infer_archive_bounds)
+                    {
                         let ty = with_ty(field)?;
                         archive_where
                             .predicates
                             .push(parse_quote! { #ty: #rkyv_path::Archive });
                     }
 
-                    let resolver_fields = fields.named.iter().map(|f| {
-                        let name = &f.ident;
-                        let ty = with_ty(f).unwrap();
-                        quote! { #name: #rkyv_path::Resolver<#ty> }
-                    });
-
-                    let archived_def = if attributes.archive_as.is_none() {
-                        let archived_fields = fields.named.iter().map(|f| {
-                            let field_name = f.ident.as_ref();
+                    // Resolver fields have the same visibility as the
+                    // original fields so that resolvers can be built manually
+                    let resolver_fields =
+                        fields.named.iter().filter(is_not_skipped).map(|f| {
+                            let name = &f.ident;
                             let ty = with_ty(f).unwrap();
                             let vis = &f.vis;
-                            let field_doc = format!(
-                                "The archived counterpart of [`{}::{}`]",
-                                name,
-                                field_name.unwrap()
-                            );
-                            let archive_attrs = field_archive_attrs(f);
-                            quote! {
-                                #[doc = #field_doc]
-                                #(#[#archive_attrs])*
-                                #vis #field_name: #rkyv_path::Archived<#ty>
-                            }
+                            quote! { #vis #name: #rkyv_path::Resolver<#ty> }
                         });
 
+                    let archived_def = if attributes.archive_as.is_none() {
+                        let mut archived_fields = fields
+                            .named
+                            .iter()
+                            .filter(is_not_skipped)
+                            .collect::<Vec<_>>();
+                        if reorder_fields {
+                            // Fields are always accessed by name, so only the
+                            // declaration order of the archived type changes.
+                            // The sort is stable so the layout only depends on
+                            // the field types.
+                            archived_fields.sort_by_key(|f| {
+                                Reverse(
+                                    with_ty(f)
+                                        .ok()
+                                        .as_ref()
+                                        .and_then(known_archived_align),
+                                )
+                            });
+                        }
+                        let archived_fields = archived_fields
+                            .into_iter()
+                            .map(|f| {
+                                let field_name = f.ident.as_ref();
+                                let archived_field_name =
+                                    archived_field_name(f);
+                                let ty = with_ty(f).unwrap();
+                                let vis = &f.vis;
+                                let field_doc = format!(
+                                    "The archived counterpart of [`{}::{}`]",
+                                    name,
+                                    field_name.unwrap()
+                                );
+                                let archive_attrs = field_archive_attrs(f);
+                                // A zero-sized marker with the requested
+                                // alignment pads the struct up to the start of
+                                // the field
+                                let align_marker = field_align(f).map(|align| {
+                                    let marker_name = Ident::new(
+                                        &format!(
+                                            "__align_{}",
+                                            strip_raw(archived_field_name.as_ref().unwrap()),
+                                        ),
+                                        align.span(),
+                                    );
+                                    let marker_ty = Ident::new(
+                                        &format!("Align{}", align.base10_digits()),
+                                        align.span(),
+                                    );
+                                    quote! {
+                                        #[doc(hidden)]
+                                        #marker_name: #rkyv_path::util::#marker_ty,
+                                    }
+                                });
+                                quote! {
+                                    #align_marker
+                                    #[doc = #field_doc]
+                                    #(#[#archive_attrs])*
+                                    #omit_check_bounds
+                                    #vis #archived_field_name: #rkyv_path::Archived<#ty>
+                                }
+                            });
+
                         Some(quote! {
                             #[automatically_derived]
                             #[doc = #archived_doc]
@@ -171,11 +480,24 @@ fn derive_archive_impl(
                         None
                     };
 
-                    let resolve_fields = fields.named.iter().map(|f| {
+                    let size_hint_values = fields
+                        .named
+                        .iter()
+                        .filter(is_not_skipped)
+                        .map(|f| {
+                            let name = &f.ident;
+                            with_cast(f, parse_quote! { (&self.#name) })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let size_hint =
+                        sum_size_hints(rkyv_path, &size_hint_values);
+
+                    let resolve_fields = fields.named.iter().filter(is_not_skipped).map(|f| {
                         let name = &f.ident;
+                        let archived_name = archived_field_name(f);
                         let field = with_cast(f, parse_quote! { (&self.#name) }).unwrap();
                         quote! {
-                            let (fp, fo) = out_field!(out.#name);
+                            let (fp, fo) = out_field!(out.#archived_name);
                             #rkyv_path::Archive::resolve(#field, pos + fp, resolver.#name, fo);
                         }
                     });
@@ -187,8 +509,11 @@ fn derive_archive_impl(
                             if compare.is_ident("PartialEq") {
                                 let mut partial_eq_where =
                                     archive_where.clone();
-                                for field in
-                                    fields.named.iter().filter(is_not_omitted)
+                                for field in fields
+                                    .named
+                                    .iter()
+                                    .filter(is_not_omitted)
+                                    .filter(is_not_skipped)
                                 {
                                     let ty = &field.ty;
                                     let wrapped_ty = with_ty(field).unwrap();
@@ -197,14 +522,22 @@ fn derive_archive_impl(
                                     );
                                 }
 
-                                let field_names =
-                                    fields.named.iter().map(|f| &f.ident);
+                                let field_names = fields
+                                    .named
+                                    .iter()
+                                    .filter(is_not_skipped)
+                                    .map(|f| &f.ident);
+                                let archived_field_names = fields
+                                    .named
+                                    .iter()
+                                    .filter(is_not_skipped)
+                                    .map(archived_field_name);
 
                                 partial_eq_impl = Some(quote! {
                                     impl #impl_generics PartialEq<#archived_type> for #name #ty_generics #partial_eq_where {
                                         #[inline]
                                         fn eq(&self, other: &#archived_type) -> bool {
-                                            true #(&& other.#field_names.eq(&self.#field_names))*
+                                            true #(&& other.#archived_field_names.eq(&self.#field_names))*
                                         }
                                     }
 
@@ -218,8 +551,11 @@ fn derive_archive_impl(
                             } else if compare.is_ident("PartialOrd") {
                                 let mut partial_ord_where =
                                     archive_where.clone();
-                                for field in
-                                    fields.named.iter().filter(is_not_omitted)
+                                for field in fields
+                                    .named
+                                    .iter()
+                                    .filter(is_not_omitted)
+                                    .filter(is_not_skipped)
                                 {
                                     let ty = &field.ty;
                                     let archived_ty = with_ty(field).unwrap();
@@ -228,17 +564,27 @@ fn derive_archive_impl(
                                     );
                                 }
 
-                                let field_names =
-                                    fields.named.iter().map(|f| &f.ident);
+                                let field_names = fields
+                                    .named
+                                    .iter()
+                                    .filter(is_not_skipped)
+                                    .map(|f| &f.ident);
+                                let archived_field_names = fields
+                                    .named
+                                    .iter()
+                                    .filter(is_not_skipped)
+                                    .map(archived_field_name);
 
+                                // The bounds only allow comparing archived fields with original
+                                // fields, so those orderings are reversed
                                 partial_ord_impl = Some(quote! {
                                     impl #impl_generics PartialOrd<#archived_type> for #name #ty_generics #partial_ord_where {
                                         #[inline]
                                         fn partial_cmp(&self, other: &#archived_type) -> Option<::core::cmp::Ordering> {
                                             #(
-                                                match other.#field_names.partial_cmp(&self.#field_names) {
+                                                match other.#archived_field_names.partial_cmp(&self.#field_names) {
                                                     Some(::core::cmp::Ordering::Equal) => (),
-                                                    x => return x,
+                                                    x => return x.map(::core::cmp::Ordering::reverse),
                                                 }
                                             )*
                                             Some(::core::cmp::Ordering::Equal)
@@ -248,7 +594,7 @@ fn derive_archive_impl(
                                     impl #impl_generics PartialOrd<#name #ty_generics> for #archived_type #partial_ord_where {
                                         #[inline]
                                         fn partial_cmp(&self, other: &#name #ty_generics) -> Option<::core::cmp::Ordering> {
-                                            other.partial_cmp(self)
+                                            other.partial_cmp(self).map(::core::cmp::Ordering::reverse)
                                         }
                                     }
                                 });
@@ -265,7 +611,11 @@ fn derive_archive_impl(
                         && attributes.copy_safe.is_some()
                     {
                         let mut copy_safe_where = where_clause.clone();
-                        for field in fields.named.iter().filter(is_not_omitted)
+                        for field in fields
+                            .named
+                            .iter()
+                            .filter(is_not_omitted)
+                            .filter(is_not_skipped)
                         {
                             let ty = with_ty(field).unwrap();
                             copy_safe_where
@@ -280,10 +630,49 @@ fn derive_archive_impl(
                         None
                     };
 
+                    let pin_fields_impl = if attributes.pin_fields.is_some() {
+                        let pin_fns = fields.named.iter().filter(is_not_skipped).map(|f| {
+                            let field_name = f.ident.as_ref().unwrap();
+                            let archived_field_name =
+                                archived_field_name(f).unwrap();
+                            let fn_name = Ident::new(
+                                &format!(
+                                    "{}_pin",
+                                    strip_raw(&archived_field_name)
+                                ),
+                                archived_field_name.span(),
+                            );
+                            let ty = with_ty(f).unwrap();
+                            let vis = &f.vis;
+                            let fn_doc = format!(
+                                "Returns a pinned mutable reference to the archived [`{}::{}`]",
+                                name, field_name,
+                            );
+                            quote! {
+                                #[doc = #fn_doc]
+                                #[inline]
+                                #vis fn #fn_name(self: ::core::pin::Pin<&mut Self>) -> ::core::pin::Pin<&mut #rkyv_path::Archived<#ty>> {
+                                    unsafe { self.map_unchecked_mut(|s| &mut s.#archived_field_name) }
+                                }
+                            }
+                        });
+
+                        Some(quote! {
+                            #[automatically_derived]
+                            impl #impl_generics #archived_name #ty_generics #archive_where {
+                                #(#pin_fns)*
+                            }
+                        })
+                    } else {
+                        None
+                    };
+
                     (
                         quote! {
                             #archived_def
 
+                            #pin_fields_impl
+
                             #[automatically_derived]
                             #[doc = #resolver_doc]
                             #vis struct #resolver #generics #archive_where {
@@ -295,6 +684,11 @@ fn derive_archive_impl(
                                 type Archived = #archived_type;
                                 type Resolver = #resolver #ty_generics;
 
+                                #[inline]
+                                fn serialized_size_hint(&self) -> usize {
+                                    #size_hint
+                                }
+
                                 // Some resolvers will be (), this allow is to prevent clippy from complaining
                                 #[allow(clippy::unit_arg)]
                                 #[inline]
@@ -311,21 +705,29 @@ fn derive_archive_impl(
                 }
                 Fields::Unnamed(ref fields) => {
                     let mut archive_where = where_clause.clone();
-                    for field in fields.unnamed.iter().filter(is_not_omitted) {
+                    for field in
+                        fields.unnamed.iter().filter(infer_archive_bounds)
+                    {
                         let ty = with_ty(field)?;
                         archive_where
                             .predicates
                             .push(parse_quote! { #ty: #rkyv_path::Archive });
                     }
 
-                    let resolver_fields = fields.unnamed.iter().map(|f| {
-                        let ty = with_ty(f).unwrap();
-                        quote! { #rkyv_path::Resolver<#ty> }
-                    });
+                    let resolver_fields =
+                        fields.unnamed.iter().filter(is_not_skipped).map(|f| {
+                            let ty = with_ty(f).unwrap();
+                            let vis = &f.vis;
+                            quote! { #vis #rkyv_path::Resolver<#ty> }
+                        });
 
                     let archived_def = if attributes.archive_as.is_none() {
-                        let archived_fields =
-                            fields.unnamed.iter().enumerate().map(|(i, f)| {
+                        let archived_fields = fields
+                            .unnamed
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, f)| is_not_skipped(f))
+                            .map(|(i, f)| {
                                 let ty = with_ty(f).unwrap();
                                 let vis = &f.vis;
                                 let field_doc = format!(
@@ -336,6 +738,7 @@ fn derive_archive_impl(
                                 quote! {
                                     #[doc = #field_doc]
                                     #(#[#archive_attrs])*
+                                    #omit_check_bounds
                                     #vis #rkyv_path::Archived<#ty>
                                 }
                             });
@@ -351,12 +754,28 @@ fn derive_archive_impl(
                         None
                     };
 
-                    let resolve_fields = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let size_hint_values = fields
+                        .unnamed
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, f)| is_not_skipped(f))
+                        .map(|(i, f)| {
+                            let index = Index::from(i);
+                            with_cast(f, parse_quote! { (&self.#index) })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let size_hint =
+                        sum_size_hints(rkyv_path, &size_hint_values);
+
+                    // Skipped fields aren't archived, so archived fields are
+                    // indexed separately
+                    let resolve_fields = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).enumerate().map(|(archived_i, (i, f))| {
                         let index = Index::from(i);
+                        let archived_index = Index::from(archived_i);
                         let field = with_cast(f, parse_quote! { (&self.#index) }).unwrap();
                         quote! {
-                            let (fp, fo) = out_field!(out.#index);
-                            #rkyv_path::Archive::resolve(#field, pos + fp, resolver.#index, fo);
+                            let (fp, fo) = out_field!(out.#archived_index);
+                            #rkyv_path::Archive::resolve(#field, pos + fp, resolver.#archived_index, fo);
                         }
                     });
 
@@ -367,8 +786,11 @@ fn derive_archive_impl(
                             if compare.is_ident("PartialEq") {
                                 let mut partial_eq_where =
                                     archive_where.clone();
-                                for field in
-                                    fields.unnamed.iter().filter(is_not_omitted)
+                                for field in fields
+                                    .unnamed
+                                    .iter()
+                                    .filter(is_not_omitted)
+                                    .filter(is_not_skipped)
                                 {
                                     let ty = &field.ty;
                                     let wrapped_ty = with_ty(field).unwrap();
@@ -381,13 +803,17 @@ fn derive_archive_impl(
                                     .unnamed
                                     .iter()
                                     .enumerate()
-                                    .map(|(i, _)| Index::from(i));
+                                    .filter(|(_, f)| is_not_skipped(f))
+                                    .map(|(i, _)| Index::from(i))
+                                    .collect::<Vec<_>>();
+                                let archived_field_names =
+                                    (0..field_names.len()).map(Index::from);
 
                                 partial_eq_impl = Some(quote! {
                                     impl #impl_generics PartialEq<#archived_type> for #name #ty_generics #partial_eq_where {
                                         #[inline]
                                         fn eq(&self, other: &#archived_type) -> bool {
-                                            true #(&& other.#field_names.eq(&self.#field_names))*
+                                            true #(&& other.#archived_field_names.eq(&self.#field_names))*
                                         }
                                     }
 
@@ -401,8 +827,11 @@ fn derive_archive_impl(
                             } else if compare.is_ident("PartialOrd") {
                                 let mut partial_ord_where =
                                     archive_where.clone();
-                                for field in
-                                    fields.unnamed.iter().filter(is_not_omitted)
+                                for field in fields
+                                    .unnamed
+                                    .iter()
+                                    .filter(is_not_omitted)
+                                    .filter(is_not_skipped)
                                 {
                                     let ty = &field.ty;
                                     let wrapped_ty = with_ty(field).unwrap();
@@ -415,16 +844,22 @@ fn derive_archive_impl(
                                     .unnamed
                                     .iter()
                                     .enumerate()
-                                    .map(|(i, _)| Index::from(i));
-
+                                    .filter(|(_, f)| is_not_skipped(f))
+                                    .map(|(i, _)| Index::from(i))
+                                    .collect::<Vec<_>>();
+                                let archived_field_names =
+                                    (0..field_names.len()).map(Index::from);
+
+                                // The bounds only allow comparing archived fields with original
+                                // fields, so those orderings are reversed
                                 partial_ord_impl = Some(quote! {
                                     impl #impl_generics PartialOrd<#archived_type> for #name #ty_generics #partial_ord_where {
                                         #[inline]
                                         fn partial_cmp(&self, other: &#archived_type) -> Option<::core::cmp::Ordering> {
                                             #(
-                                                match other.#field_names.partial_cmp(&self.#field_names) {
+                                                match other.#archived_field_names.partial_cmp(&self.#field_names) {
                                                     Some(::core::cmp::Ordering::Equal) => (),
-                                                    x => return x,
+                                                    x => return x.map(::core::cmp::Ordering::reverse),
                                                 }
                                             )*
                                             Some(::core::cmp::Ordering::Equal)
@@ -434,7 +869,7 @@ fn derive_archive_impl(
                                     impl #impl_generics PartialOrd<#name #ty_generics> for #archived_type #partial_ord_where {
                                         #[inline]
                                         fn partial_cmp(&self, other: &#name #ty_generics) -> Option<::core::cmp::Ordering> {
-                                            other.partial_cmp(self)
+                                            other.partial_cmp(self).map(::core::cmp::Ordering::reverse)
                                         }
                                     }
                                 });
@@ -448,8 +883,11 @@ fn derive_archive_impl(
                         && attributes.copy_safe.is_some()
                     {
                         let mut copy_safe_where = where_clause.clone();
-                        for field in
-                            fields.unnamed.iter().filter(is_not_omitted)
+                        for field in fields
+                            .unnamed
+                            .iter()
+                            .filter(is_not_omitted)
+                            .filter(is_not_skipped)
                         {
                             let ty = with_ty(field).unwrap();
                             copy_safe_where
@@ -477,6 +915,11 @@ fn derive_archive_impl(
                                 type Archived = #archived_type;
                                 type Resolver = #resolver #ty_generics;
 
+                                #[inline]
+                                fn serialized_size_hint(&self) -> usize {
+                                    #size_hint
+                                }
+
                                 // Some resolvers will be (), this allow is to prevent clippy from complaining
                                 #[allow(clippy::unit_arg)]
                                 #[inline]
@@ -591,7 +1034,8 @@ fn derive_archive_impl(
             for variant in data.variants.iter() {
                 match variant.fields {
                     Fields::Named(ref fields) => {
-                        for field in fields.named.iter().filter(is_not_omitted)
+                        for field in
+                            fields.named.iter().filter(infer_archive_bounds)
                         {
                             let ty = with_ty(field)?;
                             archive_where.predicates.push(
@@ -601,7 +1045,7 @@ fn derive_archive_impl(
                     }
                     Fields::Unnamed(ref fields) => {
                         for field in
-                            fields.unnamed.iter().filter(is_not_omitted)
+                            fields.unnamed.iter().filter(infer_archive_bounds)
                         {
                             let ty = with_ty(field)?;
                             archive_where.predicates.push(
@@ -617,20 +1061,23 @@ fn derive_archive_impl(
                 let variant = &v.ident;
                 match v.fields {
                     Fields::Named(ref fields) => {
-                        let fields = fields.named.iter().map(|f| {
-                            let field_name = f.ident.as_ref();
-                            let ty = with_ty(f).unwrap();
-                            let field_doc = format!(
-                                "The resolver for [`{}::{}::{}`]",
-                                name,
-                                variant,
-                                field_name.unwrap(),
+                        let fields =
+                            fields.named.iter().filter(is_not_skipped).map(
+                                |f| {
+                                    let field_name = f.ident.as_ref();
+                                    let ty = with_ty(f).unwrap();
+                                    let field_doc = format!(
+                                        "The resolver for [`{}::{}::{}`]",
+                                        name,
+                                        variant,
+                                        field_name.unwrap(),
+                                    );
+                                    quote! {
+                                        #[doc = #field_doc]
+                                        #field_name: #rkyv_path::Resolver<#ty>
+                                    }
+                                },
                             );
-                            quote! {
-                                #[doc = #field_doc]
-                                #field_name: #rkyv_path::Resolver<#ty>
-                            }
-                        });
                         let variant_doc = format!(
                             "The resolver for [`{}::{}`]",
                             name, variant
@@ -644,8 +1091,12 @@ fn derive_archive_impl(
                         }
                     }
                     Fields::Unnamed(ref fields) => {
-                        let fields =
-                            fields.unnamed.iter().enumerate().map(|(i, f)| {
+                        let fields = fields
+                            .unnamed
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, f)| is_not_skipped(f))
+                            .map(|(i, f)| {
                                 let ty = with_ty(f).unwrap();
                                 let field_doc = format!(
                                     "The resolver for [`{}::{}::{}`]",
@@ -685,17 +1136,17 @@ fn derive_archive_impl(
                 let archived_variant_name = Ident::new(&format!("ArchivedVariant{}", strip_raw(variant)), v.span());
                 match v.fields {
                     Fields::Named(ref fields) => {
-                        let self_bindings = fields.named.iter().map(|f| {
+                        let self_bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                             let name = &f.ident;
                             let binding = Ident::new(&format!("self_{}", strip_raw(name.as_ref().unwrap())), name.span());
                             quote! { #name: #binding }
                         });
-                        let resolver_bindings = fields.named.iter().map(|f| {
+                        let resolver_bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                             let name = &f.ident;
                             let binding = Ident::new(&format!("resolver_{}", strip_raw(name.as_ref().unwrap())), name.span());
                             quote! { #name: #binding }
                         });
-                        let resolves = fields.named.iter().map(|f| {
+                        let resolves = fields.named.iter().filter(is_not_skipped).map(|f| {
                             let name = &f.ident;
                             let self_binding = Ident::new(&format!("self_{}", strip_raw(name.as_ref().unwrap())), name.span());
                             let resolver_binding = Ident::new(&format!("resolver_{}", strip_raw(name.as_ref().unwrap())), name.span());
@@ -708,7 +1159,7 @@ fn derive_archive_impl(
                         quote! {
                             #resolver::#variant { #(#resolver_bindings,)* } => {
                                 match self {
-                                    #name::#variant { #(#self_bindings,)* } => {
+                                    #name::#variant { #(#self_bindings,)* .. } => {
                                         let out = out.cast::<#archived_variant_name #ty_generics>();
                                         ::core::ptr::addr_of_mut!((*out).__tag)
                                             .write(ArchivedTag::#variant);
@@ -722,15 +1173,19 @@ fn derive_archive_impl(
                     }
                     Fields::Unnamed(ref fields) => {
                         let self_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
-                            let name = Ident::new(&format!("self_{}", i), f.span());
-                            quote! { #name }
+                            if is_skipped(f) {
+                                quote! { _ }
+                            } else {
+                                let name = Ident::new(&format!("self_{}", i), f.span());
+                                quote! { #name }
+                            }
                         });
-                        let resolver_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                        let resolver_bindings = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                             let name = Ident::new(&format!("resolver_{}", i), f.span());
                             quote! { #name }
                         });
-                        let resolves = fields.unnamed.iter().enumerate().map(|(i, f)| {
-                            let index = Index::from(i + 1);
+                        let resolves = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).enumerate().map(|(archived_i, (i, f))| {
+                            let index = Index::from(archived_i + 1);
                             let self_binding = Ident::new(&format!("self_{}", i), f.span());
                             let resolver_binding = Ident::new(&format!("resolver_{}", i), f.span());
                             let value = with_cast(f, parse_quote! { #self_binding }).unwrap();
@@ -761,24 +1216,89 @@ fn derive_archive_impl(
                 }
             });
 
-            let repr = match data.variants.len() as u128 {
-                0..=255 => quote! { #[repr(u8)] },
-                256..=65_535 => quote! { #[repr(u16)] },
-                65_536..=4_294_967_295 => quote! { #[repr(u32)] },
-                4_294_967_296..=18_446_744_073_709_551_615 => {
-                    quote! { #[repr(u64)] }
+            let size_hint_arms = data
+                .variants
+                .iter()
+                .map(|v| {
+                    let variant = &v.ident;
+                    match v.fields {
+                        Fields::Named(ref fields) => {
+                            let names = fields
+                                .named
+                                .iter()
+                                .filter(is_not_skipped)
+                                .map(|f| &f.ident)
+                                .collect::<Vec<_>>();
+                            let values = fields
+                                .named
+                                .iter()
+                                .filter(is_not_skipped)
+                                .map(|f| {
+                                    let name = &f.ident;
+                                    with_cast(f, parse_quote! { #name })
+                                })
+                                .collect::<Result<Vec<_>, _>>()?;
+                            let size_hint = sum_size_hints(rkyv_path, &values);
+                            Ok(quote! {
+                                #name::#variant { #(ref #names,)* .. } => #size_hint
+                            })
+                        }
+                        Fields::Unnamed(ref fields) => {
+                            let bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                if is_skipped(f) {
+                                    quote! { _ }
+                                } else {
+                                    let name = Ident::new(&format!("self_{}", i), f.span());
+                                    quote! { ref #name }
+                                }
+                            });
+                            let values = fields
+                                .unnamed
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, f)| is_not_skipped(f))
+                                .map(|(i, f)| {
+                                    let name = Ident::new(&format!("self_{}", i), f.span());
+                                    with_cast(f, parse_quote! { #name })
+                                })
+                                .collect::<Result<Vec<_>, _>>()?;
+                            let size_hint = sum_size_hints(rkyv_path, &values);
+                            Ok(quote! {
+                                #name::#variant(#(#bindings,)*) => #size_hint
+                            })
+                        }
+                        Fields::Unit => Ok(quote! { #name::#variant => 0 }),
+                    }
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let discriminants = enum_discriminants(data)?;
+            let tag_ty = enum_tag_ty(data, &discriminants, attributes)?;
+            let repr = quote! { #[repr(#tag_ty)] };
+            let is_single_byte = tag_ty == "u8" || tag_ty == "i8";
+
+            // Multi-byte tags are stored in the archive's byte order so that
+            // archived enums are portable between hosts.
+            let make_discriminant = |i: usize| {
+                let discriminant = discriminant_tokens(discriminants[i]);
+                if is_single_byte || cfg!(feature = "native_endian") {
+                    quote! { #discriminant }
+                } else if cfg!(feature = "big_endian") {
+                    quote! { #tag_ty::to_be(#discriminant) }
+                } else {
+                    quote! { #tag_ty::to_le(#discriminant) }
                 }
-                _ => quote! { #[repr(u128)] },
             };
 
             let archived_def = if attributes.archive_as.is_none() {
                 let archived_variants = data.variants.iter().enumerate().map(|(i, v)| {
                     let variant = &v.ident;
-                    let discriminant = Literal::usize_unsuffixed(i);
+                    let discriminant = make_discriminant(i);
                     match v.fields {
                         Fields::Named(ref fields) => {
-                            let fields = fields.named.iter().map(|f| {
+                            let fields = fields.named.iter().filter(is_not_skipped).map(|f| {
                                 let field_name = f.ident.as_ref();
+                                let archived_field_name = archived_field_name(f);
                                 let ty = with_ty(f).unwrap();
                                 let vis = &f.vis;
                                 let field_doc = format!(
@@ -791,7 +1311,8 @@ fn derive_archive_impl(
                                 quote! {
                                     #[doc = #field_doc]
                                     #(#[#archive_attrs])*
-                                    #vis #field_name: #rkyv_path::Archived<#ty>
+                                    #omit_check_bounds
+                                    #vis #archived_field_name: #rkyv_path::Archived<#ty>
                                 }
                             });
                             let variant_doc =
@@ -805,7 +1326,7 @@ fn derive_archive_impl(
                             }
                         }
                         Fields::Unnamed(ref fields) => {
-                            let fields = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                            let fields = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                                 let ty = with_ty(f).unwrap();
                                 let vis = &f.vis;
                                 let field_doc = format!(
@@ -816,6 +1337,7 @@ fn derive_archive_impl(
                                 quote! {
                                     #[doc = #field_doc]
                                     #(#[#archive_attrs])*
+                                    #omit_check_bounds
                                     #vis #rkyv_path::Archived<#ty>
                                 }
                             });
@@ -855,7 +1377,7 @@ fn derive_archive_impl(
             let archived_variant_tags =
                 data.variants.iter().enumerate().map(|(i, v)| {
                     let variant = &v.ident;
-                    let discriminant = Literal::usize_unsuffixed(i);
+                    let discriminant = make_discriminant(i);
                     quote! { #variant = #discriminant }
                 });
 
@@ -864,7 +1386,7 @@ fn derive_archive_impl(
                 let archived_variant_name = Ident::new(&format!("ArchivedVariant{}", strip_raw(variant)), v.span());
                 match v.fields {
                     Fields::Named(ref fields) => {
-                        let fields = fields.named.iter().map(|f| {
+                        let fields = fields.named.iter().filter(is_not_skipped).map(|f| {
                             let name = &f.ident;
                             let ty = with_ty(f).unwrap();
                             quote! { #name: Archived<#ty> }
@@ -879,7 +1401,7 @@ fn derive_archive_impl(
                         }
                     }
                     Fields::Unnamed(ref fields) => {
-                        let fields = fields.unnamed.iter().map(|f| {
+                        let fields = fields.unnamed.iter().filter(is_not_skipped).map(|f| {
                             let ty = with_ty(f).unwrap();
                             quote! { Archived<#ty> }
                         });
@@ -905,6 +1427,7 @@ fn derive_archive_impl(
                                         .named
                                         .iter()
                                         .filter(is_not_omitted)
+                                        .filter(is_not_skipped)
                                     {
                                         let ty = &field.ty;
                                         let wrapped_ty =
@@ -919,6 +1442,7 @@ fn derive_archive_impl(
                                         .unnamed
                                         .iter()
                                         .filter(is_not_omitted)
+                                        .filter(is_not_skipped)
                                     {
                                         let ty = &field.ty;
                                         let wrapped_ty =
@@ -936,36 +1460,47 @@ fn derive_archive_impl(
                             let variant = &v.ident;
                             match v.fields {
                                 Fields::Named(ref fields) => {
-                                    let field_names = fields.named.iter()
+                                    let field_names = fields.named.iter().filter(is_not_skipped)
                                         .map(|f| &f.ident)
                                         .collect::<Vec<_>>();
-                                    let self_bindings = fields.named.iter().map(|f| {
+                                    let archived_field_names = fields.named.iter().filter(is_not_skipped)
+                                        .map(archived_field_name)
+                                        .collect::<Vec<_>>();
+                                    let self_bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                                         f.ident.as_ref().map(|ident| {
                                             Ident::new(&format!("self_{}", strip_raw(ident)), ident.span())
                                         })
                                     }).collect::<Vec<_>>();
-                                    let other_bindings = fields.named.iter().map(|f| {
+                                    let other_bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                                         f.ident.as_ref().map(|ident| {
                                             Ident::new(&format!("other_{}", strip_raw(ident)), ident.span())
                                         })
                                     }).collect::<Vec<_>>();
                                     quote! {
-                                        #name::#variant { #(#field_names: #self_bindings,)* } => match other {
-                                            #archived_name::#variant { #(#field_names: #other_bindings,)* } => true #(&& #other_bindings.eq(#self_bindings))*,
+                                        #name::#variant { #(#field_names: #self_bindings,)* .. } => match other {
+                                            #archived_name::#variant { #(#archived_field_names: #other_bindings,)* } => true #(&& #other_bindings.eq(#self_bindings))*,
                                             #[allow(unreachable_patterns)]
                                             _ => false,
                                         }
                                     }
                                 }
                                 Fields::Unnamed(ref fields) => {
-                                    let self_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                    let self_patterns = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                        if is_skipped(f) {
+                                            quote! { _ }
+                                        } else {
+                                            let name = Ident::new(&format!("self_{}", i), f.span());
+                                            quote! { #name }
+                                        }
+                                    });
+                                    let self_bindings = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                                         Ident::new(&format!("self_{}", i), f.span())
                                     }).collect::<Vec<_>>();
-                                    let other_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                    let other_bindings = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                                         Ident::new(&format!("other_{}", i), f.span())
                                     }).collect::<Vec<_>>();
                                     quote! {
-                                        #name::#variant(#(#self_bindings,)*) => match other {
+                                        #name::#variant(#(#self_patterns,)*) => match other {
                                             #archived_name::#variant(#(#other_bindings,)*) => true #(&& #other_bindings.eq(#self_bindings))*,
                                             #[allow(unreachable_patterns)]
                                             _ => false,
@@ -1008,6 +1543,7 @@ fn derive_archive_impl(
                                         .named
                                         .iter()
                                         .filter(is_not_omitted)
+                                        .filter(is_not_skipped)
                                     {
                                         let ty = &field.ty;
                                         let wrapped_ty =
@@ -1022,6 +1558,7 @@ fn derive_archive_impl(
                                         .unnamed
                                         .iter()
                                         .filter(is_not_omitted)
+                                        .filter(is_not_skipped)
                                     {
                                         let ty = &field.ty;
                                         let wrapped_ty =
@@ -1035,33 +1572,39 @@ fn derive_archive_impl(
                             }
                         }
 
+                        // Variants are ordered by their discriminants like the
+                        // derived `PartialOrd`
                         let self_disc =
                             data.variants.iter().enumerate().map(|(i, v)| {
                                 let variant = &v.ident;
+                                let discriminant =
+                                    ordered_discriminant(discriminants[i]);
                                 match v.fields {
                                     Fields::Named(_) => quote! {
-                                        #name::#variant { .. } => #i
+                                        #name::#variant { .. } => #discriminant
                                     },
                                     Fields::Unnamed(_) => quote! {
-                                        #name::#variant ( .. ) => #i
+                                        #name::#variant ( .. ) => #discriminant
                                     },
                                     Fields::Unit => quote! {
-                                        #name::#variant => #i
+                                        #name::#variant => #discriminant
                                     },
                                 }
                             });
                         let other_disc =
                             data.variants.iter().enumerate().map(|(i, v)| {
                                 let variant = &v.ident;
+                                let discriminant =
+                                    ordered_discriminant(discriminants[i]);
                                 match v.fields {
                                     Fields::Named(_) => quote! {
-                                        #archived_name::#variant { .. } => #i
+                                        #archived_name::#variant { .. } => #discriminant
                                     },
                                     Fields::Unnamed(_) => quote! {
-                                        #archived_name::#variant ( .. ) => #i
+                                        #archived_name::#variant ( .. ) => #discriminant
                                     },
                                     Fields::Unit => quote! {
-                                        #archived_name::#variant => #i
+                                        #archived_name::#variant => #discriminant
                                     },
                                 }
                             });
@@ -1070,26 +1613,29 @@ fn derive_archive_impl(
                             let variant = &v.ident;
                             match v.fields {
                                 Fields::Named(ref fields) => {
-                                    let field_names = fields.named.iter()
+                                    let field_names = fields.named.iter().filter(is_not_skipped)
                                         .map(|f| &f.ident)
                                         .collect::<Vec<_>>();
-                                    let self_bindings = fields.named.iter().map(|f| {
+                                    let archived_field_names = fields.named.iter().filter(is_not_skipped)
+                                        .map(archived_field_name)
+                                        .collect::<Vec<_>>();
+                                    let self_bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                                         f.ident.as_ref().map(|ident| {
                                             Ident::new(&format!("self_{}", strip_raw(ident)), ident.span())
                                         })
                                     }).collect::<Vec<_>>();
-                                    let other_bindings = fields.named.iter().map(|f| {
+                                    let other_bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                                         f.ident.as_ref().map(|ident| {
                                             Ident::new(&format!("other_{}", strip_raw(ident)), ident.span())
                                         })
                                     }).collect::<Vec<_>>();
                                     quote! {
-                                        #name::#variant { #(#field_names: #self_bindings,)* } => match other {
-                                            #archived_name::#variant { #(#field_names: #other_bindings,)* } => {
+                                        #name::#variant { #(#field_names: #self_bindings,)* .. } => match other {
+                                            #archived_name::#variant { #(#archived_field_names: #other_bindings,)* } => {
                                                 #(
                                                     match #other_bindings.partial_cmp(#self_bindings) {
                                                         Some(::core::cmp::Ordering::Equal) => (),
-                                                        cmp => return cmp,
+                                                        cmp => return cmp.map(::core::cmp::Ordering::reverse),
                                                     }
                                                 )*
                                                 Some(::core::cmp::Ordering::Equal)
@@ -1100,19 +1646,27 @@ fn derive_archive_impl(
                                     }
                                 }
                                 Fields::Unnamed(ref fields) => {
-                                    let self_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                    let self_patterns = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                        if is_skipped(f) {
+                                            quote! { _ }
+                                        } else {
+                                            let name = Ident::new(&format!("self_{}", i), f.span());
+                                            quote! { #name }
+                                        }
+                                    });
+                                    let self_bindings = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                                         Ident::new(&format!("self_{}", i), f.span())
                                     }).collect::<Vec<_>>();
-                                    let other_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                                    let other_bindings = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                                         Ident::new(&format!("other_{}", i), f.span())
                                     }).collect::<Vec<_>>();
                                     quote! {
-                                        #name::#variant(#(#self_bindings,)*) => match other {
+                                        #name::#variant(#(#self_patterns,)*) => match other {
                                             #archived_name::#variant(#(#other_bindings,)*) => {
                                                 #(
                                                     match #other_bindings.partial_cmp(#self_bindings) {
                                                         Some(::core::cmp::Ordering::Equal) => (),
-                                                        cmp => return cmp,
+                                                        cmp => return cmp.map(::core::cmp::Ordering::reverse),
                                                     }
                                                 )*
                                                 Some(::core::cmp::Ordering::Equal)
@@ -1132,6 +1686,8 @@ fn derive_archive_impl(
                             }
                         });
 
+                        // The bounds only allow comparing archived fields with original
+                        // fields, so those orderings are reversed
                         partial_ord_impl = Some(quote! {
                             impl #impl_generics PartialOrd<#archived_type> for #name #ty_generics #partial_ord_where {
                                 #[inline]
@@ -1151,20 +1707,116 @@ fn derive_archive_impl(
                             impl #impl_generics PartialOrd<#name #ty_generics> for #archived_type #partial_ord_where {
                                 #[inline]
                                 fn partial_cmp(&self, other: &#name #ty_generics) -> Option<::core::cmp::Ordering> {
-                                    match other.partial_cmp(self) {
-                                        Some(::core::cmp::Ordering::Less) => Some(::core::cmp::Ordering::Greater),
-                                        Some(::core::cmp::Ordering::Greater) => Some(::core::cmp::Ordering::Less),
-                                        cmp => cmp,
-                                    }
+                                    other.partial_cmp(self).map(::core::cmp::Ordering::reverse)
                                 }
                             }
                         });
                     } else {
-                        return Err(Error::new_spanned(compare, "unrecognized compare argument, supported compares are PartialEq (PartialOrd is not supported for enums)"));
+                        return Err(Error::new_spanned(compare, "unrecognized compare argument, supported compares are PartialEq and PartialOrd"));
                     }
                 }
             }
 
+            let variant_accessors_impl = if attributes.archive_as.is_none() {
+                let accessor_fns = data.variants.iter().map(|v| {
+                    let variant = &v.ident;
+                    let snake_name = to_snake_case(&strip_raw(variant));
+                    let is_name = Ident::new(&format!("is_{}", snake_name), variant.span());
+                    let as_name = Ident::new(&format!("as_{}", snake_name), variant.span());
+                    let is_doc = format!(
+                        "Returns whether this is the archived counterpart of [`{}::{}`]",
+                        name, variant,
+                    );
+                    let as_doc = format!(
+                        "Returns references to the archived fields of [`{}::{}`] if this \
+                         is that variant",
+                        name, variant,
+                    );
+                    let is_pattern = match v.fields {
+                        Fields::Named(_) => quote! { Self::#variant { .. } },
+                        Fields::Unnamed(_) => quote! { Self::#variant(..) },
+                        Fields::Unit => quote! { Self::#variant },
+                    };
+                    let (pattern, bindings, tys) = match v.fields {
+                        Fields::Named(ref fields) => {
+                            let fields = fields.named.iter().filter(is_not_skipped).collect::<Vec<_>>();
+                            let archived_field_names = fields.iter().map(|f| archived_field_name(f)).collect::<Vec<_>>();
+                            let bindings = fields.iter().map(|f| {
+                                let ident = f.ident.as_ref().unwrap();
+                                Ident::new(&format!("field_{}", strip_raw(ident)), ident.span())
+                            }).collect::<Vec<_>>();
+                            let tys = fields.iter().map(|f| with_ty(f).unwrap()).collect::<Vec<_>>();
+                            (
+                                quote! { Self::#variant { #(#archived_field_names: #bindings,)* } },
+                                bindings,
+                                tys,
+                            )
+                        }
+                        Fields::Unnamed(ref fields) => {
+                            let fields = fields.unnamed.iter().filter(is_not_skipped).collect::<Vec<_>>();
+                            let bindings = fields.iter().enumerate().map(|(i, f)| {
+                                Ident::new(&format!("field_{}", i), f.span())
+                            }).collect::<Vec<_>>();
+                            let tys = fields.iter().map(|f| with_ty(f).unwrap()).collect::<Vec<_>>();
+                            (
+                                quote! { Self::#variant(#(#bindings,)*) },
+                                bindings,
+                                tys,
+                            )
+                        }
+                        Fields::Unit => (quote! { Self::#variant }, Vec::new(), Vec::new()),
+                    };
+
+                    let is_fn = quote! {
+                        #[doc = #is_doc]
+                        #[inline]
+                        #vis fn #is_name(&self) -> bool {
+                            ::core::matches!(self, #is_pattern)
+                        }
+                    };
+                    let as_fn = match bindings.len() {
+                        0 => None,
+                        1 => Some(quote! {
+                            #[doc = #as_doc]
+                            #[inline]
+                            #vis fn #as_name(&self) -> ::core::option::Option<&Archived<#(#tys)*>> {
+                                match self {
+                                    #pattern => ::core::option::Option::Some(#(#bindings)*),
+                                    #[allow(unreachable_patterns)]
+                                    _ => ::core::option::Option::None,
+                                }
+                            }
+                        }),
+                        _ => Some(quote! {
+                            #[doc = #as_doc]
+                            #[inline]
+                            #vis fn #as_name(&self) -> ::core::option::Option<(#(&Archived<#tys>,)*)> {
+                                match self {
+                                    #pattern => ::core::option::Option::Some((#(#bindings,)*)),
+                                    #[allow(unreachable_patterns)]
+                                    _ => ::core::option::Option::None,
+                                }
+                            }
+                        }),
+                    };
+
+                    quote! {
+                        #is_fn
+                        #as_fn
+                    }
+                });
+
+                Some(quote! {
+                    #[automatically_derived]
+                    #[allow(dead_code)]
+                    impl #impl_generics #archived_name #ty_generics #archive_where {
+                        #(#accessor_fns)*
+                    }
+                })
+            } else {
+                None
+            };
+
             let copy_safe_impl = if cfg!(feature = "copy")
                 && attributes.copy_safe.is_some()
             {
@@ -1172,8 +1824,11 @@ fn derive_archive_impl(
                 for variant in data.variants.iter() {
                     match variant.fields {
                         Fields::Named(ref fields) => {
-                            for field in
-                                fields.named.iter().filter(is_not_omitted)
+                            for field in fields
+                                .named
+                                .iter()
+                                .filter(is_not_omitted)
+                                .filter(is_not_skipped)
                             {
                                 let ty = with_ty(field).unwrap();
                                 copy_safe_where
@@ -1182,8 +1837,11 @@ fn derive_archive_impl(
                             }
                         }
                         Fields::Unnamed(ref fields) => {
-                            for field in
-                                fields.unnamed.iter().filter(is_not_omitted)
+                            for field in fields
+                                .unnamed
+                                .iter()
+                                .filter(is_not_omitted)
+                                .filter(is_not_skipped)
                             {
                                 let ty = with_ty(field).unwrap();
                                 copy_safe_where
@@ -1224,6 +1882,13 @@ fn derive_archive_impl(
                         type Archived = #archived_type;
                         type Resolver = #resolver #ty_generics;
 
+                        #[inline]
+                        fn serialized_size_hint(&self) -> usize {
+                            match *self {
+                                #(#size_hint_arms,)*
+                            }
+                        }
+
                         // Some resolvers will be (), this allow is to prevent clippy from complaining
                         #[allow(clippy::unit_arg)]
                         #[inline]
@@ -1237,20 +1902,47 @@ fn derive_archive_impl(
                     #partial_eq_impl
                     #partial_ord_impl
                     #copy_safe_impl
+                    #variant_accessors_impl
                 },
             )
         }
         Data::Union(_) => {
             return Err(Error::new_spanned(
                 input,
-                "Archive cannot be derived for unions",
+                "Archive cannot be derived for unions without \
+                 #[archive(unsafe_union)]",
             ))
         }
     };
 
+    let diff_impl = if attributes.diff.is_some() {
+        Some(derive_diff_fields(
+            &input,
+            attributes,
+            &archived_name,
+            rkyv_path,
+        )?)
+    } else {
+        None
+    };
+
+    let swap_endian_impl = if attributes.swap_endian.is_some() {
+        Some(derive_swap_endian(
+            &input,
+            attributes,
+            &archived_name,
+            rkyv_path,
+        )?)
+    } else {
+        None
+    };
+
     Ok(quote! {
         #archive_types
 
+        #diff_impl
+        #swap_endian_impl
+
         #[automatically_derived]
         const _: () = {
             use core::marker::PhantomData;
@@ -1,8 +1,8 @@
 use quote::ToTokens;
 use syn::{
     meta::ParseNestedMeta, parenthesized, parse::Parse, punctuated::Punctuated,
-    AttrStyle, DeriveInput, Error, Ident, LitStr, Meta, Path, Token,
-    WherePredicate,
+    AttrStyle, Data, DeriveInput, Error, Field, Ident, LitInt, LitStr, Meta,
+    Path, Token, Type, WherePredicate,
 };
 
 #[derive(Default)]
@@ -15,12 +15,29 @@ pub struct Attributes {
     pub archive_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     pub serialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     pub deserialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub bound: Bound,
     pub check_bytes: Option<Path>,
+    pub copy: Option<Path>,
     pub copy_safe: Option<Path>,
+    pub pin_fields: Option<Path>,
+    pub minimize_padding: Option<Path>,
+    pub diff: Option<Path>,
+    pub swap_endian: Option<Path>,
+    pub unsafe_union: Option<Path>,
+    pub repr: Option<Ident>,
     pub rkyv_path: Option<Path>,
     pub rkyv_path_str: Option<LitStr>,
 }
 
+/// Bounds that replace the inferred bounds of a generated impl.
+#[derive(Default)]
+pub struct Bound {
+    pub archive: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub serialize: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub deserialize: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub check: Option<Punctuated<WherePredicate, Token![,]>>,
+}
+
 fn try_set_attribute<T: ToTokens>(
     attribute: &mut Option<T>,
     value: T,
@@ -37,6 +54,18 @@ fn try_set_attribute<T: ToTokens>(
     }
 }
 
+/// Parses the name of a generated type, which may be given either as an
+/// identifier (`archived = ArchivedFoo`) or as a string
+/// (`archived = "ArchivedFoo"`).
+fn parse_type_name(meta: &ParseNestedMeta<'_>) -> Result<Ident, Error> {
+    let value = meta.value()?;
+    if value.peek(LitStr) {
+        value.parse::<LitStr>()?.parse()
+    } else {
+        value.parse()
+    }
+}
+
 fn parse_archive_attributes(
     attributes: &mut Attributes,
     meta: ParseNestedMeta<'_>,
@@ -47,12 +76,68 @@ fn parse_archive_attributes(
         }
 
         try_set_attribute(&mut attributes.check_bytes, meta.path, "check_bytes")
+    } else if meta.path.is_ident("copy") {
+        if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+            return Err(meta.error("copy argument must be a path"));
+        }
+
+        try_set_attribute(&mut attributes.copy, meta.path, "copy")
     } else if meta.path.is_ident("copy_safe") {
         if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
             return Err(meta.error("copy_safe argument must be a path"));
         }
 
         try_set_attribute(&mut attributes.copy_safe, meta.path, "copy_safe")
+    } else if meta.path.is_ident("pin_fields") {
+        if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+            return Err(meta.error("pin_fields argument must be a path"));
+        }
+
+        try_set_attribute(&mut attributes.pin_fields, meta.path, "pin_fields")
+    } else if meta.path.is_ident("minimize_padding") {
+        if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+            return Err(meta.error("minimize_padding argument must be a path"));
+        }
+
+        try_set_attribute(
+            &mut attributes.minimize_padding,
+            meta.path,
+            "minimize_padding",
+        )
+    } else if meta.path.is_ident("diff") {
+        if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+            return Err(meta.error("diff argument must be a path"));
+        }
+
+        try_set_attribute(&mut attributes.diff, meta.path, "diff")
+    } else if meta.path.is_ident("swap_endian") {
+        if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+            return Err(meta.error("swap_endian argument must be a path"));
+        }
+
+        try_set_attribute(&mut attributes.swap_endian, meta.path, "swap_endian")
+    } else if meta.path.is_ident("unsafe_union") {
+        if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+            return Err(meta.error("unsafe_union argument must be a path"));
+        }
+
+        try_set_attribute(
+            &mut attributes.unsafe_union,
+            meta.path,
+            "unsafe_union",
+        )
+    } else if meta.path.is_ident("repr") {
+        let repr;
+        parenthesized!(repr in meta.input);
+        let repr = repr.parse::<Ident>()?;
+        if repr != "C" && int_repr_range(&repr).is_none() {
+            return Err(Error::new_spanned(
+                repr,
+                "unsupported repr, supported reprs are C, u8, u16, u32, u64, \
+                 i8, i16, i32, and i64",
+            ));
+        }
+        try_set_attribute(&mut attributes.repr, repr, "repr")
     } else if meta.path.is_ident("compare") {
         let traits;
         parenthesized!(traits in meta.input);
@@ -88,16 +173,20 @@ fn parse_archive_attributes(
             clauses,
             "deserialize_bounds",
         )
+    } else if meta.path.is_ident("bound") {
+        meta.parse_nested_meta(|nested| {
+            parse_bound_attributes(&mut attributes.bound, nested)
+        })
     } else if meta.path.is_ident("archived") {
         try_set_attribute(
             &mut attributes.archived,
-            meta.value()?.parse()?,
+            parse_type_name(&meta)?,
             "archived",
         )
     } else if meta.path.is_ident("resolver") {
         try_set_attribute(
             &mut attributes.resolver,
-            meta.value()?.parse()?,
+            parse_type_name(&meta)?,
             "resolver",
         )
     } else if meta.path.is_ident("as") {
@@ -120,6 +209,46 @@ fn parse_archive_attributes(
     }
 }
 
+/// Returns the range of values that can be represented by an integer repr.
+pub fn int_repr_range(repr: &Ident) -> Option<(i128, i128)> {
+    Some(match repr.to_string().as_str() {
+        "u8" => (0, u8::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "u64" => (0, u64::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        _ => return None,
+    })
+}
+
+fn parse_bound_attributes(
+    bound: &mut Bound,
+    meta: ParseNestedMeta<'_>,
+) -> Result<(), Error> {
+    let (attribute, name) = if meta.path.is_ident("archive") {
+        (&mut bound.archive, "bound(archive)")
+    } else if meta.path.is_ident("serialize") {
+        (&mut bound.serialize, "bound(serialize)")
+    } else if meta.path.is_ident("deserialize") {
+        (&mut bound.deserialize, "bound(deserialize)")
+    } else if meta.path.is_ident("check") {
+        (&mut bound.check, "bound(check)")
+    } else {
+        return Err(meta.error(
+            "unrecognized bound argument, supported bounds are archive, \
+             serialize, deserialize, and check",
+        ));
+    };
+
+    let clauses = meta.value()?.parse::<LitStr>()?.parse_with(
+        Punctuated::<WherePredicate, Token![,]>::parse_terminated,
+    )?;
+    try_set_attribute(attribute, clauses, name)
+}
+
 pub fn parse_attributes(input: &DeriveInput) -> Result<Attributes, Error> {
     let mut result = Attributes::default();
     for attr in input.attrs.iter() {
@@ -141,5 +270,132 @@ pub fn parse_attributes(input: &DeriveInput) -> Result<Attributes, Error> {
         }
     }
 
+    for field in fields(&input.data) {
+        let align = parse_field_attributes(field)?;
+        if let (Some(align), false) =
+            (align, matches!(input.data, Data::Struct(_)))
+        {
+            return Err(Error::new_spanned(
+                align,
+                "align may only be used on the fields of structs",
+            ));
+        }
+    }
+
     Ok(result)
 }
+
+fn fields(data: &Data) -> Box<dyn '_ + Iterator<Item = &Field>> {
+    match data {
+        Data::Struct(data) => Box::new(data.fields.iter()),
+        Data::Enum(data) => {
+            Box::new(data.variants.iter().flat_map(|v| v.fields.iter()))
+        }
+        Data::Union(data) => Box::new(data.fields.named.iter()),
+    }
+}
+
+/// The alignments that can be given to a field with `align = ...`, which are
+/// the alignments of the `AlignN` types in `rkyv::util`.
+const FIELD_ALIGNS: [usize; 13] =
+    [1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096];
+
+/// Parses the attributes of a field, returning the alignment given with
+/// `align = ...` if any.
+fn parse_field_attributes(field: &Field) -> Result<Option<LitInt>, Error> {
+    let mut skip = None;
+    let mut rename = None;
+    let mut with = None;
+    let mut align = None;
+    for attr in field.attrs.iter() {
+        if attr.path().is_ident("archive") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    try_set_attribute(&mut skip, meta.path, "skip")
+                } else if meta.path.is_ident("rename") {
+                    let name = meta.value()?.parse::<LitStr>()?;
+                    name.parse::<Ident>()?;
+                    try_set_attribute(&mut rename, name, "rename")
+                } else if meta.path.is_ident("with") {
+                    let wrapper = meta.value()?.parse::<LitStr>()?;
+                    wrapper.parse::<Type>()?;
+                    try_set_attribute(&mut with, wrapper, "with")
+                } else if meta.path.is_ident("align") {
+                    let value = meta.value()?.parse::<LitInt>()?;
+                    if !FIELD_ALIGNS.contains(&value.base10_parse()?) {
+                        return Err(Error::new_spanned(
+                            value,
+                            "align must be a power of two between 1 and 4096",
+                        ));
+                    }
+                    try_set_attribute(&mut align, value, "align")
+                } else {
+                    Err(Error::new_spanned(
+                        meta.path,
+                        "unrecognized field archive argument, supported field \
+                         arguments are skip, rename, with, and align",
+                    ))
+                }
+            })?;
+        }
+    }
+
+    if let Some(rename) = rename {
+        if field.ident.is_none() {
+            return Err(Error::new_spanned(
+                rename,
+                "rename may only be used on named fields",
+            ));
+        }
+        if skip.is_some() {
+            return Err(Error::new_spanned(
+                rename,
+                "rename may not be used on skipped fields because they are \
+                 not archived",
+            ));
+        }
+    }
+
+    if let Some(ref align) = align {
+        if field.ident.is_none() {
+            return Err(Error::new_spanned(
+                align,
+                "align may only be used on named fields",
+            ));
+        }
+        if skip.is_some() {
+            return Err(Error::new_spanned(
+                align,
+                "align may not be used on skipped fields because they are not \
+                 archived",
+            ));
+        }
+    }
+
+    // Skipped fields aren't archived, so attributes that change how a field is
+    // archived can't be applied to them
+    if skip.is_some() {
+        if let Some(with) = with {
+            return Err(Error::new_spanned(
+                with,
+                "with may not be used on skipped fields because they are not \
+                 archived",
+            ));
+        }
+        for attr in field.attrs.iter() {
+            let path = attr.path();
+            if path.is_ident("with") || path.is_ident("archive_attr") {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!(
+                        "#[{}] may not be used on skipped fields because they \
+                         are not archived",
+                        path.to_token_stream(),
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(align)
+}
@@ -0,0 +1,269 @@
+use crate::attributes::Attributes;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, punctuated::Punctuated, Data, DeriveInput, Error, Path, Token,
+    WhereClause, WherePredicate,
+};
+
+/// Checks that `#[archive(copy)]` can be used on the given type.
+fn check_copy(
+    input: &DeriveInput,
+    attributes: &Attributes,
+) -> Result<(), Error> {
+    let copy = attributes.copy.as_ref().unwrap();
+
+    let data = match input.data {
+        Data::Struct(ref data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                copy,
+                "copy may only be used on structs",
+            ))
+        }
+    };
+
+    // The archived type is the type itself, so arguments that customize the
+    // generated archived type can't be used
+    let incompatible: [(&str, Option<&dyn ToTokens>); 11] = [
+        (
+            "as = \"...\"",
+            attributes.archive_as.as_ref().map(|x| x as _),
+        ),
+        (
+            "archived = \"...\"",
+            attributes.archived.as_ref().map(|x| x as _),
+        ),
+        (
+            "resolver = \"...\"",
+            attributes.resolver.as_ref().map(|x| x as _),
+        ),
+        (
+            "archive_attr(...)",
+            attributes.attrs.first().map(|x| x as _),
+        ),
+        (
+            "check_bytes",
+            attributes.check_bytes.as_ref().map(|x| x as _),
+        ),
+        ("compare(...)", attributes.compares.as_ref().map(|x| x as _)),
+        ("pin_fields", attributes.pin_fields.as_ref().map(|x| x as _)),
+        ("repr(...)", attributes.repr.as_ref().map(|x| x as _)),
+        (
+            "minimize_padding",
+            attributes.minimize_padding.as_ref().map(|x| x as _),
+        ),
+        ("diff", attributes.diff.as_ref().map(|x| x as _)),
+        (
+            "swap_endian",
+            attributes.swap_endian.as_ref().map(|x| x as _),
+        ),
+    ];
+    for (name, tokens) in incompatible {
+        if let Some(tokens) = tokens {
+            return Err(Error::new_spanned(
+                tokens,
+                format!(
+                    "{} may not be used with copy because no archived type is \
+                     generated",
+                    name,
+                ),
+            ));
+        }
+    }
+
+    // Every field is archived as itself, so field attributes that change how
+    // fields are archived can't be used
+    for field in data.fields.iter() {
+        for attr in field.attrs.iter() {
+            let path = attr.path();
+            if path.is_ident("with")
+                || path.is_ident("archive")
+                || path.is_ident("archive_attr")
+            {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!(
+                        "#[{}] may not be used on the fields of a copy type",
+                        path.to_token_stream(),
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the where clause for a copy type with the given additional bounds.
+///
+/// Each field must archive as itself, which rules out fields that contain
+/// relative pointers or have a different archived layout.
+fn copy_where_clause(
+    input: &DeriveInput,
+    rkyv_path: &Path,
+    bounds: &[&Option<Punctuated<WherePredicate, Token![,]>>],
+) -> WhereClause {
+    let mut where_clause = input
+        .generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| parse_quote! { where });
+
+    for bound in bounds.iter().copied().flatten().flatten() {
+        where_clause.predicates.push(bound.clone());
+    }
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    where_clause
+        .predicates
+        .push(parse_quote! { #name #ty_generics: ::core::marker::Copy });
+    if let Data::Struct(ref data) = input.data {
+        for field in data.fields.iter() {
+            let ty = &field.ty;
+            where_clause.predicates.push(parse_quote! {
+                #ty: #rkyv_path::Archive<Archived = #ty>
+            });
+        }
+    }
+
+    where_clause
+}
+
+pub fn derive_archive_copy(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    check_copy(input, attributes)?;
+
+    let where_clause = copy_where_clause(
+        input,
+        rkyv_path,
+        &[&attributes.archive_bounds, &attributes.bound.archive],
+    );
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+
+    // Every field archives as itself, so the type can be copy optimized as
+    // long as all of its fields can be and it has no padding between them
+    let field_tys = match input.data {
+        Data::Struct(ref data) => {
+            data.fields.iter().map(|f| &f.ty).collect::<Vec<_>>()
+        }
+        _ => unreachable!(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::Archive for #name #ty_generics
+        #where_clause
+        {
+            const COPY_OPTIMIZATION: #rkyv_path::CopyOptimization<Self> =
+                unsafe {
+                    #rkyv_path::CopyOptimization::enable_if(
+                        0 #(+ ::core::mem::size_of::<#field_tys>())*
+                            == ::core::mem::size_of::<Self>()
+                        #(&& <#field_tys as #rkyv_path::Archive>
+                            ::COPY_OPTIMIZATION
+                            .is_enabled())*
+                    )
+                };
+
+            type Archived = Self;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                _: usize,
+                _: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                out.write(*self);
+            }
+        }
+    })
+}
+
+pub fn derive_serialize_copy(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let where_clause = copy_where_clause(
+        input,
+        rkyv_path,
+        &[
+            &attributes.archive_bounds,
+            &attributes.serialize_bounds,
+            &attributes.bound.archive,
+            &attributes.bound.serialize,
+        ],
+    );
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.insert(
+        0,
+        parse_quote! { __S: #rkyv_path::rancor::Fallible + ?Sized },
+    );
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::Serialize<__S> for #name #ty_generics
+        #where_clause
+        {
+            #[inline]
+            fn serialize(
+                &self,
+                _: &mut __S,
+            ) -> ::core::result::Result<Self::Resolver, __S::Error> {
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+pub fn derive_deserialize_copy(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let where_clause = copy_where_clause(
+        input,
+        rkyv_path,
+        &[
+            &attributes.archive_bounds,
+            &attributes.deserialize_bounds,
+            &attributes.bound.archive,
+            &attributes.bound.deserialize,
+        ],
+    );
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.insert(
+        0,
+        parse_quote! { __D: #rkyv_path::rancor::Fallible + ?Sized },
+    );
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::Deserialize<#name #ty_generics, __D>
+            for #name #ty_generics
+        #where_clause
+        {
+            #[inline]
+            fn deserialize(
+                &self,
+                _: &mut __D,
+            ) -> ::core::result::Result<#name #ty_generics, __D::Error> {
+                ::core::result::Result::Ok(*self)
+            }
+        }
+    })
+}
@@ -1,13 +1,15 @@
 use crate::{
     attributes::{parse_attributes, Attributes},
-    util::is_not_omitted,
+    copy::derive_deserialize_copy,
+    union::derive_deserialize_union,
+    util::{archived_field_name, is_not_omitted, is_not_skipped, is_skipped},
     with::{make_with_ty, with_inner},
 };
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
     parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
-    Error, Fields, Generics, Ident, Index,
+    Error, Field, Fields, Generics, Ident, Index, WherePredicate,
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -21,6 +23,12 @@ fn derive_deserialize_impl(
 ) -> Result<TokenStream, Error> {
     let default_rkyv_path = parse_quote! { ::rkyv };
     let rkyv_path = attributes.rkyv_path.as_ref().unwrap_or(&default_rkyv_path);
+    if attributes.copy.is_some() {
+        return derive_deserialize_copy(&input, attributes, rkyv_path);
+    }
+    if attributes.unsafe_union.is_some() {
+        return derive_deserialize_union(&input, attributes, rkyv_path);
+    }
     let with_ty = make_with_ty(rkyv_path);
 
     let where_clause = input.generics.make_where_clause();
@@ -34,6 +42,29 @@ fn derive_deserialize_impl(
             where_clause.predicates.push(bound.clone());
         }
     }
+    for bounds in [&attributes.bound.archive, &attributes.bound.deserialize] {
+        for bound in bounds.iter().flatten() {
+            where_clause.predicates.push(bound.clone());
+        }
+    }
+    // Field bounds are only inferred when they aren't replaced with
+    // `bound(deserialize = "...")`
+    let infer_deserialize_bounds = |f: &&Field| {
+        attributes.bound.deserialize.is_none() && is_not_omitted(f)
+    };
+    // Skipped fields are deserialized with their default value
+    let field_bounds = |field: &Field| -> Result<Vec<WherePredicate>, Error> {
+        if is_skipped(field) {
+            let ty = &field.ty;
+            Ok(vec![parse_quote! { #ty: ::core::default::Default }])
+        } else {
+            let ty = with_ty(field)?;
+            Ok(vec![
+                parse_quote! { #ty: #rkyv_path::Archive },
+                parse_quote! { #rkyv_path::Archived<#ty>: #rkyv_path::Deserialize<#ty, __D> },
+            ])
+        }
+    };
 
     let mut impl_input_params = Punctuated::default();
     impl_input_params
@@ -57,24 +88,24 @@ fn derive_deserialize_impl(
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
                 let mut deserialize_where = where_clause.clone();
-                for field in fields.named.iter().filter(is_not_omitted) {
-                    let ty = with_ty(field)?;
-                    deserialize_where
-                        .predicates
-                        .push(parse_quote! { #ty: #rkyv_path::Archive });
-                    deserialize_where.predicates.push(
-                        parse_quote! { #rkyv_path::Archived<#ty>: #rkyv_path::Deserialize<#ty, __D> },
-                    );
+                for field in
+                    fields.named.iter().filter(infer_deserialize_bounds)
+                {
+                    deserialize_where.predicates.extend(field_bounds(field)?);
                 }
 
                 let deserialize_fields = fields.named.iter().map(|f| {
                     let name = &f.ident;
+                    if is_skipped(f) {
+                        return quote! { #name: ::core::default::Default::default() };
+                    }
                     let ty = with_ty(f).unwrap();
+                    let archived_name = archived_field_name(f);
                     let value = with_inner(
                         f,
                         parse_quote! {
                             #rkyv_path::Deserialize::<#ty, __D>::deserialize(
-                                &self.#name,
+                                &self.#archived_name,
                                 deserializer,
                             )?
                         },
@@ -96,32 +127,34 @@ fn derive_deserialize_impl(
             }
             Fields::Unnamed(ref fields) => {
                 let mut deserialize_where = where_clause.clone();
-                for field in fields.unnamed.iter().filter(is_not_omitted) {
-                    let ty = with_ty(field)?;
-                    deserialize_where
-                        .predicates
-                        .push(parse_quote! { #ty: #rkyv_path::Archive });
-                    deserialize_where.predicates.push(
-                        parse_quote! { #rkyv_path::Archived<#ty>: #rkyv_path::Deserialize<#ty, __D> },
-                    );
+                for field in
+                    fields.unnamed.iter().filter(infer_deserialize_bounds)
+                {
+                    deserialize_where.predicates.extend(field_bounds(field)?);
                 }
 
-                let deserialize_fields =
-                    fields.unnamed.iter().enumerate().map(|(i, f)| {
-                        let index = Index::from(i);
-                        let ty = with_ty(f).unwrap();
-                        let value = with_inner(
-                            f,
-                            parse_quote! {
-                                #rkyv_path::Deserialize::<#ty, __D>::deserialize(
-                                    &self.#index,
-                                    deserializer,
-                                )?
-                            },
-                        )
-                        .unwrap();
-                        quote! { #value }
-                    });
+                // Skipped fields aren't archived, so archived fields are
+                // indexed separately
+                let mut archived_index = 0;
+                let deserialize_fields = fields.unnamed.iter().map(|f| {
+                    if is_skipped(f) {
+                        return quote! { ::core::default::Default::default() };
+                    }
+                    let index = Index::from(archived_index);
+                    archived_index += 1;
+                    let ty = with_ty(f).unwrap();
+                    let value = with_inner(
+                        f,
+                        parse_quote! {
+                            #rkyv_path::Deserialize::<#ty, __D>::deserialize(
+                                &self.#index,
+                                deserializer,
+                            )?
+                        },
+                    )
+                    .unwrap();
+                    quote! { #value }
+                });
 
                 quote! {
                     impl #impl_generics #rkyv_path::Deserialize<#name #ty_generics, __D> for #rkyv_path::Archived<#name #ty_generics> #deserialize_where {
@@ -148,28 +181,23 @@ fn derive_deserialize_impl(
             for variant in data.variants.iter() {
                 match variant.fields {
                     Fields::Named(ref fields) => {
-                        for field in fields.named.iter().filter(is_not_omitted)
+                        for field in
+                            fields.named.iter().filter(infer_deserialize_bounds)
                         {
-                            let ty = with_ty(field)?;
-                            deserialize_where.predicates.push(
-                                parse_quote! { #ty: #rkyv_path::Archive },
-                            );
                             deserialize_where
                                 .predicates
-                                .push(parse_quote! { #rkyv_path::Archived<#ty>: #rkyv_path::Deserialize<#ty, __D> });
+                                .extend(field_bounds(field)?);
                         }
                     }
                     Fields::Unnamed(ref fields) => {
-                        for field in
-                            fields.unnamed.iter().filter(is_not_omitted)
+                        for field in fields
+                            .unnamed
+                            .iter()
+                            .filter(infer_deserialize_bounds)
                         {
-                            let ty = with_ty(field)?;
-                            deserialize_where.predicates.push(
-                                parse_quote! { #ty: #rkyv_path::Archive },
-                            );
                             deserialize_where
                                 .predicates
-                                .push(parse_quote! { #rkyv_path::Archived<#ty>: #rkyv_path::Deserialize<#ty, __D> });
+                                .extend(field_bounds(field)?);
                         }
                     }
                     Fields::Unit => (),
@@ -180,12 +208,20 @@ fn derive_deserialize_impl(
                 let variant = &v.ident;
                 match v.fields {
                     Fields::Named(ref fields) => {
-                        let bindings = fields.named.iter().map(|f| {
+                        let bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                             let name = &f.ident;
-                            quote! { #name }
+                            let archived_name = archived_field_name(f);
+                            if archived_name == *name {
+                                quote! { #name }
+                            } else {
+                                quote! { #archived_name: #name }
+                            }
                         });
                         let fields = fields.named.iter().map(|f| {
                             let name = &f.ident;
+                            if is_skipped(f) {
+                                return quote! { #name: ::core::default::Default::default() };
+                            }
                             let ty = with_ty(f).unwrap();
                             let value = with_inner(
                                 f,
@@ -204,11 +240,14 @@ fn derive_deserialize_impl(
                         }
                     }
                     Fields::Unnamed(ref fields) => {
-                        let bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                        let bindings = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                             let name = Ident::new(&format!("_{}", i), f.span());
                             quote! { #name }
                         });
                         let fields = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                            if is_skipped(f) {
+                                return quote! { ::core::default::Default::default() };
+                            }
                             let binding = Ident::new(&format!("_{}", i), f.span());
                             let ty = with_ty(f).unwrap();
                             let value = with_inner(
@@ -247,7 +286,8 @@ fn derive_deserialize_impl(
         Data::Union(_) => {
             return Err(Error::new_spanned(
                 input,
-                "Deserialize cannot be derived for unions",
+                "Deserialize cannot be derived for unions without \
+                 #[archive(unsafe_union)]",
             ))
         }
     };
@@ -0,0 +1,211 @@
+use crate::{
+    attributes::Attributes,
+    util::{archived_field_name, is_not_omitted, is_not_skipped, strip_raw},
+    with::make_with_ty,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, spanned::Spanned, Data, DeriveInput, Error, Field, Fields,
+    Ident, LitStr, Path, WhereClause,
+};
+
+/// Returns the where clause for the `DiffFields` impl.
+///
+/// The archived type of each field must be comparable with itself.
+fn diff_where_clause(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<WhereClause, Error> {
+    let with_ty = make_with_ty(rkyv_path);
+    // The archive bounds were already added to the where clause of the input
+    let mut where_clause = input
+        .generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| parse_quote! { where });
+
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => {
+            data.variants.iter().flat_map(|v| v.fields.iter()).collect()
+        }
+        Data::Union(_) => Vec::new(),
+    };
+    for field in fields
+        .into_iter()
+        .filter(is_not_omitted)
+        .filter(is_not_skipped)
+    {
+        let ty = with_ty(field)?;
+        if attributes.bound.archive.is_none() {
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: #rkyv_path::Archive });
+        }
+        where_clause.predicates.push(parse_quote! {
+            #rkyv_path::Archived<#ty>: ::core::cmp::PartialEq
+        });
+    }
+
+    Ok(where_clause)
+}
+
+/// Returns the bindings and field names for the archived fields of a struct or
+/// enum variant, along with a pattern that binds them.
+pub fn field_bindings(
+    fields: &Fields,
+    prefix: &str,
+) -> (Vec<Ident>, Vec<String>, TokenStream) {
+    match fields {
+        Fields::Named(ref fields) => {
+            let names = fields
+                .named
+                .iter()
+                .filter(is_not_skipped)
+                .map(|f| archived_field_name(f).unwrap())
+                .collect::<Vec<_>>();
+            let bindings = names
+                .iter()
+                .map(|name| {
+                    Ident::new(
+                        &format!("{}_{}", prefix, strip_raw(name)),
+                        name.span(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let pattern = quote! { { #(#names: #bindings,)* } };
+            let names = names.iter().map(strip_raw).collect();
+            (bindings, names, pattern)
+        }
+        Fields::Unnamed(ref fields) => {
+            let fields = fields
+                .unnamed
+                .iter()
+                .filter(is_not_skipped)
+                .collect::<Vec<_>>();
+            let bindings = fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| {
+                    Ident::new(&format!("{}_{}", prefix, i), f.span())
+                })
+                .collect::<Vec<_>>();
+            let pattern = quote! { ( #(#bindings,)* ) };
+            let names = (0..fields.len()).map(|i| i.to_string()).collect();
+            (bindings, names, pattern)
+        }
+        Fields::Unit => (Vec::new(), Vec::new(), TokenStream::new()),
+    }
+}
+
+pub fn derive_diff_fields(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    archived_name: &Ident,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let diff = attributes.diff.as_ref().unwrap();
+    if attributes.archive_as.is_some() {
+        return Err(Error::new_spanned(
+            diff,
+            "diff may not be used with as = \"...\"",
+        ));
+    }
+
+    let where_clause = diff_where_clause(input, attributes, rkyv_path)?;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let address_range = quote! { #rkyv_path::diff::address_range };
+
+    let report_fields = |self_bindings: &[Ident],
+                         other_bindings: &[Ident],
+                         names: &[String]| {
+        let names = names.iter().map(|name| LitStr::new(name, diff.span()));
+        quote! {
+            #(
+                if #self_bindings != #other_bindings {
+                    report(
+                        ::core::option::Option::Some(#names),
+                        #address_range(#self_bindings),
+                        #address_range(#other_bindings),
+                    );
+                }
+            )*
+        }
+    };
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let (self_bindings, names, pattern) =
+                field_bindings(&data.fields, "self");
+            let (other_bindings, _, other_pattern) =
+                field_bindings(&data.fields, "other");
+            let reports =
+                report_fields(&self_bindings, &other_bindings, &names);
+            quote! {
+                let Self #pattern = self;
+                let Self #other_pattern = other;
+                #reports
+            }
+        }
+        Data::Enum(ref data) => {
+            let arms = data.variants.iter().map(|v| {
+                let variant = &v.ident;
+                let (self_bindings, names, pattern) =
+                    field_bindings(&v.fields, "self");
+                let (other_bindings, _, other_pattern) =
+                    field_bindings(&v.fields, "other");
+                let names = names
+                    .iter()
+                    .map(|name| format!("{}::{}", strip_raw(variant), name))
+                    .collect::<Vec<_>>();
+                let reports =
+                    report_fields(&self_bindings, &other_bindings, &names);
+                quote! {
+                    (Self::#variant #pattern, Self::#variant #other_pattern) => {
+                        #reports
+                    }
+                }
+            });
+            quote! {
+                match (self, other) {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => report(
+                        ::core::option::Option::None,
+                        #address_range(self),
+                        #address_range(other),
+                    ),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "DiffFields cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::diff::DiffFields
+            for #archived_name #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn diff_fields(
+                &self,
+                other: &Self,
+                report: &mut dyn FnMut(
+                    ::core::option::Option<&'static str>,
+                    ::core::ops::Range<usize>,
+                    ::core::ops::Range<usize>,
+                ),
+            ) {
+                #body
+            }
+        }
+    })
+}
@@ -8,9 +8,13 @@
 
 mod archive;
 mod attributes;
+mod copy;
 mod deserialize;
+mod diff;
 mod serde;
 mod serialize;
+mod swap_endian;
+mod union;
 mod util;
 mod with;
 
@@ -30,31 +34,140 @@ use syn::{parse_macro_input, DeriveInput};
 /// - `archived = "..."`: Changes the name of the generated archived type to the given value. By
 ///   default, archived types are named "Archived" + `the name of the type`.
 /// - `resolver = "..."`: Changes the name of the generated resolver type to the given value. By
-///   default, resolver types are named `the name of the type` + "Resolver".
-/// - `repr(...)`: *Deprecated, use `#[archive_attr(repr(...))]` instead.* Sets the representation
-///   for the archived type to the given representation. Available representation options may vary
-///   depending on features and type layout.
+///   default, resolver types are named `the name of the type` + "Resolver". See
+///   [Resolvers](#resolvers) for how they are used.
+/// - `repr(...)`: Sets the type of the tag of an archived enum. Supported reprs are `u8`, `u16`,
+///   `u32`, `u64`, `i8`, `i16`, `i32`, and `i64`. By default, the tag is the smallest unsigned
+///   integer that fits every discriminant. The `#[repr(...)]` of the enum itself does not change
+///   the archived tag. Explicit discriminants (e.g. `A = 10`) are preserved in the archived enum,
+///   and must be integer literals.
+/// - `repr(C)`: Makes the generated archived struct `#[repr(C)]`, so its fields are laid out in
+///   declaration order with the padding of the platform C ABI. This is already the case when the
+///   `strict` feature is enabled. The layout is only fully specified if the archived type of
+///   every field has a specified layout too, like primitives or other `repr(C)` types. This can't
+///   be checked by the derive, so it is up to the user to make sure of it for FFI. Only supported
+///   on structs, and not compatible with `as = "..."`. Use `#[archive_attr(repr(...))]` for other
+///   representations.
 /// - `compare(...)`: Implements common comparison operators between the original and archived
 ///   types. Supported comparisons are `PartialEq` and `PartialOrd` (i.e.
-///   `#[archive(compare(PartialEq, PartialOrd))]`).
-/// - `bound(...)`: Adds additional bounds to trait implementations. This can be especially useful
-///   when dealing with recursive structures, where bounds may need to be omitted to prevent
-///   recursive type definitions. Use `archive = "..."` to specify `Archive` bounds,
-///   `serialize = "..."` to specify `Serialize` bounds, and `deserialize = "..."` to specify
-///   `Deserialize` bounds.
+///   `#[archive(compare(PartialEq, PartialOrd))]`). The comparisons are implemented in both
+///   directions and compare field by field, so each archived field type must be comparable with
+///   its original type. Enums compare equal only when their variants match. `PartialOrd` orders
+///   lexicographically by field in declaration order, and enums by variant index and then by
+///   their fields. This matches the derived `PartialOrd` and `Ord` of the original type, so the
+///   ordering of the original type must be derived (or equivalent) for archived and original
+///   values to be ordered consistently.
+/// - `archive_bounds(...)`, `serialize_bounds(...)`, `deserialize_bounds(...)`: Adds additional
+///   bounds to the `Archive`, `Serialize`, and `Deserialize` implementations respectively. The
+///   serializer and deserializer generics are named `__S` and `__D`.
+/// - `bound(...)`: Replaces the bounds that are inferred from the field types with the given ones.
+///   Use `archive = "..."` to specify `Archive` bounds, `serialize = "..."` to specify `Serialize`
+///   bounds, `deserialize = "..."` to specify `Deserialize` bounds, and `check = "..."` to
+///   specify `CheckBytes` bounds (requires `check_bytes`, the context generic is named `__C`).
+///   This is especially useful for recursive structures, where the inferred bounds would be
+///   recursive, and for generic types where the inferred bounds are too strict.
 /// - `check_bytes`: Derive `CheckBytes` on the archived type, in order to enable safe
-///   deserialization. Requires `validation` feature. Not compatible with `as = "..."`. In that
-///   case, use `#[derive(CheckBytes)]` on the archived type, and include a `use rkyv::bytecheck`
-///   statement.
+///   deserialization. `CheckBytes` is only derived when the `bytecheck` feature is enabled, so
+///   the attribute can be left on types when validation is disabled. Not compatible with
+///   `as = "..."`. In that case, use `#[derive(CheckBytes)]` on the archived type, and include a
+///   `use rkyv::bytecheck` statement.
+/// - `copy`: Archives a `Copy` struct as itself, so that archiving it is a plain byte copy and
+///   `Archived<T>` is `T`. Every field must archive as itself (e.g. `u8`, `bool`, or another
+///   `copy` type), which rules out fields that contain relative pointers like `String` or `Box`.
+///   Only supported on structs, and not compatible with arguments or field attributes that
+///   change the archived type. Derive `CheckBytes` on the type itself to validate it. If the
+///   struct has no padding bytes, slices of it are serialized with a single copy.
 /// - `copy_safe`: States that the archived type is tightly packed with no padding bytes. This
 ///   qualifies it for copy optimizations. (requires nightly)
+/// - `pin_fields`: Generates a `<field>_pin` method on the archived type for each named field,
+///   which projects a `Pin<&mut ArchivedStruct>` to a `Pin<&mut Archived<Field>>`. This allows
+///   in-place mutation of archived data (e.g. through `access_unchecked_mut`) without
+///   hand-writing pin projections. Only supported on structs with named fields, and not
+///   compatible with `as = "..."`.
+/// - `minimize_padding`: Lays out the fields of the archived type in order of descending
+///   alignment instead of declaration order, which removes the padding between fields like a `u8`
+///   followed by a `u64`. The fields keep their names and the original type keeps its field
+///   order, only the archived layout changes. Alignments are only known for primitives with a
+///   fixed size (e.g. `u8`, `u64`, `f32`, `char`) and arrays of them. If every field has a known
+///   alignment, the archived type is `#[repr(C)]` so the layout is stable. This changes the
+///   serialized format of the type, so archives written with and without it are not compatible.
+///   If any field has an unknown alignment, the fields keep their declaration order and the
+///   archived type keeps the representation it would have without `minimize_padding`. Only
+///   supported on structs with named fields, and not compatible with `as = "..."`.
+/// - `diff`: Implements `rkyv::diff::DiffFields` for the archived type, which compares two archived
+///   values field by field with `PartialEq` and reports the name and byte range of each field that
+///   differs. The archived type of each field must implement `PartialEq`. Enums compare the fields
+///   of matching variants, and report the whole value when the variants differ. The reported
+///   ranges only cover the inline bytes of each field, so fields like `String` and `Vec` don't
+///   include the data they point to. Not compatible with `as = "..."`.
+/// - `swap_endian`: Implements `rkyv::endian::SwapEndian` for the archived type, which reports the
+///   byte range of each multi-byte scalar so that archives can be converted between byte orders.
+///   The archived type of each field must implement `SwapEndian`. Enums also report their tag if
+///   it is larger than one byte. Not compatible with `as = "..."`.
+/// - `unsafe_union`: Archives a `#[repr(C)]` union as a copy of its raw bytes, and generates an
+///   archived union with the same fields and layout. Every field must be `Copy` and `Unpin`, and
+///   may not contain pointers (including relative pointers like those in `String` or `Box`).
+///   **This is unsafe:** all of the bytes of the union are copied, so the union must implement
+///   `rkyv::FullyInitialized` to assert that every byte is initialized when it's serialized, and
+///   every bit pattern must be valid for every field because the archived union's `CheckBytes`
+///   impl (with `check_bytes`) can only check its size and alignment. The fields keep their native
+///   types instead of being archived, so deriving fails with a compile error on targets whose byte
+///   order differs from the byte order of the archive. Not compatible with `as = "..."`,
+///   `resolver = "..."`, `compare(...)`, or `copy_safe`.
 /// - `as = "..."`: Instead of generating a separate archived type, this type will archive as the
 ///   named type. This is useful for types which are generic over their parameters.
 /// - `crate = "..."`: Chooses an alternative crate path to import rkyv from.
 ///
 /// `#[archive_attr(...)]` adds the attributes passed as arguments as attributes to the generated
 /// type. This is commonly used with attributes like `derive(...)` to derive trait implementations
-/// for the archived type.
+/// for the archived type. The built-in archived types hash the same as the types they archive, so
+/// `#[archive_attr(derive(Hash))]` on a struct hashes the same as `#[derive(Hash)]` on the original
+/// struct. This does not hold for enums, which hash their discriminants with different types.
+///
+/// # Resolvers
+///
+/// Archiving a value is split into two steps. `serialize` writes the dependencies of each field
+/// (like the bytes of a `String`) and returns a resolver that holds their positions, and then
+/// `resolve` uses the resolver to write the archived value itself. The generated resolver type
+/// has the same shape as the original type, with each field replaced by the resolver of that
+/// field (`Resolver<T>`), and its fields have the same visibility as the original fields. This
+/// allows resolvers to be built manually for custom serialization flows, for example to serialize
+/// some fields differently and then resolve the whole value with `WriterExt::resolve_aligned`.
+///
+/// # Skipping fields
+///
+/// Adding `#[archive(skip)]` to a field omits it from the archived type entirely. Skipped fields
+/// are not serialized, and are reconstructed with `Default::default()` when deserializing, so their
+/// type must implement `Default` to derive `Deserialize`. Because skipped fields are not archived,
+/// they may not be used with `#[with(...)]` or `#[archive_attr(...)]`. Skipping works on fields of
+/// structs, tuple structs, and enum variants.
+///
+/// # Renaming fields
+///
+/// Adding `#[archive(rename = "...")]` to a named field changes the name of that field in the
+/// archived type, for example to avoid colliding with the methods generated by `pin_fields`. The
+/// original field is still archived into and deserialized from the renamed field, and the layout
+/// of the archived type is unchanged. To rename the archived type itself, use `archived = "..."`.
+///
+/// # Aligning fields
+///
+/// Adding `#[archive(align = N)]` to a named field of a struct pads the archived type so that the
+/// field starts at an offset that is a multiple of `N`, and raises the alignment of the archived
+/// type to at least `N`. This is useful for fields that are reinterpreted as types with a larger
+/// alignment, like a `[u8; 64]` that holds SIMD vectors. `N` must be a power of two between 1 and
+/// 4096. The archived type is always `#[repr(C)]` when a field is aligned, and validation checks
+/// the alignment of the archived type like for any other type. Adding or changing an alignment
+/// changes the serialized format of the type.
+///
+/// # Enum variant accessors
+///
+/// Archived enums are regular Rust enums with the same variants as the original, so they can be
+/// matched on directly. For each variant `Variant`, the archived enum also has an `is_variant`
+/// method that returns whether the value is that variant, and variants with fields have an
+/// `as_variant` method that returns a reference to the archived field (or a tuple of references to
+/// the archived fields in declaration order) if the value is that variant. Variant names are
+/// converted to snake case, so `HttpError` gets `is_http_error` and `as_http_error`. These methods
+/// are not generated when using `as = "..."`.
 ///
 /// # Recursive types
 ///
@@ -72,6 +185,8 @@ use syn::{parse_macro_input, DeriveInput};
 /// reference as if it were a field of the struct. Wrappers can be applied to fields using the
 /// `#[with(...)]` attribute. Multiple wrappers can be used, and they are applied in reverse order
 /// (i.e. `#[with(A, B, C)]` will archive `MyType` as `With<With<With<MyType, C>, B, A>`).
+/// A single wrapper can also be given with `#[archive(with = "...")]`, which behaves the same as
+/// `#[with(...)]` and can be combined with other field arguments like `rename`.
 #[proc_macro_derive(
     Archive,
     attributes(archive, archive_attr, omit_bounds, with)
@@ -1,13 +1,15 @@
 use crate::{
     attributes::{parse_attributes, Attributes},
-    util::{is_not_omitted, strip_raw},
+    copy::derive_serialize_copy,
+    union::derive_serialize_union,
+    util::{is_not_omitted, is_not_skipped, is_skipped, strip_raw},
     with::{make_with_cast, make_with_ty},
 };
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
     parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
-    Error, Fields, Generics, Ident, Index,
+    Error, Field, Fields, Generics, Ident, Index,
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -21,6 +23,12 @@ fn derive_serialize_impl(
 ) -> Result<TokenStream, Error> {
     let default_rkyv_path = parse_quote! { ::rkyv };
     let rkyv_path = attributes.rkyv_path.as_ref().unwrap_or(&default_rkyv_path);
+    if attributes.copy.is_some() {
+        return derive_serialize_copy(&input, attributes, rkyv_path);
+    }
+    if attributes.unsafe_union.is_some() {
+        return derive_serialize_union(&input, attributes, rkyv_path);
+    }
     let with_ty = make_with_ty(rkyv_path);
     let with_cast = make_with_cast(rkyv_path);
 
@@ -35,6 +43,18 @@ fn derive_serialize_impl(
             where_clause.predicates.push(bound.clone());
         }
     }
+    for bounds in [&attributes.bound.archive, &attributes.bound.serialize] {
+        for bound in bounds.iter().flatten() {
+            where_clause.predicates.push(bound.clone());
+        }
+    }
+    // Field bounds are only inferred when they aren't replaced with
+    // `bound(serialize = "...")`
+    let infer_serialize_bounds = |f: &&Field| {
+        attributes.bound.serialize.is_none()
+            && is_not_omitted(f)
+            && is_not_skipped(f)
+    };
 
     let mut impl_input_params = Punctuated::default();
     impl_input_params
@@ -63,14 +83,15 @@ fn derive_serialize_impl(
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
                 let mut serialize_where = where_clause.clone();
-                for field in fields.named.iter().filter(is_not_omitted) {
+                for field in fields.named.iter().filter(infer_serialize_bounds)
+                {
                     let ty = with_ty(field)?;
                     serialize_where
                         .predicates
                         .push(parse_quote! { #ty: #rkyv_path::Serialize<__S> });
                 }
 
-                let resolver_values = fields.named.iter().map(|f| {
+                let resolver_values = fields.named.iter().filter(is_not_skipped).map(|f| {
                     let name = &f.ident;
                     let field = with_cast(f, parse_quote! { &self.#name }).unwrap();
                     quote! { #name: #rkyv_path::Serialize::<__S>::serialize(#field, serializer)? }
@@ -89,14 +110,16 @@ fn derive_serialize_impl(
             }
             Fields::Unnamed(ref fields) => {
                 let mut serialize_where = where_clause.clone();
-                for field in fields.unnamed.iter().filter(is_not_omitted) {
+                for field in
+                    fields.unnamed.iter().filter(infer_serialize_bounds)
+                {
                     let ty = with_ty(field)?;
                     serialize_where
                         .predicates
                         .push(parse_quote! { #ty: #rkyv_path::Serialize<__S> });
                 }
 
-                let resolver_values = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                let resolver_values = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                     let index = Index::from(i);
                     let field = with_cast(f, parse_quote! { &self.#index }).unwrap();
                     quote! { #rkyv_path::Serialize::<__S>::serialize(#field, serializer)? }
@@ -129,7 +152,8 @@ fn derive_serialize_impl(
             for variant in data.variants.iter() {
                 match variant.fields {
                     Fields::Named(ref fields) => {
-                        for field in fields.named.iter().filter(is_not_omitted)
+                        for field in
+                            fields.named.iter().filter(infer_serialize_bounds)
                         {
                             let ty = with_ty(field)?;
                             serialize_where
@@ -139,7 +163,7 @@ fn derive_serialize_impl(
                     }
                     Fields::Unnamed(ref fields) => {
                         for field in
-                            fields.unnamed.iter().filter(is_not_omitted)
+                            fields.unnamed.iter().filter(infer_serialize_bounds)
                         {
                             let ty = with_ty(field)?;
                             serialize_where
@@ -155,11 +179,11 @@ fn derive_serialize_impl(
                 let variant = &v.ident;
                 match v.fields {
                     Fields::Named(ref fields) => {
-                        let bindings = fields.named.iter().map(|f| {
+                        let bindings = fields.named.iter().filter(is_not_skipped).map(|f| {
                             let name = &f.ident;
                             quote! { #name }
                         });
-                        let fields = fields.named.iter().map(|f| {
+                        let fields = fields.named.iter().filter(is_not_skipped).map(|f| {
                             let name = &f.ident;
                             let field = with_cast(f, parse_quote! { #name }).unwrap();
                             quote! {
@@ -167,17 +191,21 @@ fn derive_serialize_impl(
                             }
                         });
                         quote! {
-                            Self::#variant { #(#bindings,)* } => #resolver::#variant {
+                            Self::#variant { #(#bindings,)* .. } => #resolver::#variant {
                                 #(#fields,)*
                             }
                         }
                     }
                     Fields::Unnamed(ref fields) => {
                         let bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
-                            let name = Ident::new(&format!("_{}", i), f.span());
-                            quote! { #name }
+                            if is_skipped(f) {
+                                quote! { _ }
+                            } else {
+                                let name = Ident::new(&format!("_{}", i), f.span());
+                                quote! { #name }
+                            }
                         });
-                        let fields = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                        let fields = fields.unnamed.iter().enumerate().filter(|(_, f)| is_not_skipped(f)).map(|(i, f)| {
                             let binding = Ident::new(&format!("_{}", i), f.span());
                             let field = with_cast(f, parse_quote! { #binding }).unwrap();
                             quote! {
@@ -208,7 +236,8 @@ fn derive_serialize_impl(
         Data::Union(_) => {
             return Err(Error::new_spanned(
                 input,
-                "Serialize cannot be derived for unions",
+                "Serialize cannot be derived for unions without \
+                 #[archive(unsafe_union)]",
             ))
         }
     };
@@ -0,0 +1,132 @@
+use crate::{
+    archive::{enum_discriminants, enum_tag_ty},
+    attributes::Attributes,
+    diff::field_bindings,
+    util::{is_not_omitted, is_not_skipped},
+    with::make_with_ty,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Field, Ident, Path, WhereClause,
+};
+
+/// Returns the where clause for the `SwapEndian` impl.
+///
+/// The archived type of each field must implement `SwapEndian`.
+fn swap_endian_where_clause(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<WhereClause, Error> {
+    let with_ty = make_with_ty(rkyv_path);
+    // The archive bounds were already added to the where clause of the input
+    let mut where_clause = input
+        .generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| parse_quote! { where });
+
+    let fields: Vec<&Field> = match input.data {
+        Data::Struct(ref data) => data.fields.iter().collect(),
+        Data::Enum(ref data) => {
+            data.variants.iter().flat_map(|v| v.fields.iter()).collect()
+        }
+        Data::Union(_) => Vec::new(),
+    };
+    for field in fields
+        .into_iter()
+        .filter(is_not_omitted)
+        .filter(is_not_skipped)
+    {
+        let ty = with_ty(field)?;
+        if attributes.bound.archive.is_none() {
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: #rkyv_path::Archive });
+        }
+        where_clause.predicates.push(parse_quote! {
+            #rkyv_path::Archived<#ty>: #rkyv_path::endian::SwapEndian
+        });
+    }
+
+    Ok(where_clause)
+}
+
+pub fn derive_swap_endian(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    archived_name: &Ident,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let swap_endian = attributes.swap_endian.as_ref().unwrap();
+    if attributes.archive_as.is_some() {
+        return Err(Error::new_spanned(
+            swap_endian,
+            "swap_endian may not be used with as = \"...\"",
+        ));
+    }
+
+    let where_clause = swap_endian_where_clause(input, attributes, rkyv_path)?;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let swap_endian_trait = quote! { #rkyv_path::endian::SwapEndian };
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let (bindings, _, pattern) = field_bindings(&data.fields, "self");
+            quote! {
+                let Self #pattern = self;
+                #(#swap_endian_trait::scalar_ranges(#bindings, report);)*
+            }
+        }
+        Data::Enum(ref data) => {
+            let discriminants = enum_discriminants(data)?;
+            let tag_ty = enum_tag_ty(data, &discriminants, attributes)?;
+            // The tag is always at the start of the archived enum
+            let report_tag = if tag_ty == "u8" || tag_ty == "i8" {
+                None
+            } else {
+                Some(quote! {
+                    let start = self as *const Self as *const u8 as usize;
+                    report(start..start + ::core::mem::size_of::<#tag_ty>());
+                })
+            };
+            let arms = data.variants.iter().map(|v| {
+                let variant = &v.ident;
+                let (bindings, _, pattern) = field_bindings(&v.fields, "self");
+                quote! {
+                    Self::#variant #pattern => {
+                        #(#swap_endian_trait::scalar_ranges(#bindings, report);)*
+                    }
+                }
+            });
+            quote! {
+                #report_tag
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "SwapEndian cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #swap_endian_trait for #archived_name #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn scalar_ranges(
+                &self,
+                report: &mut dyn FnMut(::core::ops::Range<usize>),
+            ) {
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,418 @@
+use crate::{attributes::Attributes, util::strip_raw};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, punctuated::Punctuated, Attribute, Data, DataUnion,
+    DeriveInput, Error, GenericArgument, Ident, Path, PathArguments, Token,
+    Type, WhereClause, WherePredicate,
+};
+
+/// Returns an error if the given field type syntactically contains a pointer.
+///
+/// Pointers can't be archived as raw bytes because the addresses they hold are
+/// meaningless once the archive is written. This catches the common cases with
+/// a clear error, and the `Unpin` bound on each field rejects archived types
+/// that contain relative pointers.
+fn check_no_pointers(ty: &Type) -> Result<(), Error> {
+    let pointer_error = || {
+        Err(Error::new_spanned(
+            ty,
+            "the fields of an unsafe_union may not contain pointers",
+        ))
+    };
+    match ty {
+        Type::Ptr(_) | Type::Reference(_) | Type::BareFn(_) => pointer_error(),
+        Type::Array(array) => check_no_pointers(&array.elem),
+        Type::Group(group) => check_no_pointers(&group.elem),
+        Type::Paren(paren) => check_no_pointers(&paren.elem),
+        Type::Tuple(tuple) => {
+            tuple.elems.iter().try_for_each(check_no_pointers)
+        }
+        Type::Path(path) => {
+            for segment in path.path.segments.iter() {
+                if segment.ident == "RelPtr" || segment.ident == "RawRelPtr" {
+                    return pointer_error();
+                }
+                if let PathArguments::AngleBracketed(ref args) =
+                    segment.arguments
+                {
+                    for arg in args.args.iter() {
+                        if let GenericArgument::Type(ty) = arg {
+                            check_no_pointers(ty)?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that `#[archive(unsafe_union)]` can be used on the given type.
+fn check_union<'a>(
+    input: &'a DeriveInput,
+    attributes: &Attributes,
+) -> Result<&'a DataUnion, Error> {
+    let unsafe_union = attributes.unsafe_union.as_ref().unwrap();
+
+    let data = match input.data {
+        Data::Union(ref data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                unsafe_union,
+                "unsafe_union may only be used on unions",
+            ))
+        }
+    };
+
+    // The bytes of the union are copied into the archived union, so both must
+    // have the same defined layout
+    let is_repr_c = input.attrs.iter().any(|attr| {
+        let mut is_c = false;
+        if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                is_c |= meta.path.is_ident("C");
+                Ok(())
+            });
+        }
+        is_c
+    });
+    if !is_repr_c {
+        return Err(Error::new_spanned(
+            unsafe_union,
+            "unsafe_union requires the union to be #[repr(C)]",
+        ));
+    }
+
+    let incompatible: [(&str, Option<&dyn ToTokens>); 4] = [
+        (
+            "as = \"...\"",
+            attributes.archive_as.as_ref().map(|x| x as _),
+        ),
+        (
+            "resolver = \"...\"",
+            attributes.resolver.as_ref().map(|x| x as _),
+        ),
+        ("compare(...)", attributes.compares.as_ref().map(|x| x as _)),
+        ("copy_safe", attributes.copy_safe.as_ref().map(|x| x as _)),
+    ];
+    for (name, tokens) in incompatible {
+        if let Some(tokens) = tokens {
+            return Err(Error::new_spanned(
+                tokens,
+                format!("{} may not be used with unsafe_union", name),
+            ));
+        }
+    }
+
+    // Every field is archived as itself, so field attributes that change how
+    // fields are archived can't be used
+    for field in data.fields.named.iter() {
+        for attr in field.attrs.iter() {
+            let path = attr.path();
+            if path.is_ident("with")
+                || path.is_ident("archive")
+                || path.is_ident("archive_attr")
+            {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!(
+                        "#[{}] may not be used on the fields of an \
+                         unsafe_union",
+                        path.to_token_stream(),
+                    ),
+                ));
+            }
+        }
+        check_no_pointers(&field.ty)?;
+    }
+
+    Ok(data)
+}
+
+/// Returns the where clause for an unsafe union with the given additional
+/// bounds.
+///
+/// Each field must be plain data: `Copy` rules out fields that own resources,
+/// and `Unpin` rules out archived types that contain relative pointers. All of
+/// the bytes of the union are copied, so the user must also assert that they
+/// are always initialized by implementing `FullyInitialized`.
+fn union_where_clause(
+    input: &DeriveInput,
+    bounds: &[&Option<Punctuated<WherePredicate, Token![,]>>],
+    rkyv_path: &Path,
+) -> WhereClause {
+    let mut where_clause = input
+        .generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| parse_quote! { where });
+
+    for bound in bounds.iter().copied().flatten().flatten() {
+        where_clause.predicates.push(bound.clone());
+    }
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    where_clause.predicates.push(parse_quote! {
+        #name #ty_generics: #rkyv_path::FullyInitialized
+    });
+
+    if let Data::Union(ref data) = input.data {
+        for field in data.fields.named.iter() {
+            let ty = &field.ty;
+            where_clause.predicates.push(parse_quote! {
+                #ty: ::core::marker::Copy + ::core::marker::Unpin
+            });
+        }
+    }
+
+    where_clause
+}
+
+fn archived_union_name(input: &DeriveInput, attributes: &Attributes) -> Ident {
+    attributes.archived.clone().unwrap_or_else(|| {
+        Ident::new(
+            &format!("Archived{}", strip_raw(&input.ident)),
+            input.ident.span(),
+        )
+    })
+}
+
+pub fn derive_archive_union(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let data = check_union(input, attributes)?;
+
+    let where_clause = union_where_clause(
+        input,
+        &[&attributes.archive_bounds, &attributes.bound.archive],
+        rkyv_path,
+    );
+
+    let name = &input.ident;
+
+    // The fields keep their native types, so they are only archived correctly
+    // when the target has the same byte order as the archive
+    let endian_check = if cfg!(feature = "native_endian") {
+        None
+    } else {
+        let (endian, order) = if cfg!(feature = "big_endian") {
+            ("big", "big-endian")
+        } else {
+            ("little", "little-endian")
+        };
+        let message = format!(
+            "unsafe_union requires a {} target because archived data is {}",
+            order, order,
+        );
+        Some(quote! {
+            #[cfg(not(target_endian = #endian))]
+            ::core::compile_error!(#message);
+        })
+    };
+
+    let vis = &input.vis;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let archived_name = archived_union_name(input, attributes);
+    let archived_doc = format!("An archived [`{}`]", name);
+
+    // The archived union has the same layout as the union, so it takes the
+    // same repr
+    let reprs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"));
+    let archive_attrs = attributes
+        .attrs
+        .iter()
+        .map::<Attribute, _>(|d| parse_quote! { #[#d] });
+    let fields = data.fields.named.iter().map(|f| {
+        let field_vis = &f.vis;
+        let field_name = &f.ident;
+        let ty = &f.ty;
+        let field_doc = format!(
+            "The archived counterpart of [`{}::{}`]",
+            name,
+            strip_raw(field_name.as_ref().unwrap()),
+        );
+        quote! {
+            #[doc = #field_doc]
+            #field_vis #field_name: #ty
+        }
+    });
+
+    // Union contents can't be interpreted, so only the bounds and alignment of
+    // the archived union are checked before it is accessed
+    let check_bytes_impl =
+        if attributes.check_bytes.is_some() && cfg!(feature = "bytecheck") {
+            let mut check_generics = input.generics.clone();
+            check_generics.params.insert(
+                0,
+                parse_quote! {
+                    __C: #rkyv_path::rancor::Fallible + ?::core::marker::Sized
+                },
+            );
+            let (check_impl_generics, _, _) = check_generics.split_for_impl();
+            let mut check_where = where_clause.clone();
+            if let Some(ref bounds) = attributes.bound.check {
+                for bound in bounds {
+                    check_where.predicates.push(bound.clone());
+                }
+            }
+            Some(quote! {
+                // SAFETY: Deriving with `unsafe_union` asserts that every bit
+                // pattern of the union is valid, so any properly aligned and
+                // sized archived union is valid.
+                unsafe impl #check_impl_generics
+                    #rkyv_path::bytecheck::CheckBytes<__C>
+                    for #archived_name #ty_generics
+                #check_where
+                {
+                    #[inline]
+                    unsafe fn check_bytes(
+                        _: *const Self,
+                        _: &mut __C,
+                    ) -> ::core::result::Result<(), __C::Error> {
+                        ::core::result::Result::Ok(())
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
+    Ok(quote! {
+        #endian_check
+
+        #[automatically_derived]
+        #[doc = #archived_doc]
+        #(#reprs)*
+        #(#archive_attrs)*
+        #[derive(::core::clone::Clone, ::core::marker::Copy)]
+        #vis union #archived_name #generics #where_clause {
+            #(#fields,)*
+        }
+
+        #check_bytes_impl
+
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::Archive for #name #ty_generics
+        #where_clause
+        {
+            type Archived = #archived_name #ty_generics;
+            type Resolver = ();
+
+            #[inline]
+            unsafe fn resolve(
+                &self,
+                _: usize,
+                _: Self::Resolver,
+                out: *mut Self::Archived,
+            ) {
+                ::core::ptr::copy_nonoverlapping(
+                    (self as *const Self).cast::<u8>(),
+                    out.cast::<u8>(),
+                    ::core::mem::size_of::<Self>(),
+                );
+            }
+        }
+    })
+}
+
+pub fn derive_serialize_union(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    check_union(input, attributes)?;
+
+    let where_clause = union_where_clause(
+        input,
+        &[
+            &attributes.archive_bounds,
+            &attributes.serialize_bounds,
+            &attributes.bound.archive,
+            &attributes.bound.serialize,
+        ],
+        rkyv_path,
+    );
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.insert(
+        0,
+        parse_quote! { __S: #rkyv_path::rancor::Fallible + ?Sized },
+    );
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::Serialize<__S> for #name #ty_generics
+        #where_clause
+        {
+            #[inline]
+            fn serialize(
+                &self,
+                _: &mut __S,
+            ) -> ::core::result::Result<Self::Resolver, __S::Error> {
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+pub fn derive_deserialize_union(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    check_union(input, attributes)?;
+
+    let where_clause = union_where_clause(
+        input,
+        &[
+            &attributes.archive_bounds,
+            &attributes.deserialize_bounds,
+            &attributes.bound.archive,
+            &attributes.bound.deserialize,
+        ],
+        rkyv_path,
+    );
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.insert(
+        0,
+        parse_quote! { __D: #rkyv_path::rancor::Fallible + ?Sized },
+    );
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let archived_name = archived_union_name(input, attributes);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::Deserialize<#name #ty_generics, __D>
+            for #archived_name #ty_generics
+        #where_clause
+        {
+            #[inline]
+            fn deserialize(
+                &self,
+                _: &mut __D,
+            ) -> ::core::result::Result<#name #ty_generics, __D::Error> {
+                // SAFETY: The archived union has the same layout as the union.
+                ::core::result::Result::Ok(unsafe {
+                    ::core::ptr::read(
+                        (self as *const Self).cast::<#name #ty_generics>(),
+                    )
+                })
+            }
+        }
+    })
+}
@@ -1,5 +1,5 @@
 use proc_macro2::Ident;
-use syn::{Field, Meta};
+use syn::{Field, Lit, LitInt, LitStr, Meta, Token, Type};
 
 pub fn strip_raw(ident: &Ident) -> String {
     let as_string = ident.to_string();
@@ -9,6 +9,31 @@ pub fn strip_raw(ident: &Ident) -> String {
         .unwrap_or(as_string)
 }
 
+/// Converts an `UpperCamelCase` identifier like an enum variant name to
+/// `snake_case`.
+pub fn to_snake_case(ident: &str) -> String {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(ident.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower =
+                matches!(chars.get(i + 1), Some(n) if n.is_lowercase());
+            // Split before the start of each word, treating runs of capitals
+            // like `HTTPError` as a word followed by `Error`
+            if prev != '_'
+                && (prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next_is_lower))
+            {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
 pub fn is_not_omitted(f: &&Field) -> bool {
     f.attrs.iter().all(|attr| {
         if let Meta::Path(path) = &attr.meta {
@@ -18,3 +43,88 @@ pub fn is_not_omitted(f: &&Field) -> bool {
         }
     })
 }
+
+pub fn is_skipped(f: &Field) -> bool {
+    f.attrs.iter().any(|attr| {
+        let mut skip = false;
+        if attr.path().is_ident("archive") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<Lit>()?;
+                }
+                skip |= meta.path.is_ident("skip");
+                Ok(())
+            });
+        }
+        skip
+    })
+}
+
+pub fn is_not_skipped(f: &&Field) -> bool {
+    !is_skipped(f)
+}
+
+/// Returns the name of a field in the archived type.
+pub fn archived_field_name(f: &Field) -> Option<Ident> {
+    let mut name = f.ident.clone();
+    for attr in f.attrs.iter() {
+        if attr.path().is_ident("archive") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<Lit>()?;
+                }
+                Ok(())
+            });
+        }
+    }
+    name
+}
+
+/// Returns the alignment given to a field with `#[archive(align = ...)]`, if
+/// any.
+pub fn field_align(f: &Field) -> Option<LitInt> {
+    let mut align = None;
+    for attr in f.attrs.iter() {
+        if attr.path().is_ident("archive") {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("align") {
+                    align = Some(meta.value()?.parse::<LitInt>()?);
+                } else if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<Lit>()?;
+                }
+                Ok(())
+            });
+        }
+    }
+    align
+}
+
+/// Returns the archived alignment of a field type if it can be determined from
+/// the type alone.
+///
+/// This only recognizes primitives with a fixed size and arrays of them. The
+/// returned alignment is an upper bound, since archived primitives are less
+/// aligned when the `unaligned` feature is enabled.
+pub fn known_archived_align(ty: &Type) -> Option<usize> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let ident = path.path.get_ident()?;
+            let align = match ident.to_string().as_str() {
+                "bool" | "u8" | "i8" | "NonZeroU8" | "NonZeroI8" => 1,
+                "u16" | "i16" | "NonZeroU16" | "NonZeroI16" => 2,
+                "u32" | "i32" | "f32" | "char" | "NonZeroU32"
+                | "NonZeroI32" => 4,
+                "u64" | "i64" | "f64" | "NonZeroU64" | "NonZeroI64" => 8,
+                "u128" | "i128" | "NonZeroU128" | "NonZeroI128" => 16,
+                _ => return None,
+            };
+            Some(align)
+        }
+        Type::Array(array) => known_archived_align(&array.elem),
+        Type::Group(group) => known_archived_align(&group.elem),
+        Type::Paren(paren) => known_archived_align(&paren.elem),
+        _ => None,
+    }
+}
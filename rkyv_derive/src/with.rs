@@ -1,6 +1,6 @@
 use syn::{
-    parse_quote, punctuated::Punctuated, token::Comma, Error, Expr, Field,
-    Meta, Path, Type,
+    parse_quote, punctuated::Punctuated, token::Comma, Attribute, Error, Expr,
+    Field, Lit, LitStr, Meta, Path, Token, Type,
 };
 
 #[inline]
@@ -18,6 +18,8 @@ pub fn with<B, F: FnMut(B, &Type) -> B>(
                     Some(attr.parse_args_with(
                         Punctuated::<Type, Comma>::parse_separated_nonempty,
                     ))
+                } else if list.path.is_ident("archive") {
+                    Some(archive_with(attr))
                 } else {
                     None
                 }
@@ -29,6 +31,21 @@ pub fn with<B, F: FnMut(B, &Type) -> B>(
     Ok(fields.iter().flatten().rev().fold(init, f))
 }
 
+/// Parses the wrapper given with `#[archive(with = "...")]`, if any.
+fn archive_with(attr: &Attribute) -> Result<Punctuated<Type, Comma>, Error> {
+    let mut wrappers = Punctuated::new();
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("with") {
+            wrappers.push(meta.value()?.parse::<LitStr>()?.parse::<Type>()?);
+        } else if meta.input.peek(Token![=]) {
+            // Other field arguments are validated when parsing attributes
+            meta.value()?.parse::<Lit>()?;
+        }
+        Ok(())
+    })?;
+    Ok(wrappers)
+}
+
 #[inline]
 pub fn make_with_ty(
     rkyv_path: &Path,
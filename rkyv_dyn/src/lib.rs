@@ -7,18 +7,19 @@
 //!
 //! ## Features
 //!
-//! - `bytecheck`: Enables validation support through `bytecheck`.
+//! - `bytecheck`: Enables validation support through `bytecheck`. Archived
+//!   trait objects are checked against the registered trait impls, see
+//!   [`validation`] for details.
 
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(missing_docs)]
 #![deny(rustdoc::missing_crate_level_docs)]
 
 mod lazy_static;
-// TODO: re-enable
-// #[cfg(feature = "bytecheck")]
-// mod bytecheck;
+#[cfg(feature = "bytecheck")]
+pub mod validation;
 
-use core::{alloc::Layout, hash, marker::PhantomData};
+use core::{alloc::Layout, any::TypeId, hash, marker::PhantomData};
 pub use lazy_static::LazyStatic;
 use ptr_meta::{DynMetadata, Pointee};
 use rancor::Fallible;
@@ -29,6 +30,19 @@ use rkyv::{
     Archived, Serialize,
 };
 pub use rkyv_dyn_derive::archive_dyn;
+#[cfg(feature = "bytecheck")]
+pub use validation::CheckBytesDyn;
+
+/// A type that can be registered as a trait impl.
+///
+/// With the `bytecheck` feature enabled, registered types must also be
+/// checkable so that archived trait objects can be validated. Otherwise, this is
+/// implemented for all types.
+#[cfg(not(feature = "bytecheck"))]
+pub trait CheckBytesDyn {}
+
+#[cfg(not(feature = "bytecheck"))]
+impl<T: ?Sized> CheckBytesDyn for T {}
 
 /// The type of trait impl IDs.
 pub type ImplId = FixedUsize;
@@ -320,32 +334,46 @@ pub struct TraitImpl {
     // The type of this `DynMetadata` is erased. Whatever uses it will transmute
     // it to the correct `DynMetadata<T>`.
     metadata: DynMetadata<()>,
+    trait_id: TypeId,
+    layout: Layout,
+    #[cfg(feature = "bytecheck")]
+    check_bytes: unsafe fn(
+        *const (),
+        &mut dyn validation::DynContext,
+    ) -> Result<(), rancor::BoxedError>,
 }
 
 impl TraitImpl {
-    /// Creates a new trait impl from a trait object pointer.
+    /// Creates a new trait impl of the trait object `T` for the type `U` from
+    /// a trait object pointer.
     ///
     /// # Safety
     ///
-    /// `pointer` must have valid metadata.
-    pub unsafe fn from_pointer<
-        T: Pointee<Metadata = DynMetadata<T>> + ?Sized,
-    >(
-        pointer: *const T,
-    ) -> Self {
-        Self::from_metadata(ptr_meta::metadata(pointer))
-    }
-
-    /// Creates a new trait impl from its trait object metadata.
-    pub fn from_metadata<T: ?Sized>(metadata: DynMetadata<T>) -> Self {
+    /// `pointer` must have valid metadata for a `U` as a `T`.
+    pub unsafe fn from_pointer<U, T>(pointer: *const T) -> Self
+    where
+        U: CheckBytesDyn,
+        T: Pointee<Metadata = DynMetadata<T>> + ?Sized + 'static,
+    {
         Self {
             // SAFETY: All `DynMetadata<T>` have the same layout and validity.
             // They all contain a single erased `&'static VTable` reference and
             // a `PhantomData<T>`.
-            metadata: unsafe { core::mem::transmute(metadata) },
+            metadata: unsafe {
+                core::mem::transmute(ptr_meta::metadata(pointer))
+            },
+            trait_id: TypeId::of::<T>(),
+            layout: Layout::new::<U>(),
+            #[cfg(feature = "bytecheck")]
+            check_bytes: U::check_bytes_dyn,
         }
     }
 
+    /// Returns the layout of the type that implements the trait.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
     /// Returns the trait object metadata of this trait implementation downcast
     /// to the given type.
     ///
@@ -362,15 +390,25 @@ impl TraitImpl {
 /// See [`register_trait_impls`] for a macro that registers these trait impls
 /// globally.
 ///
+/// With the `bytecheck` feature enabled, the type must implement
+/// [`CheckBytesDyn`].
+///
 /// # Example
 /// ```
+/// use rkyv::bytecheck::CheckBytes;
+/// use rkyv_dyn::trait_impl;
+///
+/// #[derive(CheckBytes)]
+/// #[check_bytes(crate = "rkyv::bytecheck")]
 /// struct MyType;
 ///
+/// #[ptr_meta::pointee]
 /// trait MyTrait {}
 ///
 /// impl MyTrait for MyType {}
 ///
 /// let trait_impl = trait_impl!(MyType as dyn MyTrait);
+/// assert_eq!(trait_impl.layout(), core::alloc::Layout::new::<MyType>());
 /// ```
 #[macro_export]
 macro_rules! trait_impl {
@@ -378,8 +416,8 @@ macro_rules! trait_impl {
         // SAFETY: The given pointer is guaranteed to have valid metadata
         // because we just made them.
         unsafe {
-            $crate::TraitImpl::from_pointer(
-                ::core::ptr::null::<$type>() as *const $trait
+            $crate::TraitImpl::from_pointer::<$type, $trait>(
+                ::core::ptr::null::<$type>() as *const $trait,
             )
         }
     };
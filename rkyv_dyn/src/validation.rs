@@ -0,0 +1,286 @@
+//! Validation implementations and helper types.
+//!
+//! Archived trait objects store the impl ID of their concrete type as their
+//! metadata. Checking an archived trait object first checks that its impl ID
+//! is registered in [`TRAIT_IMPLS`] for the archived trait, then checks the
+//! bytes of the concrete type that the impl ID refers to.
+//!
+//! Archived traits can implement `CheckBytes` and `LayoutRaw` for their trait
+//! objects with [`check_bytes_dyn`] and [`layout_raw_dyn`].
+
+use core::{
+    alloc::{Layout, LayoutError},
+    any::{type_name, TypeId},
+    fmt,
+    ops::Range,
+};
+use std::{collections::HashMap, sync::OnceLock};
+
+use bytecheck::{CheckBytes, Verify};
+use ptr_meta::{DynMetadata, Pointee};
+use rancor::{fail, BoxedError, Error, Fallible};
+use rkyv::validation::{ArchiveContext, SharedContext};
+
+use crate::{ArchivedDynMetadata, TraitImpl, TRAIT_IMPLS};
+
+/// An object-safe version of a validation context.
+///
+/// Archived trait objects are checked with a `DynContext` so that the check
+/// for each registered type can be called through a function pointer. Any
+/// context that implements [`ArchiveContext`] and [`SharedContext`] can be
+/// used to check archived trait objects.
+pub trait DynContext:
+    ArchiveContext<BoxedError> + SharedContext<BoxedError>
+{
+}
+
+impl Fallible for dyn DynContext + '_ {
+    type Error = BoxedError;
+}
+
+impl<C> DynContext for C where
+    C: ArchiveContext<BoxedError> + SharedContext<BoxedError> + ?Sized
+{
+}
+
+/// A type that can be checked through a type-erased pointer.
+///
+/// This is implemented for all types that can be checked with a
+/// [`DynContext`], and is required to register a type as a trait impl.
+pub trait CheckBytesDyn {
+    /// Checks whether the given pointer points to a valid value of this type.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be properly aligned and point to enough bytes to
+    /// represent this type.
+    unsafe fn check_bytes_dyn(
+        value: *const (),
+        context: &mut dyn DynContext,
+    ) -> Result<(), BoxedError>;
+}
+
+impl<T> CheckBytesDyn for T
+where
+    T: for<'a> CheckBytes<dyn DynContext + 'a>,
+{
+    unsafe fn check_bytes_dyn(
+        value: *const (),
+        context: &mut dyn DynContext,
+    ) -> Result<(), BoxedError> {
+        T::check_bytes(value.cast(), context)
+    }
+}
+
+/// Adapts a validation context to a [`DynContext`] by boxing its errors.
+struct ContextAdapter<'a, C: ?Sized> {
+    inner: &'a mut C,
+}
+
+unsafe impl<C> ArchiveContext<BoxedError> for ContextAdapter<'_, C>
+where
+    C: Fallible + ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    fn check_subtree_ptr(
+        &mut self,
+        ptr: *const u8,
+        layout: &Layout,
+    ) -> Result<(), BoxedError> {
+        self.inner
+            .check_subtree_ptr(ptr, layout)
+            .map_err(BoxedError::new)
+    }
+
+    fn claimed_ranges(&self) -> &[(usize, usize)] {
+        self.inner.claimed_ranges()
+    }
+
+    unsafe fn push_prefix_subtree_range(
+        &mut self,
+        root: *const u8,
+        end: *const u8,
+    ) -> Result<Range<usize>, BoxedError> {
+        self.inner
+            .push_prefix_subtree_range(root, end)
+            .map_err(BoxedError::new)
+    }
+
+    unsafe fn push_suffix_subtree_range(
+        &mut self,
+        start: *const u8,
+        root: *const u8,
+    ) -> Result<Range<usize>, BoxedError> {
+        self.inner
+            .push_suffix_subtree_range(start, root)
+            .map_err(BoxedError::new)
+    }
+
+    unsafe fn pop_subtree_range(
+        &mut self,
+        range: Range<usize>,
+    ) -> Result<(), BoxedError> {
+        self.inner.pop_subtree_range(range).map_err(BoxedError::new)
+    }
+}
+
+impl<C> SharedContext<BoxedError> for ContextAdapter<'_, C>
+where
+    C: Fallible + SharedContext + ?Sized,
+    C::Error: Error,
+{
+    fn register_shared_ptr(
+        &mut self,
+        address: usize,
+        type_id: TypeId,
+    ) -> Result<bool, BoxedError> {
+        self.inner
+            .register_shared_ptr(address, type_id)
+            .map_err(BoxedError::new)
+    }
+}
+
+/// Returns the registered trait impl of `T` with the given impl ID.
+fn registered_impl<T: ?Sized + 'static>(
+    impl_id: usize,
+) -> Option<&'static TraitImpl> {
+    TRAIT_IMPLS
+        .get()?
+        .get(impl_id)
+        .filter(|trait_impl| trait_impl.trait_id == TypeId::of::<T>())
+}
+
+/// The registered trait impls indexed by their trait and erased metadata.
+type ImplIndex = HashMap<(TypeId, DynMetadata<()>), &'static TraitImpl>;
+
+/// Returns the registered trait impl of `T` with the given metadata.
+fn find_impl<T: ?Sized + 'static>(
+    metadata: DynMetadata<T>,
+) -> Option<&'static TraitImpl> {
+    static IMPL_INDEX: OnceLock<ImplIndex> = OnceLock::new();
+
+    // `TRAIT_IMPLS` can only be initialized once, so the index never goes
+    // stale after it's built.
+    let trait_impls = TRAIT_IMPLS.get()?;
+    let index = IMPL_INDEX.get_or_init(|| {
+        trait_impls
+            .iter()
+            .map(|trait_impl| {
+                ((trait_impl.trait_id, trait_impl.metadata), trait_impl)
+            })
+            .collect()
+    });
+
+    // SAFETY: All `DynMetadata<T>` have the same layout and validity. They all
+    // contain a single erased `&'static VTable` reference and a
+    // `PhantomData<T>`.
+    let metadata = unsafe {
+        core::mem::transmute::<DynMetadata<T>, DynMetadata<()>>(metadata)
+    };
+    index.get(&(TypeId::of::<T>(), metadata)).copied()
+}
+
+#[derive(Debug)]
+struct UnregisteredImplId {
+    impl_id: usize,
+    trait_name: &'static str,
+}
+
+impl fmt::Display for UnregisteredImplId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "impl id {} is not registered for `{}`",
+            self.impl_id, self.trait_name,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnregisteredImplId {}
+
+unsafe impl<T, C> Verify<C> for ArchivedDynMetadata<T>
+where
+    T: ?Sized + 'static,
+    C: Fallible + ?Sized,
+    C::Error: Error,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        let impl_id = self.impl_id() as usize;
+        if registered_impl::<T>(impl_id).is_none() {
+            fail!(UnregisteredImplId {
+                impl_id,
+                trait_name: type_name::<T>(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct UnregisteredMetadata {
+    trait_name: &'static str,
+}
+
+impl fmt::Display for UnregisteredMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trait object metadata is not registered for `{}`",
+            self.trait_name,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnregisteredMetadata {}
+
+/// Checks the bytes of an archived trait object with its registered impl.
+///
+/// This can be used to implement `CheckBytes` for archived trait objects.
+///
+/// # Safety
+///
+/// `value` must be properly aligned and point to enough bytes to represent the
+/// concrete type that its metadata refers to.
+pub unsafe fn check_bytes_dyn<T, C>(
+    value: *const T,
+    context: &mut C,
+) -> Result<(), C::Error>
+where
+    T: Pointee<Metadata = DynMetadata<T>> + ?Sized + 'static,
+    C: Fallible + ArchiveContext + SharedContext + ?Sized,
+    C::Error: Error,
+{
+    let trait_impl = match find_impl(ptr_meta::metadata(value)) {
+        Some(trait_impl) => trait_impl,
+        None => fail!(UnregisteredMetadata {
+            trait_name: type_name::<T>(),
+        }),
+    };
+
+    let mut adapter = ContextAdapter { inner: context };
+    unsafe { (trait_impl.check_bytes)(value.cast(), &mut adapter) }
+        .map_err(C::Error::new)
+}
+
+/// Returns the layout of the concrete type of a trait object from its
+/// registered impl.
+///
+/// This can be used to implement `LayoutRaw` for archived trait objects.
+///
+/// Returns a `LayoutError` if `metadata` was not registered for `T`. Metadata
+/// looked up from a checked [`ArchivedDynMetadata`] is always registered.
+pub fn layout_raw_dyn<T>(
+    metadata: DynMetadata<T>,
+) -> Result<Layout, LayoutError>
+where
+    T: ?Sized + 'static,
+{
+    match find_impl(metadata) {
+        Some(trait_impl) => Ok(trait_impl.layout),
+        // `LayoutError` can't be constructed directly, so make one from an
+        // invalid alignment
+        None => Layout::from_size_align(0, 0),
+    }
+}
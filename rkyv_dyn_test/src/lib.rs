@@ -96,6 +96,38 @@ mod tests {
                 }
             }
 
+            #[cfg(feature = "bytecheck")]
+            unsafe impl<SE, DE, C> rkyv::bytecheck::CheckBytes<C>
+                for dyn DeserializeTestTrait<SE, DE>
+            where
+                SE: 'static,
+                DE: 'static,
+                C: Fallible
+                    + rkyv::validation::ArchiveContext
+                    + rkyv::validation::SharedContext
+                    + ?Sized,
+                C::Error: rkyv::rancor::Error,
+            {
+                unsafe fn check_bytes(
+                    value: *const Self,
+                    context: &mut C,
+                ) -> Result<(), C::Error> {
+                    rkyv_dyn::validation::check_bytes_dyn(value, context)
+                }
+            }
+
+            #[cfg(feature = "bytecheck")]
+            impl<SE: 'static, DE: 'static> rkyv::validation::LayoutRaw
+                for dyn DeserializeTestTrait<SE, DE>
+            {
+                fn layout_raw(
+                    metadata: DynMetadata<Self>,
+                ) -> Result<std::alloc::Layout, std::alloc::LayoutError>
+                {
+                    rkyv_dyn::validation::layout_raw_dyn(metadata)
+                }
+            }
+
             impl<T, SE, DE> DeserializeTestTrait<SE, DE> for T where
                 T: TestTrait
                     + DeserializeDyn<dyn SerializeTestTrait<SE, DE>, DE>
@@ -128,6 +160,7 @@ mod tests {
             }
 
             #[derive(Archive, Serialize, Deserialize)]
+            #[archive(check_bytes)]
             pub struct Test {
                 id: i32,
             }
@@ -199,6 +232,29 @@ mod tests {
             )
             .unwrap();
             assert_eq!(value.get_id(), deserialized_value.get_id());
+
+            #[cfg(feature = "bytecheck")]
+            {
+                use rkyv::{access, util::AlignedVec};
+
+                let checked_value = access::<
+                    Box<dyn SerializeTestTrait<Failure, Failure>>,
+                    Failure,
+                >(buf.as_ref())
+                .unwrap();
+                assert_eq!(value.get_id(), checked_value.get_id());
+
+                // The impl ID is the metadata at the end of the root box
+                let mut bad_buf = AlignedVec::new();
+                bad_buf.extend_from_slice(buf.as_ref());
+                let len = bad_buf.len();
+                bad_buf[len - core::mem::size_of::<ImplId>()..].fill(0xff);
+                assert!(access::<
+                    Box<dyn SerializeTestTrait<Failure, Failure>>,
+                    Failure,
+                >(bad_buf.as_ref())
+                .is_err());
+            }
         }
     }
 
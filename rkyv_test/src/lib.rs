@@ -54,6 +54,88 @@ mod tests {
         test_archive(&Some(42));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_128_bit_integers() {
+        use core::mem::{align_of, size_of};
+        use rkyv::Archived;
+
+        assert_eq!(size_of::<Archived<i128>>(), 16);
+        assert_eq!(align_of::<Archived<i128>>(), 16);
+        assert_eq!(size_of::<Archived<u128>>(), 16);
+        assert_eq!(align_of::<Archived<u128>>(), 16);
+
+        test_archive(&i128::MIN);
+        test_archive(&i128::MAX);
+        test_archive(&-1i128);
+        test_archive(&u128::MIN);
+        test_archive(&u128::MAX);
+        test_archive(&[i128::MIN, 0, i128::MAX]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_num_and_cmp_wrappers() {
+        use core::{
+            cmp::Reverse,
+            num::{Saturating, Wrapping},
+        };
+        use rkyv::{Archive, Deserialize, Serialize};
+
+        test_archive(&Wrapping(1234567890u32));
+        test_archive(&Saturating(-12345i16));
+        test_archive(&Reverse(42i64));
+        test_archive(&[Reverse(1u8), Reverse(2u8)]);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq, PartialOrd))]
+        #[archive_attr(derive(Debug))]
+        struct Task {
+            priority: Reverse<u32>,
+        }
+
+        // Archived values order in reverse, and against unarchived values
+        #[cfg(not(feature = "strict"))]
+        let task = Task {
+            priority: Reverse(1),
+        };
+        #[cfg(not(feature = "strict"))]
+        test_archive_with(&task, |value, archived| {
+            archived == value
+                && *archived
+                    < Task {
+                        priority: Reverse(0),
+                    }
+                && *archived
+                    > Task {
+                        priority: Reverse(2),
+                    }
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn assert_archived_layout() {
+        use core::mem::{align_of, size_of};
+        use rkyv::{assert_archived_layout, Archive, Archived};
+
+        #[derive(Archive)]
+        struct Header {
+            magic: [u8; 4],
+            version: u16,
+            flags: u16,
+            len: u32,
+        }
+
+        assert_archived_layout!(Header, size = 12, align = 4);
+        assert_archived_layout!(u64, size = 8, align = 8,);
+        assert_archived_layout!(Option<u32>, size = 8);
+        assert_archived_layout!((), align = 1);
+
+        assert_eq!(size_of::<Archived<Header>>(), 12);
+        assert_eq!(align_of::<Archived<Header>>(), 4);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_refs() {
@@ -143,4 +225,47 @@ mod tests {
         test_archive::<r#virtual>(&r#virtual { r#virtual: 42 });
         test_archive::<r#try>(&r#try::r#try { r#try: 42 });
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_bound_and_control_flow() {
+        use core::ops::{Bound, ControlFlow};
+        use rkyv::ops::ArchivedBound;
+
+        test_archive(&Bound::Included(42u64));
+        test_archive(&Bound::Excluded(42u64));
+        test_archive(&Bound::<u64>::Unbounded);
+        test_archive(&ControlFlow::<i32, u32>::Continue(12345u32));
+        test_archive(&ControlFlow::<i32, u32>::Break(-12345i32));
+
+        let bound = ArchivedBound::Excluded(7);
+        assert_eq!(bound.as_ref(), Bound::Excluded(&7));
+        assert_eq!(bound.map(|x| x * 2), Bound::Excluded(14));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_repr_c() {
+        use core::mem::{align_of, offset_of, size_of};
+        use rkyv::{Archive, Archived};
+
+        #[allow(dead_code)]
+        #[derive(Archive)]
+        #[archive(repr(C))]
+        struct Header {
+            tag: u8,
+            len: u32,
+            flags: u16,
+        }
+
+        // Fields are laid out in declaration order with C padding
+        let align = align_of::<Archived<u32>>();
+        assert_eq!(offset_of!(ArchivedHeader, tag), 0);
+        assert_eq!(offset_of!(ArchivedHeader, len), align);
+        assert_eq!(offset_of!(ArchivedHeader, flags), align + 4);
+        assert_eq!(
+            size_of::<ArchivedHeader>(),
+            (align + 6).next_multiple_of(align)
+        );
+    }
 }
@@ -18,6 +18,7 @@ mod tests {
         collections::{BTreeMap, BTreeSet},
         rc::{Rc, Weak},
         string::{String, ToString},
+        sync::Arc,
         vec,
         vec::Vec,
     };
@@ -26,6 +27,7 @@ mod tests {
         borrow::Cow,
         collections::{BTreeMap, BTreeSet},
         rc::{Rc, Weak},
+        sync::Arc,
     };
 
     #[cfg(feature = "wasm")]
@@ -52,6 +54,101 @@ mod tests {
         test_archive_with(&vec![1, 2, 3, 4], |a, b| **a == **b);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_boxed_slice_layout() {
+        use core::mem::size_of;
+        use rkyv::RelPtr;
+
+        // Boxed slices and strs archive as a relative pointer and length with
+        // no capacity, the same as `Vec` and `String`
+        assert_eq!(
+            size_of::<Archived<Box<[u32]>>>(),
+            size_of::<RelPtr<[Archived<u32>]>>()
+        );
+        assert_eq!(size_of::<Archived<Box<str>>>(), size_of::<RelPtr<str>>());
+        assert_eq!(
+            size_of::<Archived<Box<[u32]>>>(),
+            size_of::<Archived<Vec<u32>>>()
+        );
+
+        let value = vec![1u32, 2, 3, 4].into_boxed_slice();
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Box<[u32]>>(buf.as_ref()) };
+        let slice: &[Archived<u32>] = archived;
+        assert_eq!(slice, &[1, 2, 3, 4]);
+        assert_eq!(
+            buf.as_slice(),
+            to_bytes::<_, 256, Failure>(&value.to_vec())
+                .unwrap()
+                .as_slice()
+        );
+
+        let value = "hello world".to_string().into_boxed_str();
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Box<str>>(buf.as_ref()) };
+        let s: &str = archived;
+        assert_eq!(s, "hello world");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_vec_access() {
+        let value = Box::new(vec![
+            "hello".to_string(),
+            "world".to_string(),
+            "foo".to_string(),
+        ]);
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived =
+            unsafe { access_unchecked::<Box<Vec<String>>>(buf.as_ref()) };
+        let archived = archived.get();
+
+        assert_eq!(archived.len(), 3);
+        assert!(!archived.is_empty());
+        assert_eq!(archived[1], "world");
+        assert_eq!(archived.get(2).map(|s| s.as_str()), Some("foo"));
+        assert!(archived.get(3).is_none());
+        assert_eq!(archived.first().map(|s| s.as_str()), Some("hello"));
+
+        let mut items = Vec::new();
+        for item in archived {
+            items.push(item.as_str());
+        }
+        assert_eq!(items, ["hello", "world", "foo"]);
+        assert!(archived.iter().eq(value.iter()));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_string_like_str() {
+        use core::borrow::Borrow;
+
+        fn borrow_str<T: Borrow<str> + ?Sized>(value: &T) -> &str {
+            value.borrow()
+        }
+
+        let value = "hello world".to_string();
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<String>(buf.as_ref()) };
+
+        assert_eq!(format!("{}", archived), "hello world");
+        assert_eq!(format!("{:?}", archived), format!("{:?}", value));
+        assert_eq!(archived.len(), 11);
+        assert!(archived.starts_with("hello"));
+        assert_eq!(&archived[6..], "world");
+        assert_eq!(borrow_str(archived), "hello world");
+        assert_eq!(archived.as_ref() as &str, "hello world");
+
+        assert_eq!(archived, "hello world");
+        assert_eq!("hello world", archived);
+        assert_eq!(*archived, *"hello world");
+        assert_eq!(archived, &value);
+        assert_eq!(&value, archived);
+        assert!(archived < &"world".to_string());
+        assert!(*archived > *"apple");
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_option() {
@@ -63,6 +160,77 @@ mod tests {
         test_archive(&Some(Box::new(vec![1, 2, 3, 4])));
     }
 
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_tuple_12() {
+        let value = (
+            1u8,
+            "hello".to_string(),
+            2u16,
+            true,
+            3u32,
+            "world".to_string(),
+            4u64,
+            5f32,
+            'x',
+            6i16,
+            vec![7i32, 8, 9],
+            10f64,
+        );
+
+        test_archive_with(&value, |a, b| {
+            a.0 == b.0
+                && a.1 == b.1
+                && a.2 == b.2
+                && a.3 == b.3
+                && a.4 == b.4
+                && a.5 == b.5
+                && a.6 == b.6
+                && a.7 == b.7
+                && a.8 == b.8
+                && a.9 == b.9
+                && a.10 == b.10
+                && a.11 == b.11
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_array() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Entry {
+            key: String,
+            value: u32,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            hash: [u64; 32],
+            table: [Entry; 16],
+        }
+
+        let mut hash = [0; 32];
+        for (i, x) in hash.iter_mut().enumerate() {
+            *x = (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        }
+        let value = Test {
+            hash,
+            table: core::array::from_fn(|i| Entry {
+                key: format!("entry {}", i),
+                value: i as u32,
+            }),
+        };
+
+        test_archive(&value);
+        test_archive(&[[1u8, 2, 3], [4, 5, 6]]);
+        test_archive(&["hello".to_string(), "world".to_string()]);
+    }
+
     #[test]
     fn option_is_copy() {
         #[derive(
@@ -112,6 +280,24 @@ mod tests {
         test_archive::<Result<(), _>>(&Err("hello world".to_string()));
         test_archive::<Result<(), _>>(&Err(vec![1, 2, 3, 4]));
         test_archive::<Result<(), _>>(&Err(Box::new(vec![1, 2, 3, 4])));
+
+        let ok = Result::<String, u32>::Ok("hello world".to_string());
+        let buf = to_bytes::<_, 256, Failure>(&ok).unwrap();
+        let archived =
+            unsafe { access_unchecked::<Result<String, u32>>(buf.as_slice()) };
+        assert!(archived.is_ok());
+        assert!(!archived.is_err());
+        assert_eq!(archived.as_ref().ok().unwrap(), "hello world");
+        assert!(archived.as_ref().err().is_none());
+
+        let err = Result::<String, u32>::Err(42);
+        let buf = to_bytes::<_, 256, Failure>(&err).unwrap();
+        let archived =
+            unsafe { access_unchecked::<Result<String, u32>>(buf.as_slice()) };
+        assert!(archived.is_err());
+        assert!(!archived.is_ok());
+        assert!(archived.as_ref().ok().is_none());
+        assert_eq!(*archived.as_ref().err().unwrap(), 42);
     }
 
     #[cfg(all(feature = "std", feature = "bytecheck"))]
@@ -313,6 +499,60 @@ mod tests {
         ]);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_generic_wrapper() {
+        use core::fmt;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Wrapper<T> {
+            inner: T,
+            tag: u32,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Pair<A, B>(A, B);
+
+        // The archived types of generic structs only implement `Debug` when
+        // bounded on their archived fields, so compare by hand
+        fn roundtrip<T>(value: &T)
+        where
+            T: fmt::Debug
+                + PartialEq
+                + Serialize<Strategy<DefaultSerializer, Failure>>,
+            T::Archived: PartialEq<T>
+                + Deserialize<T, Strategy<DefaultDeserializer, Failure>>,
+        {
+            let buf = serialize_into(value, DefaultSerializer::default())
+                .unwrap()
+                .into_writer();
+            let archived = unsafe { access_unchecked::<T>(buf.as_ref()) };
+            assert!(archived == value);
+
+            let deserialized = deserialize::<T, _, Failure>(
+                archived,
+                &mut DefaultDeserializer::default(),
+            )
+            .unwrap();
+            assert_eq!(&deserialized, value);
+        }
+
+        roundtrip(&Wrapper {
+            inner: "hello world".to_string(),
+            tag: 1,
+        });
+        roundtrip(&Wrapper {
+            inner: Box::new(vec![1u32, 2, 3]),
+            tag: 2,
+        });
+        roundtrip(&Wrapper {
+            inner: Pair(1u8, "pair".to_string()),
+            tag: 3,
+        });
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_enum() {
@@ -341,6 +581,45 @@ mod tests {
         ]);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn deserialize_from_bytes() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        enum Shape {
+            Circle(u32),
+            Rect { w: u32, h: u32 },
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            name: String,
+            tags: Vec<String>,
+            parent: Option<String>,
+            shapes: Vec<Shape>,
+            ratio: f64,
+        }
+
+        let value = Test {
+            name: "child".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+            parent: Some("parent".to_string()),
+            shapes: vec![Shape::Circle(3), Shape::Rect { w: 2, h: 4 }],
+            ratio: 1.25,
+        };
+
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let deserialized =
+            unsafe { rkyv::from_bytes_unchecked::<Test, Failure>(&bytes) }
+                .unwrap();
+        assert_eq!(deserialized, value);
+
+        // The owned value is independent of the archive and can be mutated
+        let mut deserialized = deserialized;
+        deserialized.tags.push("c".to_string());
+        drop(bytes);
+        assert_eq!(deserialized.tags.len(), 3);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_generic_enum() {
@@ -491,6 +770,101 @@ mod tests {
         test_archive(&TestGeneric(42));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_copy_attribute() {
+        #[derive(
+            Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+        )]
+        #[archive(copy)]
+        struct Color {
+            r: u8,
+            g: u8,
+            b: u8,
+            a: u8,
+        }
+
+        assert_eq!(core::mem::size_of::<Archived<Color>>(), 4);
+
+        let color = Color {
+            r: 1,
+            g: 2,
+            b: 3,
+            a: 255,
+        };
+        test_archive(&color);
+        test_archive(&[color; 4]);
+        test_archive(&vec![color, Color { a: 0, ..color }]);
+
+        #[derive(
+            Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+        )]
+        #[archive(copy)]
+        struct Pair<T>(T, T);
+
+        test_archive(&Pair(true, false));
+        test_archive(&Pair(color, color));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn copy_optimization() {
+        use rkyv::rend::u16_le;
+
+        #[derive(
+            Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+        )]
+        #[archive(copy)]
+        struct Color {
+            r: u8,
+            g: u8,
+            b: u8,
+            a: u8,
+        }
+
+        #[derive(
+            Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+        )]
+        #[archive(copy)]
+        struct Padded {
+            a: u8,
+            b: u16_le,
+        }
+
+        assert!(u8::COPY_OPTIMIZATION.is_enabled());
+        assert!(<[Color; 2]>::COPY_OPTIMIZATION.is_enabled());
+        assert!(!Padded::COPY_OPTIMIZATION.is_enabled());
+        assert!(!<[Padded; 2]>::COPY_OPTIMIZATION.is_enabled());
+        assert!(!String::COPY_OPTIMIZATION.is_enabled());
+
+        let bytes = (0..=255u8).collect::<Vec<_>>();
+        let buf = to_bytes::<_, 256, Failure>(&bytes).unwrap();
+        let archived = unsafe { access_unchecked::<Vec<u8>>(buf.as_ref()) };
+        assert_eq!(archived.as_slice(), bytes.as_slice());
+
+        let colors = (0..16u8)
+            .map(|i| Color {
+                r: i,
+                g: i + 1,
+                b: i + 2,
+                a: 255,
+            })
+            .collect::<Vec<_>>();
+        test_archive(&colors);
+        test_archive(&vec![[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        test_archive(&vec![1u32, 2, 3, 0xdead_beef]);
+        test_archive(&vec![
+            Padded {
+                a: 1,
+                b: u16_le::from_native(2),
+            },
+            Padded {
+                a: 3,
+                b: u16_le::from_native(4),
+            },
+        ]);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_derives() {
@@ -604,6 +978,217 @@ mod tests {
         assert_eq!(value.b[1], "WORLD");
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_pin_fields() {
+        #[derive(Archive, Serialize)]
+        #[archive(pin_fields)]
+        struct Test {
+            count: u32,
+            scores: [f32; 3],
+            name: String,
+            r#type: Option<u8>,
+        }
+
+        let value = Test {
+            count: 1,
+            scores: [1.0, 2.0, 3.0],
+            name: "hello".to_string(),
+            r#type: Some(7),
+        };
+
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let len = buf.len();
+        let mut value =
+            unsafe { access_unchecked_mut::<Test>(Pin::new(buf.as_mut())) };
+
+        *value.as_mut().count_pin() = 2.into();
+        value.as_mut().scores_pin()[1] = 5.0.into();
+        value
+            .as_mut()
+            .name_pin()
+            .pin_mut_str()
+            .make_ascii_uppercase();
+        *value.as_mut().type_pin().as_pin_mut().unwrap() = 9;
+
+        assert_eq!(value.count, 2);
+        assert_eq!(value.scores, [1.0, 5.0, 3.0]);
+        assert_eq!(value.name, "HELLO");
+        assert_eq!(value.r#type.as_ref(), Some(&9));
+
+        // Mutating in place never changes the layout of the buffer
+        let archived = unsafe { access_unchecked::<Test>(buf.as_ref()) };
+        assert_eq!(buf.len(), len);
+        assert_eq!(archived.count, 2);
+        assert_eq!(archived.name, "HELLO");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_skip() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Named {
+            id: u32,
+            #[archive(skip)]
+            cache: Vec<u64>,
+            name: String,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Unnamed(#[archive(skip)] Option<u64>, u32, String);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        enum Enum {
+            Named {
+                #[archive(skip)]
+                cache: u64,
+                value: u32,
+            },
+            Unnamed(u32, #[archive(skip)] u64, String),
+        }
+
+        // Skipped fields aren't part of the archived layout
+        assert_eq!(
+            core::mem::size_of::<ArchivedNamed>(),
+            core::mem::size_of::<(Archived<u32>, Archived<String>)>(),
+        );
+        assert_eq!(
+            core::mem::size_of::<ArchivedUnnamed>(),
+            core::mem::size_of::<(Archived<u32>, Archived<String>)>(),
+        );
+
+        fn roundtrip<T>(value: &T, expected: &T)
+        where
+            T: core::fmt::Debug
+                + PartialEq
+                + Serialize<Strategy<DefaultSerializer, Failure>>,
+            T::Archived: PartialEq<T>
+                + Deserialize<T, Strategy<DefaultDeserializer, Failure>>,
+        {
+            let buf = serialize_into(value, DefaultSerializer::default())
+                .unwrap()
+                .into_writer();
+            let archived = unsafe { access_unchecked::<T>(buf.as_ref()) };
+            assert!(archived == value);
+
+            // Skipped fields are reconstructed with their default values
+            let deserialized = deserialize::<T, _, Failure>(
+                archived,
+                &mut DefaultDeserializer::default(),
+            )
+            .unwrap();
+            assert_eq!(&deserialized, expected);
+        }
+
+        roundtrip(
+            &Named {
+                id: 42,
+                cache: vec![1, 2, 3],
+                name: "hello world".to_string(),
+            },
+            &Named {
+                id: 42,
+                cache: Vec::new(),
+                name: "hello world".to_string(),
+            },
+        );
+        roundtrip(
+            &Unnamed(Some(7), 42, "hello world".to_string()),
+            &Unnamed(None, 42, "hello world".to_string()),
+        );
+        roundtrip(
+            &Enum::Named {
+                cache: 7,
+                value: 42,
+            },
+            &Enum::Named {
+                cache: 0,
+                value: 42,
+            },
+        );
+        roundtrip(
+            &Enum::Unnamed(42, 7, "hello world".to_string()),
+            &Enum::Unnamed(42, 0, "hello world".to_string()),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_rename() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq, PartialOrd), pin_fields)]
+        #[archive_attr(derive(Debug))]
+        struct Original {
+            id: u32,
+            #[archive(rename = "title")]
+            name: String,
+            #[archive(rename = "r#type")]
+            kind: u8,
+        }
+
+        #[derive(
+            Archive, Serialize, Deserialize, Debug, PartialEq, PartialOrd,
+        )]
+        #[archive(compare(PartialEq, PartialOrd))]
+        #[archive_attr(derive(Debug))]
+        enum Event {
+            Message {
+                #[archive(rename = "text")]
+                body: String,
+                id: u32,
+            },
+            Empty,
+        }
+
+        // Renaming doesn't change the layout
+        #[derive(Archive)]
+        #[allow(dead_code)]
+        struct Unrenamed {
+            id: u32,
+            name: String,
+            kind: u8,
+        }
+        assert_eq!(
+            core::mem::size_of::<ArchivedOriginal>(),
+            core::mem::size_of::<ArchivedUnrenamed>(),
+        );
+
+        let value = Original {
+            id: 1,
+            name: "hello".to_string(),
+            kind: 2,
+        };
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let mut archived =
+            unsafe { access_unchecked_mut::<Original>(Pin::new(buf.as_mut())) };
+        assert_eq!(archived.title, "hello");
+        assert_eq!(archived.r#type, 2);
+        *archived.as_mut().type_pin() = 3;
+        assert_eq!(archived.r#type, 3);
+        test_archive(&value);
+
+        test_archive(&Event::Message {
+            body: "hello world".to_string(),
+            id: 42,
+        });
+        test_archive(&Event::Empty);
+        let buf = to_bytes::<_, 256, Failure>(&Event::Message {
+            body: "hi".to_string(),
+            id: 1,
+        })
+        .unwrap();
+        match unsafe { access_unchecked::<Event>(buf.as_ref()) } {
+            ArchivedEvent::Message { text, id } => {
+                assert_eq!(text, "hi");
+                assert_eq!(*id, 1);
+            }
+            ArchivedEvent::Empty => panic!("expected message"),
+        }
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn enum_mutable_ref() {
@@ -655,35 +1240,91 @@ mod tests {
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn recursive_self_types() {
+    fn recursive_structures_replaced_bounds() {
         #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
-        #[archive(compare(PartialEq))]
         #[archive_attr(derive(Debug))]
-        // The derive macros don't apply the right bounds from Box so we have to manually specify
-        // what bounds to apply
-        #[archive(serialize_bounds(__S: Writer))]
-        pub enum LinkedList<T: Archive>
-        where
-            T::Archived: core::fmt::Debug,
-        {
-            Empty,
-            Node {
-                val: T,
-                #[omit_bounds]
-                next: Box<Self>,
-            },
+        // Replacing the inferred bounds avoids the recursive `Box<Node>`
+        // bounds without marking each field with `#[omit_bounds]`
+        #[archive(bound(
+            archive = "",
+            serialize = "__S: Writer",
+            deserialize = "",
+        ))]
+        enum Node {
+            Nil,
+            Cons(Box<Node>),
         }
 
-        test_archive(&LinkedList::Node {
-            val: 42i32,
-            next: Box::new(LinkedList::Node {
-                val: 100i32,
-                next: Box::new(LinkedList::Empty),
-            }),
-        });
-    }
-
-    #[test]
+        test_archive_with(
+            &Node::Cons(Box::new(Node::Cons(Box::new(Node::Nil)))),
+            |_, archived| match archived {
+                ArchivedNode::Cons(next) => {
+                    matches!(**next, ArchivedNode::Cons(_))
+                }
+                ArchivedNode::Nil => false,
+            },
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn generic_replaced_bounds() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive(bound(archive = "T: Archive + Clone"))]
+        struct Cached<T> {
+            value: T,
+            hits: u32,
+        }
+
+        let value = Cached {
+            value: "hello world".to_string(),
+            hits: 3,
+        };
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived =
+            unsafe { access_unchecked::<Cached<String>>(buf.as_ref()) };
+        assert!(archived == &value);
+
+        let deserialized = deserialize::<Cached<String>, _, Failure>(
+            archived,
+            &mut DefaultDeserializer::default(),
+        )
+        .unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn recursive_self_types() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        // The derive macros don't apply the right bounds from Box so we have to manually specify
+        // what bounds to apply
+        #[archive(serialize_bounds(__S: Writer))]
+        pub enum LinkedList<T: Archive>
+        where
+            T::Archived: core::fmt::Debug,
+        {
+            Empty,
+            Node {
+                val: T,
+                #[omit_bounds]
+                next: Box<Self>,
+            },
+        }
+
+        test_archive(&LinkedList::Node {
+            val: 42i32,
+            next: Box::new(LinkedList::Node {
+                val: 100i32,
+                next: Box::new(LinkedList::Empty),
+            }),
+        });
+    }
+
+    #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn complex_bounds() {
         use core::marker::PhantomData;
@@ -786,6 +1427,399 @@ mod tests {
         test_archive(&value);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_ranges() {
+        use core::ops::{Range, RangeInclusive};
+
+        test_archive_with(&(0usize..10), |a, b| {
+            a.start == b.start.to_native() as usize
+                && a.end == b.end.to_native() as usize
+        });
+        test_archive(&(1u32..=100));
+
+        let buf = to_bytes::<_, 256, Failure>(&(0usize..10)).unwrap();
+        let archived = unsafe { access_unchecked::<Range<usize>>(&buf) };
+        assert_eq!(archived.start, 0);
+        assert_eq!(archived.end, 10);
+        assert!(archived.contains(&Archived::<usize>::from_native(9)));
+        assert!(!archived.contains(&Archived::<usize>::from_native(10)));
+        assert!(!archived.is_empty());
+
+        let buf = to_bytes::<_, 256, Failure>(&(1u32..=100)).unwrap();
+        let archived = unsafe { access_unchecked::<RangeInclusive<u32>>(&buf) };
+        assert_eq!(archived.start, 1);
+        assert_eq!(archived.end, 100);
+        assert!(archived.contains(&Archived::<u32>::from_native(100)));
+        assert!(!archived.contains(&Archived::<u32>::from_native(0)));
+        assert!(!archived.is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_into_aligned_vec() {
+        #[derive(Archive, Serialize)]
+        struct Test {
+            id: u64,
+            names: [String; 64],
+        }
+
+        let value = Test {
+            id: 42,
+            names: core::array::from_fn(|i| format!("{:0>100}", i)),
+        };
+
+        // The writer starts empty and grows as the value is archived
+        let writer =
+            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
+        assert!(writer.len() > 64 * 100);
+        assert_eq!(writer.as_ptr().align_offset(AlignedVec::ALIGNMENT), 0);
+
+        let root_pos = writer.len() - core::mem::size_of::<ArchivedTest>();
+        let archived = unsafe {
+            rkyv::util::access_pos_unchecked::<Test>(
+                writer.as_slice(),
+                root_pos,
+            )
+        };
+        assert_eq!(archived.id, 42);
+        for (i, name) in archived.names.iter().enumerate() {
+            assert_eq!(name.as_str(), format!("{:0>100}", i));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn counting_writer() {
+        use rkyv::ser::{
+            allocator::{BackupAllocator, BumpAllocator, GlobalAllocator},
+            sharing::Unify,
+            writer::CountingWriter,
+            Composite, Positional,
+        };
+
+        type CountingSerializer = Composite<
+            CountingWriter,
+            BackupAllocator<BumpAllocator<256>, GlobalAllocator>,
+            Unify,
+        >;
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            a: u8,
+            b: String,
+            c: Vec<u64>,
+            d: Rc<[u16]>,
+            e: Rc<[u16]>,
+            f: Option<Box<u32>>,
+        }
+
+        let shared = Rc::<[u16]>::from(vec![1, 2, 3]);
+        let value = Test {
+            a: 1,
+            b: "a string which is long enough to be stored out of line"
+                .to_string(),
+            c: vec![1, 2, 3, 4, 5],
+            d: shared.clone(),
+            e: shared,
+            f: Some(Box::new(42)),
+        };
+
+        let counter = serialize_into::<_, _, Failure>(
+            &value,
+            CountingSerializer::default(),
+        )
+        .unwrap();
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        assert_eq!(counter.pos(), bytes.len());
+
+        // Starting at an unaligned position counts the same padding as a
+        // real writer
+        let counter = serialize_into::<_, _, Failure>(
+            &"hello world".to_string(),
+            CountingWriter::with_pos(1),
+        )
+        .unwrap();
+        let writer = serialize_into::<_, _, Failure>(
+            &"hello world".to_string(),
+            BufferWriter::with_pos(AlignedBytes([0u8; 64]), 1),
+        )
+        .unwrap();
+        assert_eq!(counter.pos(), writer.pos());
+
+        // Counting past the end of the address space fails
+        let mut counter = CountingWriter::with_pos(usize::MAX - 1);
+        Writer::<Failure>::write(&mut counter, &[0]).unwrap();
+        Writer::<Failure>::write(&mut counter, &[0]).unwrap_err();
+        assert_eq!(counter.pos(), usize::MAX);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_byte_order() {
+        #[derive(Archive, Serialize)]
+        struct Test {
+            a: u32,
+            b: i16,
+            c: f32,
+            d: f64,
+        }
+
+        fn to_endian<const N: usize>(le: [u8; N]) -> [u8; N] {
+            if cfg!(feature = "big_endian") {
+                let mut be = le;
+                be.reverse();
+                be
+            } else {
+                le
+            }
+        }
+
+        let bytes = to_bytes::<_, 0, Failure>(&0x0102_0304u32).unwrap();
+        assert_eq!(bytes.as_slice(), to_endian([4, 3, 2, 1]));
+        let bytes = to_bytes::<_, 0, Failure>(&1.0f32).unwrap();
+        assert_eq!(bytes.as_slice(), to_endian(1.0f32.to_le_bytes()));
+
+        let value = Test {
+            a: 0xdead_beef,
+            b: -2,
+            c: 1.5,
+            d: -0.25,
+        };
+        let bytes = to_bytes::<_, 0, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(&bytes) };
+
+        // Accessors convert back to the native representation of the host
+        assert_eq!(archived.a.to_native(), 0xdead_beef);
+        assert_eq!(archived.b.to_native(), -2);
+        assert_eq!(archived.c.to_native(), 1.5);
+        assert_eq!(archived.d.to_native(), -0.25);
+
+        let a_pos = core::mem::offset_of!(ArchivedTest, a);
+        assert_eq!(
+            bytes[a_pos..a_pos + 4],
+            to_endian(0xdead_beefu32.to_le_bytes())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_fixed_width_usize() {
+        use core::{
+            mem::size_of,
+            num::{NonZeroIsize, NonZeroUsize},
+        };
+        use rkyv::primitive::{FixedIsize, FixedUsize};
+
+        // The archived width is chosen by the `pointer_width_*` feature
+        // rather than the host
+        assert_eq!(size_of::<Archived<usize>>(), size_of::<FixedUsize>());
+        assert_eq!(size_of::<Archived<isize>>(), size_of::<FixedIsize>());
+
+        let max = FixedUsize::MAX as usize;
+        let buf = to_bytes::<_, 0, Failure>(&max).unwrap();
+        let archived = unsafe { access_unchecked::<usize>(&buf) };
+        assert_eq!(archived.to_native() as usize, max);
+        let deserialized =
+            deserialize::<usize, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, max);
+
+        let min = FixedIsize::MIN as isize;
+        let buf = to_bytes::<_, 0, Failure>(&min).unwrap();
+        let archived = unsafe { access_unchecked::<isize>(&buf) };
+        let deserialized =
+            deserialize::<isize, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, min);
+
+        // Values that don't fit in the archived width are rejected instead of
+        // being truncated
+        if size_of::<FixedUsize>() < size_of::<usize>() {
+            to_bytes::<_, 0, Failure>(&(max + 1)).unwrap_err();
+            to_bytes::<_, 0, Failure>(&(min - 1)).unwrap_err();
+            to_bytes::<_, 0, Failure>(&vec![0usize, max + 1]).unwrap_err();
+            to_bytes::<_, 0, Failure>(&NonZeroUsize::new(max + 1).unwrap())
+                .unwrap_err();
+            to_bytes::<_, 0, Failure>(&NonZeroIsize::new(min - 1).unwrap())
+                .unwrap_err();
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[cfg(all(target_pointer_width = "64", not(feature = "pointer_width_64")))]
+    fn archive_fixed_width_len() {
+        use rkyv::{primitive::FixedUsize, ser::AllocSerializer};
+
+        // Vecs of zero-sized types can be longer than the archived length
+        // without allocating, and must be rejected instead of truncated. They
+        // are serialized directly because size hints visit every element.
+        let mut value = Vec::<()>::new();
+        // SAFETY: a vec of zero-sized types can hold any number of elements
+        #[allow(clippy::uninit_vec)]
+        unsafe {
+            value.set_len(FixedUsize::MAX as usize + 1);
+        }
+        serialize_into::<_, _, Failure>(
+            &value,
+            AllocSerializer::<0>::default(),
+        )
+        .unwrap_err();
+        let value = value.into_boxed_slice();
+        serialize_into::<_, _, Failure>(
+            &value,
+            AllocSerializer::<0>::default(),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn rel_ptr_offset_width() {
+        use core::mem::{size_of, MaybeUninit};
+        use rkyv::{
+            primitive::FixedIsize,
+            rancor::BoxedError,
+            rel_ptr::{RawRelPtrI16, RawRelPtrI32},
+        };
+
+        // Archived containers use the offset width selected by the
+        // `pointer_width_*` feature
+        assert_eq!(size_of::<Archived<Box<u32>>>(), size_of::<FixedIsize>());
+
+        let mut ptr = MaybeUninit::<RawRelPtrI16>::uninit();
+        unsafe {
+            RawRelPtrI16::try_emplace::<Failure>(0, 32_767, ptr.as_mut_ptr())
+                .unwrap();
+            assert_eq!(ptr.assume_init_ref().offset(), 32_767);
+            RawRelPtrI16::try_emplace::<Failure>(40_000, 0, ptr.as_mut_ptr())
+                .unwrap_err();
+
+            let error = RawRelPtrI16::try_emplace::<BoxedError>(
+                0,
+                40_000,
+                ptr.as_mut_ptr(),
+            )
+            .unwrap_err();
+            assert!(error.to_string().contains("too far for the offset type"));
+        }
+
+        let mut ptr = MaybeUninit::<RawRelPtrI32>::uninit();
+        unsafe {
+            RawRelPtrI32::try_emplace::<Failure>(0, 40_000, ptr.as_mut_ptr())
+                .unwrap();
+            assert_eq!(ptr.assume_init_ref().offset(), 40_000);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[cfg(all(target_pointer_width = "64", not(feature = "pointer_width_64")))]
+    fn rel_ptr_offset_overflow() {
+        use core::mem::MaybeUninit;
+        use rkyv::{
+            rel_ptr::{RawRelPtrI32, RawRelPtrI64},
+            string::repr::ArchivedStringRepr,
+        };
+
+        const THREE_GIB: usize = 3 << 30;
+
+        // Offsets past 2 GiB don't fit in 32 bits and must not be truncated
+        let mut ptr = MaybeUninit::<RawRelPtrI32>::uninit();
+        unsafe {
+            RawRelPtrI32::try_emplace::<Failure>(
+                0,
+                THREE_GIB,
+                ptr.as_mut_ptr(),
+            )
+            .unwrap_err();
+        }
+
+        let mut repr = MaybeUninit::<ArchivedStringRepr>::uninit();
+        unsafe {
+            ArchivedStringRepr::try_emplace_out_of_line::<Failure>(
+                "a string too long to be inlined",
+                0,
+                THREE_GIB,
+                repr.as_mut_ptr(),
+            )
+            .unwrap_err();
+        }
+
+        let mut ptr = MaybeUninit::<RawRelPtrI64>::uninit();
+        unsafe {
+            RawRelPtrI64::try_emplace::<Failure>(
+                0,
+                THREE_GIB,
+                ptr.as_mut_ptr(),
+            )
+            .unwrap();
+            assert_eq!(ptr.assume_init_ref().offset(), THREE_GIB as isize);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn rel_ptr_offset_sign() {
+        use core::mem::MaybeUninit;
+        use rkyv::{rancor::BoxedError, rel_ptr::RawRelPtrU16};
+
+        // Archived data is written before the pointers to it, so unsigned
+        // offsets can only point forward
+        let mut ptr = MaybeUninit::<RawRelPtrU16>::uninit();
+        unsafe {
+            RawRelPtrU16::try_emplace::<Failure>(0, 100, ptr.as_mut_ptr())
+                .unwrap();
+            assert_eq!(ptr.assume_init_ref().offset(), 100);
+
+            let error = RawRelPtrU16::try_emplace::<BoxedError>(
+                100,
+                0,
+                ptr.as_mut_ptr(),
+            )
+            .unwrap_err();
+            assert!(error.to_string().contains("offset is negative"));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[cfg(all(target_pointer_width = "64", not(feature = "pointer_width_64")))]
+    fn rel_ptr_offset_overflow_while_serializing() {
+        use rkyv::{
+            rancor::BoxedError,
+            ser::{Positional, Writer},
+            util::serialize_rel_ptr,
+        };
+
+        // Skips 3 GiB after the first write without storing anything
+        struct SparseWriter {
+            pos: usize,
+            gap: usize,
+        }
+
+        impl Positional for SparseWriter {
+            fn pos(&self) -> usize {
+                self.pos
+            }
+        }
+
+        impl<E> Writer<E> for SparseWriter {
+            fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+                self.pos += bytes.len() + core::mem::take(&mut self.gap);
+                Ok(())
+            }
+        }
+
+        let mut writer = SparseWriter {
+            pos: 0,
+            gap: 3 << 30,
+        };
+        let error =
+            serialize_rel_ptr::<str, _, BoxedError>("hello", &mut writer)
+                .unwrap_err();
+        assert!(error.to_string().contains("too far for the offset type"));
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_shared_ptr() {
@@ -872,6 +1906,109 @@ mod tests {
         assert_eq!(Rc::weak_count(&deserialized.b), 0);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn writer_large_padding() {
+        use rkyv::ser::{Positional as _, WriterExt as _};
+
+        let mut writer = AlignedVec::new();
+        let writer = Strategy::<_, Failure>::wrap(&mut writer);
+
+        writer.write(&[1]).unwrap();
+        assert_eq!(writer.align(64).unwrap(), 64);
+        writer.pad(100).unwrap();
+        assert_eq!(writer.pos(), 164);
+        assert_eq!(writer.align(4096).unwrap(), 4096);
+
+        assert_eq!(writer[0], 1);
+        assert!(writer[1..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn reuse_serializer() {
+        use rkyv::{ser::AllocSerializer, util::serialize};
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            a: Rc<u32>,
+            b: Rc<u32>,
+            name: String,
+        }
+
+        let shared = Rc::new(10);
+        let value = Test {
+            a: shared.clone(),
+            b: shared.clone(),
+            name: "hello world".to_string(),
+        };
+
+        let mut serializer = AllocSerializer::<256>::default();
+        for _ in 0..3 {
+            serializer.reset();
+            serialize::<_, _, Failure>(&value, &mut serializer).unwrap();
+
+            // The shared pointers from previous values must be forgotten, or
+            // else they would point into the previous output
+            let expected = to_bytes::<_, 256, Failure>(&value).unwrap();
+            assert_eq!(serializer.writer.as_slice(), expected.as_slice());
+
+            let archived = unsafe {
+                access_unchecked::<Test>(serializer.writer.as_slice())
+            };
+            assert_eq!(*archived.a, 10);
+            assert_eq!(*archived.b, 10);
+            assert_eq!(archived.name, "hello world");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_shared_arc() {
+        #[derive(Debug, PartialEq, Archive, Deserialize, Serialize)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            shared: Vec<Arc<String>>,
+            unique: Arc<String>,
+        }
+
+        let shared = Arc::new("hello world".to_string());
+        let value = Test {
+            shared: vec![shared.clone(), shared.clone(), shared.clone()],
+            unique: Arc::new("hello world".to_string()),
+        };
+
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(buf.as_ref()) };
+        assert_eq!(archived, &value);
+
+        // Every clone of the shared pointer points at the same archived value
+        let first = archived.shared[0].get() as *const Archived<String>;
+        for ptr in archived.shared.iter() {
+            assert_eq!(ptr.get() as *const Archived<String>, first);
+        }
+        // Distinct pointers still get their own archived value
+        assert_ne!(archived.unique.get() as *const Archived<String>, first);
+
+        let mut deserializer = DefaultDeserializer::default();
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut deserializer)
+                .unwrap();
+        core::mem::drop(deserializer);
+        assert_eq!(deserialized, value);
+        assert!(Arc::ptr_eq(
+            &deserialized.shared[0],
+            &deserialized.shared[1]
+        ));
+        assert!(Arc::ptr_eq(
+            &deserialized.shared[0],
+            &deserialized.shared[2]
+        ));
+        assert!(!Arc::ptr_eq(&deserialized.shared[0], &deserialized.unique));
+        assert_eq!(Arc::strong_count(&deserialized.shared[0]), 3);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_unsized_shared_ptr() {
@@ -1068,14 +2205,68 @@ mod tests {
             }
         }
 
-        let value = Test {
-            a: 42,
-            b: Some(12),
-            c: "hello world".to_string(),
-            d: vec![1, 2, 3, 4],
+        let value = Test {
+            a: 42,
+            b: Some(12),
+            c: "hello world".to_string(),
+            d: vec![1, 2, 3, 4],
+        };
+
+        test_archive(&value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_resolver_visibility() {
+        use rkyv::ser::WriterExt as _;
+
+        mod types {
+            use rkyv::{Archive, Serialize};
+
+            #[derive(Archive, Serialize)]
+            #[archive(resolver = "LogResolver")]
+            pub struct Log {
+                pub level: u8,
+                pub message: String,
+            }
+
+            #[derive(Archive, Serialize)]
+            pub struct Pair(pub String, pub u32);
+        }
+        use types::{Log, LogResolver, Pair, PairResolver};
+
+        // Resolvers can be built outside of the module that defines them
+        let value = Log {
+            level: 3,
+            message: "disk full".to_string(),
         };
-
-        test_archive(&value);
+        let mut serializer = DefaultSerializer::default();
+        let strategy = Strategy::<_, Failure>::wrap(&mut serializer);
+        let resolver = LogResolver {
+            level: (),
+            message: value.message.serialize(strategy).unwrap(),
+        };
+        strategy.align_for::<Archived<Log>>().unwrap();
+        unsafe {
+            strategy.resolve_aligned(&value, resolver).unwrap();
+        }
+        let bytes = serializer.into_writer();
+        let archived = unsafe { access_unchecked::<Log>(&bytes) };
+        assert_eq!(archived.level, 3);
+        assert_eq!(archived.message, "disk full");
+
+        let value = Pair("answer".to_string(), 42);
+        let mut serializer = DefaultSerializer::default();
+        let strategy = Strategy::<_, Failure>::wrap(&mut serializer);
+        let resolver = PairResolver(value.0.serialize(strategy).unwrap(), ());
+        strategy.align_for::<Archived<Pair>>().unwrap();
+        unsafe {
+            strategy.resolve_aligned(&value, resolver).unwrap();
+        }
+        let bytes = serializer.into_writer();
+        let archived = unsafe { access_unchecked::<Pair>(&bytes) };
+        assert_eq!(archived.0, "answer");
+        assert_eq!(archived.1, 42);
     }
 
     #[test]
@@ -1103,6 +2294,96 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn compare_partial_ord() {
+        use core::cmp::Ordering;
+
+        #[derive(
+            Archive, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord,
+        )]
+        #[archive(compare(PartialEq, PartialOrd))]
+        struct Key {
+            group: u32,
+            name: String,
+        }
+
+        #[derive(
+            Archive, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord,
+        )]
+        #[archive(compare(PartialEq, PartialOrd))]
+        struct Pair(i32, i32);
+
+        #[derive(
+            Archive, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord,
+        )]
+        #[archive(compare(PartialEq, PartialOrd))]
+        enum Event {
+            Start(u32),
+            Stop { code: u8, pair: Pair },
+            Idle,
+        }
+
+        fn check<T>(values: &[T])
+        where
+            T: Ord
+                + PartialOrd<T::Archived>
+                + Serialize<Strategy<DefaultSerializer, Failure>>,
+            T::Archived: PartialOrd<T>,
+        {
+            for a in values {
+                let buf = serialize_into(a, DefaultSerializer::default())
+                    .unwrap()
+                    .into_writer();
+                let archived = unsafe { access_unchecked::<T>(buf.as_ref()) };
+                for b in values {
+                    assert_eq!(archived.partial_cmp(b), Some(a.cmp(b)));
+                    assert_eq!(
+                        PartialOrd::<T::Archived>::partial_cmp(b, archived),
+                        Some(b.cmp(a))
+                    );
+                }
+            }
+        }
+
+        let key = |group, name: &str| Key {
+            group,
+            name: name.to_string(),
+        };
+        check(&[key(1, "b"), key(1, "a"), key(0, "z"), key(2, "a")]);
+        check(&[Pair(1, 2), Pair(2, 1), Pair(1, 1), Pair(-1, 3)]);
+        check(&[
+            Event::Idle,
+            Event::Stop {
+                code: 2,
+                pair: Pair(0, 1),
+            },
+            Event::Stop {
+                code: 2,
+                pair: Pair(0, 0),
+            },
+            Event::Start(4),
+            Event::Start(3),
+        ]);
+
+        // Sorted archived keys can be searched with live keys
+        let mut keys = vec![key(3, "c"), key(1, "b"), key(1, "a"), key(2, "z")];
+        keys.sort();
+        let buf = to_bytes::<_, 256, Failure>(&keys).unwrap();
+        let archived = unsafe { access_unchecked::<Vec<Key>>(buf.as_ref()) };
+        let search = |probe: &Key| {
+            archived.binary_search_by(|k| k.partial_cmp(probe).unwrap())
+        };
+        assert_eq!(search(&key(1, "b")), Ok(1));
+        assert_eq!(search(&key(2, "z")), Ok(2));
+        assert_eq!(search(&key(2, "a")), Err(2));
+        assert_eq!(search(&key(0, "a")), Err(0));
+        assert_eq!(
+            archived[0].partial_cmp(&key(1, "a")),
+            Some(Ordering::Equal),
+        );
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn default_type_parameters() {
@@ -1134,6 +2415,39 @@ mod tests {
         check::<AlignedVec, Failure>();
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn buffer_writer_overflow() {
+        use rkyv::rancor::BoxedError;
+
+        let value = "a string which is too long to fit in the buffer";
+        let error = serialize_into::<_, _, BoxedError>(
+            &value.to_string(),
+            BufferWriter::new(AlignedBytes([0u8; 16])),
+        )
+        .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains(&format!("writing {} bytes", value.len())));
+        assert!(message.contains("len is 16"));
+
+        // The same value fits in a larger buffer
+        serialize_into::<_, _, BoxedError>(
+            &value.to_string(),
+            BufferWriter::new(AlignedBytes([0u8; 64])),
+        )
+        .unwrap();
+
+        // Writes are rejected after moving past the end of the buffer, even
+        // when the end of the write overflows `usize`
+        let mut writer = BufferWriter::new(AlignedBytes([0u8; 16]));
+        writer.set_pos(32);
+        Writer::<BoxedError>::write(&mut writer, &[1, 2, 3, 4]).unwrap_err();
+        writer.set_pos(usize::MAX - 1);
+        let error = Writer::<BoxedError>::write(&mut writer, &[1, 2, 3, 4])
+            .unwrap_err();
+        assert!(error.to_string().contains("len is 16"));
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn buffer_serializer_zeroes_padding() {
@@ -1218,6 +2532,12 @@ mod tests {
         .unwrap();
         let buf = serializer.into_writer();
 
+        // The tag is stored in the byte order of the archive on every host
+        #[cfg(feature = "little_endian")]
+        assert_eq!(buf.as_slice(), [0x00, 0x01]);
+        #[cfg(feature = "big_endian")]
+        assert_eq!(buf.as_slice(), [0x01, 0x00]);
+
         let archived =
             unsafe { access_unchecked::<ReallyBigEnum>(buf.as_ref()) };
         assert_eq!(archived, &ArchivedReallyBigEnum::V100);
@@ -1270,30 +2590,82 @@ mod tests {
         assert_eq!(core::mem::size_of::<ArchivedTestStruct>(), 6);
     }
 
-    // TODO: fix derive handling of explicit archived enum reprs
-    // #[test]
-    // #[cfg(not(feature = "strict"))]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn repr_int() {
-    //     #[derive(Archive)]
-    //     #[allow(dead_code)]
-    //     pub enum InferredRepr {
-    //         V0,
-    //         V1,
-    //     }
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn repr_int() {
+        #[derive(Archive)]
+        #[allow(dead_code)]
+        pub enum InferredRepr {
+            V0,
+            V1,
+        }
 
-    //     assert_eq!(core::mem::size_of::<ArchivedInferredRepr>(), 1);
+        assert_eq!(core::mem::size_of::<ArchivedInferredRepr>(), 1);
 
-    //     #[derive(Archive)]
-    //     #[archive_attr(repr(u16))]
-    //     #[allow(dead_code)]
-    //     pub enum ExplicitRepr {
-    //         V0,
-    //         V1,
-    //     }
+        #[derive(Archive)]
+        #[archive(repr(u16))]
+        #[allow(dead_code)]
+        pub enum ExplicitRepr {
+            V0,
+            V1,
+        }
 
-    //     assert_eq!(core::mem::size_of::<ArchivedExplicitRepr>(), 2);
-    // }
+        assert_eq!(core::mem::size_of::<ArchivedExplicitRepr>(), 2);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive(repr(u8))]
+        #[archive_attr(derive(Debug))]
+        pub enum ExplicitDiscriminants {
+            A = 10,
+            B,
+            C = 200,
+        }
+
+        assert_eq!(ArchivedExplicitDiscriminants::A as u8, 10);
+        assert_eq!(ArchivedExplicitDiscriminants::B as u8, 11);
+        assert_eq!(ArchivedExplicitDiscriminants::C as u8, 200);
+
+        let buf =
+            to_bytes::<_, 256, Failure>(&ExplicitDiscriminants::C).unwrap();
+        assert_eq!(buf.as_slice(), &[200]);
+        test_archive(&ExplicitDiscriminants::B);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive(repr(i16))]
+        #[archive_attr(derive(Debug))]
+        #[repr(i16)]
+        pub enum Signed {
+            Negative(u8) = -300,
+            Positive { value: u8 } = 300,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedSigned>(), 4);
+        test_archive(&Signed::Negative(1));
+        test_archive(&Signed::Positive { value: 2 });
+
+        // Discriminants that don't fit in a byte widen the inferred repr
+        #[derive(Archive)]
+        #[allow(dead_code)]
+        pub enum Widened {
+            A = 1,
+            B = 1000,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedWidened>(), 2);
+
+        // The repr of the original enum doesn't widen the archived tag
+        #[derive(Archive)]
+        #[repr(u32)]
+        #[allow(dead_code)]
+        pub enum NativeRepr {
+            A(u8),
+            B,
+        }
+
+        assert_eq!(core::mem::size_of::<ArchivedNativeRepr>(), 2);
+    }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
@@ -1434,7 +2806,7 @@ mod tests {
         use core::{convert::Infallible, str::FromStr};
         use rkyv::{
             access_unchecked, deserialize,
-            rancor::{Failure, Fallible},
+            rancor::{Error, Failure, Fallible},
             ser::Writer,
             util::{serialize_into, AlignedVec},
             with::{ArchiveWith, DeserializeWith, SerializeWith},
@@ -1460,8 +2832,11 @@ mod tests {
             }
         }
 
-        impl<T: ToString, S: Fallible + Writer + ?Sized> SerializeWith<T, S>
-            for ConvertToString
+        impl<T, S> SerializeWith<T, S> for ConvertToString
+        where
+            T: ToString,
+            S: Fallible + Writer + ?Sized,
+            S::Error: Error,
         {
             fn serialize_with(
                 value: &T,
@@ -1535,6 +2910,36 @@ mod tests {
             assert_eq!(deserialized.1, 10);
         }
 
+        #[test]
+        #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+        fn with_archive_attribute() {
+            #[derive(Archive, Serialize, Deserialize)]
+            struct Test {
+                #[archive(with = "ConvertToString")]
+                value: i32,
+                #[archive(rename = "renamed", with = "ConvertToString")]
+                other: i32,
+            }
+
+            let value = Test {
+                value: 10,
+                other: 20,
+            };
+            let result =
+                serialize_into::<_, _, Failure>(&value, AlignedVec::new())
+                    .unwrap();
+            let archived =
+                unsafe { access_unchecked::<Test>(result.as_slice()) };
+
+            assert_eq!(archived.value, "10");
+            assert_eq!(archived.renamed, "20");
+
+            let deserialized =
+                deserialize::<Test, _, Infallible>(archived, &mut ()).unwrap();
+            assert_eq!(deserialized.value, 10);
+            assert_eq!(deserialized.other, 20);
+        }
+
         #[test]
         #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
         fn with_enum() {
@@ -1631,6 +3036,44 @@ mod tests {
         test_archive(&value);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_atomics() {
+        use core::sync::atomic::{
+            AtomicBool, AtomicI16, AtomicU64, AtomicUsize, Ordering,
+        };
+
+        #[derive(Archive, Debug, Deserialize, Serialize)]
+        struct Metrics {
+            healthy: AtomicBool,
+            offset: AtomicI16,
+            requests: AtomicU64,
+            active: AtomicUsize,
+        }
+
+        let value = Metrics {
+            healthy: AtomicBool::new(true),
+            offset: AtomicI16::new(-3),
+            requests: AtomicU64::new(12345678901),
+            active: AtomicUsize::new(7),
+        };
+        value.requests.fetch_add(1, Ordering::Relaxed);
+
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Metrics>(buf.as_ref()) };
+        assert!(archived.healthy);
+        assert_eq!(archived.offset, -3);
+        assert_eq!(archived.requests, 12345678902);
+        assert_eq!(archived.active, 7);
+
+        let deserialized =
+            deserialize::<Metrics, _, Failure>(archived, &mut ()).unwrap();
+        assert!(deserialized.healthy.load(Ordering::Relaxed));
+        assert_eq!(deserialized.offset.load(Ordering::Relaxed), -3);
+        assert_eq!(deserialized.requests.load(Ordering::Relaxed), 12345678902);
+        assert_eq!(deserialized.active.load(Ordering::Relaxed), 7);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn with_as_atomic() {
@@ -1653,99 +3096,367 @@ mod tests {
             access_unchecked_mut::<Test>(Pin::new(result.as_mut_slice()))
         };
 
-        assert_eq!(archived.value.load(Ordering::Relaxed), 42);
+        assert_eq!(archived.value.load(Ordering::Relaxed), 42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_inline() {
+        use rkyv::with::Inline;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test<'a> {
+            #[with(Inline)]
+            value: &'a i32,
+        }
+
+        let a = 42;
+        let value = Test { value: &a };
+        let result =
+            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.value, 42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_boxed() {
+        use rkyv::with::Boxed;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test {
+            #[with(Boxed)]
+            value: i32,
+        }
+
+        let value = Test { value: 42 };
+        let result =
+            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.value.get(), &42);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_boxed_inline() {
+        use rkyv::with::BoxedInline;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test<'a> {
+            #[with(BoxedInline)]
+            value: &'a str,
+        }
+
+        let a = "hello world";
+        let value = Test { value: &a };
+        let result =
+            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.value.as_ref(), "hello world");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_as_owned() {
+        use rkyv::with::AsOwned;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Test<'a> {
+            #[with(AsOwned)]
+            a: Cow<'a, u32>,
+            #[with(AsOwned)]
+            b: Cow<'a, [u32]>,
+            #[with(AsOwned)]
+            c: Cow<'a, str>,
+        }
+
+        let value = Test {
+            a: Cow::Borrowed(&100),
+            b: Cow::Borrowed(&[1, 2, 3, 4, 5, 6]),
+            c: Cow::Borrowed("hello world"),
+        };
+        let result = serialize_into::<_, _, Failure>(
+            &value,
+            DefaultSerializer::default(),
+        )
+        .unwrap()
+        .into_writer();
+        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.a, 100);
+        assert_eq!(archived.b, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(archived.c, "hello world");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_inline_string() {
+        use core::mem::size_of;
+        use rkyv::{
+            primitive::ArchivedUsize, string::ArchivedInlineString,
+            with::InlineString,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(InlineString<16>)]
+            short: String,
+            #[with(InlineString<16>)]
+            exact: String,
+            #[with(InlineString<16>)]
+            long: String,
+            #[with(InlineString<16>)]
+            empty: String,
+        }
+
+        assert_eq!(
+            size_of::<ArchivedInlineString<16>>(),
+            size_of::<ArchivedUsize>() + 16,
+        );
+
+        let value = Test {
+            short: "hello".to_string(),
+            exact: "sixteen bytes!!!".to_string(),
+            long: "this string does not fit inline".to_string(),
+            empty: String::new(),
+        };
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(&bytes) };
+
+        assert!(archived.short.is_inline());
+        assert_eq!(archived.short, "hello");
+        assert!(archived.exact.is_inline());
+        assert_eq!(archived.exact, "sixteen bytes!!!");
+        assert!(!archived.long.is_inline());
+        assert_eq!(archived.long, "this string does not fit inline");
+        assert_eq!(archived.long.len(), 31);
+        assert!(archived.empty.is_inline());
+        assert!(archived.empty.is_empty());
+
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[cfg(feature = "pointer_width_16")]
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_inline_string_offset_overflow() {
+        use rkyv::with::InlineString;
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            #[with(InlineString<16>)]
+            long: String,
+        }
+
+        // The offset to the bytes of the string doesn't fit in an i16
+        let value = Test {
+            long: "a".repeat(40_000),
+        };
+        to_bytes::<_, 256, Failure>(&value).unwrap_err();
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_inline() {
-        use rkyv::with::Inline;
-
-        #[derive(Archive, Serialize, Deserialize)]
+    fn archive_cow() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
         struct Test<'a> {
-            #[with(Inline)]
-            value: &'a i32,
+            a: Cow<'a, str>,
+            b: Cow<'a, [u8]>,
         }
 
-        let a = 42;
-        let value = Test { value: &a };
-        let result =
-            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
-        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+        let borrowed = Test {
+            a: Cow::Borrowed("hello world"),
+            b: Cow::Borrowed(&[1, 2, 3, 4, 5, 6]),
+        };
+        let owned = Test {
+            a: Cow::Owned("hello world".to_string()),
+            b: Cow::Owned(vec![1, 2, 3, 4, 5, 6]),
+        };
 
-        assert_eq!(archived.value, 42);
+        // Both variants archive to the same bytes
+        let borrowed_bytes = to_bytes::<_, 256, Failure>(&borrowed).unwrap();
+        let owned_bytes = to_bytes::<_, 256, Failure>(&owned).unwrap();
+        assert_eq!(borrowed_bytes.as_slice(), owned_bytes.as_slice());
+
+        let archived =
+            unsafe { access_unchecked::<Test>(borrowed_bytes.as_slice()) };
+        assert!(archived == &borrowed);
+        assert!(archived == &owned);
+        assert_eq!(archived.a.as_str(), "hello world");
+        assert_eq!(archived.b.as_slice(), &[1, 2, 3, 4, 5, 6]);
+
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut ()).unwrap();
+        assert!(matches!(deserialized.a, Cow::Owned(_)));
+        assert!(matches!(deserialized.b, Cow::Owned(_)));
+        assert_eq!(deserialized, borrowed);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_boxed() {
-        use rkyv::with::Boxed;
+    fn with_capacity() {
+        use rkyv::with::WithCapacity;
 
-        #[derive(Archive, Serialize, Deserialize)]
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(pin_fields)]
         struct Test {
-            #[with(Boxed)]
-            value: i32,
+            #[with(WithCapacity)]
+            values: Vec<u32>,
+            #[with(WithCapacity)]
+            names: Vec<String>,
         }
 
-        let value = Test { value: 42 };
-        let result =
-            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
-        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+        let mut values = Vec::with_capacity(8);
+        values.extend([1, 2, 3]);
+        let mut names = Vec::with_capacity(2);
+        names.push("hello".to_string());
+        let value = Test { values, names };
 
-        assert_eq!(archived.value.get(), &42);
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let len = buf.len();
+        let mut archived =
+            unsafe { access_unchecked_mut::<Test>(Pin::new(buf.as_mut())) };
+        assert_eq!(archived.values.len(), 3);
+        assert_eq!(archived.values.capacity(), 8);
+        assert_eq!(archived.names.capacity(), 2);
+        assert_eq!(archived.names[0], "hello");
+
+        let mut values = archived.as_mut().values_pin();
+        for i in 4..=8 {
+            values.as_mut().push(i.into()).unwrap();
+        }
+        assert_eq!(values.as_mut().push(9.into()), Err(9.into()));
+        assert_eq!(*values, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        // Pushing never changes the size of the buffer
+        assert_eq!(buf.len(), len);
+        let archived = unsafe { access_unchecked::<Test>(buf.as_ref()) };
+        assert_eq!(archived.values, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized.values, [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(deserialized.values.capacity(), 8);
+        assert_eq!(deserialized.names, value.names);
+        assert!(deserialized.names.capacity() >= 2);
     }
 
+    #[cfg(feature = "pointer_width_64")]
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_boxed_inline() {
-        use rkyv::with::BoxedInline;
+    fn with_capacity_overflow() {
+        use rkyv::vec::ArchivedCapacityVec;
+
+        // The capacity fits in an archived usize, but its size in bytes does
+        // not fit in a usize
+        let mut serializer = DefaultSerializer::default();
+        let strategy = Strategy::<_, Failure>::wrap(&mut serializer);
+        let result = ArchivedCapacityVec::<Archived<u32>>::serialize_from_slice(
+            &[1u32, 2, 3],
+            usize::MAX / 2,
+            strategy,
+        );
+        assert!(result.is_err());
+    }
 
-        #[derive(Archive, Serialize, Deserialize)]
-        struct Test<'a> {
-            #[with(BoxedInline)]
-            value: &'a str,
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_packed_bits() {
+        use rkyv::with::PackedBits;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(PackedBits)]
+            empty: Vec<bool>,
+            #[with(PackedBits)]
+            flags: Vec<bool>,
         }
 
-        let a = "hello world";
-        let value = Test { value: &a };
-        let result =
-            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
-        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+        let value = Test {
+            empty: Vec::new(),
+            flags: (0..1000).map(|i| i % 7 == 0 || i % 5 == 1).collect(),
+        };
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(buf.as_ref()) };
 
-        assert_eq!(archived.value.as_ref(), "hello world");
+        assert!(archived.empty.is_empty());
+        assert_eq!(archived.flags.len(), 1000);
+        assert_eq!(archived.flags.as_bytes().len(), 125);
+        for (i, &flag) in value.flags.iter().enumerate() {
+            assert_eq!(archived.flags.get(i), flag);
+        }
+        assert_eq!(archived.flags, *value.flags.as_slice());
+
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
     }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn with_as_owned() {
-        use rkyv::with::AsOwned;
+    fn with_chunked_string() {
+        use rkyv::with::Chunked;
 
-        #[derive(Archive, Serialize, Deserialize)]
-        struct Test<'a> {
-            #[with(AsOwned)]
-            a: Cow<'a, u32>,
-            #[with(AsOwned)]
-            b: Cow<'a, [u32]>,
-            #[with(AsOwned)]
-            c: Cow<'a, str>,
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(Chunked<8>)]
+            empty: String,
+            #[with(Chunked<1>)]
+            a: String,
+            #[with(Chunked<3>)]
+            b: String,
+            #[with(Chunked<7>)]
+            c: String,
         }
 
+        // One-, two-, three-, and four-byte chars split across chunks
+        let text = "a\u{e9}\u{2603}\u{1f980}\"b\"\u{e9}\u{1f980}\u{2603}cd";
         let value = Test {
-            a: Cow::Borrowed(&100),
-            b: Cow::Borrowed(&[1, 2, 3, 4, 5, 6]),
-            c: Cow::Borrowed("hello world"),
+            empty: String::new(),
+            a: text.to_string(),
+            b: text.to_string(),
+            c: text.to_string(),
         };
-        let result = serialize_into::<_, _, Failure>(
-            &value,
-            DefaultSerializer::default(),
-        )
-        .unwrap()
-        .into_writer();
-        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(buf.as_ref()) };
 
-        assert_eq!(archived.a, 100);
-        assert_eq!(archived.b, [1, 2, 3, 4, 5, 6]);
-        assert_eq!(archived.c, "hello world");
+        assert!(archived.empty.is_empty());
+        assert_eq!(archived.empty.chunk_count(), 0);
+        assert_eq!(archived.empty.to_string(), "");
+
+        for (string, chunk_size) in
+            [(&archived.a, 1), (&archived.b, 3), (&archived.c, 7)]
+        {
+            assert_eq!(string.len(), text.len());
+            assert_eq!(string.chunk_size(), chunk_size);
+            assert_eq!(string.chunk_count(), text.len().div_ceil(chunk_size));
+            assert_eq!(string.chunk(0), Some(&text.as_bytes()[..chunk_size]));
+            for i in 0..=text.len() {
+                assert_eq!(string.get_byte(i), text.as_bytes().get(i).copied());
+                assert_eq!(
+                    string.is_char_boundary(i),
+                    text.is_char_boundary(i)
+                );
+                let c = text.get(i..).and_then(|rest| rest.chars().next());
+                assert_eq!(string.char_at(i), c);
+            }
+            assert!(string.chars().eq(text.chars()));
+            assert!(string.bytes().eq(text.bytes()));
+            assert_eq!(string.to_string(), text);
+            assert_eq!(format!("{:?}", string), format!("{:?}", text));
+            assert_eq!(string, text);
+        }
+        assert_eq!(archived.a, archived.c);
+
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
     }
 
     #[test]
@@ -1875,6 +3586,37 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_niche_char() {
+        use core::mem::size_of;
+        use rkyv::{primitive::ArchivedChar, with::Niche};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(Niche)]
+            a: Option<char>,
+            #[with(Niche)]
+            b: Option<char>,
+        }
+
+        assert_eq!(size_of::<Archived<Test>>(), 2 * size_of::<ArchivedChar>());
+
+        let value = Test {
+            a: Some('\u{10ffff}'),
+            b: None,
+        };
+        let result = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(result.as_slice()) };
+
+        assert_eq!(archived.a, Some('\u{10ffff}'));
+        assert!(archived.b.is_none());
+
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn with_niche_nonzero() {
@@ -2137,6 +3879,46 @@ mod tests {
         assert_eq!(value, deserialized);
     }
 
+    #[test]
+    // This test is unfortunately too slow to run through miri
+    #[cfg_attr(miri, ignore)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    // This test creates structures too big to fit in 16-bit offsets
+    #[cfg(not(feature = "pointer_width_16"))]
+    fn archive_btree_map_range() {
+        use core::ops::Bound::{self, Excluded, Included, Unbounded};
+
+        let mut value = BTreeMap::new();
+        for i in 0..10_000 {
+            value.insert(format!("{:05}", i * 2), i);
+        }
+
+        let result =
+            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
+        let archived = unsafe {
+            access_unchecked::<BTreeMap<String, i32>>(result.as_slice())
+        };
+
+        let check = |range: (Bound<&str>, Bound<&str>)| {
+            let expected = value.range::<str, _>(range);
+            let actual = archived.range::<str, _>(range);
+            assert!(expected
+                .map(|(k, v)| (k.as_str(), *v))
+                .eq(actual.map(|(k, v)| (k.as_str(), v.to_native()))));
+        };
+
+        check((Unbounded, Unbounded));
+        check((Included("00100"), Excluded("00200")));
+        check((Included("00101"), Included("00199")));
+        check((Included("05000"), Unbounded));
+        check((Unbounded, Included("07777")));
+        check((Included("19998"), Unbounded));
+        check((Included("20000"), Unbounded));
+        check((Included("00007"), Excluded("00007")));
+        check((Excluded("01000"), Excluded("01002")));
+        check((Excluded("01000"), Unbounded));
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_empty_btree_map() {
@@ -2194,7 +3976,7 @@ mod tests {
     #[cfg_attr(miri, ignore)]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     // This test creates structures too big to fit in 16-bit offsets
-    #[cfg(not(feature = "size_16"))]
+    #[cfg(not(feature = "pointer_width_16"))]
     fn archive_btree_map_large() {
         let mut value = BTreeMap::new();
         for i in 0..100_000 {
@@ -2347,4 +4129,326 @@ mod tests {
 
         drop(ManuallyDrop::into_inner(vec));
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_cells() {
+        use core::cell::{Cell, RefCell};
+
+        test_archive_with(&Cell::new(42u32), |a, b| a.get() == *b);
+        test_archive_with(&RefCell::new(vec![1u32, 2, 3]), |a, b| {
+            a.borrow().as_slice() == b.as_slice()
+        });
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive_attr(derive(Debug))]
+        struct Config {
+            hits: Cell<u64>,
+            cache: RefCell<Vec<String>>,
+        }
+
+        let value = Config {
+            hits: Cell::new(7),
+            cache: RefCell::new(vec!["computed".to_string()]),
+        };
+        test_archive_with(&value, |a, b| {
+            b.hits == a.hits.get() && b.cache == *a.cache.borrow()
+        });
+
+        // Cells are archived as a snapshot of their current contents
+        value.cache.borrow_mut().push("later".to_string());
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Config>(&bytes) };
+        assert_eq!(archived.cache.len(), 2);
+
+        // A mutably borrowed `RefCell` can't be serialized
+        let guard = value.cache.borrow_mut();
+        to_bytes::<_, 256, Failure>(&value)
+            .expect_err("expected mutably borrowed error");
+        drop(guard);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_minimize_padding() {
+        use core::mem::{offset_of, size_of};
+
+        #[derive(Archive)]
+        #[cfg_attr(not(feature = "strict"), archive_attr(repr(C)))]
+        struct Declared {
+            flag: u8,
+            value: u64,
+            small: [u16; 3],
+            ch: char,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(minimize_padding, compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Minimized {
+            flag: u8,
+            value: u64,
+            small: [u16; 3],
+            ch: char,
+        }
+
+        assert!(
+            size_of::<Archived<Minimized>>() < size_of::<Archived<Declared>>()
+        );
+
+        let value = Minimized {
+            flag: 1,
+            value: 2,
+            small: [3, 4, 5],
+            ch: 'x',
+        };
+        test_archive(&value);
+
+        // The archived fields are laid out by descending alignment
+        let value = offset_of!(ArchivedMinimized, value);
+        let ch = offset_of!(ArchivedMinimized, ch);
+        let small = offset_of!(ArchivedMinimized, small);
+        let flag = offset_of!(ArchivedMinimized, flag);
+        assert_eq!(value, 0);
+        assert!(value < ch && ch < small && small < flag);
+
+        // Fields with an unknown alignment keep the layout the type would
+        // have without minimizing padding, which is never larger
+        #[derive(Archive)]
+        struct DeclaredMixed {
+            flag: u8,
+            value: u64,
+            small: u16,
+            name: String,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(minimize_padding, compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Mixed {
+            flag: u8,
+            value: u64,
+            small: u16,
+            name: String,
+        }
+
+        assert_eq!(
+            size_of::<Archived<Mixed>>(),
+            size_of::<Archived<DeclaredMixed>>()
+        );
+
+        test_archive(&Mixed {
+            flag: 1,
+            value: 2,
+            small: 3,
+            name: "hello".to_string(),
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_field_align() {
+        use core::mem::{align_of, offset_of};
+        use rkyv::util::{Align32, Aligned};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Vectors {
+            id: u8,
+            #[archive(align = 32)]
+            lanes: [u8; 64],
+            #[archive(align = 16, rename = "renamed_tail")]
+            tail: u8,
+            name: String,
+        }
+
+        assert_eq!(align_of::<ArchivedVectors>(), 32);
+        assert_eq!(offset_of!(ArchivedVectors, lanes) % 32, 0);
+        assert_eq!(offset_of!(ArchivedVectors, renamed_tail) % 16, 0);
+
+        let mut lanes = [0u8; 64];
+        for (i, lane) in lanes.iter_mut().enumerate() {
+            *lane = i as u8;
+        }
+        let value = Vectors {
+            id: 1,
+            lanes,
+            tail: 2,
+            name: "simd".to_string(),
+        };
+
+        // The field is aligned in any buffer that is aligned to at least the
+        // alignment of the archived type. `AlignedVec` only guarantees an
+        // alignment of 16, so the archive is copied into a buffer with a
+        // larger alignment before it's accessed.
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let mut buffer = Aligned::<Align32, _>::new([0u8; 256]);
+        buffer[..bytes.len()].copy_from_slice(&bytes);
+        let archived =
+            unsafe { access_unchecked::<Vectors>(&buffer[..bytes.len()]) };
+        assert_eq!(archived.lanes.as_ptr() as usize % 32, 0);
+        assert_eq!(archived.lanes, lanes);
+        assert_eq!(archived.renamed_tail, 2);
+        assert_eq!(archived, &value);
+
+        let deserialized =
+            deserialize::<Vectors, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_vec_mut() {
+        #[derive(Archive, Serialize)]
+        #[archive(pin_fields)]
+        struct Counters {
+            name: String,
+            counts: Vec<u32>,
+        }
+
+        let value = Counters {
+            name: "requests".to_string(),
+            counts: vec![1, 2, 3, 4],
+        };
+
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let mut archived =
+            unsafe { access_unchecked_mut::<Counters>(Pin::new(buf.as_mut())) };
+
+        *archived.as_mut().counts_pin().get_mut_at(1).unwrap() += 10;
+        assert!(archived.as_mut().counts_pin().get_mut_at(4).is_none());
+        for count in archived.as_mut().counts_pin().get_mut_at(2..).unwrap() {
+            *count = 0.into();
+        }
+        archived.as_mut().counts_pin().as_mut_slice().swap(0, 1);
+
+        assert_eq!(archived.counts.as_slice(), &[12, 1, 0, 0]);
+        assert_eq!(archived.name, "requests");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_non_copy_arrays() {
+        test_archive(&["a".to_string(), "b".to_string()]);
+        test_archive(&[vec![1u32, 2], vec![], vec![3]]);
+
+        // Each element is archived inline in the archived array
+        let value = ["hello".to_string(), "world".to_string()];
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<[String; 2]>(buf.as_ref()) };
+        assert_eq!(
+            core::mem::size_of_val(archived),
+            2 * core::mem::size_of::<Archived<String>>(),
+        );
+        assert_eq!(archived[0], "hello");
+        assert_eq!(archived[1], "world");
+        let deserialized =
+            deserialize::<[String; 2], _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[cfg(any(
+        all(feature = "little_endian", target_endian = "little"),
+        all(feature = "big_endian", target_endian = "big"),
+    ))]
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_unsafe_union() {
+        #[derive(Archive, Serialize, Deserialize, Clone, Copy)]
+        #[archive(unsafe_union)]
+        #[repr(C)]
+        union Value {
+            i: i64,
+            f: f64,
+            bytes: [u8; 8],
+        }
+
+        unsafe impl rkyv::FullyInitialized for Value {}
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Cell {
+            tag: u8,
+            value: Value,
+        }
+
+        let cells = vec![
+            Cell {
+                tag: 0,
+                value: Value { i: -42 },
+            },
+            Cell {
+                tag: 1,
+                value: Value { f: 1.5 },
+            },
+            Cell {
+                tag: 2,
+                value: Value {
+                    bytes: *b"rkyv\0\0\0\0",
+                },
+            },
+        ];
+
+        let buf = to_bytes::<_, 256, Failure>(&cells).unwrap();
+        let archived = unsafe { access_unchecked::<Vec<Cell>>(buf.as_ref()) };
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived[0].tag, 0);
+        assert_eq!(unsafe { archived[0].value.i }, -42);
+        assert_eq!(unsafe { archived[1].value.f }, 1.5);
+        assert_eq!(unsafe { &archived[2].value.bytes }, b"rkyv\0\0\0\0");
+
+        let deserialized =
+            deserialize::<Vec<Cell>, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(unsafe { deserialized[0].value.i }, -42);
+        assert_eq!(unsafe { deserialized[1].value.f }, 1.5);
+        assert_eq!(unsafe { deserialized[2].value.bytes }, *b"rkyv\0\0\0\0");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn serialized_size_hint() {
+        #[derive(Archive, Serialize, Default)]
+        struct Named {
+            name: String,
+            values: Vec<u32>,
+            nickname: Option<String>,
+            #[archive(skip)]
+            #[allow(dead_code)]
+            cache: String,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct Unnamed(u64, String);
+
+        #[derive(Archive, Serialize)]
+        enum Shape {
+            Named { label: String, inner: Named },
+            Unnamed(u8, Vec<u16>),
+            Unit,
+        }
+
+        let named = Named {
+            name: "rkyv".to_string(),
+            values: vec![1, 2, 3],
+            nickname: Some("ab".to_string()),
+            cache: "not archived".to_string(),
+        };
+        assert_eq!(named.serialized_size_hint(), 4 + 3 * 4 + 2);
+        assert_eq!(Named::default().serialized_size_hint(), 0);
+        assert_eq!(Unnamed(1, "abc".to_string()).serialized_size_hint(), 3);
+
+        let shape = Shape::Named {
+            label: "x".to_string(),
+            inner: named,
+        };
+        assert_eq!(shape.serialized_size_hint(), 1 + 4 + 3 * 4 + 2);
+        assert_eq!(Shape::Unnamed(1, vec![1, 2]).serialized_size_hint(), 4);
+        assert_eq!(Shape::Unit.serialized_size_hint(), 0);
+
+        // The output of to_bytes is presized with the hint
+        let hint = core::mem::size_of::<ArchivedShape>()
+            + shape.serialized_size_hint();
+        let buf = to_bytes::<_, 256, Failure>(&shape).unwrap();
+        assert!(buf.capacity() >= hint);
+    }
 }
@@ -2,14 +2,86 @@
 mod tests {
     use crate::util::alloc::*;
     use rkyv::{
-        access_unchecked, rancor::Failure, ser::writer::IoWriter, serialize,
-        to_bytes, util::AlignedBytes, Archive, Deserialize, Serialize,
+        access_unchecked,
+        rancor::Failure,
+        ser::{writer::IoWriter, Positional},
+        serialize, to_bytes,
+        util::AlignedBytes,
+        Archive, Deserialize, Serialize,
     };
     use std::collections::{HashMap, HashSet};
 
     #[cfg(feature = "wasm")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_system_time() {
+        use rkyv::{deserialize, rancor::BoxedError};
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        let value = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<SystemTime>(buf.as_ref()) };
+        assert_eq!(archived.as_secs(), 1_700_000_000);
+        assert_eq!(archived.subsec_nanos(), 123_456_789);
+
+        let deserialized =
+            deserialize::<SystemTime, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Times before the UNIX epoch can't be archived
+        let before_epoch = UNIX_EPOCH - Duration::from_secs(1);
+        to_bytes::<_, 256, BoxedError>(&before_epoch)
+            .expect_err("expected time before UNIX epoch to fail");
+
+        // Durations too long to add to the UNIX epoch can't be deserialized
+        let buf =
+            to_bytes::<_, 256, Failure>(&Duration::new(u64::MAX, 0)).unwrap();
+        let archived = unsafe { access_unchecked::<SystemTime>(buf.as_ref()) };
+        let error = deserialize::<SystemTime, _, BoxedError>(archived, &mut ())
+            .unwrap_err();
+        assert!(error.to_string().contains("overflowed `SystemTime`"));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn io_writer_stream() {
+        use rkyv::util::{serialize_into, AlignedVec};
+
+        #[derive(Archive, Serialize)]
+        struct Example {
+            id: u32,
+            name: String,
+            tags: [String; 3],
+        }
+
+        let value = Example {
+            id: 7,
+            name: "a name that is stored out of line".to_string(),
+            tags: ["foo".to_string(), "bar".to_string(), "baz".to_string()],
+        };
+
+        let writer =
+            serialize_into::<_, _, Failure>(&value, IoWriter::new(Vec::new()))
+                .unwrap();
+        let root_pos = writer.pos() - core::mem::size_of::<ArchivedExample>();
+        let streamed = writer.into_inner();
+
+        let expected =
+            serialize_into::<_, _, Failure>(&value, AlignedVec::new()).unwrap();
+        assert_eq!(streamed.as_slice(), expected.as_slice());
+
+        let mut aligned = AlignedVec::new();
+        aligned.extend_from_slice(&streamed);
+        let archived = unsafe {
+            rkyv::util::access_pos_unchecked::<Example>(&aligned, root_pos)
+        };
+        assert_eq!(archived.id, 7);
+        assert_eq!(archived.name, "a name that is stored out of line");
+        assert_eq!(archived.tags[2], "baz");
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn write_serializer() {
@@ -226,6 +298,65 @@ mod tests {
         test_archive(&value);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_hash_matches_original() {
+        use rkyv::{rancor::Strategy, util::serialize_into};
+        use std::{
+            collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+            hash::{Hash, Hasher},
+        };
+
+        fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        fn check<T>(value: &T)
+        where
+            T: Hash + Serialize<Strategy<DefaultSerializer, Failure>>,
+            T::Archived: Hash,
+        {
+            let buf = serialize_into(value, DefaultSerializer::default())
+                .unwrap()
+                .into_writer();
+            let archived = unsafe { access_unchecked::<T>(buf.as_ref()) };
+            assert_eq!(hash_of(archived), hash_of(value));
+        }
+
+        #[derive(Archive, Serialize, Hash)]
+        #[archive_attr(derive(Hash))]
+        struct Key {
+            id: u32,
+            name: String,
+            tags: Vec<String>,
+        }
+
+        check(&"hello world".to_string());
+        check(&vec!["a".to_string(), "b".to_string()]);
+        check(&Some(Box::<str>::from("boxed")));
+        check(&[1u32, 2, 3].into_iter().collect::<BTreeSet<_>>());
+        check(
+            &[("a".to_string(), 1u32), ("b".to_string(), 2)]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        );
+        check(&Key {
+            id: 42,
+            name: "key".to_string(),
+            tags: vec!["x".to_string()],
+        });
+
+        // Archived strings can be looked up with live keys
+        let buf = to_bytes::<_, 256, Failure>(&"hello".to_string()).unwrap();
+        let archived = unsafe { access_unchecked::<String>(buf.as_ref()) };
+        let mut index = HashMap::new();
+        index.insert(archived.as_str(), 1);
+        assert_eq!(hash_of(archived), hash_of("hello"));
+        assert_eq!(index.get("hello"), Some(&1));
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn c_string() {
@@ -237,6 +368,54 @@ mod tests {
         test_archive(&value);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_locks() {
+        use rkyv::deserialize;
+        use std::sync::{Mutex, RwLock};
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct State {
+            config: Mutex<Vec<String>>,
+            count: RwLock<u32>,
+        }
+
+        let value = State {
+            config: Mutex::new(vec!["verbose".to_string()]),
+            count: RwLock::new(10),
+        };
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<State>(&bytes) };
+        assert_eq!(archived.config.as_slice(), ["verbose"]);
+        assert_eq!(archived.count, 10);
+
+        let deserialized =
+            deserialize::<State, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(
+            *deserialized.config.lock().unwrap(),
+            *value.config.lock().unwrap(),
+        );
+        assert_eq!(*deserialized.count.read().unwrap(), 10);
+
+        // Poisoned locks return an error instead of panicking
+        #[cfg(not(feature = "wasm"))]
+        {
+            use std::sync::Arc;
+
+            let poisoned = Arc::new(Mutex::new(1u32));
+            let lock = poisoned.clone();
+            std::thread::spawn(move || {
+                let _guard = lock.lock().unwrap();
+                panic!("poisoning the lock");
+            })
+            .join()
+            .unwrap_err();
+            assert!(poisoned.is_poisoned());
+            to_bytes::<_, 256, Failure>(&*poisoned)
+                .expect_err("expected poisoned lock error");
+        }
+    }
+
     // TODO: figure out errors
 
     // #[test]
@@ -371,4 +550,125 @@ mod tests {
         value.insert(());
         test_archive(&value);
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn deterministic_hash_maps() {
+        use std::collections::hash_map::RandomState;
+
+        let entries = (0..200u32)
+            .map(|i| (format!("key {i}"), vec![i; (i % 5) as usize]))
+            .collect::<Vec<_>>();
+
+        // Each map has a different insertion order and hasher state
+        let forward = entries.iter().cloned().collect::<HashMap<_, _>>();
+        let mut reverse = HashMap::with_hasher(RandomState::new());
+        for (key, value) in entries.iter().rev().cloned() {
+            reverse.insert(key, value);
+        }
+        let mut shuffled = HashMap::with_hasher(RandomState::new());
+        for i in 0..entries.len() {
+            let (key, value) = entries[(i * 37) % entries.len()].clone();
+            shuffled.insert(key, value);
+        }
+        assert_eq!(forward, reverse);
+        assert_eq!(forward, shuffled);
+
+        let expected = to_bytes::<_, 4096, Failure>(&forward).unwrap();
+        for map in [&reverse, &shuffled] {
+            let bytes = to_bytes::<_, 4096, Failure>(map).unwrap();
+            assert_eq!(bytes.as_slice(), expected.as_slice());
+        }
+
+        let forward = entries
+            .iter()
+            .map(|(k, _)| k.clone())
+            .collect::<HashSet<_>>();
+        let reverse = entries
+            .iter()
+            .rev()
+            .map(|(k, _)| k.clone())
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            to_bytes::<_, 4096, Failure>(&forward).unwrap().as_slice(),
+            to_bytes::<_, 4096, Failure>(&reverse).unwrap().as_slice(),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_hasher() {
+        use core::hash::{BuildHasherDefault, Hasher};
+        use rkyv::{deserialize, with::WithHasher};
+
+        #[derive(Default)]
+        struct Fnv(u64);
+
+        impl Hasher for Fnv {
+            fn finish(&self) -> u64 {
+                self.0 ^ (self.0 >> 32)
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                for &byte in bytes {
+                    self.0 = (self.0 ^ byte as u64).wrapping_mul(0x100000001b3);
+                }
+            }
+        }
+
+        type FnvBuildHasher = BuildHasherDefault<Fnv>;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(WithHasher<Fnv>)]
+            map: HashMap<String, u32, FnvBuildHasher>,
+            #[with(WithHasher<Fnv>)]
+            set: HashSet<String, FnvBuildHasher>,
+        }
+
+        let value = Test {
+            map: (0..20).map(|i| (format!("key {i}"), i)).collect(),
+            set: (0..20).map(|i| format!("value {}", i * 3)).collect(),
+        };
+        let buf = to_bytes::<_, 1024, Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<Test>(buf.as_ref()) };
+
+        let _: Fnv = archived.map.hasher();
+        assert_eq!(archived.map.len(), 20);
+        for (key, value) in value.map.iter() {
+            assert_eq!(&archived.map[key.as_str()], value);
+        }
+        assert!(archived.map.get("missing").is_none());
+        for i in 0..60 {
+            let key = format!("value {i}");
+            assert_eq!(archived.set.contains(key.as_str()), i % 3 == 0);
+        }
+
+        let deserialized =
+            deserialize::<Test, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
+
+        // A hasher that can't separate keys fails instead of searching forever
+        #[derive(Default)]
+        struct Constant;
+
+        impl Hasher for Constant {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, _: &[u8]) {}
+        }
+
+        #[derive(Archive, Serialize)]
+        struct Degenerate {
+            #[with(WithHasher<Constant>)]
+            map: HashMap<u32, u32>,
+        }
+
+        let value = Degenerate {
+            map: (0..4).map(|i| (i, i)).collect(),
+        };
+        to_bytes::<_, 1024, Failure>(&value).unwrap_err();
+    }
 }
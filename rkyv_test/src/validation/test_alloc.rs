@@ -3,25 +3,33 @@ mod tests {
     use crate::{util::alloc::*, validation::util::alloc::serialize_and_check};
     #[cfg(not(feature = "std"))]
     use alloc::{
+        borrow::Cow,
         boxed::Box,
         collections::{BTreeMap, BTreeSet},
-        rc::Rc,
+        format,
+        rc::{Rc, Weak},
         string::{String, ToString},
+        sync::Arc,
         vec,
         vec::Vec,
     };
     use rkyv::{
         access,
         bytecheck::CheckBytes,
-        rancor::{Error, Failure},
+        rancor::{BoxedError, Error, Failure},
         ser::Writer,
         to_bytes,
         util::{serialize_into, AlignedBytes},
         validation::util::access_pos,
-        Archive, Serialize,
+        Archive, Deserialize, Serialize,
     };
     #[cfg(feature = "std")]
-    use std::rc::Rc;
+    use std::{
+        borrow::Cow,
+        collections::{BTreeMap, BTreeSet},
+        rc::{Rc, Weak},
+        sync::Arc,
+    };
 
     #[cfg(feature = "wasm")]
     use wasm_bindgen_test::*;
@@ -139,6 +147,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn check_tuple_12() {
+        serialize_and_check::<_, Failure>(&(
+            1u8,
+            "hello".to_string(),
+            2u16,
+            true,
+            3u32,
+            "world".to_string(),
+            4u64,
+            5f32,
+            'x',
+            6i16,
+            vec![7i32, 8, 9],
+            10f64,
+        ));
+    }
+
     #[cfg(feature = "pointer_width_32")]
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
@@ -155,6 +182,214 @@ mod tests {
         let result =
             access_pos::<Option<Box<[u8]>>, Failure>(synthetic_buf.as_ref(), 0);
         result.unwrap_err();
+
+        let value = Result::<String, u32>::Err(42);
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        access::<Result<String, u32>, Failure>(buf.as_slice()).unwrap();
+        let tag_pos = buf.len()
+            - core::mem::size_of::<rkyv::Archived<Result<String, u32>>>();
+        buf.as_mut_slice()[tag_pos] = 2;
+        access::<Result<String, u32>, Failure>(buf.as_slice())
+            .expect_err("expected invalid tag error");
+
+        // Tags are checked with the archived repr and discriminants
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes, repr(u16))]
+        enum Status {
+            Active = 10,
+            Idle = 300,
+        }
+
+        for value in [Status::Active, Status::Idle] {
+            let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+            assert_eq!(buf.len(), 2);
+            access::<Status, Failure>(buf.as_slice()).unwrap();
+        }
+        for tag in [0, 11, 301] {
+            let tag = rkyv::Archived::<u16>::from_native(tag);
+            let buf = AlignedBytes(unsafe {
+                core::mem::transmute::<rkyv::Archived<u16>, [u8; 2]>(tag)
+            });
+            assert!(access_pos::<Status, Failure>(buf.as_ref(), 0).is_err());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_mixed_enum_tags() {
+        // Mostly unit variants with a few data-carrying ones still infer a
+        // one-byte tag
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[allow(dead_code)]
+        #[rustfmt::skip]
+        enum Mixed {
+            A(u32),
+            B { x: u16 },
+            C(u8),
+            V3, V4, V5, V6, V7, V8, V9, V10, V11, V12, V13, V14, V15, V16, V17,
+            V18, V19, V20, V21, V22, V23, V24, V25, V26, V27, V28, V29, V30,
+            V31, V32, V33, V34, V35, V36, V37, V38, V39, V40, V41, V42, V43,
+            V44, V45, V46, V47, V48, V49, V50, V51, V52, V53, V54, V55, V56,
+            V57, V58, V59, V60, V61, V62, V63, V64, V65, V66, V67, V68, V69,
+            V70, V71, V72, V73, V74, V75, V76, V77, V78, V79, V80, V81, V82,
+            V83, V84, V85, V86, V87, V88, V89, V90, V91, V92, V93, V94, V95,
+            V96, V97, V98, V99, V100, V101, V102, V103, V104, V105, V106, V107,
+            V108, V109, V110, V111, V112, V113, V114, V115, V116, V117, V118,
+            V119, V120, V121, V122, V123, V124, V125, V126, V127, V128, V129,
+            V130, V131, V132, V133, V134, V135, V136, V137, V138, V139, V140,
+            V141, V142, V143, V144, V145, V146, V147, V148, V149, V150, V151,
+            V152, V153, V154, V155, V156, V157, V158, V159, V160, V161, V162,
+            V163, V164, V165, V166, V167, V168, V169, V170, V171, V172, V173,
+            V174, V175, V176, V177, V178, V179, V180, V181, V182, V183, V184,
+            V185, V186, V187, V188, V189, V190, V191, V192, V193, V194, V195,
+            V196, V197, V198, V199, V200, V201, V202,
+        }
+
+        let size = core::mem::size_of::<rkyv::Archived<Mixed>>();
+        assert_eq!(size, 8);
+
+        let values = [Mixed::A(1), Mixed::B { x: 2 }, Mixed::C(3), Mixed::V202];
+        for value in values {
+            let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+            access::<Mixed, Failure>(buf.as_slice()).unwrap();
+        }
+
+        // Tags are checked against the number of variants
+        let mut buf = to_bytes::<_, 256, Failure>(&Mixed::V3).unwrap();
+        let tag_pos = buf.len() - size;
+        for tag in [202, 203, 255] {
+            buf.as_mut_slice()[tag_pos] = tag;
+            let result = access::<Mixed, Failure>(buf.as_slice());
+            assert_eq!(result.is_ok(), tag < 203);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_mismatched_enum_payload() {
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        #[allow(dead_code, clippy::box_collection)]
+        enum Payload {
+            A(u32),
+            B,
+            C(Box<Vec<String>>),
+        }
+
+        use core::mem::{align_of, size_of};
+        use rkyv::{
+            primitive::{ArchivedIsize, FixedIsize},
+            Archived,
+        };
+
+        // Every variant is checked within the bytes of the whole archived
+        // enum, which is as large as its largest variant
+        let size = size_of::<Archived<Payload>>();
+        assert!(size > size_of::<Archived<u32>>());
+        assert!(size > size_of::<ArchivedIsize>());
+
+        let value = Payload::C(Box::new(vec!["hello".to_string()]));
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        access::<Payload, Failure>(buf.as_slice()).unwrap();
+
+        // The relative pointer of `C` follows the tag
+        let ptr_pos = align_of::<Archived<Box<Vec<String>>>>();
+        let ptr_offset = ptr_pos as FixedIsize;
+        let end_offset = (size - ptr_pos) as FixedIsize;
+
+        // The bytes of `A` are read as the relative pointer of `C`, which must
+        // still point to a valid region of the archive
+        let offsets = [
+            FixedIsize::MAX,  // Far past the end of the archive
+            -ptr_offset - 16, // Before the start of the archive
+            -ptr_offset,      // Back at the enum itself
+            end_offset,       // Past the end of the archive
+        ];
+        for offset in offsets {
+            let mut buf = to_bytes::<_, 256, Failure>(&Payload::A(0)).unwrap();
+            assert_eq!(buf.len(), size);
+            unsafe {
+                buf.as_mut_ptr()
+                    .add(ptr_pos)
+                    .cast::<ArchivedIsize>()
+                    .write_unaligned(ArchivedIsize::from_native(offset));
+            }
+            access::<Payload, Failure>(buf.as_slice()).unwrap();
+
+            buf.as_mut_slice()[0] = 2;
+            access::<Payload, Failure>(buf.as_slice())
+                .expect_err("expected payload of A to be rejected as C");
+        }
+
+        // The payload region is claimed in full even for smaller variants
+        let buf = to_bytes::<_, 256, Failure>(&Payload::B).unwrap();
+        assert_eq!(buf.len(), size);
+        access_pos::<Payload, Failure>(&buf.as_slice()[..size - 1], 0)
+            .expect_err("expected truncated enum to be rejected");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_array() {
+        serialize_and_check::<_, Failure>(&[
+            "hello".to_string(),
+            "world".to_string(),
+        ]);
+
+        // Invalid bool in the middle of the array
+        let mut buf = to_bytes::<_, 256, Failure>(&[true; 8]).unwrap();
+        buf.as_mut_slice()[5] = 2;
+        let error =
+            access::<[bool; 8], BoxedError>(buf.as_slice()).unwrap_err();
+        assert!(error.to_string().contains("index '5'"));
+
+        // Invalid string in an array of non-copy elements
+        let mut buf =
+            to_bytes::<_, 256, Failure>(&["a".to_string(), "b".to_string()])
+                .unwrap();
+        let offset = {
+            let archived =
+                access::<[String; 2], Failure>(buf.as_slice()).unwrap();
+            archived[1].as_ptr() as usize - buf.as_ptr() as usize
+        };
+        buf.as_mut_slice()[offset] = 0xff;
+        let error =
+            access::<[String; 2], BoxedError>(buf.as_slice()).unwrap_err();
+        assert!(error.to_string().contains("index '1'"));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn out_of_bounds_offsets() {
+        use core::mem::size_of;
+        use rkyv::primitive::{ArchivedIsize, FixedIsize};
+
+        let buf = to_bytes::<_, 256, Failure>(&Box::new(42u32)).unwrap();
+        access::<Box<u32>, Failure>(buf.as_slice()).unwrap();
+
+        // The root is a single archived offset at the end of the buffer
+        let width = size_of::<ArchivedIsize>();
+        let root = buf.len() - width;
+        let offsets = [
+            FixedIsize::MAX,
+            FixedIsize::MIN,
+            width as FixedIsize,
+            -((buf.len() + width) as FixedIsize),
+        ];
+        for offset in offsets {
+            let mut corrupted = buf.clone();
+            unsafe {
+                corrupted
+                    .as_mut_ptr()
+                    .add(root)
+                    .cast::<ArchivedIsize>()
+                    .write_unaligned(ArchivedIsize::from_native(offset));
+            }
+            access::<Box<u32>, Failure>(corrupted.as_slice())
+                .expect_err("expected out-of-bounds offset error");
+        }
     }
 
     #[cfg(feature = "pointer_width_32")]
@@ -177,6 +412,78 @@ mod tests {
             .unwrap_err();
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn claimed_ranges() {
+        use core::mem::size_of;
+        use rkyv::{
+            primitive::{ArchivedIsize, ArchivedUsize},
+            validation::{
+                util::{access_pos_with_context, access_with_context},
+                validators::DefaultValidator,
+            },
+            Archived,
+        };
+
+        // The synthetic archive from `overlapping_claims`, with both boxes
+        // pointing to the same bytes after the array
+        let array_size = size_of::<Archived<[Box<[u8]>; 2]>>();
+        let box_size = size_of::<Archived<Box<[u8]>>>();
+        let mut bytes = AlignedBytes([0u8; 64]);
+        for i in 0..2 {
+            let pos = i * box_size;
+            unsafe {
+                let out = bytes.as_mut().as_mut_ptr().add(pos);
+                out.cast::<ArchivedIsize>().write_unaligned(
+                    ArchivedIsize::from_native((array_size - pos) as _),
+                );
+                out.add(size_of::<ArchivedIsize>())
+                    .cast::<ArchivedUsize>()
+                    .write_unaligned(ArchivedUsize::from_native(11));
+            }
+        }
+        bytes.as_mut()[array_size..array_size + 11]
+            .copy_from_slice(b"Hello world");
+        let synthetic_buf = &bytes.as_ref()[..array_size + 11];
+
+        // Claims are not recorded by default
+        let mut validator = DefaultValidator::new(synthetic_buf);
+        access_pos_with_context::<[Box<[u8]>; 2], _, Failure>(
+            synthetic_buf,
+            0,
+            &mut validator,
+        )
+        .unwrap_err();
+        assert!(validator.claimed_ranges().is_empty());
+
+        // The last claimed range is the one that failed
+        let mut validator = DefaultValidator::new(synthetic_buf);
+        validator.record_claims();
+        access_pos_with_context::<[Box<[u8]>; 2], _, Failure>(
+            synthetic_buf,
+            0,
+            &mut validator,
+        )
+        .unwrap_err();
+        assert_eq!(
+            validator.claimed_ranges(),
+            &[(0, array_size), (array_size, 11)],
+        );
+
+        // Claims are also available after validation succeeds
+        let buf = to_bytes::<_, 256, Failure>(&vec![1u32, 2, 3]).unwrap();
+        let vec_size = size_of::<Archived<Vec<u32>>>();
+        let root = buf.len() - vec_size;
+        let mut validator = DefaultValidator::new(buf.as_ref());
+        validator.record_claims();
+        access_with_context::<Vec<u32>, _, Failure>(
+            buf.as_ref(),
+            &mut validator,
+        )
+        .unwrap();
+        assert_eq!(validator.claimed_ranges(), &[(root, vec_size), (0, 12)],);
+    }
+
     #[cfg(feature = "pointer_width_32")]
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
@@ -230,7 +537,12 @@ mod tests {
                             context,
                         )?;
                     }
-                    _ => panic!(),
+                    _ => rkyv::rancor::fail!(
+                        rkyv::bytecheck::InvalidEnumDiscriminantError {
+                            enum_name: "ArchivedNode",
+                            invalid_discriminant: tag,
+                        }
+                    ),
                 }
                 Ok(())
             }
@@ -249,6 +561,123 @@ mod tests {
         access_pos::<Node, Failure>(synthetic_buf.as_ref(), 0).unwrap_err();
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_128_bit_integers() {
+        serialize_and_check::<_, Failure>(&i128::MIN);
+        serialize_and_check::<_, Failure>(&i128::MAX);
+        serialize_and_check::<_, Failure>(&u128::MAX);
+        serialize_and_check::<_, Failure>(&vec![i128::MIN, 0, i128::MAX]);
+
+        // 128-bit integers must be aligned to 16 bytes
+        let buf = AlignedBytes([0u8; 32]);
+        access_pos::<u128, Failure>(buf.as_ref(), 16).unwrap();
+        access_pos::<u128, Failure>(buf.as_ref(), 8).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_num_and_cmp_wrappers() {
+        use core::{
+            cmp::Reverse,
+            num::{Saturating, Wrapping},
+        };
+
+        serialize_and_check::<_, Failure>(&Wrapping(u64::MAX));
+        serialize_and_check::<_, Failure>(&Saturating(i8::MIN));
+        serialize_and_check::<_, Failure>(&Reverse(true));
+        serialize_and_check::<_, Failure>(&Reverse("hello world".to_string()));
+
+        // The inner value is checked
+        let buf = AlignedBytes([2u8; 1]);
+        access::<Reverse<bool>, Failure>(buf.as_ref())
+            .expect_err("expected invalid bool error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_finite_floats() {
+        use rkyv::{with::Finite, Archived};
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Reading {
+            #[with(Finite)]
+            ratio: f32,
+            #[with(Finite)]
+            amount: f64,
+            unchecked: f64,
+        }
+
+        serialize_and_check::<_, Failure>(&Reading {
+            ratio: 0.5,
+            amount: -1234.5,
+            unchecked: f64::NAN,
+        });
+
+        assert!(to_bytes::<_, 256, Failure>(&Reading {
+            ratio: f32::INFINITY,
+            amount: 0.0,
+            unchecked: 0.0,
+        })
+        .is_err());
+        assert!(to_bytes::<_, 256, Failure>(&Reading {
+            ratio: 0.0,
+            amount: f64::NAN,
+            unchecked: 0.0,
+        })
+        .is_err());
+
+        let mut buf = to_bytes::<_, 256, Failure>(&Reading {
+            ratio: 0.5,
+            amount: 1.0,
+            unchecked: 0.0,
+        })
+        .unwrap();
+        let archived = access::<Reading, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(archived.ratio.to_native(), 0.5);
+        assert_eq!(archived.amount.to_native(), 1.0);
+
+        // Corrupt the amount into a NaN
+        let offset =
+            &archived.amount as *const _ as usize - buf.as_ptr() as usize;
+        let nan = Archived::<f64>::from_native(f64::NAN);
+        buf.as_mut_slice()[offset..offset + 8].copy_from_slice(unsafe {
+            &*(&nan as *const Archived<f64>).cast::<[u8; 8]>()
+        });
+        assert!(access::<Reading, Failure>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_phantom_marker() {
+        use core::marker::PhantomData;
+
+        // Markers don't need to implement any rkyv traits
+        struct Locked;
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Door<State> {
+            id: u32,
+            _state: PhantomData<State>,
+        }
+
+        assert_eq!(
+            core::mem::size_of::<rkyv::Archived<PhantomData<Locked>>>(),
+            0
+        );
+
+        let value = Door::<Locked> {
+            id: 42,
+            _state: PhantomData,
+        };
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<Door<Locked>, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(archived.id, 42);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn derive_unit_struct() {
@@ -277,6 +706,157 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_nested_vecs() {
+        use rkyv::validation::{
+            util::access_with_context, validators::DefaultValidator,
+        };
+
+        serialize_and_check::<_, Failure>(&vec![vec![1u32, 2], vec![3]]);
+        serialize_and_check::<_, Failure>(&vec![vec![], vec![1u32], vec![]]);
+        serialize_and_check::<_, Failure>(&vec![
+            vec![vec!["a".to_string()], vec![]],
+            vec![vec!["b".to_string(), "c".to_string()]],
+        ]);
+
+        let value = vec![vec![1u32, 2], vec![3]];
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<Vec<Vec<u32>>, Failure>(&bytes).unwrap();
+        assert_eq!(archived.len(), 2);
+        assert_eq!(archived[0].as_slice(), &[1, 2]);
+        assert_eq!(archived[1].as_slice(), &[3]);
+
+        // The outer data and the data of each inner vec are claimed without
+        // overlapping
+        let mut validator = DefaultValidator::new(bytes.as_slice());
+        validator.record_claims();
+        access_with_context::<Vec<Vec<u32>>, _, Failure>(
+            bytes.as_slice(),
+            &mut validator,
+        )
+        .unwrap();
+        let ranges = validator.claimed_ranges();
+        assert_eq!(ranges.len(), 4);
+        for (i, a) in ranges.iter().enumerate() {
+            for b in ranges[i + 1..].iter() {
+                assert!(a.0 + a.1 <= b.0 || b.0 + b.1 <= a.0);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_nested_arrays() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(check_bytes, compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Matrix([[f32; 4]; 4]);
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Grid<T, const M: usize, const N: usize>([[T; M]; N]);
+
+        let mut rows = [[0f32; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = (i * 4 + j) as f32 * 0.5;
+            }
+        }
+        let matrix = Matrix(rows);
+        test_archive(&matrix);
+        serialize_and_check::<_, Failure>(&matrix);
+
+        // The nested arrays are stored inline in the archived struct
+        assert_eq!(
+            core::mem::size_of::<ArchivedMatrix>(),
+            core::mem::size_of::<[[f32; 4]; 4]>(),
+        );
+        let bytes = to_bytes::<_, 256, Failure>(&matrix).unwrap();
+        let archived = access::<Matrix, Failure>(&bytes).unwrap();
+        assert_eq!(archived.0[2][3], 5.5);
+
+        let grid = Grid([
+            ["a".to_string(), "b".to_string()],
+            ["c".to_string(), "d".to_string()],
+            ["e".to_string(), "f".to_string()],
+        ]);
+        serialize_and_check::<_, Failure>(&grid);
+        let bytes = to_bytes::<_, 256, Failure>(&grid).unwrap();
+        let archived = access::<Grid<String, 2, 3>, Failure>(&bytes).unwrap();
+        assert_eq!(archived.0[2][1], "f");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn access_typed_root() {
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Test {
+            a: u32,
+            b: String,
+        }
+
+        let value = Test {
+            a: 42,
+            b: "hello world".to_string(),
+        };
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(archived.a, 42);
+        assert_eq!(archived.b, "hello world");
+
+        let pos = buf.len() - core::mem::size_of::<ArchivedTest>();
+        let at_pos =
+            access_pos::<Test, Failure>(buf.as_ref(), pos as isize).unwrap();
+        assert!(core::ptr::eq(archived, at_pos));
+
+        // Out-of-bounds and misaligned roots are rejected
+        assert!(
+            access_pos::<Test, Failure>(buf.as_ref(), buf.len() as isize)
+                .is_err()
+        );
+        assert!(access_pos::<Test, Failure>(buf.as_ref(), pos as isize - 1)
+            .is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_generic() {
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Wrapper<T> {
+            inner: T,
+            tag: u32,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        enum Either<L, R> {
+            Left(L),
+            Right { value: R },
+        }
+
+        serialize_and_check::<_, Failure>(&Wrapper {
+            inner: "hello world".to_string(),
+            tag: 1,
+        });
+        serialize_and_check::<_, Failure>(&Wrapper {
+            inner: Box::new(vec![1u32, 2, 3]),
+            tag: 2,
+        });
+        serialize_and_check::<_, Failure>(&Either::<String, u32>::Left(
+            "left".to_string(),
+        ));
+        serialize_and_check::<_, Failure>(&Wrapper {
+            inner: Either::<u32, Vec<String>>::Right {
+                value: vec!["right".to_string()],
+            },
+            tag: 3,
+        });
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn derive_tuple_struct() {
@@ -291,6 +871,51 @@ mod tests {
         ));
     }
 
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_tuples() {
+        use rkyv::validation::{
+            util::access_with_context, validators::DefaultValidator,
+        };
+
+        type Test = (u32, String, Box<Vec<String>>);
+
+        // Long enough to be stored out of line with any pointer width
+        let string = "a string that is too long to be inlined";
+        assert!(string.len() > rkyv::string::repr::INLINE_CAPACITY);
+
+        let value: Test = (
+            42,
+            string.to_string(),
+            Box::new(vec!["yes".to_string(), "no".to_string()]),
+        );
+        serialize_and_check::<_, Failure>(&value);
+        serialize_and_check::<_, Failure>(&(42u32, "hello world".to_string()));
+
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let offset = {
+            let archived = access::<Test, Failure>(buf.as_slice()).unwrap();
+            assert_eq!(archived.0, 42);
+            assert_eq!(archived.1, string);
+            assert_eq!(archived.2.len(), 2);
+            assert_eq!(archived.2[1], "no");
+            archived.1.as_ptr() as usize - buf.as_ptr() as usize
+        };
+
+        // The out-of-line bytes of the second element are claimed
+        let mut validator = DefaultValidator::new(buf.as_slice());
+        validator.record_claims();
+        access_with_context::<Test, _, Failure>(buf.as_slice(), &mut validator)
+            .unwrap();
+        assert!(validator.claimed_ranges().contains(&(offset, string.len())));
+
+        // Errors in the second element are reported at its index
+        buf.as_mut_slice()[offset] = 0xff;
+        let error = access::<Test, BoxedError>(buf.as_slice()).unwrap_err();
+        assert!(error.to_string().contains("index 1 of tuple"));
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn derive_enum() {
@@ -310,6 +935,167 @@ mod tests {
         ])));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_enum_accessors() {
+        #[allow(dead_code)]
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        enum Test {
+            A(u32),
+            B(String, Vec<String>),
+            HttpError {
+                code: u16,
+                #[archive(rename = "archived_message")]
+                message: String,
+            },
+            Unit,
+        }
+
+        let buf = to_bytes::<_, 256, Failure>(&Test::A(42)).unwrap();
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        assert!(archived.is_a());
+        assert!(!archived.is_b());
+        assert_eq!(archived.as_a().map(|x| x.to_native()), Some(42));
+        assert!(archived.as_b().is_none());
+
+        let buf = to_bytes::<_, 256, Failure>(&Test::B(
+            "hello world".to_string(),
+            vec!["yes".to_string(), "no".to_string()],
+        ))
+        .unwrap();
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        let (string, strings) = archived.as_b().unwrap();
+        assert_eq!(string, "hello world");
+        assert_eq!(strings[1], "no");
+        assert!(archived.as_http_error().is_none());
+
+        let buf = to_bytes::<_, 256, Failure>(&Test::HttpError {
+            code: 404,
+            message: "not found".to_string(),
+        })
+        .unwrap();
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        assert!(archived.is_http_error());
+        let (code, message) = archived.as_http_error().unwrap();
+        assert_eq!(*code, 404);
+        assert_eq!(message, "not found");
+        // The archived enum can also be matched directly
+        match archived {
+            ArchivedTest::HttpError {
+                archived_message, ..
+            } => assert_eq!(archived_message, "not found"),
+            _ => panic!("expected HttpError"),
+        }
+
+        let buf = to_bytes::<_, 256, Failure>(&Test::Unit).unwrap();
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        assert!(archived.is_unit());
+        assert!(!archived.is_a());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn derive_diff() {
+        use rkyv::{
+            diff::{diff, FieldDiff},
+            Archived,
+        };
+
+        #[derive(Archive, Serialize, Clone)]
+        #[archive(check_bytes, diff)]
+        struct Test {
+            id: u32,
+            #[archive(rename = "archived_name")]
+            name: String,
+            #[archive(skip)]
+            #[allow(dead_code)]
+            cache: u64,
+            tags: Vec<String>,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes, diff)]
+        struct Tuple(u32, String);
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes, diff)]
+        enum Enum {
+            A(u32, u32),
+            B { value: String },
+        }
+
+        let old = Test {
+            id: 1,
+            name: "hello".to_string(),
+            cache: 0,
+            tags: vec!["a".to_string()],
+        };
+        let old_buf = to_bytes::<_, 256, Failure>(&old).unwrap();
+
+        // Identical archives have no differences
+        assert!(diff::<Test, Failure>(&old_buf, &old_buf)
+            .unwrap()
+            .is_empty());
+
+        let mut new = old.clone();
+        new.cache = 1;
+        new.tags.push("b".to_string());
+        let new_buf = to_bytes::<_, 256, Failure>(&new).unwrap();
+        let differences = diff::<Test, Failure>(&old_buf, &new_buf).unwrap();
+        let old_root = access::<Test, Failure>(&old_buf).unwrap();
+        let new_root = access::<Test, Failure>(&new_buf).unwrap();
+        let offset = |bytes: &[u8], field: *const Archived<Vec<String>>| {
+            let start = field as usize - bytes.as_ptr() as usize;
+            start..start + core::mem::size_of::<Archived<Vec<String>>>()
+        };
+        assert_eq!(
+            differences,
+            [FieldDiff {
+                field: Some("tags"),
+                old: offset(&old_buf, &old_root.tags),
+                new: offset(&new_buf, &new_root.tags),
+            }],
+        );
+
+        new.id = 2;
+        new.name = "world".to_string();
+        let new_buf = to_bytes::<_, 256, Failure>(&new).unwrap();
+        let fields = diff::<Test, Failure>(&old_buf, &new_buf)
+            .unwrap()
+            .into_iter()
+            .map(|d| d.field)
+            .collect::<Vec<_>>();
+        assert_eq!(fields, [Some("id"), Some("archived_name"), Some("tags")]);
+
+        let old_buf =
+            to_bytes::<_, 256, Failure>(&Tuple(1, "a".to_string())).unwrap();
+        let new_buf =
+            to_bytes::<_, 256, Failure>(&Tuple(1, "b".to_string())).unwrap();
+        let differences = diff::<Tuple, Failure>(&old_buf, &new_buf).unwrap();
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].field, Some("1"));
+
+        // Fields of the same variant are compared
+        let old_buf = to_bytes::<_, 256, Failure>(&Enum::A(1, 2)).unwrap();
+        let new_buf = to_bytes::<_, 256, Failure>(&Enum::A(1, 3)).unwrap();
+        let differences = diff::<Enum, Failure>(&old_buf, &new_buf).unwrap();
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].field, Some("A::1"));
+
+        // Different variants differ entirely
+        let new_buf = to_bytes::<_, 256, Failure>(&Enum::B {
+            value: "hello".to_string(),
+        })
+        .unwrap();
+        let differences = diff::<Enum, Failure>(&old_buf, &new_buf).unwrap();
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].field, None);
+        let size = core::mem::size_of::<Archived<Enum>>();
+        assert_eq!(differences[0].old.len(), size);
+        assert_eq!(differences[0].new.len(), size);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn recursive_type() {
@@ -340,17 +1126,175 @@ mod tests {
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn check_shared_ptr() {
-        #[derive(Archive, Serialize, Eq, PartialEq)]
+    fn max_subtree_depth() {
+        use core::num::NonZeroUsize;
+        use rkyv::validation::{
+            util::access_with_context, validators::DefaultValidator,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(serialize_bounds(__S: Writer))]
         #[archive(check_bytes)]
-        struct Test {
-            a: Rc<u32>,
-            b: Rc<u32>,
+        #[archive_attr(check_bytes(
+            bounds(__C: ::rkyv::validation::ArchiveContext)
+        ))]
+        enum Node {
+            Nil,
+            Cons(
+                #[omit_bounds]
+                #[archive_attr(omit_bounds)]
+                Box<Node>,
+            ),
         }
 
-        let shared = Rc::new(10);
-        let value = Test {
-            a: shared.clone(),
+        let mut value = Node::Nil;
+        for _ in 0..16 {
+            value = Node::Cons(Box::new(value));
+        }
+        let buf = to_bytes::<_, 1024, Failure>(&value).unwrap();
+
+        let mut validator = DefaultValidator::with_max_depth(
+            buf.as_ref(),
+            NonZeroUsize::new(32),
+        );
+        access_with_context::<Node, _, Failure>(buf.as_ref(), &mut validator)
+            .expect("expected nesting within the maximum depth to validate");
+
+        let mut validator = DefaultValidator::with_max_depth(
+            buf.as_ref(),
+            NonZeroUsize::new(8),
+        );
+        assert!(access_with_context::<Node, _, Failure>(
+            buf.as_ref(),
+            &mut validator
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn compare_validated() {
+        #[derive(Archive, Serialize, Clone, Debug, PartialEq)]
+        #[archive(check_bytes, compare(PartialEq))]
+        struct Point(i32, i32);
+
+        #[derive(Archive, Serialize, Clone, Debug, PartialEq)]
+        #[archive(check_bytes, compare(PartialEq))]
+        enum Shape {
+            Empty,
+            Line(Point, Point),
+            Path { points: Vec<Point>, closed: bool },
+        }
+
+        #[derive(Archive, Serialize, Clone, Debug, PartialEq)]
+        #[archive(check_bytes, compare(PartialEq))]
+        struct Drawing {
+            name: String,
+            tags: Vec<String>,
+            shapes: Vec<Shape>,
+            background: Option<Box<Shape>>,
+        }
+
+        let value = Drawing {
+            name: "sketch".to_string(),
+            tags: vec!["draft".to_string(), "shared".to_string()],
+            shapes: vec![
+                Shape::Line(Point(0, 0), Point(1, 1)),
+                Shape::Path {
+                    points: vec![Point(1, 2), Point(3, 4), Point(5, 6)],
+                    closed: true,
+                },
+            ],
+            background: Some(Box::new(Shape::Empty)),
+        };
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<Drawing, Failure>(buf.as_ref()).unwrap();
+        assert!(archived == &value);
+        assert!(&value == archived);
+
+        // Differences in nested fields and enum variants are detected
+        for other in [
+            Drawing {
+                tags: vec!["draft".to_string()],
+                ..value.clone()
+            },
+            Drawing {
+                background: None,
+                ..value.clone()
+            },
+            Drawing {
+                shapes: vec![Shape::Empty, Shape::Empty],
+                ..value.clone()
+            },
+        ] {
+            assert!(archived != &other);
+            assert!(&other != archived);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn recursive_type_replaced_bounds() {
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive(bound(
+            archive = "",
+            serialize = "__S: Writer",
+            check = "__C: ::rkyv::validation::ArchiveContext",
+        ))]
+        enum Node {
+            Nil,
+            Cons(Box<Node>),
+        }
+
+        serialize_and_check::<_, Failure>(&Node::Cons(Box::new(Node::Cons(
+            Box::new(Node::Nil),
+        ))));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_cow() {
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Test<'a> {
+            a: Cow<'a, str>,
+            b: Cow<'a, [u8]>,
+        }
+
+        serialize_and_check::<_, Failure>(&Test {
+            a: Cow::Borrowed("hello world"),
+            b: Cow::Owned(vec![1, 2, 3, 4]),
+        });
+
+        // Invalid UTF-8 in the archived string
+        let value = Test {
+            a: Cow::Borrowed("a very long string that is stored out of line"),
+            b: Cow::Borrowed(&[]),
+        };
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let pos = buf
+            .as_slice()
+            .windows(4)
+            .position(|w| w == b"a ve")
+            .unwrap();
+        buf.as_mut_slice()[pos] = 0xff;
+        assert!(access::<Test, Failure>(buf.as_slice()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_shared_ptr() {
+        #[derive(Archive, Serialize, Eq, PartialEq)]
+        #[archive(check_bytes)]
+        struct Test {
+            a: Rc<u32>,
+            b: Rc<u32>,
+        }
+
+        let shared = Rc::new(10);
+        let value = Test {
+            a: shared.clone(),
             b: shared.clone(),
         };
 
@@ -364,125 +1308,1389 @@ mod tests {
         access::<Test, Failure>(buf.as_ref()).unwrap();
     }
 
-    // TODO: re-enable after btreemap validation is fixed
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn check_b_tree() {
-    //     let mut value = BTreeMap::new();
-    //     value.insert("foo".to_string(), 10);
-    //     value.insert("bar".to_string(), 20);
-    //     value.insert("baz".to_string(), 40);
-    //     value.insert("bat".to_string(), 80);
-
-    //     let mut serializer = DefaultSerializer::default();
-    //     serializer.serialize_value(&value).unwrap();
-    //     let buf = serializer.into_serializer().into_inner();
-
-    //     access::<BTreeMap<String, i32>, Failure>(buf.as_ref()).unwrap();
-    // }
-
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn check_invalid_b_tree_set() {
-    //     let data = AlignedBytes([
-    //         0, 0, 0, 0, 253, 6, 239, 6, 255, 255, 255, 252, 0, 0, 0, 0, 0, 0,
-    //         0, 0, 1, 0, 0, 5, 0, 0, 0, 0, 240, 255, 255, 255, 1, 128, 0, 249,
-    //         220, 255, 255, 255, 4, 0, 0, 96, 0, 0, 0, 249, 232, 255, 255, 255,
-    //     ]);
-
-    //     rkyv::from_bytes::<BTreeSet<u8>, Failure>(&data.0).unwrap_err();
-
-    //     let data = AlignedBytes([
-    //         1, 29, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 253, 0, 0, 116, 255, 255, 40,
-    //         0, 8, 0, 0, 0, 236, 255, 255, 255, 1, 128, 72, 0, 220, 255, 255,
-    //         255, 236, 255, 255, 255, 0, 0, 0, 0, 32, 0, 255, 254, 255, 0, 94,
-    //         2, 33, 0, 0, 0, 0, 0, 0, 0, 61, 1, 38, 0, 0, 32, 0, 255, 255, 1, 0,
-    //         1, 255, 255, 0, 184, 4, 0, 28, 0, 8, 0, 2, 142, 255, 255, 255, 3,
-    //         1, 255, 251, 0, 184, 255, 255, 255,
-    //     ]);
-
-    //     rkyv::from_bytes::<BTreeSet<Box<u8>>, Failure>(&data.0).unwrap_err();
-    // }
-
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn check_empty_b_tree() {
-    //     let value = BTreeMap::<u8, ()>::new();
-
-    //     let mut serializer = DefaultSerializer::default();
-    //     serializer.serialize_value(&value).unwrap();
-    //     let buf = serializer.into_serializer().into_inner();
-
-    //     access::<BTreeMap<u8, ()>, Failure>(buf.as_ref()).unwrap();
-    // }
-
-    // #[test]
-    // // This test is unfortunately too slow to run through miri
-    // #[cfg_attr(miri, ignore)]
-    // // This test creates structures too big to fit in 16-bit offsets
-    // #[cfg(not(feature = "pointer_width_16"))]
-    // fn check_b_tree_large() {
-    //     let mut value = BTreeMap::new();
-    //     for i in 0..100_000 {
-    //         value.insert(i.to_string(), i);
-    //     }
-
-    //     let mut serializer = DefaultSerializer::default();
-    //     serializer.serialize_value(&value).unwrap();
-    //     let buf = serializer.into_serializer().into_inner();
-
-    //     access::<BTreeMap<String, i32>, Failure>(buf.as_ref()).unwrap();
-    // }
-
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn b_tree_struct_member() {
-    //     #[derive(Archive, Serialize, Deserialize, Debug, Default)]
-    //     #[archive(check_bytes)]
-    //     pub struct MyType {
-    //         pub some_list: BTreeMap<String, Vec<f32>>,
-    //         pub values: Vec<f32>,
-    //     }
-
-    //     let mut value = MyType::default();
-
-    //     value
-    //         .some_list
-    //         .entry("Asdf".to_string())
-    //         .and_modify(|e| e.push(1.0))
-    //         .or_insert_with(|| vec![2.0]);
-
-    //     let serializer = serialize_into::<_, _, Failure>(
-    //         &value,
-    //         DefaultSerializer::default(),
-    //     ).unwrap();
-    //     let buf = serializer.into_serializer().into_inner();
-
-    //     let _ = from_bytes::<MyType, Failure>(&buf).unwrap();
-    // }
-
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn check_valid_durations() {
-    //     use core::time::Duration;
-
-    //     access::<Duration, Failure>(&[0xFF, 16]).unwrap_err();
-    // }
-
-    // #[test]
-    // #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    // fn check_invalid_btreemap() {
-    //     let data = AlignedBytes([
-    //         0, 0, 0, 0, 0, 0, 0, 0, 0, 0x30, 0, 0x00, 0x00, 0x00, 0x0c, 0xa5,
-    //         0xf0, 0xff, 0xff, 0xff,
-    //     ]);
-    //     rkyv::from_bytes::<BTreeMap<u8, Box<u8>>, Failure>(&data.0).unwrap_err();
-    // }
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_weak_ptr() {
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Test {
+            a: Rc<u32>,
+            b: Weak<u32>,
+            c: Weak<u32>,
+            d: Weak<u32>,
+        }
+
+        let shared = Rc::new(10);
+        let dropped = Rc::new(20);
+        let value = Test {
+            a: shared.clone(),
+            b: Rc::downgrade(&shared),
+            c: Weak::new(),
+            d: Rc::downgrade(&dropped),
+        };
+        drop(dropped);
+
+        let buf = serialize_into::<_, _, Failure>(
+            &value,
+            DefaultSerializer::default(),
+        )
+        .unwrap()
+        .into_writer();
+
+        // The weak pointer aliases the target of the strong pointer
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        let upgraded = archived.b.upgrade().unwrap();
+        assert!(core::ptr::eq(archived.a.get(), upgraded.get()));
+        assert!(archived.c.upgrade().is_none());
+        assert!(archived.d.upgrade().is_none());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_shared_arc() {
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Test {
+            a: Arc<String>,
+            b: Vec<Arc<String>>,
+        }
+
+        let shared = Arc::new("hello world".to_string());
+        let value = Test {
+            a: shared.clone(),
+            b: vec![shared.clone(), Arc::new("foo".to_string()), shared],
+        };
+
+        let buf = serialize_into::<_, _, Failure>(
+            &value,
+            DefaultSerializer::default(),
+        )
+        .unwrap()
+        .into_writer();
+
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        assert!(core::ptr::eq(archived.a.get(), archived.b[0].get()));
+        assert!(core::ptr::eq(archived.a.get(), archived.b[2].get()));
+        assert!(!core::ptr::eq(archived.a.get(), archived.b[1].get()));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_b_tree() {
+        let mut value = BTreeMap::new();
+        value.insert("foo".to_string(), 10);
+        value.insert("bar".to_string(), 20);
+        value.insert("baz".to_string(), 40);
+        value.insert("bat".to_string(), 80);
+
+        let buf = serialize_into::<_, _, Failure>(
+            &value,
+            DefaultSerializer::default(),
+        )
+        .unwrap()
+        .into_writer();
+
+        access::<BTreeMap<String, i32>, Failure>(buf.as_ref()).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_b_tree_set() {
+        use rkyv::util::access_unchecked;
+
+        let mut value = BTreeSet::new();
+        value.insert(1u32);
+        value.insert(2u32);
+        value.insert(3u32);
+
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<BTreeSet<u32>, Failure>(buf.as_ref()).unwrap();
+        assert!(archived.contains(&rkyv::Archived::<u32>::from_native(2)));
+        assert!(!archived.contains(&rkyv::Archived::<u32>::from_native(4)));
+
+        let key_pos = {
+            let archived =
+                unsafe { access_unchecked::<BTreeSet<u32>>(buf.as_slice()) };
+            archived
+                .iter()
+                .map(|k| k as *const _ as usize - buf.as_ptr() as usize)
+                .collect::<Vec<_>>()
+        };
+        let key_size = core::mem::size_of::<rkyv::Archived<u32>>();
+
+        // Duplicate elements
+        let bytes = buf.as_mut_slice();
+        bytes.copy_within(key_pos[1]..key_pos[1] + key_size, key_pos[2]);
+        access::<BTreeSet<u32>, Failure>(buf.as_ref())
+            .expect_err("expected duplicate elements error");
+    }
 
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-    fn check_invalid_string() {
-        let data = AlignedBytes([0x10; 16]);
-        rkyv::from_bytes::<String, Failure>(&data.0).unwrap_err();
+    fn check_invalid_b_tree_set() {
+        let data = AlignedBytes([
+            0, 0, 0, 0, 253, 6, 239, 6, 255, 255, 255, 252, 0, 0, 0, 0, 0, 0,
+            0, 0, 1, 0, 0, 5, 0, 0, 0, 0, 240, 255, 255, 255, 1, 128, 0, 249,
+            220, 255, 255, 255, 4, 0, 0, 96, 0, 0, 0, 249, 232, 255, 255, 255,
+        ]);
+
+        rkyv::from_bytes::<BTreeSet<u8>, Failure>(&data.0).unwrap_err();
+
+        let data = AlignedBytes([
+            1, 29, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 253, 0, 0, 116, 255, 255, 40,
+            0, 8, 0, 0, 0, 236, 255, 255, 255, 1, 128, 72, 0, 220, 255, 255,
+            255, 236, 255, 255, 255, 0, 0, 0, 0, 32, 0, 255, 254, 255, 0, 94,
+            2, 33, 0, 0, 0, 0, 0, 0, 0, 61, 1, 38, 0, 0, 32, 0, 255, 255, 1, 0,
+            1, 255, 255, 0, 184, 4, 0, 28, 0, 8, 0, 2, 142, 255, 255, 255, 3,
+            1, 255, 251, 0, 184, 255, 255, 255,
+        ]);
+
+        rkyv::from_bytes::<BTreeSet<Box<u8>>, Failure>(&data.0).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_empty_b_tree() {
+        let value = BTreeMap::<u8, ()>::new();
+
+        let buf = serialize_into::<_, _, Failure>(
+            &value,
+            DefaultSerializer::default(),
+        )
+        .unwrap()
+        .into_writer();
+
+        access::<BTreeMap<u8, ()>, Failure>(buf.as_ref()).unwrap();
+    }
+
+    #[test]
+    // This test is unfortunately too slow to run through miri
+    #[cfg_attr(miri, ignore)]
+    // This test creates structures too big to fit in 16-bit offsets
+    #[cfg(not(feature = "pointer_width_16"))]
+    fn check_b_tree_large() {
+        let mut value = BTreeMap::new();
+        for i in 0..100_000 {
+            value.insert(i.to_string(), i);
+        }
+
+        let buf = serialize_into::<_, _, Failure>(
+            &value,
+            DefaultSerializer::default(),
+        )
+        .unwrap()
+        .into_writer();
+
+        access::<BTreeMap<String, i32>, Failure>(buf.as_ref()).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_b_tree_range() {
+        use core::ops::Bound;
+
+        let mut value = BTreeMap::new();
+        for i in 0..1000u32 {
+            value.insert(i * 3, i.to_string());
+        }
+
+        let buf = to_bytes::<_, 65536, Failure>(&value).unwrap();
+        let archived =
+            access::<BTreeMap<u32, String>, Failure>(buf.as_ref()).unwrap();
+
+        let points = [0, 1, 2, 3, 4, 299, 300, 301, 1500, 2996, 2997, 2998];
+        let bounds = |x: &u32| {
+            [Bound::Included(*x), Bound::Excluded(*x), Bound::Unbounded]
+        };
+        for start in points.iter().flat_map(bounds) {
+            for end in points.iter().flat_map(bounds) {
+                let is_empty_excluded = matches!(
+                    (start, end),
+                    (Bound::Excluded(s), Bound::Excluded(e)) if s == e
+                );
+                let is_reversed = match (start, end) {
+                    (
+                        Bound::Included(s) | Bound::Excluded(s),
+                        Bound::Included(e) | Bound::Excluded(e),
+                    ) => s > e,
+                    _ => false,
+                };
+                if is_empty_excluded || is_reversed {
+                    continue;
+                }
+
+                let expected = value
+                    .range((start, end))
+                    .map(|(k, v)| (*k, v.as_str()))
+                    .collect::<Vec<_>>();
+                let start = start.map(rkyv::Archived::<u32>::from_native);
+                let end = end.map(rkyv::Archived::<u32>::from_native);
+                let actual = archived
+                    .range((start.as_ref(), end.as_ref()))
+                    .map(|(k, v)| (k.to_native(), v.as_str()))
+                    .collect::<Vec<_>>();
+                assert_eq!(actual, expected, "{:?}..{:?}", start, end);
+            }
+        }
+
+        // Borrowed forms of the key can be used for the bounds
+        let mut value = BTreeMap::new();
+        for word in ["apple", "banana", "cherry", "date", "elderberry"] {
+            value.insert(word.to_string(), word.len());
+        }
+        let buf = to_bytes::<_, 1024, Failure>(&value).unwrap();
+        let archived =
+            access::<BTreeMap<String, usize>, Failure>(buf.as_ref()).unwrap();
+        let keys = archived
+            .range::<str, _>((Bound::Excluded("b"), Bound::Included("date")))
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["banana", "cherry", "date"]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn b_tree_struct_member() {
+        #[derive(Archive, Serialize, Debug, Default)]
+        #[archive(check_bytes)]
+        pub struct MyType {
+            pub some_list: BTreeMap<String, Vec<f32>>,
+            pub values: Vec<f32>,
+        }
+
+        let mut value = MyType::default();
+
+        value
+            .some_list
+            .entry("Asdf".to_string())
+            .and_modify(|e| e.push(1.0))
+            .or_insert_with(|| vec![2.0]);
+
+        serialize_and_check::<_, Failure>(&value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_unsorted_b_tree() {
+        use rkyv::util::access_unchecked;
+
+        let mut value = BTreeMap::new();
+        value.insert(1u32, 10u32);
+        value.insert(2u32, 20u32);
+        value.insert(3u32, 30u32);
+
+        let mut buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        access::<BTreeMap<u32, u32>, Failure>(buf.as_ref()).unwrap();
+
+        let key_pos = {
+            let archived = unsafe {
+                access_unchecked::<BTreeMap<u32, u32>>(buf.as_slice())
+            };
+            archived
+                .keys()
+                .map(|k| k as *const _ as usize - buf.as_ptr() as usize)
+                .collect::<Vec<_>>()
+        };
+        let key_size = core::mem::size_of::<rkyv::Archived<u32>>();
+
+        // Misordered keys
+        let mut misordered = buf.clone();
+        let bytes = misordered.as_mut_slice();
+        for i in 0..key_size {
+            bytes.swap(key_pos[0] + i, key_pos[2] + i);
+        }
+        access::<BTreeMap<u32, u32>, Failure>(misordered.as_ref())
+            .expect_err("expected unsorted keys error");
+
+        // Duplicate keys
+        let bytes = buf.as_mut_slice();
+        bytes.copy_within(key_pos[0]..key_pos[0] + key_size, key_pos[1]);
+        access::<BTreeMap<u32, u32>, Failure>(buf.as_ref())
+            .expect_err("expected duplicate keys error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_nonzero() {
+        use core::{
+            mem::size_of,
+            num::{
+                NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
+                NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8,
+            },
+        };
+        use rkyv::{with::Niche, Archived};
+
+        let zeroes = AlignedBytes([0u8; 16]);
+        access::<NonZeroI8, Failure>(&zeroes[..1]).unwrap_err();
+        access::<NonZeroI16, Failure>(&zeroes[..2]).unwrap_err();
+        access::<NonZeroI32, Failure>(&zeroes[..4]).unwrap_err();
+        access::<NonZeroI64, Failure>(&zeroes[..8]).unwrap_err();
+        access::<NonZeroI128, Failure>(&zeroes[..16]).unwrap_err();
+        access::<NonZeroU8, Failure>(&zeroes[..1]).unwrap_err();
+        access::<NonZeroU16, Failure>(&zeroes[..2]).unwrap_err();
+        access::<NonZeroU32, Failure>(&zeroes[..4]).unwrap_err();
+        access::<NonZeroU64, Failure>(&zeroes[..8]).unwrap_err();
+        access::<NonZeroU128, Failure>(&zeroes[..16]).unwrap_err();
+
+        serialize_and_check::<_, Failure>(&NonZeroU64::new(42).unwrap());
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Id {
+            #[with(Niche)]
+            inner: Option<NonZeroU64>,
+        }
+
+        assert_eq!(size_of::<Archived<Id>>(), 8);
+
+        // A niched option may legitimately hold zero
+        let archived = access::<Id, Failure>(&zeroes[..8]).unwrap();
+        assert!(archived.inner.is_none());
+        serialize_and_check::<_, Failure>(&Id {
+            inner: NonZeroU64::new(42),
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_over_aligned_root() {
+        use rkyv::{
+            rend::f64_le,
+            ser::{writer::BufferWriter, Positional as _},
+            util::{Align32, Aligned},
+        };
+
+        #[derive(Archive, Serialize, CheckBytes, Clone, Copy)]
+        #[archive(copy)]
+        #[check_bytes(crate = "rkyv::bytecheck")]
+        #[repr(C, align(32))]
+        struct F64x4([f64_le; 4]);
+
+        let value = F64x4([
+            f64_le::from_native(1.0),
+            f64_le::from_native(2.0),
+            f64_le::from_native(3.0),
+            f64_le::from_native(4.0),
+        ]);
+
+        let writer = serialize_into::<_, _, Failure>(
+            &value,
+            BufferWriter::new(Aligned::<Align32, _>::new([0u8; 32])),
+        )
+        .unwrap();
+        assert_eq!(writer.pos(), 32);
+        let buf = writer.into_inner();
+        let archived = access::<F64x4, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(archived as *const F64x4 as usize % 32, 0);
+        assert_eq!(archived.0[3], 4.0);
+
+        // The same archive is rejected when its root is under-aligned
+        let mut shifted = Aligned::<Align32, _>::new([0u8; 48]);
+        shifted[16..].copy_from_slice(buf.as_ref());
+        assert!(access::<F64x4, Failure>(&shifted[16..]).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_niche_option_box() {
+        use core::mem::size_of;
+        use rkyv::{boxed::ArchivedBox, with::Niche, Archived};
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Node {
+            #[with(Niche)]
+            value: Option<Box<str>>,
+        }
+
+        assert_eq!(size_of::<Archived<Node>>(), size_of::<ArchivedBox<str>>());
+
+        serialize_and_check::<_, Failure>(&Node { value: None });
+        serialize_and_check::<_, Failure>(&Node {
+            value: Some("hello world".into()),
+        });
+
+        // A non-null pointer must point to a valid value
+        let mut buf = to_bytes::<_, 256, Failure>(&Node {
+            value: Some("hello world".into()),
+        })
+        .unwrap();
+        let len = buf.len();
+        buf.as_mut_slice()[len - size_of::<Archived<Node>>()] = 0x7f;
+        access::<Node, Failure>(buf.as_slice())
+            .expect_err("expected out-of-bounds pointer error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_niche_option_char() {
+        use rkyv::with::Niche;
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(Niche)]
+            value: Option<char>,
+        }
+
+        serialize_and_check::<_, Failure>(&Test { value: None });
+        serialize_and_check::<_, Failure>(&Test { value: Some('x') });
+
+        // The niche is `None`, and other values above `char::MAX` are rejected
+        let buf = AlignedBytes([0xff; 4]);
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        assert!(archived.value.is_none());
+        let buf = AlignedBytes([0x00, 0xd8, 0xd8, 0x00]);
+        access::<Test, Failure>(buf.as_ref())
+            .expect_err("expected invalid char error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_ranges() {
+        serialize_and_check::<_, Failure>(&(0usize..10));
+        serialize_and_check::<_, Failure>(&(1u32..=100));
+
+        // Invalid endpoints
+        let buf = AlignedBytes([2u8, 1u8]);
+        access::<core::ops::Range<bool>, Failure>(buf.as_ref())
+            .expect_err("expected invalid start error");
+        let buf = AlignedBytes([1u8, 2u8]);
+        access::<core::ops::RangeInclusive<bool>, Failure>(buf.as_ref())
+            .expect_err("expected invalid end error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_bound_and_control_flow() {
+        use core::ops::{Bound, ControlFlow};
+
+        serialize_and_check::<_, Failure>(&Bound::Included(10u64));
+        serialize_and_check::<_, Failure>(&Bound::Excluded(10u64));
+        serialize_and_check::<_, Failure>(&Bound::<u64>::Unbounded);
+        serialize_and_check::<_, Failure>(&ControlFlow::<u32, String>::Break(
+            1,
+        ));
+        serialize_and_check::<_, Failure>(
+            &ControlFlow::<u32, String>::Continue("hello world".to_string()),
+        );
+
+        let buf = AlignedBytes([2u8, 0u8]);
+        let bound = access::<Bound<u8>, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(bound.as_ref(), Bound::Unbounded);
+        let buf = AlignedBytes([1u8, 5u8]);
+        let flow =
+            access::<ControlFlow<u8, u8>, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(flow.break_value(), Some(&5));
+        assert!(flow.is_break());
+
+        // Invalid tags
+        let buf = AlignedBytes([3u8, 0u8]);
+        access::<Bound<u8>, Failure>(buf.as_ref())
+            .expect_err("expected invalid bound tag error");
+        let buf = AlignedBytes([2u8, 0u8]);
+        access::<ControlFlow<u8, u8>, Failure>(buf.as_ref())
+            .expect_err("expected invalid control flow tag error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_atomics() {
+        use core::sync::atomic::{AtomicBool, AtomicU32};
+
+        serialize_and_check::<_, Failure>(&AtomicU32::new(42));
+        serialize_and_check::<_, Failure>(&AtomicBool::new(true));
+
+        // Atomics are archived as plain values, so they are checked the same
+        let buf = AlignedBytes([2u8]);
+        access::<AtomicBool, Failure>(buf.as_ref())
+            .expect_err("expected invalid bool error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_char() {
+        use rkyv::Archived;
+
+        for c in ['\0', 'a', 'é', '\u{D7FF}', '\u{E000}', char::MAX] {
+            let buf = to_bytes::<_, 256, Failure>(&c).unwrap();
+            let archived = access::<char, Failure>(buf.as_slice()).unwrap();
+            assert_eq!(archived.to_native(), c);
+        }
+
+        for invalid in [0xD800u32, 0xDBFF, 0xDC00, 0xDFFF, 0x110000, u32::MAX] {
+            let buf = AlignedBytes(unsafe {
+                core::mem::transmute::<Archived<u32>, [u8; 4]>(
+                    Archived::<u32>::from_native(invalid),
+                )
+            });
+            access::<char, Failure>(buf.as_ref())
+                .expect_err("expected invalid char error");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn custom_context() {
+        use core::{alloc::Layout, ops::Range};
+        use rkyv::validation::{
+            util::access_with_context, validators::DefaultValidator,
+            ArchiveContext,
+        };
+
+        struct CountingValidator {
+            inner: DefaultValidator,
+            checked: usize,
+        }
+
+        unsafe impl<E> ArchiveContext<E> for CountingValidator
+        where
+            DefaultValidator: ArchiveContext<E>,
+        {
+            fn check_subtree_ptr(
+                &mut self,
+                ptr: *const u8,
+                layout: &Layout,
+            ) -> Result<(), E> {
+                self.checked += 1;
+                self.inner.check_subtree_ptr(ptr, layout)
+            }
+
+            unsafe fn push_prefix_subtree_range(
+                &mut self,
+                root: *const u8,
+                end: *const u8,
+            ) -> Result<Range<usize>, E> {
+                self.inner.push_prefix_subtree_range(root, end)
+            }
+
+            unsafe fn push_suffix_subtree_range(
+                &mut self,
+                start: *const u8,
+                root: *const u8,
+            ) -> Result<Range<usize>, E> {
+                self.inner.push_suffix_subtree_range(start, root)
+            }
+
+            unsafe fn pop_subtree_range(
+                &mut self,
+                range: Range<usize>,
+            ) -> Result<(), E> {
+                self.inner.pop_subtree_range(range)
+            }
+        }
+
+        let value = vec![Box::new(1u32), Box::new(2u32), Box::new(3u32)];
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+
+        let mut context = CountingValidator {
+            inner: DefaultValidator::new(buf.as_ref()),
+            checked: 0,
+        };
+        let archived = access_with_context::<Vec<Box<u32>>, _, Failure>(
+            buf.as_ref(),
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(archived.len(), 3);
+        // The root, the vec elements, and each of the boxes
+        assert_eq!(context.checked, 5);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn max_claimed_bytes() {
+        use rkyv::validation::{
+            util::access_with_context, validators::DefaultValidator,
+        };
+
+        let value = vec![0u8; 200];
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+
+        let mut validator =
+            DefaultValidator::with_limits(buf.as_ref(), None, Some(1024));
+        access_with_context::<Vec<u8>, _, Failure>(
+            buf.as_ref(),
+            &mut validator,
+        )
+        .expect("expected claims within the maximum to validate");
+
+        let mut validator =
+            DefaultValidator::with_limits(buf.as_ref(), None, Some(64));
+        access_with_context::<Vec<u8>, _, Failure>(
+            buf.as_ref(),
+            &mut validator,
+        )
+        .expect_err("expected claims past the maximum to fail");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_unsized_roots() {
+        use rkyv::{
+            util::{access_unsized_unchecked, serialize_rel_ptr_into},
+            validation::util::access_unsized,
+        };
+
+        let serializer = serialize_rel_ptr_into::<[u32], _, Failure>(
+            [1, 2, 3, 4].as_ref(),
+            DefaultSerializer::default(),
+        )
+        .unwrap();
+        let buf = serializer.into_writer();
+        let archived = access_unsized::<[u32], Failure>(buf.as_ref()).unwrap();
+        assert_eq!(archived.len(), 4);
+        assert_eq!(archived[2], 3);
+        assert!(archived.iter().zip([1, 2, 3, 4]).all(|(a, b)| *a == b));
+
+        let serializer = serialize_rel_ptr_into::<str, _, Failure>(
+            "hello world",
+            DefaultSerializer::default(),
+        )
+        .unwrap();
+        let mut buf = serializer.into_writer();
+        let archived = access_unsized::<str, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(archived, "hello world");
+
+        let str_pos = {
+            let archived =
+                unsafe { access_unsized_unchecked::<str>(buf.as_ref()) };
+            archived.as_ptr() as usize - buf.as_ptr() as usize
+        };
+        buf.as_mut_slice()[str_pos] = 0xff;
+        access_unsized::<str, Failure>(buf.as_ref())
+            .expect_err("expected invalid UTF-8 error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_error_trace() {
+        use rkyv::{util::access_unchecked, Archived};
+
+        let mut buf = to_bytes::<_, 256, Failure>(&Box::new('a')).unwrap();
+        let char_pos = {
+            let archived =
+                unsafe { access_unchecked::<Box<char>>(buf.as_slice()) };
+            archived.get() as *const _ as usize - buf.as_ptr() as usize
+        };
+        let root_pos = buf.len() - core::mem::size_of::<Archived<Box<char>>>();
+
+        let invalid = unsafe {
+            core::mem::transmute::<Archived<u32>, [u8; 4]>(
+                Archived::<u32>::from_native(0xD800),
+            )
+        };
+        buf.as_mut_slice()[char_pos..char_pos + 4].copy_from_slice(&invalid);
+
+        let message = access::<Box<char>, BoxedError>(buf.as_slice())
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("while checking boxed value of type"));
+        assert!(message.contains(&format!(
+            "while checking root of type `{}` at position {}",
+            core::any::type_name::<Archived<Box<char>>>(),
+            root_pos,
+        )));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_valid_durations() {
+        use core::time::Duration;
+        use rkyv::Archived;
+
+        let max = Duration::new(u64::MAX, 999_999_999);
+        let mut buf = to_bytes::<_, 256, Failure>(&max).unwrap();
+        let archived = access::<Duration, Failure>(buf.as_slice()).unwrap();
+        assert_eq!(archived.as_secs(), u64::MAX);
+        assert_eq!(archived.subsec_nanos(), 999_999_999);
+        assert_eq!(Duration::from(*archived), max);
+
+        // Replace the nanoseconds with exactly one billion
+        let valid = Archived::<u32>::from_native(999_999_999);
+        let invalid = Archived::<u32>::from_native(1_000_000_000);
+        let valid = unsafe {
+            core::slice::from_raw_parts(
+                (&valid as *const Archived<u32>).cast::<u8>(),
+                4,
+            )
+        };
+        let invalid = unsafe {
+            core::slice::from_raw_parts(
+                (&invalid as *const Archived<u32>).cast::<u8>(),
+                4,
+            )
+        };
+        let pos = buf.as_slice().windows(4).position(|w| w == valid).unwrap();
+        buf.as_mut_slice()[pos..pos + 4].copy_from_slice(invalid);
+        access::<Duration, Failure>(buf.as_slice())
+            .expect_err("expected invalid duration error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_invalid_btreemap() {
+        let data = AlignedBytes([
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0x30, 0, 0x00, 0x00, 0x00, 0x0c, 0xa5,
+            0xf0, 0xff, 0xff, 0xff,
+        ]);
+        rkyv::from_bytes::<BTreeMap<u8, Box<u8>>, Failure>(&data.0)
+            .unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_invalid_string() {
+        let data = AlignedBytes([0x10; 16]);
+        rkyv::from_bytes::<String, Failure>(&data.0).unwrap_err();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_lazy_archive() {
+        use core::mem::size_of;
+        use rkyv::{
+            ser::{writer::BufferWriter, Positional as _},
+            util::serialize_rel_ptr,
+            validation::lazy::LazyArchive,
+            RelPtr,
+        };
+
+        let mut writer = BufferWriter::new(AlignedBytes([0u8; 64]));
+        serialize_rel_ptr::<str, _, Failure>("hello", &mut writer).unwrap();
+        let first = writer.pos() - size_of::<RelPtr<str>>();
+        serialize_rel_ptr::<str, _, Failure>("world", &mut writer).unwrap();
+        let second = writer.pos() - size_of::<RelPtr<str>>();
+        let len = writer.pos();
+        let mut buf = writer.into_inner();
+
+        // Corrupt the second string so it is no longer valid UTF-8
+        let world = buf.as_ref()[..len]
+            .windows(5)
+            .position(|w| w == b"world")
+            .unwrap();
+        buf.as_mut()[world] = 0xff;
+
+        let mut archive = LazyArchive::new(&buf.as_ref()[..len]);
+        let ptr = archive
+            .access_pos::<RelPtr<str>, Failure>(first as isize)
+            .unwrap();
+        assert_eq!(archive.follow::<_, Failure>(ptr).unwrap(), "hello");
+        // Following the same pointer again uses the cached result
+        assert_eq!(archive.follow::<_, Failure>(ptr).unwrap(), "hello");
+
+        // The corrupt string is only detected when it is accessed
+        let ptr = archive
+            .access_pos::<RelPtr<str>, Failure>(second as isize)
+            .unwrap();
+        archive
+            .follow::<_, Failure>(ptr)
+            .expect_err("expected invalid UTF-8 error");
+
+        // Pointers outside of the archive are rejected
+        let other_buf = AlignedBytes([0u8; 64]);
+        let mut other = LazyArchive::new(other_buf.as_ref());
+        other
+            .follow::<_, Failure>(ptr)
+            .expect_err("expected out of bounds error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_sections() {
+        use rkyv::{
+            ser::AllocSerializer,
+            util::{access_section_unchecked, Sections},
+            validation::util::{access_section, access_sections},
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Symbol {
+            name: u32,
+            address: u64,
+        }
+
+        let strings = vec!["main".to_string(), "exit".to_string()];
+        let symbols = vec![
+            Symbol {
+                name: 0,
+                address: 0x1000,
+            },
+            Symbol {
+                name: 1,
+                address: 0x2000,
+            },
+        ];
+
+        let mut serializer = AllocSerializer::<256>::default();
+        let mut sections = Sections::new();
+        sections
+            .serialize::<_, _, Failure>("strings", &strings, &mut serializer)
+            .unwrap();
+        sections
+            .serialize::<_, _, Failure>("symbols", &symbols, &mut serializer)
+            .unwrap();
+        sections
+            .serialize::<_, _, Failure>("flag", &true, &mut serializer)
+            .unwrap();
+        // Section names must be unique
+        sections
+            .serialize::<_, _, Failure>("flag", &false, &mut serializer)
+            .expect_err("expected duplicate section error");
+        assert_eq!(sections.len(), 3);
+        let flag_pos = sections.position("flag").unwrap();
+        sections.finish::<_, Failure>(&mut serializer).unwrap();
+        let mut bytes = serializer.into_writer();
+
+        let directory = access_sections::<Failure>(&bytes).unwrap();
+        assert_eq!(directory.len(), 3);
+
+        let archived_symbols =
+            access_section::<Vec<Symbol>, Failure>(&bytes, "symbols").unwrap();
+        assert_eq!(archived_symbols[1].address, 0x2000);
+        let archived_strings =
+            access_section::<Vec<String>, Failure>(&bytes, "strings").unwrap();
+        assert_eq!(
+            archived_strings[archived_symbols[0].name.to_native() as usize],
+            "main"
+        );
+        assert!(*unsafe {
+            access_section_unchecked::<bool>(&bytes, "flag").unwrap()
+        });
+        access_section::<bool, Failure>(&bytes, "missing")
+            .expect_err("expected missing section error");
+
+        // Only the requested section is checked
+        bytes[flag_pos] = 2;
+        access_section::<bool, Failure>(&bytes, "flag")
+            .expect_err("expected invalid bool error");
+        access_section::<Vec<Symbol>, Failure>(&bytes, "symbols").unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_buffer_writer_set_pos() {
+        use core::mem::size_of;
+        use rkyv::{
+            ser::{
+                allocator::GlobalAllocator, writer::BufferWriter, Composite,
+                Positional as _,
+            },
+            util::{serialize, AlignedVec},
+            Archived,
+        };
+
+        let value = vec![
+            "a string that is stored out of line".to_string(),
+            "another string that is stored out of line".to_string(),
+        ];
+
+        let mut arena = AlignedVec::new();
+        arena.resize(512, 0xcc);
+        let mut serializer = Composite::new(
+            BufferWriter::new(arena.as_mut_slice()),
+            GlobalAllocator::default(),
+            (),
+        );
+        serializer.writer.set_pos(192);
+        serialize::<_, _, Failure>(&value, &mut serializer).unwrap();
+        let end = serializer.pos();
+        let root = end - size_of::<Archived<Vec<String>>>();
+
+        // The value can be accessed from the full buffer at its root position
+        let archived =
+            access_pos::<Vec<String>, Failure>(&arena, root as isize).unwrap();
+        assert_eq!(archived.as_slice(), value.as_slice());
+
+        // The archived region is self-contained, so it can also be accessed on
+        // its own
+        let region = &arena[192..end];
+        let archived = access::<Vec<String>, Failure>(region).unwrap();
+        assert_eq!(archived.as_slice(), value.as_slice());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_buffer_writer_as_bytes() {
+        use rkyv::{ser::writer::BufferWriter, util::serialize};
+
+        let mut writer = BufferWriter::new(AlignedBytes([0xccu8; 256]));
+        assert!(writer.is_empty());
+        assert!(writer.as_bytes().is_empty());
+
+        let first = [1u32, 2, 3];
+        serialize::<_, _, Failure>(&first, &mut writer).unwrap();
+        let len = writer.len();
+        let archived = access::<[u32; 3], Failure>(writer.as_bytes()).unwrap();
+        assert_eq!(archived, &first);
+
+        // The written prefix stays the same while more values are appended
+        let prefix = writer.as_bytes().to_vec();
+        let second = "a string that is stored out of line".to_string();
+        serialize::<_, _, Failure>(&second, &mut writer).unwrap();
+        assert!(writer.len() > len);
+        assert_eq!(&writer.as_bytes()[..len], prefix.as_slice());
+        let archived = access::<String, Failure>(writer.as_bytes()).unwrap();
+        assert_eq!(archived, &second);
+
+        let buffer = writer.into_inner();
+        assert_eq!(&buffer.0[..len], prefix.as_slice());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_cells() {
+        use core::cell::{Cell, RefCell};
+
+        serialize_and_check::<_, Failure>(&Cell::new(true));
+        serialize_and_check::<_, Failure>(&RefCell::new(vec![
+            "hello".to_string(),
+            "world".to_string(),
+        ]));
+
+        // The archived value is checked like the inner value
+        let buf = AlignedBytes([2u8; 1]);
+        access::<Cell<bool>, Failure>(buf.as_ref())
+            .expect_err("expected invalid bool error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_archive_arbitrary_input() {
+        use rkyv::{util::AlignedVec, validation::util::check_archive};
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Value {
+            strings: Vec<String>,
+            map: BTreeMap<u32, Option<Box<u64>>>,
+        }
+
+        fn check(bytes: &[u8]) {
+            let mut aligned = AlignedVec::new();
+            aligned.extend_from_slice(bytes);
+            // Only whether this returns matters, not the result
+            let _ = check_archive::<Value, Failure>(aligned.as_slice());
+            // Misaligned and overrunning roots are rejected without panicking
+            aligned.push(0);
+            let _ = check_archive::<Value, Failure>(&aligned[1..]);
+        }
+
+        let mut map = BTreeMap::new();
+        map.insert(1, Some(Box::new(10)));
+        map.insert(2, None);
+        map.insert(3, Some(Box::new(30)));
+        let value = Value {
+            strings: vec!["hello".to_string(), "world".to_string()],
+            map,
+        };
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        check_archive::<Value, Failure>(buf.as_slice()).unwrap();
+
+        // Every truncation
+        for len in 0..buf.len() {
+            check(&buf[..len]);
+            check(&buf[buf.len() - len..]);
+        }
+
+        // Every single-byte corruption
+        for i in 0..buf.len() {
+            for byte in [0x00, 0x01, 0x7f, 0x80, 0xff] {
+                let mut corrupt = buf.to_vec();
+                corrupt[i] = byte;
+                check(&corrupt);
+            }
+        }
+
+        // Pseudorandom corruption and random buffers
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..1000 {
+            let mut corrupt = buf.to_vec();
+            for _ in 0..(next() % 4 + 1) {
+                let i = (next() % corrupt.len() as u64) as usize;
+                corrupt[i] = next() as u8;
+            }
+            check(&corrupt);
+
+            let len = (next() % 128) as usize;
+            let random = (0..len).map(|_| next() as u8).collect::<Vec<_>>();
+            check(&random);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_archive_ref() {
+        use rkyv::{
+            rancor::Strategy,
+            ser::{AllocSerializer, WriterExt as _},
+            util::access_unchecked,
+        };
+
+        let values = ["first", "second value", "third"].map(String::from);
+
+        let build_index = |reverse: bool| {
+            let mut serializer = AllocSerializer::<256>::default();
+            let strategy = Strategy::<_, Failure>::wrap(&mut serializer);
+            let mut positions = values
+                .iter()
+                .map(|value| value.serialize_and_resolve(strategy).unwrap())
+                .collect::<Vec<_>>();
+            if reverse {
+                positions.reverse();
+            }
+            // Build an index that points at the archived values
+            for &pos in positions.iter() {
+                strategy.archive_ref::<String>(pos).unwrap();
+            }
+            serializer.into_writer()
+        };
+
+        let bytes = build_index(false);
+        let index = access::<[Box<String>; 3], Failure>(&bytes).unwrap();
+        for (archived, value) in index.iter().zip(values.iter()) {
+            assert_eq!(archived.as_ref(), value);
+        }
+
+        // A validated archive must point to its subtrees in order, but an
+        // index in any order can be accessed without validation
+        let bytes = build_index(true);
+        access::<[Box<String>; 3], Failure>(&bytes)
+            .expect_err("expected out of order subtree error");
+        let index = unsafe { access_unchecked::<[Box<String>; 3]>(&bytes) };
+        for (archived, value) in index.iter().zip(values.iter().rev()) {
+            assert_eq!(archived.as_ref(), value);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_field_align() {
+        use rkyv::util::{Align64, Aligned};
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Vectors {
+            id: u8,
+            #[archive(align = 32)]
+            lanes: [u8; 64],
+            name: String,
+        }
+
+        let value = Vectors {
+            id: 1,
+            lanes: [7; 64],
+            name: "simd".to_string(),
+        };
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let mut buffer = Aligned::<Align64, _>::new([0u8; 512]);
+
+        buffer[..bytes.len()].copy_from_slice(&bytes);
+        let archived =
+            access::<Vectors, Failure>(&buffer[..bytes.len()]).unwrap();
+        assert_eq!(archived.lanes, [7; 64]);
+        assert_eq!(archived.name, "simd");
+
+        // Only aligned to 16 bytes, so the root is not aligned for the field
+        buffer[16..16 + bytes.len()].copy_from_slice(&bytes);
+        access::<Vectors, Failure>(&buffer[16..16 + bytes.len()])
+            .expect_err("expected unaligned error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_inline_string() {
+        use core::mem::size_of;
+        use rkyv::{
+            primitive::ArchivedUsize, string::ArchivedInlineString,
+            with::InlineString,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Names {
+            #[with(InlineString<8>)]
+            first: String,
+            #[with(InlineString<8>)]
+            last: String,
+        }
+
+        let value = Names {
+            first: "Ada".to_string(),
+            last: "Lovelace-Byron".to_string(),
+        };
+        let bytes = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<Names, Failure>(&bytes).unwrap();
+        assert!(archived.first.is_inline());
+        assert_eq!(archived.first, "Ada");
+        assert!(!archived.last.is_inline());
+        assert_eq!(archived.last, "Lovelace-Byron");
+
+        let root = bytes.len() - size_of::<ArchivedNames>();
+        let first_bytes = root + size_of::<ArchivedUsize>();
+        let last_bytes = first_bytes + size_of::<ArchivedInlineString<8>>();
+
+        // Inline bytes that are not valid UTF-8
+        let mut invalid = bytes.clone();
+        invalid[first_bytes] = 0xff;
+        access::<Names, Failure>(&invalid)
+            .expect_err("expected invalid UTF-8 error");
+
+        // An out-of-line offset that points into the root
+        let mut invalid = bytes.clone();
+        invalid[last_bytes..last_bytes + size_of::<ArchivedUsize>()].fill(0);
+        access::<Names, Failure>(&invalid)
+            .expect_err("expected out-of-bounds offset error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[cfg(feature = "strict")]
+    fn check_capacity_vec() {
+        use core::{mem::size_of, pin::Pin};
+        use rkyv::{
+            access_unchecked_mut,
+            primitive::{ArchivedUsize, FixedIsize, FixedUsize},
+            vec::ArchivedCapacityVec,
+            with::WithCapacity,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes, pin_fields)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(WithCapacity)]
+            values: Vec<u16>,
+        }
+
+        let mut values = Vec::with_capacity(4);
+        values.push(1);
+        let bytes = to_bytes::<_, 256, Failure>(&Test { values }).unwrap();
+        access::<Test, Failure>(&bytes).unwrap();
+
+        // Pushed elements are still valid
+        let mut pushed = bytes.clone();
+        let mut archived =
+            unsafe { access_unchecked_mut::<Test>(Pin::new(pushed.as_mut())) };
+        archived.as_mut().values_pin().push(2.into()).unwrap();
+        let archived = access::<Test, Failure>(&pushed).unwrap();
+        assert_eq!(archived.values, [1, 2]);
+
+        // The root is a pointer followed by the length and capacity
+        let root = bytes.len() - size_of::<ArchivedCapacityVec<u16>>();
+        let len = root + size_of::<FixedIsize>();
+        let capacity = len + size_of::<ArchivedUsize>();
+        let write = |bytes: &mut [u8], at: usize, value: FixedUsize| {
+            let value = ArchivedUsize::from_native(value);
+            let value = unsafe {
+                core::slice::from_raw_parts(
+                    (&value as *const ArchivedUsize).cast::<u8>(),
+                    size_of::<ArchivedUsize>(),
+                )
+            };
+            bytes[at..at + value.len()].copy_from_slice(value);
+        };
+
+        let mut invalid = bytes.clone();
+        write(&mut invalid, len, 5);
+        access::<Test, Failure>(&invalid)
+            .expect_err("expected length exceeding capacity error");
+
+        let mut invalid = bytes.clone();
+        write(&mut invalid, capacity, 100);
+        access::<Test, Failure>(&invalid)
+            .expect_err("expected capacity out of bounds error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_packed_bits() {
+        use rkyv::with::PackedBits;
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(PackedBits)]
+            flags: Vec<bool>,
+        }
+
+        serialize_and_check::<_, Failure>(&Test { flags: Vec::new() });
+        serialize_and_check::<_, Failure>(&Test {
+            flags: vec![true; 16],
+        });
+
+        let mut bytes = to_bytes::<_, 256, Failure>(&Test {
+            flags: vec![true; 10],
+        })
+        .unwrap();
+        let archived = access::<Test, Failure>(&bytes).unwrap();
+        assert_eq!(archived.flags.as_bytes(), [0xff, 0x03]);
+
+        // The packed bytes are written first, and bits past the end must be
+        // zero
+        bytes.as_mut_slice()[1] |= 0x80;
+        access::<Test, Failure>(&bytes)
+            .expect_err("expected trailing bits error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_chunked_string() {
+        use rkyv::with::Chunked;
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(Chunked<2>)]
+            text: String,
+        }
+
+        serialize_and_check::<_, Failure>(&Test {
+            text: String::new(),
+        });
+        serialize_and_check::<_, Failure>(&Test {
+            text: "\u{1f980} and \u{2603}".to_string(),
+        });
+
+        // The chunks are written first, so the text is at the start of the
+        // archive
+        let mut bytes = to_bytes::<_, 256, Failure>(&Test {
+            text: "a\u{e9}b\u{e9}".to_string(),
+        })
+        .unwrap();
+        let archived = access::<Test, Failure>(&bytes).unwrap();
+        assert_eq!(archived.text.chunk(0), Some([b'a', 0xc3].as_slice()));
+
+        // The first `\u{e9}` is split between the first two chunks
+        bytes.as_mut_slice()[2] = b'x';
+        access::<Test, Failure>(&bytes)
+            .expect_err("expected invalid UTF-8 across chunks");
+        bytes.as_mut_slice()[2] = 0xa9;
+        access::<Test, Failure>(&bytes).unwrap();
+
+        // The second `\u{e9}` is the whole last chunk
+        bytes.as_mut_slice()[5] = 0xff;
+        access::<Test, Failure>(&bytes)
+            .expect_err("expected invalid UTF-8 in a chunk");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_swap_endian() {
+        use core::ptr::addr_of;
+
+        use rkyv::endian::{scalar_ranges, swap_endian, swap_scalars};
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes, swap_endian, repr(u16))]
+        enum Shape {
+            Point,
+            Circle { radius: f32 },
+            Polygon(Vec<[i16; 2]>),
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes, swap_endian)]
+        struct Test {
+            id: u32,
+            flags: u8,
+            short_name: String,
+            long_name: String,
+            values: Vec<u64>,
+            parent: Option<Box<u64>>,
+            shapes: Vec<Shape>,
+        }
+
+        let value = Test {
+            id: 0x01020304,
+            flags: 0x05,
+            short_name: "short".to_string(),
+            long_name: "a name that is too long to be inline".to_string(),
+            values: vec![0x0102030405060708, 0x1112131415161718],
+            parent: Some(Box::new(0x2122232425262728)),
+            shapes: vec![
+                Shape::Point,
+                Shape::Circle { radius: 1.5 },
+                Shape::Polygon(vec![[1, 2], [3, 4], [5, 6]]),
+            ],
+        };
+
+        let buf = to_bytes::<_, 1024, Failure>(&value).unwrap();
+        let archived = access::<Test, Failure>(buf.as_ref()).unwrap();
+        let mut swapped = swap_endian::<Test, Failure>(&buf).unwrap();
+
+        // Checks that the bytes of a scalar were reversed in the swapped
+        // archive
+        let is_swapped = |ptr: *const u8, len: usize| {
+            let pos = ptr as usize - buf.as_ptr() as usize;
+            let mut bytes = swapped[pos..pos + len].to_vec();
+            bytes.reverse();
+            bytes != swapped[pos..pos + len] && bytes == buf[pos..pos + len]
+        };
+        assert!(is_swapped(addr_of!(archived.id).cast(), 4));
+        assert!(is_swapped(archived.values.as_ptr().cast(), 8));
+        let parent = addr_of!(**archived.parent.as_ref().unwrap());
+        assert!(is_swapped(parent.cast(), 8));
+        // The tag of the enum is also swapped
+        assert!(is_swapped(addr_of!(archived.shapes[2]).cast(), 2));
+
+        // Single bytes are not changed
+        let pos = addr_of!(archived.flags) as usize - buf.as_ptr() as usize;
+        assert_eq!(swapped[pos], 0x05);
+
+        // The string bytes are not changed
+        let pos = archived.long_name.as_ptr() as usize - buf.as_ptr() as usize;
+        assert_eq!(
+            &swapped[pos..pos + value.long_name.len()],
+            value.long_name.as_bytes(),
+        );
+
+        // Swapping the scalars of the original archive converts it back
+        swap_scalars(&mut swapped, &scalar_ranges(&buf, archived));
+        assert_eq!(swapped.as_slice(), buf.as_slice());
+    }
+
+    #[cfg(any(
+        all(feature = "little_endian", target_endian = "little"),
+        all(feature = "big_endian", target_endian = "big"),
+    ))]
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_unsafe_union() {
+        #[derive(Archive, Serialize, Clone, Copy)]
+        #[archive(unsafe_union, check_bytes)]
+        #[repr(C)]
+        union Value {
+            i: i64,
+            f: f64,
+        }
+
+        unsafe impl rkyv::FullyInitialized for Value {}
+
+        let value = vec![Value { i: 1 }, Value { f: -2.0 }];
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<Vec<Value>, Failure>(buf.as_ref()).unwrap();
+        assert_eq!(unsafe { archived[0].i }, 1);
+        assert_eq!(unsafe { archived[1].f }, -2.0);
+
+        // Only the bounds of the archived unions are checked
+        let mut invalid = AlignedBytes([0u8; 16]);
+        invalid.0[8..12].copy_from_slice(&(-8i32).to_le_bytes());
+        invalid.0[12..16].copy_from_slice(&3u32.to_le_bytes());
+        assert!(access::<Vec<Value>, Failure>(&invalid.0).is_err());
     }
 }
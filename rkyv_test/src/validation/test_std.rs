@@ -1,29 +1,352 @@
-// TODO: re-enable after hashmap validation is fixed
-// #[cfg(test)]
-// mod tests {
-//     use crate::validation::util::alloc::serialize_and_check;
-//     use std::collections::{HashMap, HashSet};
-
-//     #[cfg(feature = "wasm")]
-//     use wasm_bindgen_test::*;
-
-//     #[test]
-//     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
-//     fn hashmap() {
-//         let mut map = HashMap::new();
-//         map.insert("Hello".to_string(), 12);
-//         map.insert("world".to_string(), 34);
-//         map.insert("foo".to_string(), 56);
-//         map.insert("bar".to_string(), 78);
-//         map.insert("baz".to_string(), 90);
-//         serialize_and_check(&map);
-
-//         let mut set = HashSet::new();
-//         set.insert("Hello".to_string());
-//         set.insert("world".to_string());
-//         set.insert("foo".to_string());
-//         set.insert("bar".to_string());
-//         set.insert("baz".to_string());
-//         serialize_and_check(&set);
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use crate::validation::util::alloc::serialize_and_check;
+    use rkyv::{access, rancor::Failure, to_bytes, util::access_unchecked};
+    use std::collections::{HashMap, HashSet};
+
+    #[cfg(feature = "wasm")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn hashmap() {
+        let mut map = HashMap::new();
+        map.insert("Hello".to_string(), 12);
+        map.insert("world".to_string(), 34);
+        map.insert("foo".to_string(), 56);
+        map.insert("bar".to_string(), 78);
+        map.insert("baz".to_string(), 90);
+        serialize_and_check::<_, Failure>(&map);
+
+        let mut set = HashSet::new();
+        set.insert("Hello".to_string());
+        set.insert("world".to_string());
+        set.insert("foo".to_string());
+        set.insert("bar".to_string());
+        set.insert("baz".to_string());
+        serialize_and_check::<_, Failure>(&set);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn invalid_hashmap_keys() {
+        let mut map = HashMap::new();
+        map.insert(1u32, 10u32);
+        map.insert(2u32, 20u32);
+        let mut buf = to_bytes::<_, 256, Failure>(&map).unwrap();
+
+        let key_pos = {
+            let archived = unsafe {
+                access_unchecked::<HashMap<u32, u32>>(buf.as_slice())
+            };
+            archived
+                .keys()
+                .map(|k| k as *const _ as usize - buf.as_ptr() as usize)
+                .collect::<Vec<_>>()
+        };
+        let key_size = core::mem::size_of::<rkyv::Archived<u32>>();
+
+        // Swapped keys
+        let mut swapped = buf.clone();
+        let bytes = swapped.as_mut_slice();
+        for i in 0..key_size {
+            bytes.swap(key_pos[0] + i, key_pos[1] + i);
+        }
+        access::<HashMap<u32, u32>, Failure>(swapped.as_slice())
+            .expect_err("expected misplaced key error");
+
+        // Duplicate keys
+        let bytes = buf.as_mut_slice();
+        bytes.copy_within(key_pos[0]..key_pos[0] + key_size, key_pos[1]);
+        access::<HashMap<u32, u32>, Failure>(buf.as_slice())
+            .expect_err("expected duplicate key error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn invalid_hashset_elements() {
+        let mut set = HashSet::new();
+        set.insert(1u32);
+        set.insert(2u32);
+        let mut buf = to_bytes::<_, 256, Failure>(&set).unwrap();
+
+        let archived = access::<HashSet<u32>, Failure>(buf.as_slice()).unwrap();
+        assert!(archived.contains(&rkyv::Archived::<u32>::from_native(1)));
+        assert!(!archived.contains(&rkyv::Archived::<u32>::from_native(3)));
+
+        let key_pos = archived
+            .iter()
+            .map(|k| k as *const _ as usize - buf.as_ptr() as usize)
+            .collect::<Vec<_>>();
+        let key_size = core::mem::size_of::<rkyv::Archived<u32>>();
+
+        // Duplicate elements
+        let bytes = buf.as_mut_slice();
+        bytes.copy_within(key_pos[0]..key_pos[0] + key_size, key_pos[1]);
+        access::<HashSet<u32>, Failure>(buf.as_slice())
+            .expect_err("expected duplicate element error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_with_hasher() {
+        use core::hash::{BuildHasherDefault, Hasher};
+        use rkyv::{with::WithHasher, Archive, Serialize};
+
+        // A multiplicative hasher that disagrees with the default hasher
+        #[derive(Default)]
+        struct Multiply(u64);
+
+        impl Hasher for Multiply {
+            fn finish(&self) -> u64 {
+                self.0 ^ (self.0 >> 32)
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                for &byte in bytes {
+                    self.0 = (self.0 ^ byte as u64).wrapping_mul(31);
+                }
+            }
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(check_bytes)]
+        struct Test {
+            #[with(WithHasher<Multiply>)]
+            map: HashMap<String, u32, BuildHasherDefault<Multiply>>,
+        }
+
+        #[derive(Archive)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Unwrapped {
+            _map: HashMap<String, u32>,
+        }
+
+        let value = Test {
+            map: (0..20).map(|i| (format!("key {i}"), i)).collect(),
+        };
+        serialize_and_check::<_, Failure>(&value);
+
+        // Keys are placed by the chosen hasher, so checking the map with the
+        // default hasher fails
+        let buf = to_bytes::<_, 1024, Failure>(&value).unwrap();
+        access::<Unwrapped, Failure>(buf.as_ref())
+            .expect_err("expected keys to be in the wrong positions");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_ip_addrs() {
+        use core::mem::size_of;
+        use rkyv::Archived;
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        assert_eq!(size_of::<Archived<Ipv4Addr>>(), 4);
+        assert_eq!(size_of::<Archived<Ipv6Addr>>(), 16);
+        assert_eq!(size_of::<Archived<IpAddr>>(), 17);
+
+        let v4 = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+        let v6 = IpAddr::V6(Ipv6Addr::LOCALHOST);
+        serialize_and_check::<_, Failure>(&v4);
+        serialize_and_check::<_, Failure>(&v6);
+
+        let buf = to_bytes::<_, 256, Failure>(&v6).unwrap();
+        let archived = access::<IpAddr, Failure>(buf.as_slice()).unwrap();
+        assert_eq!(archived.as_ipaddr(), v6);
+
+        // Only tags 0 (V4) and 1 (V6) are valid
+        let mut buf = to_bytes::<_, 256, Failure>(&v4).unwrap();
+        let archived = access::<IpAddr, Failure>(buf.as_slice()).unwrap();
+        assert_eq!(archived.as_ipaddr(), v4);
+        buf.as_mut_slice()[0] = 2;
+        access::<IpAddr, Failure>(buf.as_slice())
+            .expect_err("expected invalid tag error");
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_c_string() {
+        use std::ffi::CString;
+
+        let value = CString::new("hello world").unwrap();
+        serialize_and_check::<_, Failure>(&value);
+
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<CString, Failure>(buf.as_slice()).unwrap();
+        assert_eq!(archived.as_c_str(), value.as_c_str());
+        assert_eq!(archived.as_bytes_with_nul(), b"hello world\0");
+
+        // The archived bytes are at the start of the buffer
+        let len = value.as_bytes_with_nul().len();
+        assert_eq!(&buf[..len], value.as_bytes_with_nul());
+
+        // Interior nul bytes are rejected
+        let mut interior = buf.clone();
+        interior.as_mut_slice()[5] = 0;
+        access::<CString, Failure>(interior.as_slice())
+            .expect_err("expected interior nul error");
+
+        // A missing nul terminator is rejected
+        let mut unterminated = buf.clone();
+        unterminated.as_mut_slice()[len - 1] = b'!';
+        access::<CString, Failure>(unterminated.as_slice())
+            .expect_err("expected missing nul terminator error");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_os_strings_and_paths() {
+        use rkyv::{deserialize, Archive, Deserialize, Serialize};
+        use std::{
+            ffi::OsString,
+            os::unix::ffi::OsStringExt as _,
+            path::{Path, PathBuf},
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(check_bytes)]
+        #[archive_attr(derive(Debug))]
+        struct Entry {
+            name: OsString,
+            path: PathBuf,
+        }
+
+        // Not valid UTF-8
+        let name = OsString::from_vec(vec![b'f', 0xff, b'o']);
+        let value = Entry {
+            name: name.clone(),
+            path: PathBuf::from("/var/lib/index").join(&name),
+        };
+        serialize_and_check::<_, Failure>(&value);
+
+        let buf = to_bytes::<_, 256, Failure>(&value).unwrap();
+        let archived = access::<Entry, Failure>(buf.as_slice()).unwrap();
+        assert_eq!(archived.name, name);
+        assert_eq!(archived.name.as_bytes(), &[b'f', 0xff, b'o']);
+        assert_eq!(archived.path, value.path);
+        assert!(archived.path.as_path().starts_with(Path::new("/var/lib")));
+
+        let deserialized =
+            deserialize::<Entry, _, Failure>(archived, &mut ()).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_streaming() {
+        use core::mem::size_of;
+        use rkyv::{
+            primitive::{ArchivedIsize, ArchivedU32},
+            rancor::Strategy,
+            ser::AllocSerializer,
+            util::AlignedVec,
+            validation::streaming::StreamingValidator,
+            Serialize,
+        };
+        use std::rc::Rc;
+
+        fn serialize_records<T>(
+            records: &[T],
+        ) -> (rkyv::util::AlignedVec, Vec<usize>)
+        where
+            T: Serialize<Strategy<AllocSerializer<256>, Failure>>,
+        {
+            let mut serializer = AllocSerializer::<256>::default();
+            let positions = records
+                .iter()
+                .map(|record| {
+                    record
+                        .serialize_and_resolve(Strategy::wrap(&mut serializer))
+                        .unwrap()
+                })
+                .collect();
+            (serializer.into_writer(), positions)
+        }
+
+        let records = (0..10)
+            .map(|i| vec![format!("a long string in record {i}"); i])
+            .collect::<Vec<_>>();
+        let (bytes, positions) = serialize_records(&records);
+
+        let mut validator =
+            StreamingValidator::with_max_window(bytes.as_slice(), Some(512));
+        for (record, &pos) in records.iter().zip(positions.iter()) {
+            let archived =
+                validator.check_pos::<Vec<String>, Failure>(pos).unwrap();
+            assert_eq!(archived.len(), record.len());
+            assert!(archived.iter().zip(record.iter()).all(|(a, b)| a == b));
+        }
+        assert_eq!(validator.position(), bytes.len());
+
+        // Values must be checked in order
+        let mut validator = StreamingValidator::new(bytes.as_slice());
+        validator
+            .check_pos::<Vec<String>, Failure>(positions[1])
+            .unwrap();
+        validator
+            .check_pos::<Vec<String>, Failure>(positions[0])
+            .expect_err("expected backward value error");
+
+        // Records that don't fit in the window are rejected
+        let mut validator =
+            StreamingValidator::with_max_window(bytes.as_slice(), Some(64));
+        validator
+            .check_pos::<Vec<String>, Failure>(positions[9])
+            .expect_err("expected window exceeded error");
+
+        // Truncated streams are rejected
+        let mut validator = StreamingValidator::new(&bytes[..bytes.len() - 1]);
+        validator
+            .check_pos::<Vec<String>, Failure>(positions[9])
+            .expect_err("expected unexpected end of stream error");
+
+        // Shared pointers point back into previous records
+        let shared = Rc::new("a long shared string".to_string());
+        let (bytes, positions) =
+            serialize_records(&[shared.clone(), shared.clone()]);
+        rkyv::validation::util::access_pos::<Rc<String>, Failure>(
+            bytes.as_slice(),
+            positions[1] as isize,
+        )
+        .unwrap();
+        let mut validator = StreamingValidator::new(bytes.as_slice());
+        validator
+            .check_pos::<Rc<String>, Failure>(positions[0])
+            .unwrap();
+        validator
+            .check_pos::<Rc<String>, Failure>(positions[1])
+            .expect_err("expected discarded bytes error");
+
+        // Values can't point to the end of the previous value, even if it's
+        // still buffered for alignment
+        let box_pos = usize::max(size_of::<u32>(), size_of::<ArchivedIsize>());
+        let mut bytes = AlignedVec::new();
+        bytes.resize(box_pos + size_of::<ArchivedIsize>(), 0);
+        unsafe {
+            let out = bytes.as_mut_ptr();
+            out.cast::<ArchivedU32>()
+                .write(ArchivedU32::from_native(42));
+            out.add(box_pos)
+                .cast::<ArchivedIsize>()
+                .write(ArchivedIsize::from_native(-(box_pos as isize) as _));
+        }
+        rkyv::validation::util::access_pos::<Box<u32>, Failure>(
+            bytes.as_slice(),
+            box_pos as isize,
+        )
+        .unwrap();
+        let mut validator = StreamingValidator::new(bytes.as_slice());
+        validator.check_pos::<u32, Failure>(0).unwrap();
+        validator
+            .check_pos::<Box<u32>, Failure>(box_pos)
+            .expect_err("expected pointer into previous value error");
+
+        // Values that end past the end of the address space are rejected
+        let mut validator = StreamingValidator::new(bytes.as_slice());
+        validator
+            .check_pos::<u32, Failure>(usize::MAX)
+            .expect_err("expected position overflow error");
+    }
+}